@@ -104,6 +104,9 @@ fn pseudo_selector_from_str<'a>(selector: &'a str, value: Option<&'a str>)
         "hover" => Ok(CssPathPseudoSelector::Hover),
         "active" => Ok(CssPathPseudoSelector::Active),
         "focus" => Ok(CssPathPseudoSelector::Focus),
+        "drag-over" => Ok(CssPathPseudoSelector::DragOver),
+        "checked" => Ok(CssPathPseudoSelector::Checked),
+        "disabled" => Ok(CssPathPseudoSelector::Disabled),
         "nth-child" => {
             let value = value.ok_or(CssPseudoSelectorParseError::EmptyNthChild)?;
             let parsed = parse_nth_child_selector(value)?;
@@ -186,6 +189,9 @@ fn test_css_pseudo_selector_parse() {
         (("hover", None), Hover),
         (("active", None), Active),
         (("focus", None), Focus),
+        (("drag-over", None), DragOver),
+        (("checked", None), Checked),
+        (("disabled", None), Disabled),
         (("nth-child", Some("4")), NthChild(Number(4))),
         (("nth-child", Some("even")), NthChild(Even)),
         (("nth-child", Some("odd")), NthChild(Odd)),
@@ -222,30 +228,48 @@ impl<'a> fmt::Display for CssParseError<'a> {
     }
 }
 
+/// Non-fatal error that was skipped over by `new_from_str_with_warnings`.
+///
+/// The rule or declaration that caused the warning is ignored, everything else
+/// in the stylesheet is still parsed and applied (similar to how browsers handle CSS errors).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CssParseWarning<'a> {
+    pub warning: CssParseErrorInner<'a>,
+    pub location: ErrorLocation,
+    /// The source text that was skipped, for example `alsdfkj: 500px;` or `:aldkfja`
+    pub offending_text: &'a str,
+}
+
+impl<'a> fmt::Display for CssParseWarning<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CSS warning at line {}:{}: {} - ignoring \"{}\"",
+            self.location.line, self.location.column, self.warning, self.offending_text)
+    }
+}
+
+/// Returns the line and column (both starting at 1) of the character at the given byte offset
+fn get_error_location(css_string: &str, byte_offset: usize) -> ErrorLocation {
+    let text_before_error = &css_string[..byte_offset];
+    let line_start = text_before_error.rfind('\n').map(|newline| newline + 1).unwrap_or(0);
+    ErrorLocation {
+        line: text_before_error.matches('\n').count() + 1,
+        column: text_before_error[line_start..].chars().count() + 1,
+    }
+}
+
+/// Parses a CSS string, returning an error on the first invalid declaration, selector or rule.
+///
+/// See `new_from_str_with_warnings` for a version that skips invalid parts of the stylesheet instead.
 pub fn new_from_str<'a>(css_string: &'a str) -> Result<Css, CssParseError<'a>> {
     let mut tokenizer = Tokenizer::new(css_string);
-    match new_from_str_inner(css_string, &mut tokenizer) {
+    match new_from_str_inner(css_string, &mut tokenizer, None) {
         Ok(stylesheet) => Ok(Css {
             stylesheets: vec![
                 stylesheet
             ],
         }),
         Err(e) => {
-            let error_location = tokenizer.pos().saturating_sub(1);
-            let line_number: usize = css_string[0..error_location].lines().count();
-
-            // Rust doesn't count "\n" as a character, so we have to add the line number count on top
-            let total_characters: usize = css_string[0..error_location].lines().take(line_number.saturating_sub(1)).map(|line| line.chars().count()).sum();
-            let total_characters = total_characters + line_number;
-            /*println!("line_number: {} error location: {}, total characters: {}", line_number,
-                     error_location, total_characters);*/
-            let characters_in_line = (error_location + 2) - total_characters;
-
-            let error_location = ErrorLocation {
-                line: line_number,
-                column: characters_in_line,
-            };
-
+            let error_location = get_error_location(css_string, tokenizer.pos().saturating_sub(1));
             Err(CssParseError {
                 error: e,
                 location: error_location,
@@ -254,6 +278,32 @@ pub fn new_from_str<'a>(css_string: &'a str) -> Result<Css, CssParseError<'a>> {
     }
 }
 
+/// Error-recovering version of `new_from_str`: Invalid declarations and rules are skipped
+/// and reported as warnings, everything else is parsed as usual. Useful for hot-reloading,
+/// so that a single typo doesn't remove the styling of the whole application.
+///
+/// ```rust
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::new_from_str_with_warnings;
+/// let (css, warnings) = new_from_str_with_warnings("
+///     div { alsdfkj: 500px; width: 400px; }
+///     p:aldkfja { width: 200px; }
+/// ");
+///
+/// assert_eq!(css.stylesheets[0].rules.len(), 1);
+/// assert_eq!(css.stylesheets[0].rules[0].declarations.len(), 1);
+/// assert_eq!(warnings.len(), 2);
+/// ```
+pub fn new_from_str_with_warnings<'a>(css_string: &'a str) -> (Css, Vec<CssParseWarning<'a>>) {
+    let mut tokenizer = Tokenizer::new(css_string);
+    let mut warnings = Vec::new();
+    let stylesheet = match new_from_str_inner(css_string, &mut tokenizer, Some(&mut warnings)) {
+        Ok(stylesheet) => stylesheet,
+        Err(_) => unreachable!("new_from_str_inner never returns an error when collecting warnings"),
+    };
+    (Css { stylesheets: vec![stylesheet] }, warnings)
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum CssPathParseError<'a> {
     EmptyPath,
@@ -349,8 +399,44 @@ pub fn parse_css_path<'a>(input: &'a str) -> Result<CssPath, CssPathParseError<'
     }
 }
 
+/// In strict mode (`warnings` is `None`), returns the error, otherwise records the
+/// error as a warning and returns `Ok(())`, so that the parser can skip the offending text.
+fn report_error<'a>(
+    error: CssParseErrorInner<'a>,
+    warnings: &mut Option<&mut Vec<CssParseWarning<'a>>>,
+    css_string: &'a str,
+    token_start: usize,
+    token_end: usize,
+) -> Result<(), CssParseErrorInner<'a>> {
+
+    let warnings = match warnings {
+        Some(w) => w,
+        None => return Err(error),
+    };
+
+    let token_text = &css_string[token_start..token_end];
+    let offending_text = token_text.trim();
+    let leading_whitespace = token_text.len() - token_text.trim_start().len();
+
+    warnings.push(CssParseWarning {
+        warning: error,
+        location: get_error_location(css_string, token_start + leading_whitespace),
+        offending_text,
+    });
+
+    Ok(())
+}
+
 /// Parses a CSS string (single-threaded) and returns the parsed rules in blocks
-fn new_from_str_inner<'a>(css_string: &'a str, tokenizer: &mut Tokenizer<'a>) -> Result<Stylesheet, CssParseErrorInner<'a>> {
+///
+/// If `warnings` is set, invalid rules and declarations are skipped and pushed to
+/// `warnings` instead of aborting the parsing, the function then never returns an error.
+fn new_from_str_inner<'a>(
+    css_string: &'a str,
+    tokenizer: &mut Tokenizer<'a>,
+    mut warnings: Option<&mut Vec<CssParseWarning<'a>>>,
+) -> Result<Stylesheet, CssParseErrorInner<'a>> {
+
     use simplecss::{Token, Combinator};
 
     let mut css_blocks = Vec::new();
@@ -367,15 +453,66 @@ fn new_from_str_inner<'a>(css_string: &'a str, tokenizer: &mut Tokenizer<'a>) ->
     let mut current_rules = Vec::new();
     // Keep track of the current path during parsing
     let mut last_path = Vec::new();
+    // Set if one of the selectors of the current block failed to parse - like in
+    // browsers, the entire block is skipped if one of its selectors is invalid
+    let mut current_block_is_invalid = false;
 
     let css_property_map = azul_css::get_css_key_map();
+
     loop {
-        let token = tokenizer.parse_next()?;
+
+        let token_start = tokenizer.pos();
+
+        let token = match tokenizer.parse_next() {
+            Ok(token) => token,
+            Err(e) => {
+                // The tokenizer can't be resynchronized after a syntax error,
+                // so only the rules that were parsed until now are kept
+                report_error(e.into(), &mut warnings, css_string, token_start, tokenizer.pos())?;
+                break;
+            }
+        };
+
+        let token_end = tokenizer.pos();
+
+        // Reports the error, then executes `$on_skip` (usually a `continue`) to skip the token
+        macro_rules! skip {
+            ($error:expr, $on_skip:expr) => {{
+                report_error($error.into(), &mut warnings, css_string, token_start, token_end)?;
+                $on_skip;
+            }};
+        }
+
+        macro_rules! try_or_skip {
+            ($result:expr, $on_skip:expr) => {
+                match $result {
+                    Ok(o) => o,
+                    Err(e) => skip!(e, $on_skip),
+                }
+            };
+        }
+
+        // Parses a selector - if the selector is invalid, the whole block is skipped
+        macro_rules! try_selector {
+            ($result:expr) => {
+                try_or_skip!($result, { current_block_is_invalid = true; continue; })
+            };
+        }
+
+        macro_rules! push_selector {
+            ($selector:expr) => {{
+                if parser_in_block {
+                    skip!(CssParseErrorInner::MalformedCss, continue);
+                }
+                last_path.push($selector);
+            }};
+        }
+
         match token {
             Token::BlockStart => {
                 if parser_in_block {
                     // multi-nested CSS blocks are currently not supported
-                    return Err(CssParseErrorInner::MalformedCss);
+                    skip!(CssParseErrorInner::MalformedCss, continue);
                 }
                 parser_in_block = true;
                 block_nesting += 1;
@@ -387,11 +524,15 @@ fn new_from_str_inner<'a>(css_string: &'a str, tokenizer: &mut Tokenizer<'a>) ->
                 last_path.clear();
             },
             Token::BlockEnd => {
-                block_nesting -= 1;
                 if !parser_in_block {
-                    return Err(CssParseErrorInner::MalformedCss);
+                    skip!(CssParseErrorInner::MalformedCss, continue);
                 }
+                block_nesting -= 1;
                 parser_in_block = false;
+                if current_block_is_invalid {
+                    current_paths.clear();
+                    current_block_is_invalid = false;
+                }
                 for path in current_paths.drain(..) {
                     css_blocks.push(CssRuleBlock {
                         path: CssPath { selectors: path },
@@ -404,56 +545,43 @@ fn new_from_str_inner<'a>(css_string: &'a str, tokenizer: &mut Tokenizer<'a>) ->
 
             // tokens that adjust the last_path
             Token::UniversalSelector => {
-                if parser_in_block {
-                    return Err(CssParseErrorInner::MalformedCss);
-                }
-                last_path.push(CssPathSelector::Global);
+                push_selector!(CssPathSelector::Global);
             },
             Token::TypeSelector(div_type) => {
-                if parser_in_block {
-                    return Err(CssParseErrorInner::MalformedCss);
-                }
-                last_path.push(CssPathSelector::Type(NodeTypePath::from_str(div_type)?));
+                push_selector!(CssPathSelector::Type(try_selector!(NodeTypePath::from_str(div_type))));
             },
             Token::IdSelector(id) => {
-                if parser_in_block {
-                    return Err(CssParseErrorInner::MalformedCss);
-                }
-                last_path.push(CssPathSelector::Id(id.to_string()));
+                push_selector!(CssPathSelector::Id(id.to_string()));
             },
             Token::ClassSelector(class) => {
-                if parser_in_block {
-                    return Err(CssParseErrorInner::MalformedCss);
-                }
-                last_path.push(CssPathSelector::Class(class.to_string()));
+                push_selector!(CssPathSelector::Class(class.to_string()));
             },
             Token::Combinator(Combinator::GreaterThan) => {
-                if parser_in_block {
-                    return Err(CssParseErrorInner::MalformedCss);
-                }
-                last_path.push(CssPathSelector::DirectChildren);
+                push_selector!(CssPathSelector::DirectChildren);
             },
             Token::Combinator(Combinator::Space) => {
-                if parser_in_block {
-                    return Err(CssParseErrorInner::MalformedCss);
-                }
-                last_path.push(CssPathSelector::Children);
+                push_selector!(CssPathSelector::Children);
             },
             Token::PseudoClass { selector, value } => {
-                if parser_in_block {
-                    return Err(CssParseErrorInner::MalformedCss);
-                }
-                last_path.push(CssPathSelector::PseudoSelector(pseudo_selector_from_str(selector, value)?));
+                push_selector!(CssPathSelector::PseudoSelector(try_selector!(pseudo_selector_from_str(selector, value))));
             },
             Token::Declaration(key, val) => {
                 if !parser_in_block {
-                    return Err(CssParseErrorInner::MalformedCss);
+                    skip!(CssParseErrorInner::MalformedCss, continue);
                 }
 
-                let parsed_key = CssPropertyType::from_str(key, &css_property_map)
-                    .ok_or(CssParseErrorInner::UnknownPropertyKey(key, val))?;
+                // Declarations of a block that is going to be skipped don't need to be reported
+                if current_block_is_invalid {
+                    continue;
+                }
 
-                current_rules.push(determine_static_or_dynamic_css_property(parsed_key, val)?);
+                let parsed_key = try_or_skip!(
+                    CssPropertyType::from_str(key, &css_property_map)
+                        .ok_or(CssParseErrorInner::UnknownPropertyKey(key, val)),
+                    continue
+                );
+
+                current_rules.push(try_or_skip!(determine_static_or_dynamic_css_property(parsed_key, val), continue));
             },
             Token::EndOfStream => {
                 break;
@@ -466,7 +594,17 @@ fn new_from_str_inner<'a>(css_string: &'a str, tokenizer: &mut Tokenizer<'a>) ->
 
     // non-even number of blocks
     if block_nesting != 0 {
-        return Err(CssParseErrorInner::UnclosedBlock);
+        let end_of_stream = css_string.len();
+        report_error(CssParseErrorInner::UnclosedBlock, &mut warnings, css_string, end_of_stream, end_of_stream)?;
+        // Like browsers, close the unclosed block at the end of the stylesheet
+        if !current_block_is_invalid {
+            for path in current_paths.drain(..) {
+                css_blocks.push(CssRuleBlock {
+                    path: CssPath { selectors: path },
+                    declarations: current_rules.clone(),
+                })
+            }
+        }
     }
 
     Ok(css_blocks.into())
@@ -848,4 +986,68 @@ fn test_case_issue_93() {
     ];
 
     assert_eq!(parsed_css, Css { stylesheets: vec![expected_rules.into()] });
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_css_parse_with_warnings() {
+
+    use azul_css::*;
+    use self::CssPathSelector::*;
+
+    let (parsed_css, warnings) = new_from_str_with_warnings("
+        #my_id {
+            alsdfkj: 500px;
+            color: #FF0000;
+        }
+        div:aldkfja, p {
+            color: #00FF00;
+        }
+        .my_class {
+            color: #0000FF;
+        }
+    ");
+
+    let expected_rules = vec![
+        CssRuleBlock {
+            path: CssPath { selectors: vec![Id("my_id".into())] },
+            declarations: vec![CssDeclaration::Static(CssProperty::TextColor(StyleTextColor(ColorU { r: 255, g: 0, b: 0, a: 255 })))],
+        },
+        CssRuleBlock {
+            path: CssPath { selectors: vec![Class("my_class".into())] },
+            declarations: vec![CssDeclaration::Static(CssProperty::TextColor(StyleTextColor(ColorU { r: 0, g: 0, b: 255, a: 255 })))],
+        },
+    ];
+
+    assert_eq!(parsed_css, Css { stylesheets: vec![expected_rules.into()] });
+    assert_eq!(warnings.len(), 2);
+
+    assert_eq!(warnings[0].warning, CssParseErrorInner::UnknownPropertyKey("alsdfkj", "500px"));
+    assert_eq!(warnings[0].location.line, 3);
+    assert!(warnings[0].offending_text.starts_with("alsdfkj: 500px"));
+
+    assert_eq!(warnings[1].warning, CssParseErrorInner::PseudoSelectorParseError(
+        CssPseudoSelectorParseError::UnknownSelector("aldkfja", None)
+    ));
+    assert_eq!(warnings[1].location.line, 6);
+    assert_eq!(warnings[1].offending_text, ":aldkfja");
+}
+
+#[test]
+fn test_css_parse_with_warnings_unclosed_block() {
+
+    use azul_css::*;
+
+    let (parsed_css, warnings) = new_from_str_with_warnings(".my_class { color: #0000FF;");
+
+    let expected_rules = vec![
+        CssRuleBlock {
+            path: CssPath { selectors: vec![CssPathSelector::Class("my_class".into())] },
+            declarations: vec![CssDeclaration::Static(CssProperty::TextColor(StyleTextColor(ColorU { r: 0, g: 0, b: 255, a: 255 })))],
+        },
+    ];
+
+    assert_eq!(parsed_css, Css { stylesheets: vec![expected_rules.into()] });
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].warning, CssParseErrorInner::UnclosedBlock);
+    assert!(new_from_str(".my_class { color: #0000FF;").is_err());
+}
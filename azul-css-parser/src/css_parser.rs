@@ -1335,6 +1335,10 @@ impl_from!(CssColorParseError<'a>, CssBackgroundParseError::ColorParseError);
 pub fn parse_style_background<'a>(input: &'a str)
 -> Result<StyleBackground, CssBackgroundParseError<'a>>
 {
+    if input.trim() == "none" {
+        return Ok(StyleBackground::NoBackground);
+    }
+
     match parse_parentheses(input, &[
         "none", "linear-gradient", "repeating-linear-gradient",
         "radial-gradient", "repeating-radial-gradient", "image",
@@ -0,0 +1,695 @@
+//! Prints a `Css` stylesheet or single `CssProperty` values back into CSS text,
+//! so that the output can be parsed again with `new_from_str`.
+
+use azul_css::{
+    Css, CssRuleBlock, CssDeclaration, CssProperty, CssPropertyType,
+    DynamicCssProperty, DynamicCssPropertyDefault,
+    PixelValue, PercentageValue, FloatValue, SizeMetric, ColorU,
+    StyleBackground, StyleBackgroundSize, StyleBackgroundRepeat, StyleBorderSide, BorderStyle,
+    StyleBorderRadius, BoxShadowPreDisplayItem, BoxShadowClipMode, GradientStopPre,
+    LinearGradient, RadialGradient, Direction, DirectionCorner, Shape, ExtendMode,
+    StyleCursor, StyleTextAlignmentHorz, StyleFontFamily, LayoutPosition, LayoutWrap, LayoutDirection,
+    LayoutJustifyContent, LayoutAlignItems, LayoutAlignContent, Overflow,
+};
+use crate::css_color::CssColor;
+
+/// Output format of `print_css`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CssPrintMode {
+    /// Prints the whole stylesheet without any unnecessary whitespace, i.e. `div{width:5px;}`
+    Compact,
+    /// Prints one declaration per line, indented by four spaces, with an
+    /// empty line between rule blocks
+    Pretty,
+}
+
+/// Prints a `Css` back to a string that can be parsed again with `new_from_str`.
+///
+/// If the `Css` consists of multiple stylesheets, they are printed one after another,
+/// so parsing the result yields one stylesheet with all the rules.
+///
+/// ```rust
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::{new_from_str, print_css, CssPrintMode};
+/// let css = new_from_str("#my_id { width: 500px; color: red; }").unwrap();
+///
+/// assert_eq!(print_css(&css, CssPrintMode::Compact), "#my_id{width:500px;color:#ff0000;}");
+/// assert_eq!(print_css(&css, CssPrintMode::Pretty), "#my_id {\n    width: 500px;\n    color: #ff0000;\n}\n");
+/// assert_eq!(new_from_str(&print_css(&css, CssPrintMode::Pretty)).unwrap(), css);
+/// ```
+pub fn print_css(css: &Css, mode: CssPrintMode) -> String {
+    let rule_separator = match mode {
+        CssPrintMode::Compact => "",
+        CssPrintMode::Pretty => "\n",
+    };
+    css.rules().map(|rule| print_css_rule_block(rule, mode)).collect::<Vec<_>>().join(rule_separator)
+}
+
+/// Prints the path and the declarations of a single rule block, i.e. `div#my_id { width: 500px; }`
+pub fn print_css_rule_block(rule: &CssRuleBlock, mode: CssPrintMode) -> String {
+
+    let declarations = rule.declarations.iter().flat_map(print_css_declaration);
+
+    match mode {
+        CssPrintMode::Compact => {
+            let declarations = declarations.map(|(key, value)| format!("{}:{};", key, value)).collect::<String>();
+            format!("{}{{{}}}", rule.path, declarations)
+        },
+        CssPrintMode::Pretty => {
+            let declarations = declarations.map(|(key, value)| format!("    {}: {};\n", key, value)).collect::<String>();
+            format!("{} {{\n{}}}\n", rule.path, declarations)
+        },
+    }
+}
+
+/// Prints a static or dynamic declaration as a list of `(key, value)` pairs.
+///
+/// Usually this is only one pair, but a `CssProperty` that was merged from multiple keys
+/// (for example a `StyleBorder` with a different `border-top` and `border-left`)
+/// has to be printed as multiple declarations.
+pub fn print_css_declaration(declaration: &CssDeclaration) -> Vec<(CssPropertyType, String)> {
+    match declaration {
+        CssDeclaration::Static(property) => print_css_property(property),
+        CssDeclaration::Dynamic(dynamic) => print_dynamic_css_property(dynamic).into_iter().collect(),
+    }
+}
+
+/// Prints a dynamic property in the `[[ my_id | 500px ]]` syntax,
+/// returns `None` if the default value doesn't fit the key of the property
+pub fn print_dynamic_css_property(dynamic: &DynamicCssProperty) -> Option<(CssPropertyType, String)> {
+    let default_value = match &dynamic.default {
+        DynamicCssPropertyDefault::Auto => String::from("auto"),
+        DynamicCssPropertyDefault::Exact(property) => print_css_property_value(dynamic.property_type, property)?,
+    };
+    Some((dynamic.property_type, format!("[[ {} | {} ]]", dynamic.dynamic_id, default_value)))
+}
+
+/// Prints a `CssProperty` as a list of `(key, value)` pairs, see `print_css_declaration`.
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::print_css_property;
+/// # use azul_css::{CssProperty, CssPropertyType, LayoutPadding, PixelValue};
+/// let padding = CssProperty::Padding(LayoutPadding::all(PixelValue::px(5.0)));
+/// assert_eq!(print_css_property(&padding), vec![(CssPropertyType::Padding, String::from("5px"))]);
+///
+/// let padding_top = CssProperty::Padding(LayoutPadding { top: Some(PixelValue::px(5.0)), .. Default::default() });
+/// assert_eq!(print_css_property(&padding_top), vec![(CssPropertyType::PaddingTop, String::from("5px"))]);
+/// ```
+pub fn print_css_property(property: &CssProperty) -> Vec<(CssPropertyType, String)> {
+
+    use azul_css::CssPropertyType::*;
+
+    // If all four sides are set, print the shorthand, otherwise print every side that is set
+    macro_rules! print_sides {
+        ($shorthand:expr, $all_sides_set:expr, [$(($side_key:expr, $side:expr)),+]) => {{
+            let keys = if $all_sides_set { vec![$shorthand] } else { vec![$($side_key),+] };
+            keys.into_iter()
+                .filter_map(|key| print_css_property_value(key, property).map(|value| (key, value)))
+                .collect()
+        }};
+    }
+
+    match property {
+        CssProperty::Border(b) => print_sides!(Border, b.top.is_some() && b.top == b.right && b.top == b.bottom && b.top == b.left,
+            [(BorderTop, b.top), (BorderRight, b.right), (BorderBottom, b.bottom), (BorderLeft, b.left)]),
+        CssProperty::BoxShadow(b) => print_sides!(BoxShadow, b.top.is_some() && b.top == b.right && b.top == b.bottom && b.top == b.left,
+            [(BoxShadowTop, b.top), (BoxShadowRight, b.right), (BoxShadowBottom, b.bottom), (BoxShadowLeft, b.left)]),
+        CssProperty::Padding(p) => print_sides!(Padding, p.top.is_some() && p.right.is_some() && p.bottom.is_some() && p.left.is_some(),
+            [(PaddingTop, p.top), (PaddingRight, p.right), (PaddingBottom, p.bottom), (PaddingLeft, p.left)]),
+        CssProperty::Margin(m) => print_sides!(Margin, m.top.is_some() && m.right.is_some() && m.bottom.is_some() && m.left.is_some(),
+            [(MarginTop, m.top), (MarginRight, m.right), (MarginBottom, m.bottom), (MarginLeft, m.left)]),
+        CssProperty::Overflow(o) => print_sides!(Overflow, o.horizontal.is_some() && o.horizontal == o.vertical,
+            [(OverflowX, o.horizontal), (OverflowY, o.vertical)]),
+        other => {
+            let key = other.get_type();
+            print_css_property_value(key, other).map(|value| (key, value)).into_iter().collect()
+        },
+    }
+}
+
+/// Prints the value of a `CssProperty` as it would appear after the given CSS key, i.e.
+/// the `5px` in `padding-top: 5px`. Returns `None` if the property can't be printed
+/// using this key (for example when printing a `CssProperty::Width` for the `height` key or
+/// a `LayoutPadding` without a `top` value for the `padding-top` key).
+pub fn print_css_property_value(key: CssPropertyType, property: &CssProperty) -> Option<String> {
+
+    use azul_css::CssPropertyType as Key;
+    use azul_css::CssProperty as Prop;
+
+    let value = match (key, property) {
+        (Key::Background, Prop::Background(b)) => print_style_background(b),
+        (Key::BackgroundColor, Prop::Background(StyleBackground::Color(c))) => print_color(*c),
+        (Key::BackgroundImage, Prop::Background(StyleBackground::Image(i))) => format!("\"{}\"", i.0),
+        (Key::BackgroundSize, Prop::BackgroundSize(s)) => print_style_background_size(*s).to_string(),
+        (Key::BackgroundRepeat, Prop::BackgroundRepeat(r)) => print_style_background_repeat(*r).to_string(),
+
+        (Key::TextColor, Prop::TextColor(c)) => print_color(c.0),
+        (Key::BorderRadius, Prop::BorderRadius(r)) => print_style_border_radius(r),
+        (Key::FontSize, Prop::FontSize(s)) => print_pixel_value(s.0),
+        (Key::FontFamily, Prop::FontFamily(f)) => print_style_font_family(f),
+        (Key::LetterSpacing, Prop::LetterSpacing(s)) => print_pixel_value(s.0),
+        (Key::WordSpacing, Prop::WordSpacing(s)) => print_pixel_value(s.0),
+        (Key::TabWidth, Prop::TabWidth(t)) => print_percentage_value(t.0),
+        (Key::LineHeight, Prop::LineHeight(l)) => print_percentage_value(l.0),
+        (Key::Cursor, Prop::Cursor(c)) => print_style_cursor(*c).to_string(),
+        (Key::TextAlign, Prop::TextAlign(t)) => print_style_text_align(*t).to_string(),
+
+        (Key::Border, Prop::Border(b)) => print_border_side(b.top?),
+        (Key::BorderTop, Prop::Border(b)) => print_border_side(b.top?),
+        (Key::BorderRight, Prop::Border(b)) => print_border_side(b.right?),
+        (Key::BorderBottom, Prop::Border(b)) => print_border_side(b.bottom?),
+        (Key::BorderLeft, Prop::Border(b)) => print_border_side(b.left?),
+
+        (Key::BoxShadow, Prop::BoxShadow(b)) => print_box_shadow(b.top?),
+        (Key::BoxShadowTop, Prop::BoxShadow(b)) => print_box_shadow(b.top?),
+        (Key::BoxShadowRight, Prop::BoxShadow(b)) => print_box_shadow(b.right?),
+        (Key::BoxShadowBottom, Prop::BoxShadow(b)) => print_box_shadow(b.bottom?),
+        (Key::BoxShadowLeft, Prop::BoxShadow(b)) => print_box_shadow(b.left?),
+
+        (Key::Width, Prop::Width(w)) => print_pixel_value(w.0),
+        (Key::Height, Prop::Height(h)) => print_pixel_value(h.0),
+        (Key::MinWidth, Prop::MinWidth(w)) => print_pixel_value(w.0),
+        (Key::MinHeight, Prop::MinHeight(h)) => print_pixel_value(h.0),
+        (Key::MaxWidth, Prop::MaxWidth(w)) => print_pixel_value(w.0),
+        (Key::MaxHeight, Prop::MaxHeight(h)) => print_pixel_value(h.0),
+
+        (Key::Position, Prop::Position(p)) => print_layout_position(*p).to_string(),
+        (Key::Top, Prop::Top(t)) => print_pixel_value(t.0),
+        (Key::Right, Prop::Right(r)) => print_pixel_value(r.0),
+        (Key::Left, Prop::Left(l)) => print_pixel_value(l.0),
+        (Key::Bottom, Prop::Bottom(b)) => print_pixel_value(b.0),
+
+        (Key::Padding, Prop::Padding(p)) => print_four_sides(p.top?, p.right?, p.bottom?, p.left?, print_pixel_value),
+        (Key::PaddingTop, Prop::Padding(p)) => print_pixel_value(p.top?),
+        (Key::PaddingRight, Prop::Padding(p)) => print_pixel_value(p.right?),
+        (Key::PaddingBottom, Prop::Padding(p)) => print_pixel_value(p.bottom?),
+        (Key::PaddingLeft, Prop::Padding(p)) => print_pixel_value(p.left?),
+
+        (Key::Margin, Prop::Margin(m)) => print_four_sides(m.top?, m.right?, m.bottom?, m.left?, print_pixel_value),
+        (Key::MarginTop, Prop::Margin(m)) => print_pixel_value(m.top?),
+        (Key::MarginRight, Prop::Margin(m)) => print_pixel_value(m.right?),
+        (Key::MarginBottom, Prop::Margin(m)) => print_pixel_value(m.bottom?),
+        (Key::MarginLeft, Prop::Margin(m)) => print_pixel_value(m.left?),
+
+        (Key::FlexWrap, Prop::FlexWrap(w)) => print_layout_wrap(*w).to_string(),
+        (Key::FlexDirection, Prop::FlexDirection(d)) => print_layout_direction(*d).to_string(),
+        (Key::FlexGrow, Prop::FlexGrow(g)) => print_float_value(g.0),
+        (Key::FlexShrink, Prop::FlexShrink(s)) => print_float_value(s.0),
+        (Key::JustifyContent, Prop::JustifyContent(j)) => print_layout_justify_content(*j).to_string(),
+        (Key::AlignItems, Prop::AlignItems(a)) => print_layout_align_items(*a).to_string(),
+        (Key::AlignContent, Prop::AlignContent(a)) => print_layout_align_content(*a).to_string(),
+
+        (Key::Overflow, Prop::Overflow(o)) => print_overflow(o.horizontal?).to_string(),
+        (Key::OverflowX, Prop::Overflow(o)) => print_overflow(o.horizontal?).to_string(),
+        (Key::OverflowY, Prop::Overflow(o)) => print_overflow(o.vertical?).to_string(),
+
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+/// Prints a number with at most three decimal places (the precision of a `FloatValue`),
+/// without trailing zeros, i.e. `5.0` => `"5"`, `0.25` => `"0.25"`
+fn print_number(number: f32) -> String {
+    // The number was divided by 1000 in `FloatValue::get`, so it has to be
+    // rounded (not truncated) to get the original fixed-point number back
+    print_fixed_point_number((number * 1000.0).round() as isize)
+}
+
+/// Prints the fixed-point number of a `FloatValue` (in thousandths)
+fn print_fixed_point_number(number: isize) -> String {
+
+    let sign = if number < 0 { "-" } else { "" };
+    let integer = number.abs() / 1000;
+    let fraction = number.abs() % 1000;
+
+    let printed = format!("{}{}.{:03}", sign, integer, fraction);
+    let printed = printed.trim_end_matches('0').trim_end_matches('.');
+
+    // The parser truncates to three decimal places, so if the printed decimal is parsed as a
+    // slightly smaller float (i.e. "66.666" as 66.665999), the value would change when it is
+    // parsed again - in that case, add half of the last decimal place before it gets truncated.
+    let parsed = printed.parse::<f32>().map(|f| FloatValue::new(f).number);
+    if parsed == Ok(number) {
+        printed.to_string()
+    } else {
+        format!("{}{}.{:03}5", sign, integer, fraction)
+    }
+}
+
+/// Prints a `FloatValue`, i.e. `1.5`
+pub fn print_float_value(value: FloatValue) -> String {
+    print_fixed_point_number(value.number)
+}
+
+/// Prints a `PercentageValue` as a plain number, i.e. `1.2` for `line-height: 120%`
+pub fn print_percentage_value(value: PercentageValue) -> String {
+    print_number(value.get())
+}
+
+/// Prints a `PixelValue` with its metric, i.e. `5px` or `1.5em`
+pub fn print_pixel_value(value: PixelValue) -> String {
+    let metric = match value.metric {
+        SizeMetric::Px => "px",
+        SizeMetric::Pt => "pt",
+        SizeMetric::Em => "em",
+    };
+    format!("{}{}", print_float_value(value.number), metric)
+}
+
+/// Prints a color as a lowercase hex color, i.e. `#ff0000` or `#ff000080` if the color is transparent
+pub fn print_color(color: ColorU) -> String {
+    CssColor::from(color).to_string(true)
+}
+
+/// Prints values for the `padding`, `margin` and `border-radius` shorthands, omitting the
+/// values that can be inferred from the other sides, so `5px 5px 5px 5px` is printed as `5px`.
+///
+/// The arguments have to be given in clockwise order, starting at the top (or top left) side.
+fn print_four_sides<T: PartialEq + Copy, F: Fn(T) -> String>(top: T, right: T, bottom: T, left: T, print_fn: F) -> String {
+    if top == right && top == bottom && top == left {
+        print_fn(top)
+    } else if top == bottom && right == left {
+        format!("{} {}", print_fn(top), print_fn(right))
+    } else if right == left {
+        format!("{} {} {}", print_fn(top), print_fn(right), print_fn(bottom))
+    } else {
+        format!("{} {} {} {}", print_fn(top), print_fn(right), print_fn(bottom), print_fn(left))
+    }
+}
+
+/// Prints a `border-radius`. Only the horizontal radius of each corner is printed,
+/// since elliptical corners (`5px / 10px`) can't be parsed yet.
+pub fn print_style_border_radius(radius: &StyleBorderRadius) -> String {
+    let r = radius.0;
+    print_four_sides(r.top_left.width, r.top_right.width, r.bottom_right.width, r.bottom_left.width, print_pixel_value)
+}
+
+/// Prints a `border` side, i.e. `1px solid #000000`
+pub fn print_border_side(border: StyleBorderSide) -> String {
+    format!("{} {} {}",
+        print_pixel_value(border.border_width),
+        print_border_style(border.border_style),
+        print_color(border.border_color),
+    )
+}
+
+/// Prints a `box-shadow`, i.e. `5px 10px 5px 0px #000000 inset`, or `none` if there is no shadow
+pub fn print_box_shadow(box_shadow: Option<BoxShadowPreDisplayItem>) -> String {
+    let box_shadow = match box_shadow {
+        Some(s) => s,
+        None => return String::from("none"),
+    };
+
+    let clip_mode = match box_shadow.clip_mode {
+        BoxShadowClipMode::Outset => "",
+        BoxShadowClipMode::Inset => " inset",
+    };
+
+    format!("{} {} {} {} {}{}",
+        print_pixel_value(box_shadow.offset[0]),
+        print_pixel_value(box_shadow.offset[1]),
+        print_pixel_value(box_shadow.blur_radius),
+        print_pixel_value(box_shadow.spread_radius),
+        print_color(box_shadow.color),
+        clip_mode,
+    )
+}
+
+/// Prints the value of a `background` key: a color, gradient, image or `none`
+pub fn print_style_background(background: &StyleBackground) -> String {
+    match background {
+        StyleBackground::LinearGradient(g) => print_linear_gradient(g),
+        StyleBackground::RadialGradient(g) => print_radial_gradient(g),
+        StyleBackground::Image(id) => format!("image(\"{}\")", id.0),
+        StyleBackground::Color(c) => print_color(*c),
+        StyleBackground::NoBackground => String::from("none"),
+    }
+}
+
+/// Prints a `linear-gradient(to right, #ff0000 0%, #0000ff 100%)`
+pub fn print_linear_gradient(gradient: &LinearGradient) -> String {
+    let gradient_type = match gradient.extend_mode {
+        ExtendMode::Clamp => "linear-gradient",
+        ExtendMode::Repeat => "repeating-linear-gradient",
+    };
+    format!("{}({}, {})", gradient_type, print_direction(gradient.direction), print_gradient_stops(&gradient.stops))
+}
+
+/// Prints a `radial-gradient(circle, #ff0000 0%, #0000ff 100%)`
+pub fn print_radial_gradient(gradient: &RadialGradient) -> String {
+    let gradient_type = match gradient.extend_mode {
+        ExtendMode::Clamp => "radial-gradient",
+        ExtendMode::Repeat => "repeating-radial-gradient",
+    };
+    let shape = match gradient.shape {
+        Shape::Ellipse => "ellipse",
+        Shape::Circle => "circle",
+    };
+    format!("{}({}, {})", gradient_type, shape, print_gradient_stops(&gradient.stops))
+}
+
+fn print_gradient_stops(stops: &[GradientStopPre]) -> String {
+    stops.iter().map(|stop| match stop.offset {
+        Some(offset) => format!("{} {}%", print_color(stop.color), print_number(offset.get())),
+        None => print_color(stop.color),
+    }).collect::<Vec<_>>().join(", ")
+}
+
+/// Prints the direction of a linear gradient, i.e. `45deg` or `to bottom right`
+pub fn print_direction(direction: Direction) -> String {
+    match direction {
+        Direction::Angle(deg) => format!("{}deg", print_float_value(deg)),
+        Direction::FromTo(_, to) => format!("to {}", print_direction_corner(to)),
+    }
+}
+
+fn print_direction_corner(corner: DirectionCorner) -> &'static str {
+    use azul_css::DirectionCorner::*;
+    match corner {
+        Right => "right",
+        Left => "left",
+        Top => "top",
+        Bottom => "bottom",
+        TopRight => "top right",
+        TopLeft => "top left",
+        BottomRight => "bottom right",
+        BottomLeft => "bottom left",
+    }
+}
+
+/// Prints a `font-family`, with every font in double quotes, i.e. `"Helvetica", "Arial"`
+pub fn print_style_font_family(font_family: &StyleFontFamily) -> String {
+    font_family.fonts.iter().map(|font| format!("\"{}\"", font.get_str())).collect::<Vec<_>>().join(", ")
+}
+
+/// Generates a function that prints an enum as one of the given keywords,
+/// inverse of the `multi_type_parser!` macro in `css_parser`
+macro_rules! keyword_printer {
+    ($fn:ident, $enum_type:ident, $([$variant:ident, $identifier_string:expr]),+) => {
+        pub fn $fn(input: $enum_type) -> &'static str {
+            match input {
+                $(
+                    $enum_type::$variant => $identifier_string,
+                )+
+            }
+        }
+    };
+}
+
+keyword_printer!(print_border_style, BorderStyle,
+    [None, "none"],
+    [Solid, "solid"],
+    [Double, "double"],
+    [Dotted, "dotted"],
+    [Dashed, "dashed"],
+    [Hidden, "hidden"],
+    [Groove, "groove"],
+    [Ridge, "ridge"],
+    [Inset, "inset"],
+    [Outset, "outset"]);
+
+keyword_printer!(print_style_cursor, StyleCursor,
+    [Alias, "alias"],
+    [AllScroll, "all-scroll"],
+    [Cell, "cell"],
+    [ColResize, "col-resize"],
+    [ContextMenu, "context-menu"],
+    [Copy, "copy"],
+    [Crosshair, "crosshair"],
+    [Default, "default"],
+    [EResize, "e-resize"],
+    [EwResize, "ew-resize"],
+    [Grab, "grab"],
+    [Grabbing, "grabbing"],
+    [Help, "help"],
+    [Move, "move"],
+    [NResize, "n-resize"],
+    [NsResize, "ns-resize"],
+    [NeswResize, "nesw-resize"],
+    [NwseResize, "nwse-resize"],
+    [Pointer, "pointer"],
+    [Progress, "progress"],
+    [RowResize, "row-resize"],
+    [SResize, "s-resize"],
+    [SeResize, "se-resize"],
+    [Text, "text"],
+    [Unset, "unset"],
+    [VerticalText, "vertical-text"],
+    [WResize, "w-resize"],
+    [Wait, "wait"],
+    [ZoomIn, "zoom-in"],
+    [ZoomOut, "zoom-out"]);
+
+keyword_printer!(print_style_background_size, StyleBackgroundSize,
+    [Contain, "contain"],
+    [Cover, "cover"]);
+
+keyword_printer!(print_style_background_repeat, StyleBackgroundRepeat,
+    [NoRepeat, "no-repeat"],
+    [Repeat, "repeat"],
+    [RepeatX, "repeat-x"],
+    [RepeatY, "repeat-y"]);
+
+keyword_printer!(print_layout_direction, LayoutDirection,
+    [Row, "row"],
+    [RowReverse, "row-reverse"],
+    [Column, "column"],
+    [ColumnReverse, "column-reverse"]);
+
+keyword_printer!(print_layout_wrap, LayoutWrap,
+    [Wrap, "wrap"],
+    [NoWrap, "nowrap"]);
+
+keyword_printer!(print_layout_justify_content, LayoutJustifyContent,
+    [Start, "flex-start"],
+    [End, "flex-end"],
+    [Center, "center"],
+    [SpaceBetween, "space-between"],
+    [SpaceAround, "space-around"]);
+
+keyword_printer!(print_layout_align_items, LayoutAlignItems,
+    [Start, "flex-start"],
+    [End, "flex-end"],
+    [Stretch, "stretch"],
+    [Center, "center"]);
+
+keyword_printer!(print_layout_align_content, LayoutAlignContent,
+    [Start, "flex-start"],
+    [End, "flex-end"],
+    [Stretch, "stretch"],
+    [Center, "center"],
+    [SpaceBetween, "space-between"],
+    [SpaceAround, "space-around"]);
+
+keyword_printer!(print_layout_position, LayoutPosition,
+    [Static, "static"],
+    [Absolute, "absolute"],
+    [Relative, "relative"]);
+
+keyword_printer!(print_overflow, Overflow,
+    [Auto, "auto"],
+    [Scroll, "scroll"],
+    [Visible, "visible"],
+    [Hidden, "hidden"]);
+
+keyword_printer!(print_style_text_align, StyleTextAlignmentHorz,
+    [Center, "center"],
+    [Left, "left"],
+    [Right, "right"]);
+
+#[cfg(test)]
+mod printer_tests {
+
+    use super::*;
+    use crate::css::new_from_str;
+    use crate::css_parser::parse_key_value_pair;
+
+    /// Small xorshift PRNG, so that the round-trip test is reproducible without extra dependencies
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+        fn below(&mut self, max: usize) -> usize {
+            (self.next() % max as u64) as usize
+        }
+        fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+            items[self.below(items.len())]
+        }
+        fn number(&mut self) -> String {
+            // up to three decimal places, like FloatValue
+            let number = self.below(200_000) as f32 / 1000.0;
+            print_number(number)
+        }
+        fn pixel(&mut self) -> String {
+            let sign = if self.below(4) == 0 { "-" } else { "" };
+            format!("{}{}{}", sign, self.number(), self.pick(&["px", "pt", "em"]))
+        }
+        fn color(&mut self) -> String {
+            match self.below(3) {
+                0 => format!("#{:06x}", self.next() & 0xffffff),
+                1 => format!("#{:08x}", self.next() & 0xffffffff),
+                _ => self.pick(&["red", "blue", "black", "white"]).to_string(),
+            }
+        }
+        /// Colors with spaces can't be used in space-separated values such as `border`
+        fn any_color(&mut self) -> String {
+            match self.below(2) {
+                0 => format!("rgba({}, {}, {}, 0.5)", self.below(256), self.below(256), self.below(256)),
+                _ => self.color(),
+            }
+        }
+        fn stops(&mut self) -> String {
+            let count = 2 + self.below(3);
+            (0..count).map(|i| {
+                if self.below(2) == 0 { format!("{} {}%", self.color(), i * 20) } else { self.color() }
+            }).collect::<Vec<_>>().join(", ")
+        }
+        fn border(&mut self) -> String {
+            format!("{} {} {}", self.pixel(), self.pick(&["solid", "dashed", "dotted", "double", "none"]), self.color())
+        }
+        fn shadow(&mut self) -> String {
+            match self.below(4) {
+                0 => String::from("none"),
+                1 => format!("{} {} {}", self.pixel(), self.pixel(), self.color()),
+                2 => format!("{} {} {} {} {}", self.pixel(), self.pixel(), self.pixel(), self.pixel(), self.color()),
+                _ => format!("{} {} {} {} {} inset", self.pixel(), self.pixel(), self.pixel(), self.pixel(), self.color()),
+            }
+        }
+        fn sides(&mut self) -> String {
+            let count = 1 + self.below(4);
+            (0..count).map(|_| self.pixel()).collect::<Vec<_>>().join(" ")
+        }
+    }
+
+    /// Generates a random, valid value for the given CSS key
+    fn random_value(rng: &mut Rng, key: CssPropertyType) -> String {
+        use azul_css::CssPropertyType::*;
+        match key {
+            Background => match rng.below(6) {
+                0 => rng.any_color(),
+                1 => String::from("none"),
+                2 => String::from("image(\"my_image\")"),
+                3 => format!("linear-gradient({}, {})", rng.pick(&["to right", "to bottom left", "45deg", "0.5rad"]), rng.stops()),
+                4 => format!("repeating-linear-gradient({})", rng.stops()),
+                _ => format!("{}({}, {})", rng.pick(&["radial-gradient", "repeating-radial-gradient"]), rng.pick(&["circle", "ellipse"]), rng.stops()),
+            },
+            BackgroundColor | TextColor => rng.any_color(),
+            BackgroundImage => String::from("\"my_image\""),
+            BackgroundSize => rng.pick(&["contain", "cover"]).to_string(),
+            BackgroundRepeat => rng.pick(&["no-repeat", "repeat", "repeat-x", "repeat-y"]).to_string(),
+            BorderRadius | Padding | Margin => rng.sides(),
+            FontFamily => String::from("\"Helvetica\", 'Arial', sans-serif"),
+            TextAlign => rng.pick(&["left", "center", "right"]).to_string(),
+            LineHeight | TabWidth | FlexGrow | FlexShrink => rng.number(),
+            Cursor => rng.pick(&["pointer", "default", "col-resize", "zoom-out"]).to_string(),
+            Position => rng.pick(&["static", "relative", "absolute"]).to_string(),
+            FlexWrap => rng.pick(&["wrap", "nowrap"]).to_string(),
+            FlexDirection => rng.pick(&["row", "row-reverse", "column", "column-reverse"]).to_string(),
+            JustifyContent => rng.pick(&["flex-start", "flex-end", "center", "space-between", "space-around"]).to_string(),
+            AlignItems => rng.pick(&["flex-start", "flex-end", "stretch", "center"]).to_string(),
+            AlignContent => rng.pick(&["flex-start", "flex-end", "stretch", "center", "space-between", "space-around"]).to_string(),
+            Overflow | OverflowX | OverflowY => rng.pick(&["auto", "scroll", "visible", "hidden"]).to_string(),
+            Border | BorderTop | BorderLeft | BorderRight | BorderBottom => rng.border(),
+            BoxShadow | BoxShadowTop | BoxShadowLeft | BoxShadowRight | BoxShadowBottom => rng.shadow(),
+            FontSize | LetterSpacing | WordSpacing | Width | Height | MinWidth | MinHeight |
+            MaxWidth | MaxHeight | Top | Right | Left | Bottom |
+            PaddingTop | PaddingLeft | PaddingRight | PaddingBottom |
+            MarginTop | MarginLeft | MarginRight | MarginBottom => rng.pixel(),
+        }
+    }
+
+    #[test]
+    fn test_print_css_round_trip() {
+
+        let key_map = azul_css::get_css_key_map();
+        let keys = key_map.values().cloned().collect::<Vec<_>>();
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..200 {
+
+            let mut input = String::new();
+
+            for block_idx in 0..(1 + rng.below(4)) {
+                input.push_str(&format!("#id_{} .class_{} {{\n", block_idx, rng.below(100)));
+                for _ in 0..rng.below(10) {
+                    let key = keys[rng.below(keys.len())];
+                    let value = random_value(&mut rng, key);
+                    // Keys such as `font-family` accept any string, so the ID would be a valid value
+                    let can_be_dynamic = parse_key_value_pair(key, "dynamic_id").is_err();
+                    let value = match rng.below(4) {
+                        0 if can_be_dynamic => format!("[[ dynamic_id | {} ]]", value),
+                        1 if can_be_dynamic => String::from("[[ dynamic_id | auto ]]"),
+                        _ => value,
+                    };
+                    input.push_str(&format!("    {}: {};\n", key, value));
+                }
+                input.push_str("}\n");
+            }
+
+            let parsed = match new_from_str(&input) {
+                Ok(o) => o,
+                Err(e) => panic!("generated invalid CSS: {}\n{}", e, input),
+            };
+
+            for mode in &[CssPrintMode::Compact, CssPrintMode::Pretty] {
+                let printed = print_css(&parsed, *mode);
+                let reparsed = match new_from_str(&printed) {
+                    Ok(o) => o,
+                    Err(e) => panic!("could not parse printed CSS: {}\n{}\ninput:\n{}", e, printed, input),
+                };
+                assert_eq!(parsed, reparsed, "\nprinted:\n{}\ninput:\n{}", printed, input);
+            }
+        }
+    }
+
+    #[test]
+    fn test_print_number() {
+        assert_eq!(print_float_value(FloatValue::new(5.0)), "5");
+        assert_eq!(print_float_value(FloatValue::new(0.25)), "0.25");
+        assert_eq!(print_float_value(FloatValue::new(-1.5)), "-1.5");
+        assert_eq!(print_float_value(FloatValue::new(0.0)), "0");
+
+        // Every printed value has to be parsed back into the same value
+        for number in -5000..5000 {
+            let value = FloatValue { number };
+            let printed = print_float_value(value);
+            assert_eq!(FloatValue::new(printed.parse::<f32>().unwrap()), value, "{}", printed);
+
+            let percentage = PercentageValue::new(value.get());
+            let printed = print_percentage_value(percentage);
+            assert_eq!(PercentageValue::new(printed.parse::<f32>().unwrap()), percentage, "{}", printed);
+        }
+    }
+
+    #[test]
+    fn test_print_css_property_sides() {
+        use azul_css::{LayoutPadding, LayoutOverflow, StyleBorder};
+
+        let padding = LayoutPadding {
+            top: Some(PixelValue::px(1.0)),
+            right: Some(PixelValue::px(2.0)),
+            bottom: Some(PixelValue::px(1.0)),
+            left: Some(PixelValue::px(2.0)),
+        };
+        assert_eq!(print_css_property(&padding.into()), vec![(CssPropertyType::Padding, String::from("1px 2px"))]);
+
+        let overflow = LayoutOverflow { horizontal: None, vertical: Some(Overflow::Scroll) };
+        assert_eq!(print_css_property(&overflow.into()), vec![(CssPropertyType::OverflowY, String::from("scroll"))]);
+
+        let side = StyleBorderSide { border_width: PixelValue::px(1.5), border_style: BorderStyle::Solid, border_color: ColorU { r: 0, g: 0, b: 0, a: 128 } };
+        let border = StyleBorder { top: Some(side), left: Some(side), .. Default::default() };
+        assert_eq!(print_css_property(&border.into()), vec![
+            (CssPropertyType::BorderTop, String::from("1.5px solid #00000080")),
+            (CssPropertyType::BorderLeft, String::from("1.5px solid #00000080")),
+        ]);
+    }
+}
@@ -8,6 +8,9 @@ use std::path::PathBuf;
 pub const DEFAULT_RELOAD_INTERVAL: Duration = Duration::from_millis(500);
 
 /// Allows dynamic reloading of a CSS file at application runtime.
+///
+/// Invalid rules and declarations in the file are skipped and reported as warnings
+/// (see `new_from_str_with_warnings`), so that a typo doesn't reset the entire style.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct HotReloader {
     file_path: PathBuf,
@@ -28,6 +31,10 @@ impl HotReloader {
 
 impl HotReloadHandler for HotReloader {
     fn reload_style(&mut self) -> Result<Css, String> {
+        self.reload_style_with_warnings().map(|(css, _)| css)
+    }
+
+    fn reload_style_with_warnings(&mut self) -> Result<(Css, Vec<String>), String> {
         use std::fs;
         use crate::css;
 
@@ -36,7 +43,10 @@ impl HotReloadHandler for HotReloader {
         let reloaded_css = fs::read_to_string(&self.file_path)
             .map_err(|e| format!("Io error: Could not load \"{}\" when loading file: \"{}\"", file_name, e))?;
 
-        css::new_from_str(&reloaded_css).map_err(|e| format!("{}: {}", file_name, e))
+        let (css, warnings) = css::new_from_str_with_warnings(&reloaded_css);
+        let warnings = warnings.iter().map(|w| format!("{}: {}", file_name, w)).collect();
+
+        Ok((css, warnings))
     }
 
     fn get_reload_interval(&self) -> Duration {
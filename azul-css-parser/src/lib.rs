@@ -21,17 +21,24 @@ mod macros;
 
 mod css_parser;
 mod css;
+mod css_printer;
 mod hot_reloader;
 
 pub use crate::css::{
     new_from_str,
+    new_from_str_with_warnings,
     parse_css_path,
     CssParseError,
+    CssParseErrorInner,
+    CssParseWarning,
     CssPathParseError,
+    ErrorLocation,
 };
 
 pub use crate::css_parser::*;
 
+pub use crate::css_printer::*;
+
 pub use crate::hot_reloader::{
     HotReloader,
 };
@@ -144,6 +144,7 @@ pub type CssContentGroup<'a> = Vec<&'a CssPathSelector>;
 pub enum NodeTypePath {
     Div,
     P,
+    Span,
     Img,
     Texture,
     IFrame,
@@ -162,9 +163,10 @@ impl<'a> fmt::Display for NodeTypePathParseError<'a> {
     }
 }
 
-const NODE_TYPE_PATH_MAP: [(NodeTypePath, &'static str); 5] = [
+const NODE_TYPE_PATH_MAP: [(NodeTypePath, &'static str); 6] = [
     (NodeTypePath::Div, "div"),
     (NodeTypePath::P, "p"),
+    (NodeTypePath::Span, "span"),
     (NodeTypePath::Img, "img"),
     (NodeTypePath::Texture, "texture"),
     (NodeTypePath::IFrame, "iframe"),
@@ -276,6 +278,12 @@ pub enum CssPathPseudoSelector {
     Active,
     /// `:focus` - element has received focus
     Focus,
+    /// `:drag-over` - something is being dragged over the element
+    DragOver,
+    /// `:checked` - element is checked, see `Dom::with_checked`
+    Checked,
+    /// `:disabled` - element is disabled, see `Dom::with_disabled`
+    Disabled,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -308,6 +316,9 @@ impl fmt::Display for CssPathPseudoSelector {
             Hover => write!(f, "hover"),
             Active => write!(f, "active"),
             Focus => write!(f, "focus"),
+            DragOver => write!(f, "drag-over"),
+            Checked => write!(f, "checked"),
+            Disabled => write!(f, "disabled"),
         }
     }
 }
@@ -14,6 +14,12 @@ pub trait HotReloadHandler {
     /// reloaded, and an human-readable error string otherwise (since the error needs to be printed
     /// to stdout when hot-reloading).
     fn reload_style(&mut self) -> Result<Css, String>;
+    /// Same as `reload_style`, but additionally returns human-readable warnings for
+    /// the parts of the style that could not be loaded and were skipped. Handlers that
+    /// can recover from errors should override this - by default, there are no warnings.
+    fn reload_style_with_warnings(&mut self) -> Result<(Css, Vec<String>), String> {
+        self.reload_style().map(|css| (css, Vec::new()))
+    }
     /// Returns how quickly the hot-reloader should reload the source format.
     fn get_reload_interval(&self) -> Duration;
 }
@@ -40,14 +46,19 @@ impl HotReloadOverrideHandler {
 
 impl HotReloadHandler for HotReloadOverrideHandler {
     fn reload_style(&mut self) -> Result<Css, String> {
+        self.reload_style_with_warnings().map(|(css, _)| css)
+    }
+
+    fn reload_style_with_warnings(&mut self) -> Result<(Css, Vec<String>), String> {
+        let (reloaded_css, warnings) = self.hot_reloader.reload_style_with_warnings()?;
         let mut css = Css::new();
         for stylesheet in self.base_style.clone().stylesheets {
             css.append_stylesheet(stylesheet);
         }
-        for stylesheet in self.hot_reloader.reload_style()?.stylesheets {
+        for stylesheet in reloaded_css.stylesheets {
             css.append_stylesheet(stylesheet);
         }
-        Ok(css)
+        Ok((css, warnings))
     }
 
     fn get_reload_interval(&self) -> Duration {
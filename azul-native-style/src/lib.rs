@@ -1,7 +1,8 @@
 //! Provides azul-compatible approximations of OS-native styles.
 
 extern crate azul_css;
-use azul_css::Css;
+use azul_css::{Css, ColorU};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 extern crate azul_css_parser;
 
@@ -26,7 +27,318 @@ pub const NATIVE_CSS: &str = concat!(
     include_str!("styles/shared/table.css"),
 );
 
-/// Returns the native style for the OS
+/// Overrides for `NATIVE_CSS` in dark mode - Windows: `styles/native_windows_dark.css`
+#[cfg(target_os="windows")]
+pub const NATIVE_CSS_DARK: &str = concat!(
+    include_str!("styles/native_windows_dark.css"),
+    include_str!("styles/shared/table_dark.css"),
+);
+
+/// Overrides for `NATIVE_CSS` in dark mode - Linux: `styles/native_linux_dark.css`
+#[cfg(target_os="linux")]
+pub const NATIVE_CSS_DARK: &str = concat!(
+    include_str!("styles/native_linux_dark.css"),
+    include_str!("styles/shared/table_dark.css"),
+);
+
+/// Overrides for `NATIVE_CSS` in dark mode - Mac: `styles/native_macos_dark.css`
+#[cfg(target_os="macos")]
+pub const NATIVE_CSS_DARK: &str = concat!(
+    include_str!("styles/native_macos_dark.css"),
+    include_str!("styles/shared/table_dark.css"),
+);
+
+/// Overrides for `NATIVE_CSS` in high-contrast mode - Windows: `styles/native_windows_high_contrast.css`
+#[cfg(target_os="windows")]
+pub const NATIVE_CSS_HIGH_CONTRAST: &str = concat!(
+    include_str!("styles/native_windows_high_contrast.css"),
+    include_str!("styles/shared/table_high_contrast.css"),
+);
+
+/// Overrides for `NATIVE_CSS` in high-contrast mode - Linux: `styles/native_linux_high_contrast.css`
+#[cfg(target_os="linux")]
+pub const NATIVE_CSS_HIGH_CONTRAST: &str = concat!(
+    include_str!("styles/native_linux_high_contrast.css"),
+    include_str!("styles/shared/table_high_contrast.css"),
+);
+
+/// Overrides for `NATIVE_CSS` in high-contrast mode - Mac: `styles/native_macos_high_contrast.css`
+#[cfg(target_os="macos")]
+pub const NATIVE_CSS_HIGH_CONTRAST: &str = concat!(
+    include_str!("styles/native_macos_high_contrast.css"),
+    include_str!("styles/shared/table_high_contrast.css"),
+);
+
+/// Color scheme of the native style
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum NativeTheme {
+    /// Default, light theme (`NATIVE_CSS`)
+    Light,
+    /// Dark theme (`NATIVE_CSS` + `NATIVE_CSS_DARK`)
+    Dark,
+    /// High-contrast theme for accessibility (`NATIVE_CSS` + `NATIVE_CSS_HIGH_CONTRAST`)
+    HighContrast,
+}
+
+impl Default for NativeTheme {
+    fn default() -> Self {
+        NativeTheme::Light
+    }
+}
+
+impl NativeTheme {
+
+    /// Returns the background color of the window in this theme - the same color as the
+    /// background of the `.__azul-native-root` node in the stylesheet of the theme.
+    ///
+    /// Returns `None` for the light theme, which doesn't change the window background.
+    pub fn get_window_background(&self) -> Option<ColorU> {
+        match self {
+            NativeTheme::Light => None,
+            NativeTheme::Dark => Some(WINDOW_BACKGROUND_DARK),
+            NativeTheme::HighContrast => Some(WINDOW_BACKGROUND_HIGH_CONTRAST),
+        }
+    }
+}
+
+/// Class of the root node of every window, see `NativeTheme::get_window_background`
+pub const NATIVE_ROOT_CLASS: &str = "__azul-native-root";
+
+#[cfg(target_os="windows")]
+const WINDOW_BACKGROUND_DARK: ColorU = ColorU { r: 32, g: 32, b: 32, a: 255 };
+#[cfg(target_os="linux")]
+const WINDOW_BACKGROUND_DARK: ColorU = ColorU { r: 53, g: 53, b: 53, a: 255 };
+#[cfg(target_os="macos")]
+const WINDOW_BACKGROUND_DARK: ColorU = ColorU { r: 30, g: 30, b: 30, a: 255 };
+
+#[cfg(target_os="windows")]
+const WINDOW_BACKGROUND_HIGH_CONTRAST: ColorU = ColorU { r: 0, g: 0, b: 0, a: 255 };
+#[cfg(any(target_os="linux", target_os="macos"))]
+const WINDOW_BACKGROUND_HIGH_CONTRAST: ColorU = ColorU { r: 255, g: 255, b: 255, a: 255 };
+
+/// Theme that was returned by the last OS query, `0` if the OS wasn't queried yet
+static LAST_SYSTEM_THEME: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns the native style for the OS. Never queries the OS: the theme is the result of the
+/// last `get_system_theme` call (i.e. by the theme watcher of the app) or `NativeTheme::Light`
+/// if the OS wasn't queried yet - see `native_with_system_theme`.
 pub fn native() -> Css {
-    azul_css_parser::new_from_str(NATIVE_CSS).unwrap()
+    native_with_theme(get_last_system_theme().unwrap_or(NativeTheme::Light))
+}
+
+/// Returns the native style for the OS, in the theme that the OS is currently set to
+/// (see `get_cached_system_theme`).
+///
+/// Note: Queries the OS on the first call, see `get_system_theme`.
+pub fn native_with_system_theme() -> Css {
+    native_with_theme(get_cached_system_theme())
+}
+
+/// Queries the theme that the OS is currently set to, falls back to `NativeTheme::Light`
+/// if the theme can't be determined.
+///
+/// Note: This spawns a process (`gsettings` on Linux, `defaults` on Mac, `reg` on Windows)
+/// and waits for it to exit, so it shouldn't be called from the UI thread. Use
+/// `get_cached_system_theme` instead, which only queries the OS once.
+pub fn get_system_theme() -> NativeTheme {
+    let theme = query_system_theme();
+    let cached = match theme {
+        NativeTheme::Light => 1,
+        NativeTheme::Dark => 2,
+        NativeTheme::HighContrast => 3,
+    };
+    LAST_SYSTEM_THEME.store(cached, Ordering::SeqCst);
+    theme
+}
+
+/// Returns the OS theme of the last `get_system_theme` call. Only queries the OS
+/// if it wasn't queried before, so this is cheap to call repeatedly.
+pub fn get_cached_system_theme() -> NativeTheme {
+    get_last_system_theme().unwrap_or_else(get_system_theme)
+}
+
+/// Returns the OS theme of the last `get_system_theme` call, `None` if the OS wasn't queried yet
+fn get_last_system_theme() -> Option<NativeTheme> {
+    match LAST_SYSTEM_THEME.load(Ordering::SeqCst) {
+        1 => Some(NativeTheme::Light),
+        2 => Some(NativeTheme::Dark),
+        3 => Some(NativeTheme::HighContrast),
+        _ => None,
+    }
+}
+
+/// Returns the native style for the OS in the given theme, regardless of the system theme.
+///
+/// The overrides for the dark / high-contrast themes are appended as a separate
+/// stylesheet, so they take precedence over the light theme without being mixed up
+/// with it during `Css::sort_by_specificity()`.
+pub fn native_with_theme(theme: NativeTheme) -> Css {
+    let mut css = azul_css_parser::new_from_str(NATIVE_CSS).unwrap();
+    match theme {
+        NativeTheme::Light => { },
+        NativeTheme::Dark => css.append(azul_css_parser::new_from_str(NATIVE_CSS_DARK).unwrap()),
+        NativeTheme::HighContrast => css.append(azul_css_parser::new_from_str(NATIVE_CSS_HIGH_CONTRAST).unwrap()),
+    }
+    css
+}
+
+// Queries the theme via "gsettings"
+#[cfg(target_os="linux")]
+fn query_system_theme() -> NativeTheme {
+
+    // GNOME 42+ stores the dark mode preference separately from the GTK theme
+    let color_scheme = gsettings_get("color-scheme").unwrap_or_default();
+    let gtk_theme = gsettings_get("gtk-theme").unwrap_or_default();
+
+    match parse_gsettings_theme(&gtk_theme) {
+        NativeTheme::Light if parse_gsettings_color_scheme(&color_scheme) => NativeTheme::Dark,
+        other => other,
+    }
+}
+
+// Queries the theme via "defaults"
+#[cfg(target_os="macos")]
+fn query_system_theme() -> NativeTheme {
+
+    let defaults_read = |domain: &str, key: &str| {
+        Command::new("defaults")
+            .arg("read")
+            .arg(domain)
+            .arg(key)
+            .output()
+            .ok().map(|output| output.stdout)
+            .and_then(|stdout_bytes| String::from_utf8(stdout_bytes).ok())
+            .map(|stdout_string| stdout_string.trim().to_string())
+    };
+
+    // "defaults read com.apple.universalaccess increaseContrast" => "1"
+    if defaults_read("com.apple.universalaccess", "increaseContrast").as_ref().map(|s| s.as_str()) == Some("1") {
+        return NativeTheme::HighContrast;
+    }
+
+    // "defaults read -g AppleInterfaceStyle" => "Dark", the key doesn't exist in light mode
+    match defaults_read("-g", "AppleInterfaceStyle") {
+        Some(ref s) if s == "Dark" => NativeTheme::Dark,
+        _ => NativeTheme::Light,
+    }
+}
+
+// Queries the theme via "reg"
+#[cfg(target_os="windows")]
+fn query_system_theme() -> NativeTheme {
+
+    let reg_query = |key: &str, value: &str| {
+        Command::new("reg")
+            .arg("query")
+            .arg(key)
+            .arg("/v")
+            .arg(value)
+            .output()
+            .ok().map(|output| output.stdout)
+            .and_then(|stdout_bytes| String::from_utf8(stdout_bytes).ok())
+    };
+
+    // High contrast themes are stored as flags, bit 0 (HCF_HIGHCONTRASTON) is set when active
+    let high_contrast = reg_query(r"HKCU\Control Panel\Accessibility\HighContrast", "Flags")
+        .and_then(|s| parse_reg_query_value(&s))
+        .map(|flags| flags & 1 == 1)
+        .unwrap_or(false);
+
+    if high_contrast {
+        return NativeTheme::HighContrast;
+    }
+
+    let apps_use_light_theme = reg_query(r"HKCU\Software\Microsoft\Windows\CurrentVersion\Themes\Personalize", "AppsUseLightTheme")
+        .and_then(|s| parse_reg_query_value(&s));
+
+    match apps_use_light_theme {
+        Some(0) => NativeTheme::Dark,
+        _ => NativeTheme::Light,
+    }
+}
+
+#[cfg(any(target_os="linux", target_os="macos", target_os="windows"))]
+use std::process::Command;
+
+/// Executes "gsettings get org.gnome.desktop.interface `key`" and returns the output
+/// without the trailing newline, i.e. `"'Adwaita-dark'"` for the key `"gtk-theme"`.
+/// Returns `None` if gsettings isn't installed.
+#[cfg(target_os="linux")]
+pub fn gsettings_get(key: &str) -> Option<String> {
+    Command::new("gsettings")
+        .arg("get")
+        .arg("org.gnome.desktop.interface")
+        .arg(key)
+        .output()
+        .ok().map(|output| output.stdout)
+        .and_then(|stdout_bytes| String::from_utf8(stdout_bytes).ok())
+        .map(|stdout_string| stdout_string.lines().collect::<String>())
+}
+
+// "'Adwaita-dark'" => Dark, "'HighContrast'" => HighContrast, "'Ambiance'" => Light
+#[cfg(target_os="linux")]
+fn parse_gsettings_theme(input: &str) -> NativeTheme {
+    let input = input.trim().trim_matches('\'').to_lowercase();
+    if input.contains("highcontrast") || input.contains("high-contrast") {
+        NativeTheme::HighContrast
+    } else if input.ends_with("-dark") || input.ends_with("_dark") || input.contains("-dark-") {
+        NativeTheme::Dark
+    } else {
+        NativeTheme::Light
+    }
+}
+
+// "'prefer-dark'" => true, "'default'" => false
+#[cfg(target_os="linux")]
+fn parse_gsettings_color_scheme(input: &str) -> bool {
+    input.trim().trim_matches('\'') == "prefer-dark"
+}
+
+// "    AppsUseLightTheme    REG_DWORD    0x0" => Some(0)
+#[cfg(target_os="windows")]
+fn parse_reg_query_value(input: &str) -> Option<u32> {
+    let value = input.lines().find(|line| line.contains("REG_DWORD"))?.split_whitespace().last()?;
+    u32::from_str_radix(value.trim_start_matches("0x"), 16).ok()
+}
+
+#[cfg(target_os="linux")]
+#[test]
+fn test_parse_gsettings_theme() {
+    assert_eq!(parse_gsettings_theme("'Adwaita'"), NativeTheme::Light);
+    assert_eq!(parse_gsettings_theme("'Adwaita-dark'"), NativeTheme::Dark);
+    assert_eq!(parse_gsettings_theme("'Yaru-dark'\n"), NativeTheme::Dark);
+    assert_eq!(parse_gsettings_theme("'Arc-Dark-solid'"), NativeTheme::Dark);
+    assert_eq!(parse_gsettings_theme("'HighContrast'"), NativeTheme::HighContrast);
+    assert_eq!(parse_gsettings_theme(""), NativeTheme::Light);
+    assert!(parse_gsettings_color_scheme("'prefer-dark'"));
+    assert!(!parse_gsettings_color_scheme("'default'"));
+}
+
+#[test]
+fn test_native_themes_parse() {
+    for theme in &[NativeTheme::Light, NativeTheme::Dark, NativeTheme::HighContrast] {
+        let css = native_with_theme(*theme);
+        let expected_stylesheets = if *theme == NativeTheme::Light { 1 } else { 2 };
+        assert_eq!(css.stylesheets.len(), expected_stylesheets);
+    }
+}
+
+#[test]
+fn test_native_root_background() {
+    use azul_css::{CssPathSelector, CssDeclaration, CssProperty, StyleBackground};
+
+    for theme in &[NativeTheme::Dark, NativeTheme::HighContrast] {
+        let css = native_with_theme(*theme);
+        let root_background = css.stylesheets[1].rules.iter()
+            .filter(|rule| rule.path.selectors == vec![CssPathSelector::Class(NATIVE_ROOT_CLASS.into())])
+            .flat_map(|rule| rule.declarations.iter())
+            .filter_map(|declaration| match declaration {
+                CssDeclaration::Static(CssProperty::Background(StyleBackground::Color(c))) => Some(*c),
+                _ => None,
+            })
+            .next();
+        assert_eq!(root_background, theme.get_window_background());
+    }
+
+    assert_eq!(NativeTheme::Light.get_window_background(), None);
 }
@@ -1,16 +1,26 @@
 use std::{
     mem,
     fmt,
-    time::Instant,
+    any::Any,
+    path::Path,
+    time::{Instant, Duration},
     collections::BTreeMap,
-    sync::{Arc, Mutex, PoisonError},
+    rc::Rc,
+    thread,
+    sync::{Arc, Mutex, PoisonError, mpsc::{channel, Sender, RecvTimeoutError}},
 };
+#[cfg(feature = "native_style")]
+use std::sync::{mpsc::Receiver, atomic::{AtomicBool, Ordering}};
 #[cfg(debug_assertions)]
 use azul_css::HotReloadHandler;
+#[cfg(feature = "native_style")]
+use azul_native_style::NativeTheme;
 use glium::{
     SwapBuffersError,
     glutin::{
+        Event, EventsLoop, EventsLoopProxy, ControlFlow,
         WindowEvent, WindowId as GliumWindowId,
+        KeyboardInput, ElementState, VirtualKeyCode,
         dpi::{LogicalPosition, LogicalSize}
     },
 };
@@ -28,6 +38,8 @@ use app_resources::ImageSource;
 #[cfg(feature = "logging")]
 use log::LevelFilter;
 use azul_css::{Css, ColorU};
+#[cfg(feature = "native_style")]
+use window::{NativeStyle, NativeThemeSetting};
 use {
     FastHashMap,
     error::ClipboardError,
@@ -36,8 +48,11 @@ use {
         WindowCreateError, WindowCreateOptions, RendererType,
     },
     window_state::{WindowSize, DebugState},
+    shortcuts::ShortcutScope,
     app_resources::TextId,
     dom::{Dom, ScrollTagId},
+    id_tree::{NodeId, NodeHierarchy},
+    ui_solver::LayoutResult,
     app_resources::{
         ImageId, FontSource, FontId, ImageReloadError,
         FontReloadError, CssImageId, RawImage,
@@ -45,13 +60,21 @@ use {
     traits::Layout,
     ui_state::UiState,
     ui_description::UiDescription,
-    async::{Task, Timer, TimerId, TerminateTimer},
-    callbacks::{FocusTarget, UpdateScreen, Redraw, DontRedraw, LayoutInfo},
+    async::{Task, TaskHandle, Timer, TimerId, TerminateTimer},
+    callbacks::{FocusTarget, DragData, UpdateScreen, Redraw, DontRedraw, LayoutInfo, LayoutCallbackType, ShouldClose, EventPhase},
 };
 pub use app_resources::AppResources;
 
 type DeviceIntSize = ::euclid::TypedSize2D<i32, DevicePixel>;
 
+/// How long the app waits for running tasks to finish when it exits, before detaching them
+const TASK_EXIT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How often the OS theme is queried for windows that follow the OS theme
+/// (querying the theme spawns a process, so it is done on a background thread)
+#[cfg(feature = "native_style")]
+const SYSTEM_THEME_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
 // Default clear color is white, to signify that there is rendering going on
 // (otherwise, "transparent") backgrounds would be painted black.
 const COLOR_WHITE: ColorU = ColorU { r: 255, g: 255, b: 255, a: 0 };
@@ -104,6 +127,12 @@ pub struct AppConfig {
     pub debug_state: DebugState,
     /// Background color for all windows
     pub background_color: ColorU,
+    /// Minimum time between two frames while something is animating, i.e. while a
+    /// `Timer` without an interval is running or a redraw is pending. The event loop
+    /// sleeps until the next OS event or timer deadline otherwise.
+    ///
+    /// Default: 16ms (~60 FPS)
+    pub min_frame_time: Duration,
 }
 
 impl Default for AppConfig {
@@ -121,6 +150,7 @@ impl Default for AppConfig {
             renderer_type: RendererType::default(),
             debug_state: DebugState::default(),
             background_color: COLOR_WHITE,
+            min_frame_time: Duration::from_millis(16),
         }
     }
 }
@@ -162,6 +192,10 @@ pub struct AppState<T> {
     pub(crate) timers: FastHashMap<TimerId, Timer<T>>,
     /// Currently running tasks (asynchronous functions running each on a different thread)
     pub(crate) tasks: Vec<Task<T>>,
+    /// Windows to open or close at the end of the current frame
+    pub(crate) window_requests: Vec<WindowRequest<T>>,
+    /// Payload of the current drag-and-drop operation
+    pub(crate) drag_data: Option<DragData>,
 }
 
 /// Same as the [AppState](./struct.AppState.html) but without the
@@ -179,6 +213,22 @@ pub struct AppStateNoData<'a, T> {
     pub(crate) timers: FastHashMap<TimerId, Timer<T>>,
     /// Currently running tasks (asynchronous functions running each on a different thread)
     pub(crate) tasks: Vec<Task<T>>,
+    /// See [`AppState.window_requests`](./struct.AppState.html#structfield.window_requests)
+    pub(crate) window_requests: Vec<WindowRequest<T>>,
+    /// See [`AppState.drag_data`](./struct.AppState.html#structfield.drag_data)
+    pub(crate) drag_data: Option<DragData>,
+}
+
+/// Changes to the set of open windows, requested by callbacks or timers via
+/// `open_window`, `close_window` or `quit`. Applied by the `App` once all
+/// callbacks and timers of the current frame have run.
+pub(crate) enum WindowRequest<T> {
+    /// Create a new window with the given style
+    Open(WindowCreateOptions<T>, Css),
+    /// Close the window with the given ID, without invoking `on_close_requested`
+    Close(GliumWindowId),
+    /// Close all windows, which ends `App::run`
+    Quit,
 }
 
 /// Error returned by the `.run()` function
@@ -249,6 +299,8 @@ impl<T> fmt::Display for RuntimeError<T> {
 
 impl<'a, T: 'a> AppStateNoData<'a, T> {
     impl_deamon_api!();
+    impl_window_api!();
+    impl_drag_api!();
 }
 
 impl<T: Layout> App<T> {
@@ -317,6 +369,32 @@ impl<T> App<T> {
         )
     }
 
+    /// Creates a new window with the native style (see `css::native()`), with the `user_overrides`
+    /// applied on top of it, similar to `css::override_native`.
+    ///
+    /// Unlike a window created via `create_window`, the style of the window is rebuilt when
+    /// the theme changes - either because the OS switched to a dark or high-contrast theme
+    /// (if `theme` is `NativeThemeSetting::System`) or because a callback called
+    /// `FakeWindow::set_native_theme`. The `user_overrides` stay applied in every theme.
+    #[cfg(feature = "native_style")]
+    pub fn create_native_window(&mut self, options: WindowCreateOptions<T>, theme: NativeThemeSetting, user_overrides: Css)
+    -> Result<Window<T>, WindowCreateError>
+    {
+        use css::{native_with_theme, get_cached_system_theme};
+
+        let current_theme = match theme {
+            NativeThemeSetting::System => get_cached_system_theme(),
+            NativeThemeSetting::Fixed(theme) => theme,
+        };
+
+        let mut css = native_with_theme(current_theme);
+        css.append(user_overrides.clone());
+
+        let mut window = self.create_window(options, css)?;
+        window.native_style = Some(NativeStyle { setting: theme, current_theme, user_overrides });
+        Ok(window)
+    }
+
     /// Spawn a new window on the screen. Note that this should only be used to
     /// create extra windows, the default window will be the window submitted to
     /// the `.run` method.
@@ -328,6 +406,13 @@ impl<T> App<T> {
             state: window.state.clone(),
             default_callbacks: DefaultCallbackSystem::new(),
             read_only_window: window.display.clone(),
+            last_layout_result: None,
+            overlays: Vec::new(),
+            menu_bar: window.create_options.menu_bar.clone(),
+            open_menu: None,
+            shortcuts: window.create_options.shortcuts.clone(),
+            #[cfg(feature = "native_style")]
+            native_theme: window.native_style.as_ref().map(|style| style.setting),
         };
 
         self.app_state.windows.insert(window_id, fake_window);
@@ -366,7 +451,10 @@ impl<T> App<T> {
         // since one Arc is still owned by the app_state.tasks structure
         //
         // See https://github.com/maps4print/azul/issues/24#issuecomment-429737273
-        mem::drop(self.app_state.tasks);
+        //
+        // Tasks that are still running after the timeout keep their reference to the
+        // data, in which case an `ArcUnlockError` is returned.
+        Task::cancel_and_join_all(mem::replace(&mut self.app_state.tasks, Vec::new()), TASK_EXIT_TIMEOUT);
 
         let unique_arc = Arc::try_unwrap(self.app_state.data).map_err(|_| RuntimeError::ArcUnlockError)?;
         unique_arc.into_inner().map_err(|e| e.into())
@@ -374,15 +462,13 @@ impl<T> App<T> {
 
     fn run_inner(&mut self) -> Result<(), RuntimeError<T>> {
 
-        use std::{thread, time::Duration};
-        use glium::glutin::Event;
         use self::RuntimeError::*;
 
         let mut ui_state_cache = {
             let app_state = &mut self.app_state;
             let mut ui_state_map = BTreeMap::new();
-            for window_id in self.windows.keys() {
-              ui_state_map.insert(*window_id, UiState::from_app_state(app_state, window_id, self.layout_callback)?);
+            for (window_id, window) in self.windows.iter() {
+              ui_state_map.insert(*window_id, UiState::from_app_state(app_state, window_id, get_layout_callback(window, self.layout_callback))?);
             }
             ui_state_map
         };
@@ -394,20 +480,29 @@ impl<T> App<T> {
         let mut last_style_reload = Instant::now();
         #[cfg(debug_assertions)]
         let mut should_print_css_error = true;
+        #[cfg(debug_assertions)]
+        let mut last_css_warnings = Vec::new();
+        #[cfg(feature = "native_style")]
+        let mut system_theme_watcher = SystemThemeWatcher::new(self.app_state.resources.fake_display.hidden_events_loop.create_proxy());
 
-        while !self.windows.is_empty() {
+        let event_loop_waker = EventLoopWaker::new(self.app_state.resources.fake_display.hidden_events_loop.create_proxy());
+        // Render the first frame immediately
+        let mut next_wakeup = Some(Instant::now());
 
-            let time_start = Instant::now();
+        while !self.windows.is_empty() {
 
             let mut closed_windows = Vec::<GliumWindowId>::new();
             let mut frame_was_resize = false;
             let mut events = Vec::new();
 
-            self.app_state.resources.fake_display.hidden_events_loop.poll_events(|e| match e {
-                // Filter out all events that are uninteresting or unnecessary
-                Event::WindowEvent { event: WindowEvent::Refresh, .. } => { },
-                _ => { events.push(e); },
-            });
+            wait_for_events(
+                &mut self.app_state.resources.fake_display.hidden_events_loop,
+                &event_loop_waker,
+                next_wakeup,
+                &mut events,
+            );
+
+            let time_start = Instant::now();
 
             // let current_desktop_events = get_desktop_events(window, &events);
 
@@ -441,11 +536,6 @@ impl<T> App<T> {
 
                 if window_was_closed {
                     closed_windows.push(*current_window_id);
-
-                    // TODO: Currently there is no way to return from the main event loop
-                    // i.e. the windows aren't actually getting closed
-                    // This is a hack, so that windows currently close properly
-                    return Ok(());
                 }
             }
 
@@ -454,17 +544,19 @@ impl<T> App<T> {
                     &mut self.windows,
                     &mut last_style_reload,
                     &mut should_print_css_error,
+                    &mut last_css_warnings,
                     &mut awakened_task
                 )?;
             }
 
-            // Close windows if necessary
-            closed_windows.into_iter().for_each(|closed_window_id| {
-                ui_state_cache.remove(&closed_window_id);
-                ui_description_cache.remove(&closed_window_id);
-                force_redraw_cache.remove(&closed_window_id);
-                self.windows.remove(&closed_window_id);
-            });
+            #[cfg(feature = "native_style")] {
+                update_native_themes(
+                    &mut self.windows,
+                    &self.app_state.windows,
+                    &mut system_theme_watcher,
+                    &mut awakened_task
+                )?;
+            }
 
             let should_redraw_timers = self.app_state.run_all_timers();
             let should_redraw_tasks = self.app_state.clean_up_finished_tasks();
@@ -480,26 +572,78 @@ impl<T> App<T> {
                 }
             }
 
+            // Open and close the windows that callbacks and timers asked for
+            let open_window_ids = self.windows.keys().cloned().collect::<Vec<_>>();
+            let window_requests = mem::replace(&mut self.app_state.window_requests, Vec::new());
+            let (windows_to_open, windows_to_close) = resolve_window_requests(window_requests, &open_window_ids);
+
+            for (options, css) in windows_to_open {
+                let window = match self.create_window(options, css) {
+                    Ok(window) => window,
+                    Err(e) => {
+                        #[cfg(feature = "logging")] {
+                            error!("Could not open window: {:?}", e);
+                        }
+                        #[cfg(not(feature = "logging"))] {
+                            let _ = e;
+                        }
+                        continue;
+                    }
+                };
+                let window_id = window.id;
+                let layout_callback = get_layout_callback(&window, self.layout_callback);
+                self.add_window(window);
+                ui_state_cache.insert(window_id, UiState::from_app_state(&mut self.app_state, &window_id, layout_callback)?);
+                ui_description_cache.insert(window_id, UiDescription::default());
+                force_redraw_cache.insert(window_id, 2);
+                awakened_task.insert(window_id, false);
+            }
+
+            closed_windows.extend(windows_to_close);
+
+            // Close windows if necessary
+            for closed_window_id in closed_windows {
+                ui_state_cache.remove(&closed_window_id);
+                ui_description_cache.remove(&closed_window_id);
+                force_redraw_cache.remove(&closed_window_id);
+                awakened_task.remove(&closed_window_id);
+                self.app_state.windows.remove(&closed_window_id);
+                if let Some(window) = self.windows.remove(&closed_window_id) {
+                    self.app_state.resources.fake_display.render_api.delete_document(window.internal.document_id);
+                }
+            }
+
             // Automatically remove unused fonts and images from webrender
             // Tell the font + image GC to start a new frame
             self.app_state.resources.garbage_collect_fonts_and_images();
 
-            if !frame_was_resize {
-                // Wait until 16ms have passed, but not during a resize event
-                let diff = time_start.elapsed();
-                const FRAME_TIME: Duration = Duration::from_millis(16);
-                if diff < FRAME_TIME {
-                    thread::sleep(FRAME_TIME - diff);
+            next_wakeup = if frame_was_resize {
+                // Don't limit the frame rate during a resize event
+                Some(Instant::now())
+            } else {
+                let next_frame = time_start + self.config.min_frame_time;
+                let needs_redraw =
+                    force_redraw_cache.values().any(|redraw| *redraw > 0) ||
+                    awakened_task.values().any(|awakened| *awakened);
+
+                let mut deadlines = vec![get_next_timer_deadline(&self.app_state.timers, next_frame)];
+                if needs_redraw {
+                    deadlines.push(Some(next_frame));
                 }
-            }
+                #[cfg(debug_assertions)] {
+                    deadlines.push(get_next_hot_reload_deadline(&self.windows, last_style_reload));
+                }
+
+                deadlines.into_iter().flatten().min()
+            };
         }
 
         Ok(())
     }
 
     /// See `AppState::add_task`.
-    pub fn add_task(&mut self, task: Task<T>) {
-        self.app_state.add_task(task);
+    pub fn add_task(&mut self, task: Task<T>) -> TaskHandle {
+        self.app_state.add_task(task)
     }
 
     /// Toggles debugging flags in webrender, updates `self.config.debug_state`
@@ -527,10 +671,14 @@ impl<T> AppState<T> {
             resources: AppResources::new(config)?,
             timers: FastHashMap::default(),
             tasks: Vec::new(),
+            window_requests: Vec::new(),
+            drag_data: None,
         })
     }
 
     impl_deamon_api!();
+    impl_window_api!();
+    impl_drag_api!();
 
     /// Run all currently registered timers
     #[must_use]
@@ -539,8 +687,18 @@ impl<T> AppState<T> {
         let mut lock = self.data.lock().unwrap();
         let mut timers_to_terminate = Vec::new();
 
+        // Timers can start new timers or tasks and open or close windows
+        let mut app_state_no_data = AppStateNoData {
+            windows: &self.windows,
+            resources: &mut self.resources,
+            timers: FastHashMap::default(),
+            tasks: Vec::new(),
+            window_requests: Vec::new(),
+            drag_data: self.drag_data.take(),
+        };
+
         for (key, timer) in self.timers.iter_mut() {
-            let (should_update, should_terminate) = timer.invoke_callback_with_data(&mut lock, &mut self.resources);
+            let (should_update, should_terminate) = timer.invoke_callback_with_data(&mut lock, &mut app_state_no_data);
 
             if should_update == Redraw &&
                should_update_screen == DontRedraw {
@@ -552,27 +710,48 @@ impl<T> AppState<T> {
             }
         }
 
+        let AppStateNoData { timers: new_timers, tasks: new_tasks, window_requests: new_window_requests, drag_data, .. } = app_state_no_data;
+        drop(lock);
+
+        self.drag_data = drag_data;
+
         for key in timers_to_terminate {
             self.timers.remove(&key);
         }
 
+        for (timer_id, timer) in new_timers {
+            self.add_timer(timer_id, timer);
+        }
+
+        for task in new_tasks {
+            self.add_task(task);
+        }
+
+        self.window_requests.extend(new_window_requests);
+
         should_update_screen
     }
 
     /// Remove all tasks that have finished executing
+    ///
+    /// Also returns `Redraw` if a task reported new progress since the last frame.
     #[must_use] fn clean_up_finished_tasks(&mut self) -> UpdateScreen {
         let old_count = self.tasks.len();
         let mut timers_to_add = Vec::new();
-        self.tasks.retain(|task| {
+        let mut progress_changed = false;
+        for mut task in mem::replace(&mut self.tasks, Vec::new()) {
+            if task.take_progress_changed() {
+                progress_changed = true;
+            }
             if task.is_finished() {
-                if let Some(timer) = task.after_completion_timer {
+                // If the task panicked without an `or_else` timer, dropping it re-throws the panic
+                if let Some(timer) = task.take_completion_timer() {
                     timers_to_add.push((TimerId::new(), timer));
                 }
-                false
             } else {
-                true
+                self.tasks.push(task);
             }
-        });
+        }
 
         let timers_is_empty = timers_to_add.is_empty();
         let new_count = self.tasks.len();
@@ -582,7 +761,7 @@ impl<T> AppState<T> {
             self.add_timer(timer_id, timer);
         }
 
-        if old_count == new_count && timers_is_empty {
+        if old_count == new_count && timers_is_empty && !progress_changed {
             DontRedraw
         } else {
             Redraw
@@ -595,6 +774,12 @@ font_api!(AppState::resources);
 text_api!(AppState::resources);
 clipboard_api!(AppState::resources);
 
+/// Returns the layout function of the window (`WindowCreateOptions::layout_callback`),
+/// falls back to the `Layout::layout()` function of the application data
+fn get_layout_callback<T>(window: &Window<T>, default_layout_callback: LayoutCallbackType<T>) -> LayoutCallbackType<T> {
+    window.create_options.layout_callback.as_ref().map(|callback| callback.0).unwrap_or(default_layout_callback)
+}
+
 /// Render the contents of one single window.
 /// Returns (if the event was a resize event, if the window was closed)
 fn render_single_window_content<T>(
@@ -612,19 +797,40 @@ fn render_single_window_content<T>(
 
     use self::RuntimeError::*;
 
+    // The timer started by `arm_long_press_timer` only wakes up the event loop,
+    // the long press is recognized from the repeated touch event
+    let mut events = events.to_vec();
+    events.extend(window.state.get_long_press_event(Instant::now()));
+
     if events.is_empty() && force_redraw_cache[window_id] == 0 {
         // Event was not a resize event, window should **not** close
         return Ok((false, false));
     }
 
+    let layout_callback = get_layout_callback(window, layout_callback);
     let (mut frame_event_info, window_should_close) = window.state.update_window_state(&events);
 
     if window_should_close {
-        // Event was not a resize event, window should close
-        return Ok((false, true));
+
+        let on_close_requested = window.create_options.on_close_requested.as_ref().map(|callback| callback.0);
+        let app_state_ref = &mut *app_state;
+
+        if should_close_window(&events, on_close_requested.map(|callback| move || callback(app_state_ref, window_id))) {
+            // Event was not a resize event, window should close
+            return Ok((false, true));
+        }
+
+        frame_event_info.should_redraw_window = true;
+    }
+
+    // A shortcut chord can't be finished after the window lost the keyboard focus
+    if events.iter().any(|event| if let WindowEvent::Focused(false) = event { true } else { false }) {
+        let fake_window = app_state.windows.get_mut(window_id).ok_or(WindowIndexError)?;
+        fake_window.shortcuts.cancel_pending_chord();
     }
 
     let mut hit_test_results = None;
+    let mut scroll_into_view = None;
 
     if frame_event_info.should_hittest {
 
@@ -651,11 +857,32 @@ fn render_single_window_content<T>(
             if let Some(overwrites_focus) = callback_result.callbacks_overwrites_focus {
                 window.state.internal.pending_focus_target = Some(overwrites_focus);
             }
+
+            if let Some(node_id) = callback_result.callbacks_scroll_into_view {
+                scroll_into_view = Some(node_id);
+            }
+
+            if callback_result.callbacks_consumed_scroll {
+                window.clear_scroll_state();
+            }
         }
+
+        arm_long_press_timer(&events, app_state);
     }
 
     // Scroll for the scrolled amount for each node that registered a scroll state.
-    let should_scroll_render = update_scroll_state(window, hit_test_results, &mut app_state.resources);
+    let mut should_scroll_render = update_scroll_state(window, hit_test_results, &mut app_state.resources);
+
+    // Scroll the parents of the node that a callback wants to have visible
+    if let Some(node_id) = scroll_into_view {
+        let last_layout_result = app_state.windows.get(window_id).ok_or(WindowIndexError)?.last_layout_result.clone();
+        if let Some(layout_result) = last_layout_result {
+            let node_hierarchy = &ui_state_cache[window_id].dom.arena.node_layout;
+            if scroll_node_into_view(window, node_hierarchy, &layout_result, node_id) {
+                should_scroll_render = true;
+            }
+        }
+    }
 
     if frame_event_info.is_resize_event {
         // This is a hack because during a resize event, winit eats the "awakened"
@@ -699,8 +926,10 @@ fn render_single_window_content<T>(
         *ui_state_cache.get_mut(window_id).ok_or(WindowIndexError)? =
             UiState::from_app_state(app_state, window_id, layout_callback)?;
 
-        // Style the DOM (is_mouse_down is necessary for styling :hover, :active + :focus nodes)
+        // Style the DOM (is_mouse_down is necessary for styling :hover, :active + :focus nodes,
+        // the drop target for styling :drag-over nodes)
         let is_mouse_down = window.state.internal.mouse_state.mouse_down();
+        let drop_target = window.state.internal.drop_target;
 
         *ui_description_cache.get_mut(window_id).ok_or(WindowIndexError)? =
             UiDescription::match_css_to_dom(
@@ -710,6 +939,7 @@ fn render_single_window_content<T>(
                 &mut window.state.internal.pending_focus_target,
                 &window.state.internal.hovered_nodes,
                 is_mouse_down,
+                drop_target,
             );
 
         // Render the window (webrender will send an Awakened event when the frame is done)
@@ -734,18 +964,23 @@ fn render_single_window_content<T>(
 
     // TODO: Render all windows again, not just this one!
     // if should_relayout || should_rerender {
-        render_inner(window, &mut app_state.resources, Transaction::new(), config.background_color);
+        let background_color = get_window_background(window, config.background_color);
+        render_inner(window, &mut app_state.resources, Transaction::new(), background_color);
     // }
 
     Ok((frame_event_info.is_resize_event, false))
 }
 
 /// Returns if there was an error with the CSS reloading, necessary so that the error message is only printed once
+///
+/// Warnings (skipped rules and declarations) are only printed when they change, the
+/// parts of the style that could be parsed are applied regardless.
 #[cfg(debug_assertions)]
 fn hot_reload_css<T>(
     windows: &mut BTreeMap<GliumWindowId, Window<T>>,
     last_style_reload: &mut Instant,
     should_print_error: &mut bool,
+    last_warnings: &mut Vec<String>,
     awakened_tasks: &mut BTreeMap<GliumWindowId, bool>)
 -> Result<(), RuntimeError<T>>
 {
@@ -763,13 +998,21 @@ fn hot_reload_css<T>(
             continue;
         }
 
-        match hot_reloader.reload_style() {
-            Ok(mut new_css) => {
+        match hot_reloader.reload_style_with_warnings() {
+            Ok((mut new_css, warnings)) => {
                 new_css.sort_by_specificity();
                 window.css = new_css;
-                if !(*should_print_error) {
-                    println!("--- OK: CSS parsed without errors, continuing hot-reload.");
+                if !(*should_print_error) || warnings != *last_warnings {
+                    for warning in &warnings {
+                        println!("{}", warning);
+                    }
+                    if warnings.is_empty() {
+                        println!("--- OK: CSS parsed without errors, continuing hot-reload.");
+                    } else {
+                        println!("--- WARNING: Skipped {} invalid CSS rule(s) or declaration(s), continuing hot-reload.", warnings.len());
+                    }
                 }
+                *last_warnings = warnings;
                 *last_style_reload = Instant::now();
                 // window.events_loop.create_proxy().wakeup().unwrap_or(());
                 *awakened_tasks.get_mut(window_id).ok_or(WindowIndexError)? = true;
@@ -788,6 +1031,334 @@ fn hot_reload_css<T>(
     Ok(())
 }
 
+/// Rebuilds the style of all windows created with `App::create_native_window`
+/// whose native theme changed, either because the OS theme changed or because
+/// the theme was changed via `FakeWindow::set_native_theme`
+#[cfg(feature = "native_style")]
+fn update_native_themes<T>(
+    windows: &mut BTreeMap<GliumWindowId, Window<T>>,
+    fake_windows: &BTreeMap<GliumWindowId, FakeWindow<T>>,
+    system_theme_watcher: &mut SystemThemeWatcher,
+    awakened_tasks: &mut BTreeMap<GliumWindowId, bool>)
+-> Result<(), RuntimeError<T>>
+{
+    use self::RuntimeError::*;
+    use css::native_with_theme;
+
+    for (window_id, window) in windows.iter_mut() {
+
+        let native_style = match window.native_style.as_mut() {
+            None => continue,
+            Some(s) => s,
+        };
+
+        if let Some(setting) = fake_windows.get(window_id).and_then(|fake_window| fake_window.native_theme) {
+            native_style.setting = setting;
+        }
+
+        let new_theme = match native_style.setting {
+            NativeThemeSetting::Fixed(theme) => theme,
+            NativeThemeSetting::System => system_theme_watcher.get_theme(),
+        };
+
+        if new_theme == native_style.current_theme {
+            continue;
+        }
+
+        let mut new_css = native_with_theme(new_theme);
+        new_css.append(native_style.user_overrides.clone());
+        new_css.sort_by_specificity();
+
+        native_style.current_theme = new_theme;
+        window.css = new_css;
+        *awakened_tasks.get_mut(window_id).ok_or(WindowIndexError)? = true;
+    }
+
+    Ok(())
+}
+
+/// Queries the OS theme every `SYSTEM_THEME_POLL_INTERVAL` on a background thread, since
+/// querying the theme spawns a process (`gsettings`, `defaults` or `reg`) and waits for it,
+/// which would block the event loop. The thread wakes up the event loop when the theme changes.
+///
+/// The thread is only started once a window follows the OS theme and exits when the
+/// watcher is dropped.
+#[cfg(feature = "native_style")]
+struct SystemThemeWatcher {
+    proxy: EventsLoopProxy,
+    /// Last theme reported by the background thread
+    current_theme: NativeTheme,
+    /// Receives the new theme whenever the OS theme changes, `None` if the thread isn't running yet
+    theme_receiver: Option<Receiver<NativeTheme>>,
+    /// Tells the background thread to exit
+    should_exit: Arc<AtomicBool>,
+}
+
+#[cfg(feature = "native_style")]
+impl SystemThemeWatcher {
+
+    fn new(proxy: EventsLoopProxy) -> Self {
+        use css::get_cached_system_theme;
+        Self {
+            proxy,
+            current_theme: get_cached_system_theme(),
+            theme_receiver: None,
+            should_exit: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Returns the current OS theme, starts the background thread on the first call
+    fn get_theme(&mut self) -> NativeTheme {
+
+        if self.theme_receiver.is_none() {
+            self.theme_receiver = Some(self.spawn_thread());
+        }
+
+        if let Some(receiver) = &self.theme_receiver {
+            while let Ok(theme) = receiver.try_recv() {
+                self.current_theme = theme;
+            }
+        }
+
+        self.current_theme
+    }
+
+    fn spawn_thread(&self) -> Receiver<NativeTheme> {
+
+        use css::get_system_theme;
+
+        let (theme_sender, theme_receiver) = channel::<NativeTheme>();
+        let proxy = self.proxy.clone();
+        let should_exit = self.should_exit.clone();
+        let mut last_theme = self.current_theme;
+
+        let spawn_result = thread::Builder::new().name("azul-system-theme".into()).spawn(move || {
+            loop {
+                thread::sleep(SYSTEM_THEME_POLL_INTERVAL);
+                if should_exit.load(Ordering::SeqCst) {
+                    return;
+                }
+                let theme = get_system_theme();
+                if theme != last_theme {
+                    last_theme = theme;
+                    if theme_sender.send(theme).is_err() {
+                        return;
+                    }
+                    // The event loop might already be closed, nothing to do then
+                    proxy.wakeup().unwrap_or(());
+                }
+            }
+        });
+
+        if let Err(e) = spawn_result {
+            #[cfg(feature = "logging")] {
+                error!("Could not spawn the system theme thread, the OS theme won't be updated: {}", e);
+            }
+            #[cfg(not(feature = "logging"))] {
+                let _ = e;
+            }
+        }
+
+        theme_receiver
+    }
+}
+
+#[cfg(feature = "native_style")]
+impl Drop for SystemThemeWatcher {
+    fn drop(&mut self) {
+        self.should_exit.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Background thread that calls `EventsLoopProxy::wakeup()` at a certain deadline.
+///
+/// glutin can only block on OS events without a timeout (`EventsLoop::run_forever`),
+/// so without this thread the app would sleep through timers and hot-reload intervals
+/// until the user moves the mouse.
+struct EventLoopWaker {
+    /// Sends the next deadline to the waker thread, replacing the previous deadline.
+    /// The thread exits once this sender is dropped.
+    deadline_sender: Sender<Instant>,
+}
+
+impl EventLoopWaker {
+
+    fn new(proxy: EventsLoopProxy) -> Self {
+
+        let (deadline_sender, deadline_receiver) = channel::<Instant>();
+
+        let spawn_result = thread::Builder::new().name("azul-event-loop-waker".into()).spawn(move || {
+            let mut next_deadline = None;
+            loop {
+                let new_deadline = match next_deadline {
+                    None => match deadline_receiver.recv() {
+                        Ok(deadline) => deadline,
+                        Err(_) => return,
+                    },
+                    Some(deadline) => {
+                        let now = Instant::now();
+                        let timeout = if deadline > now { deadline - now } else { Duration::from_millis(0) };
+                        match deadline_receiver.recv_timeout(timeout) {
+                            Ok(deadline) => deadline,
+                            Err(RecvTimeoutError::Timeout) => {
+                                // The event loop might already be closed, nothing to do then
+                                proxy.wakeup().unwrap_or(());
+                                next_deadline = None;
+                                continue;
+                            },
+                            Err(RecvTimeoutError::Disconnected) => return,
+                        }
+                    },
+                };
+                next_deadline = Some(new_deadline);
+            }
+        });
+
+        if let Err(e) = spawn_result {
+            #[cfg(feature = "logging")] {
+                error!("Could not spawn event loop waker thread, timers will only run on OS events: {}", e);
+            }
+            #[cfg(not(feature = "logging"))] {
+                let _ = e;
+            }
+        }
+
+        Self { deadline_sender }
+    }
+
+    /// Wakes up the event loop at `deadline` (unless another deadline is set before that)
+    fn wake_at(&self, deadline: Instant) {
+        self.deadline_sender.send(deadline).unwrap_or(());
+    }
+}
+
+/// Blocks until either an OS event arrives or `next_wakeup` is reached, then collects
+/// all pending events into `events`. If `next_wakeup` is `None`, there is nothing
+/// to do until the next OS event (or until a `Task` finishes and wakes up the loop).
+fn wait_for_events(
+    events_loop: &mut EventsLoop,
+    event_loop_waker: &EventLoopWaker,
+    next_wakeup: Option<Instant>,
+    events: &mut Vec<Event>)
+{
+    let should_block = match next_wakeup {
+        None => true,
+        Some(deadline) => deadline > Instant::now(),
+    };
+
+    let mut push_event = |e: Event| match e {
+        // Filter out all events that are uninteresting or unnecessary
+        Event::WindowEvent { event: WindowEvent::Refresh, .. } => { },
+        _ => { events.push(e); },
+    };
+
+    if should_block {
+        if let Some(deadline) = next_wakeup {
+            event_loop_waker.wake_at(deadline);
+        }
+        events_loop.run_forever(|e| {
+            push_event(e);
+            ControlFlow::Break
+        });
+    }
+
+    // Drain all events that arrived in the meantime
+    events_loop.poll_events(|e| push_event(e));
+}
+
+/// Wakes up the event loop after the `LONG_PRESS_DURATION` if a finger touched the screen,
+/// so that the long press is recognized even if the finger doesn't send any more events
+fn arm_long_press_timer<T>(events: &[WindowEvent], app_state: &mut AppState<T>) {
+
+    use glium::glutin::{Touch, TouchPhase};
+    use touch::LONG_PRESS_DURATION;
+
+    fn wake_up<T>(_: &mut T, _: &mut AppResources) -> (UpdateScreen, TerminateTimer) {
+        (DontRedraw, TerminateTimer::Terminate)
+    }
+
+    let touch_started = events.iter().any(|event| match event {
+        WindowEvent::Touch(Touch { phase: TouchPhase::Started, .. }) => true,
+        _ => false,
+    });
+
+    if touch_started {
+        app_state.add_timer(TimerId::new(), Timer::new(wake_up).with_delay(LONG_PRESS_DURATION));
+    }
+}
+
+/// Returns when the next timer has to run - timers without an interval are animations,
+/// so they run at the next frame (`next_frame`)
+fn get_next_timer_deadline<T>(timers: &FastHashMap<TimerId, Timer<T>>, next_frame: Instant) -> Option<Instant> {
+    timers.values().map(|timer| timer.get_next_run_time().unwrap_or(next_frame)).min()
+}
+
+/// Returns when the CSS of the windows has to be reloaded next
+#[cfg(debug_assertions)]
+fn get_next_hot_reload_deadline<T>(windows: &BTreeMap<GliumWindowId, Window<T>>, last_style_reload: Instant) -> Option<Instant> {
+    windows.values()
+        .filter_map(|window| window.css_loader.as_ref())
+        .map(|hot_reloader| last_style_reload + hot_reloader.get_reload_interval())
+        .min()
+}
+
+/// Splits the window requests of the current frame into the windows that should be opened
+/// and the (currently open) windows that should be closed. `quit` also cancels all windows
+/// that were requested to open in the same frame, so that the app really exits.
+fn resolve_window_requests<T>(window_requests: Vec<WindowRequest<T>>, open_windows: &[GliumWindowId])
+-> (Vec<(WindowCreateOptions<T>, Css)>, Vec<GliumWindowId>)
+{
+    let mut windows_to_open = Vec::new();
+    let mut windows_to_close = Vec::new();
+    let mut should_quit = false;
+
+    for window_request in window_requests {
+        match window_request {
+            WindowRequest::Open(options, css) => windows_to_open.push((options, css)),
+            WindowRequest::Close(window_id) => windows_to_close.push(window_id),
+            WindowRequest::Quit => should_quit = true,
+        }
+    }
+
+    if should_quit {
+        windows_to_open.clear();
+        windows_to_close = open_windows.to_vec();
+    }
+
+    // Ignore windows that are already closed or that are closed twice
+    windows_to_close.retain(|window_id| open_windows.contains(window_id));
+    windows_to_close.sort();
+    windows_to_close.dedup();
+
+    (windows_to_open, windows_to_close)
+}
+
+/// Returns whether a window that the user tried to close (or that was destroyed) should close.
+/// The `on_close_requested` callback of the window can veto closing the window, but it isn't
+/// invoked if the window was already destroyed.
+fn should_close_window<F>(events: &[WindowEvent], on_close_requested: Option<F>) -> bool
+where F: FnOnce() -> ShouldClose
+{
+    let window_was_destroyed = events.iter().any(|event| *event == WindowEvent::Destroyed);
+    match on_close_requested {
+        Some(callback) if !window_was_destroyed => callback() == ShouldClose::Close,
+        _ => true,
+    }
+}
+
+/// Returns the color that the window is cleared with: the window background of the native theme for
+/// windows created via `App::create_native_window`, otherwise the `AppConfig::background_color`
+#[allow(unused_variables)]
+fn get_window_background<T>(window: &Window<T>, default_background: ColorU) -> ColorU {
+    #[cfg(feature = "native_style")] {
+        let native_background = window.native_style.as_ref().and_then(|style| style.current_theme.get_window_background());
+        if let Some(background) = native_background {
+            return background;
+        }
+    }
+    default_background
+}
+
 /// Returns the currently hit-tested results, in back-to-front order
 fn do_hit_test<T>(window: &Window<T>, app_resources: &AppResources) -> Option<HitTestResult> {
 
@@ -816,6 +1387,63 @@ struct CallCallbackReturn {
     /// Whether one or more callbacks have messed with the current
     /// focused element i.e. via `.clear_focus()` or similar.
     pub callbacks_overwrites_focus: Option<FocusTarget>,
+    /// Node that one or more callbacks want to scroll into view
+    pub callbacks_scroll_into_view: Option<NodeId>,
+    /// Whether a callback already scrolled by the scroll amount of the event, see `CallbackInfo::consume_scroll`
+    pub callbacks_consumed_scroll: bool,
+}
+
+/// Step of the dispatch of one event, see `dispatch_event`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum DispatchStep {
+    /// User-defined callback of the node, in the given phase
+    Callback(NodeId, EventPhase),
+    /// Default callbacks (i.e. the editing of a `TextInput`) of all nodes, in bubble order
+    DefaultCallbacks,
+}
+
+/// Set by the callbacks of one event, controls which of the remaining steps are invoked
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+struct DispatchState {
+    propagation_stopped: bool,
+    default_prevented: bool,
+}
+
+/// Invokes the callbacks of one event in the order of the `dispatch_order`: first the capture
+/// callbacks (from the root to the target), then the default callbacks, then the normal
+/// callbacks (from the target back to the root). The default callbacks run before the normal
+/// callbacks, so that i.e. an `On::TextInput` callback of a `TextInput` already sees the edited
+/// text - so only capture callbacks can prevent them.
+///
+/// Stopping the propagation skips the remaining capture and normal callbacks, but not the
+/// default callbacks (same as in HTML). Events that don't propagate aren't affected by it.
+fn dispatch_event<E, F>(dispatch_order: &[(NodeId, EventPhase)], event_propagates: bool, mut invoke: F) -> Result<(), E>
+where F: FnMut(DispatchStep, &mut DispatchState) -> Result<(), E>
+{
+    let mut dispatch_state = DispatchState::default();
+
+    let capture_steps = dispatch_order.iter().filter(|(_, phase)| *phase == EventPhase::Capture);
+    let bubble_steps = dispatch_order.iter().filter(|(_, phase)| *phase == EventPhase::Bubble);
+
+    for (node_id, phase) in capture_steps {
+        if dispatch_state.propagation_stopped && event_propagates {
+            break;
+        }
+        invoke(DispatchStep::Callback(*node_id, *phase), &mut dispatch_state)?;
+    }
+
+    if !dispatch_state.default_prevented {
+        invoke(DispatchStep::DefaultCallbacks, &mut dispatch_state)?;
+    }
+
+    for (node_id, phase) in bubble_steps {
+        if dispatch_state.propagation_stopped && event_propagates {
+            break;
+        }
+        invoke(DispatchStep::Callback(*node_id, *phase), &mut dispatch_state)?;
+    }
+
+    Ok(())
 }
 
 /// Returns an bool whether the window should be redrawn or not (true - redraw the screen, false: don't redraw).
@@ -830,7 +1458,8 @@ fn call_callbacks<T>(
 {
     use {
         callbacks::CallbackInfo,
-        window_state::{KeyboardState, MouseState},
+        window_state::{KeyboardState, MouseState, event_filter_propagates},
+        touch::TouchState,
         self::RuntimeError::*,
     };
 
@@ -840,92 +1469,247 @@ fn call_callbacks<T>(
 
     let callbacks_filter_list = window.state.determine_callbacks(&hit_test_items, event, ui_state);
 
+    // A new drag operation replaces the payload of the previous one,
+    // files dragged in from the OS are the payload of their drag operation
+    if callbacks_filter_list.drag_started {
+        app_state.drag_data = None;
+    }
+
+    match event {
+        WindowEvent::HoveredFile(path) | WindowEvent::DroppedFile(path) => add_dragged_file(&mut app_state.drag_data, path),
+        _ => { },
+    }
+
     // TODO: this should be refactored - currently very stateful and error-prone!
     app_state.windows.get_mut(window_id).ok_or(WindowIndexError)?
         .set_keyboard_state(&window.state.internal.keyboard_state);
     app_state.windows.get_mut(window_id).ok_or(WindowIndexError)?
         .set_mouse_state(&window.state.internal.mouse_state);
+    app_state.windows.get_mut(window_id).ok_or(WindowIndexError)?
+        .set_touch_state(&window.state.internal.touch_state);
 
     let mut callbacks_overwrites_focus = None;
+    let mut callbacks_scroll_into_view = None;
+    let mut callbacks_consumed_scroll = false;
+
+    // Layout of the current frame, so that callbacks can query the geometry of nodes
+    let last_layout_result = app_state.windows.get(window_id).ok_or(WindowIndexError)?.last_layout_result.clone();
+    let layout_result = last_layout_result.as_ref().map(|layout_result| &**layout_result);
+
+    let node_hierarchy = &ui_state.dom.arena.node_layout;
+
+    // Events are dispatched one after another, DOM-style, see `dispatch_event`
+    for event_filter in callbacks_filter_list.get_event_filters() {
+
+        let dispatch_order = callbacks_filter_list.get_dispatch_order(&event_filter, node_hierarchy);
+        let event_propagates = event_filter_propagates(&event_filter);
+
+        dispatch_event(&dispatch_order, event_propagates, |step, dispatch_state| -> Result<(), RuntimeError<T>> {
+
+            match step {
+                DispatchStep::Callback(node_id, phase) => {
+
+                    let callback_results = &callbacks_filter_list.nodes_with_callbacks[&node_id];
+                    let callback = match phase {
+                        EventPhase::Capture => callback_results.capture_callbacks.get(&event_filter),
+                        EventPhase::Bubble => callback_results.normal_callbacks.get(&event_filter),
+                    };
+
+                    let callback = match callback {
+                        Some(s) => s,
+                        None => return Ok(()), // node only has a default callback for this event
+                    };
+
+                    let hit_item = &callback_results.hit_test_item;
+
+                    let mut callback_info = CallbackInfo {
+                        focus: None,
+                        window_id,
+                        hit_dom_node: node_id,
+                        target: callbacks_filter_list.get_event_target(&event_filter, node_id),
+                        phase,
+                        propagation_stopped: false,
+                        default_prevented: dispatch_state.default_prevented,
+                        ui_state: &ui_state,
+                        hit_test_items: &hit_test_items,
+                        cursor_relative_to_item: hit_item.as_ref().map(|hi| (hi.point_relative_to_item.x, hi.point_relative_to_item.y)),
+                        cursor_in_viewport: hit_item.as_ref().map(|hi| (hi.point_in_viewport.x, hi.point_in_viewport.y)),
+                        layout_result,
+                        scroll_into_view: None,
+                        scroll_consumed: false,
+                    };
+
+                    if callback.invoke(app_state, &mut callback_info) == Redraw {
+                        should_update_screen = Redraw;
+                    }
+
+                    if let Some(new_focus) = callback_info.focus {
+                        callbacks_overwrites_focus = Some(new_focus);
+                    }
+
+                    if let Some(node_id) = callback_info.scroll_into_view {
+                        callbacks_scroll_into_view = Some(node_id);
+                    }
+
+                    if callback_info.scroll_consumed {
+                        callbacks_consumed_scroll = true;
+                    }
+
+                    dispatch_state.default_prevented = callback_info.default_prevented;
+                    dispatch_state.propagation_stopped = callback_info.propagation_stopped;
+                },
+                DispatchStep::DefaultCallbacks => {
+
+                    let mut default_timers = FastHashMap::default();
+                    let mut default_tasks = Vec::new();
+                    let mut default_window_requests = Vec::new();
+
+                    // Run the default callbacks of the event in the same order as the normal callbacks
+                    {
+                        let mut lock = app_state.data.lock().map_err(|_| RuntimeError::MutexLockError)?;
+
+                        for (node_id, _) in dispatch_order.iter().filter(|(_, phase)| *phase == EventPhase::Bubble) {
+
+                            let callback_results = &callbacks_filter_list.nodes_with_callbacks[node_id];
+                            let default_callback_id = match callback_results.default_callbacks.get(&event_filter) {
+                                Some(s) => s,
+                                None => continue,
+                            };
+
+                            let hit_item = &callback_results.hit_test_item;
+
+                            let mut callback_info = CallbackInfo {
+                                focus: None,
+                                window_id,
+                                hit_dom_node: *node_id,
+                                target: callbacks_filter_list.get_event_target(&event_filter, *node_id),
+                                phase: EventPhase::Bubble,
+                                propagation_stopped: false,
+                                default_prevented: false,
+                                ui_state,
+                                hit_test_items: &hit_test_items,
+                                cursor_relative_to_item: hit_item.as_ref().map(|hi| (hi.point_relative_to_item.x, hi.point_relative_to_item.y)),
+                                cursor_in_viewport: hit_item.as_ref().map(|hi| (hi.point_in_viewport.x, hi.point_in_viewport.y)),
+                                layout_result,
+                                scroll_into_view: None,
+                                scroll_consumed: false,
+                            };
+
+                            let mut app_state_no_data = AppStateNoData {
+                                windows: &app_state.windows,
+                                resources: &mut app_state.resources,
+                                timers: FastHashMap::default(),
+                                tasks: Vec::new(),
+                                window_requests: Vec::new(),
+                                drag_data: app_state.drag_data.take(),
+                            };
+
+                            if app_state.windows[window_id].default_callbacks.run_callback(
+                                &mut *lock,
+                                default_callback_id,
+                                &mut app_state_no_data,
+                                &mut callback_info
+                            ) == Redraw {
+                                should_update_screen = Redraw;
+                            }
+
+                            default_timers.extend(app_state_no_data.timers.into_iter());
+                            default_tasks.extend(app_state_no_data.tasks.into_iter());
+                            default_window_requests.extend(app_state_no_data.window_requests.into_iter());
+                            app_state.drag_data = app_state_no_data.drag_data;
+
+                            // Overwrite the focus from the callback info
+                            if let Some(new_focus) = callback_info.focus {
+                                callbacks_overwrites_focus = Some(new_focus);
+                            }
+
+                            if let Some(node_id) = callback_info.scroll_into_view {
+                                callbacks_scroll_into_view = Some(node_id);
+                            }
+
+                            if callback_info.scroll_consumed {
+                                callbacks_consumed_scroll = true;
+                            }
+                        }
+                    }
+
+                    // If the default callbacks have started timers or tasks, add them to the main app state
+                    for (timer_id, timer) in default_timers {
+                        app_state.add_timer(timer_id, timer);
+                    }
+
+                    for task in default_tasks {
+                        app_state.add_task(task);
+                    }
+
+                    app_state.window_requests.extend(default_window_requests);
+                },
+            }
 
-    let mut default_timers = FastHashMap::default();
-    let mut default_tasks = Vec::new();
-
-    // Run all default callbacks - **before** the user-defined callbacks are run!
-    {
-        let mut lock = app_state.data.lock().map_err(|_| RuntimeError::MutexLockError)?;
-
-        for (node_id, callback_results) in callbacks_filter_list.nodes_with_callbacks.iter() {
-            let hit_item = &callback_results.hit_test_item;
-            for default_callback_id in callback_results.default_callbacks.values() {
-
-                let mut callback_info = CallbackInfo {
-                    focus: None,
-                    window_id,
-                    hit_dom_node: *node_id,
-                    ui_state,
-                    hit_test_items: &hit_test_items,
-                    cursor_relative_to_item: hit_item.as_ref().map(|hi| (hi.point_relative_to_item.x, hi.point_relative_to_item.y)),
-                    cursor_in_viewport: hit_item.as_ref().map(|hi| (hi.point_in_viewport.x, hi.point_in_viewport.y)),
-                };
-
-                let mut app_state_no_data = AppStateNoData {
-                    windows: &app_state.windows,
-                    resources: &mut app_state.resources,
-                    timers: FastHashMap::default(),
-                    tasks: Vec::new(),
-                };
+            Ok(())
+        })?;
+    }
 
-                if app_state.windows[window_id].default_callbacks.run_callback(
-                    &mut *lock,
-                    default_callback_id,
-                    &mut app_state_no_data,
-                    &mut callback_info
-                ) == Redraw {
-                    should_update_screen = Redraw;
-                }
+    // Shortcuts and menu accelerators are dispatched globally, independent of the hovered node
+    if let WindowEvent::KeyboardInput { input: KeyboardInput { state: ElementState::Pressed, virtual_keycode: Some(key), .. }, .. } = event {
 
-                default_timers.extend(app_state_no_data.timers.into_iter());
-                default_tasks.extend(app_state_no_data.tasks.into_iter());
+        let fake_window = app_state.windows.get_mut(window_id).ok_or(WindowIndexError)?;
 
-                // Overwrite the focus from the callback info
-                if let Some(new_focus) = callback_info.focus {
-                    callbacks_overwrites_focus = Some(new_focus);
-                }
-            }
-        }
-    }
+        let keyboard_state = &window.state.internal.keyboard_state;
+        let focused_node = window.state.internal.focused_node;
+        let is_scope_active = |scope: &ShortcutScope| scope.is_active(focused_node, &ui_state.dom.arena);
 
-    // If the default callbacks have started timers or tasks, add them to the main app state
-    for (timer_id, timer) in default_timers {
-        app_state.add_timer(timer_id, timer);
-    }
+        let shortcut_callback = if *key == VirtualKeyCode::Escape && fake_window.is_menu_open() {
+            fake_window.close_menu();
+            should_update_screen = Redraw;
+            None
+        } else if let Some(callback) = fake_window.shortcuts.handle_key(keyboard_state, *key, is_scope_active) {
+            Some(callback)
+        } else if fake_window.shortcuts.get_pending_chord().is_empty() {
+            fake_window.menu_bar.as_ref().and_then(|menu_bar| menu_bar.find_accelerator(keyboard_state, *key))
+        } else {
+            None
+        };
 
-    for task in default_tasks {
-        app_state.add_task(task);
-    }
+        if let Some(shortcut_callback) = shortcut_callback {
 
-    for (node_id, callback_results) in callbacks_filter_list.nodes_with_callbacks.iter() {
-        let hit_item = &callback_results.hit_test_item;
-        for callback in callback_results.normal_callbacks.values() {
+            if fake_window.is_menu_open() {
+                fake_window.close_menu();
+                should_update_screen = Redraw;
+            }
 
             let mut callback_info = CallbackInfo {
                 focus: None,
                 window_id,
-                hit_dom_node: *node_id,
-                ui_state: &ui_state,
+                hit_dom_node: NodeId::new(0),
+                target: NodeId::new(0),
+                phase: EventPhase::Bubble,
+                propagation_stopped: false,
+                default_prevented: false,
+                ui_state,
                 hit_test_items: &hit_test_items,
-                cursor_relative_to_item: hit_item.as_ref().map(|hi| (hi.point_relative_to_item.x, hi.point_relative_to_item.y)),
-                cursor_in_viewport: hit_item.as_ref().map(|hi| (hi.point_in_viewport.x, hi.point_in_viewport.y)),
+                cursor_relative_to_item: None,
+                cursor_in_viewport: None,
+                layout_result,
+                scroll_into_view: None,
+                scroll_consumed: false,
             };
 
-            if (callback.0)(app_state, &mut callback_info) == Redraw {
+            if (shortcut_callback.0)(app_state, &mut callback_info) == Redraw {
                 should_update_screen = Redraw;
             }
 
             if let Some(new_focus) = callback_info.focus {
                 callbacks_overwrites_focus = Some(new_focus);
             }
+
+            if let Some(node_id) = callback_info.scroll_into_view {
+                callbacks_scroll_into_view = Some(node_id);
+            }
+
+            if callback_info.scroll_consumed {
+                callbacks_consumed_scroll = true;
+            }
         }
     }
 
@@ -933,17 +1717,38 @@ fn call_callbacks<T>(
         should_update_screen = Redraw;
     }
 
+    // The payload is only valid until the DragEnd callbacks have run
+    if callbacks_filter_list.drag_ended {
+        app_state.drag_data = None;
+    }
+
     app_state.windows.get_mut(window_id).ok_or(WindowIndexError)?
         .set_keyboard_state(&KeyboardState::default());
     app_state.windows.get_mut(window_id).ok_or(WindowIndexError)?
         .set_mouse_state(&MouseState::default());
+    app_state.windows.get_mut(window_id).ok_or(WindowIndexError)?
+        .set_touch_state(&TouchState::default());
 
     Ok(CallCallbackReturn {
         should_update_screen,
         callbacks_overwrites_focus,
+        callbacks_scroll_into_view,
+        callbacks_consumed_scroll,
     })
 }
 
+/// Adds a file that is dragged in from the OS to the payload of the current drag operation
+fn add_dragged_file(drag_data: &mut Option<DragData>, path: &Path) {
+    if let Some(DragData::Files(files)) = drag_data {
+        if !files.iter().any(|file| file == path) {
+            files.push(path.to_path_buf());
+        }
+        return;
+    }
+
+    *drag_data = Some(DragData::Files(vec![path.to_path_buf()]));
+}
+
 /// Build the display list and send it to webrender
 fn update_display_list<T>(
     app_data: &mut Arc<Mutex<T>>,
@@ -959,7 +1764,7 @@ fn update_display_list<T>(
 
     // NOTE: layout_result contains all words, text information, etc.
     // - very important for selection!
-    let (builder, scrolled_nodes, _layout_result) = display_list.into_display_list_builder(
+    let (builder, scrolled_nodes, layout_result) = display_list.into_display_list_builder(
         app_data,
         window,
         fake_window,
@@ -969,6 +1774,7 @@ fn update_display_list<T>(
     // NOTE: Display list has to be rebuilt every frame, otherwise, the epochs get out of sync
     let display_list_builder = builder.finalize().2;
     window.internal.last_scrolled_nodes = scrolled_nodes;
+    fake_window.last_layout_result = Some(Rc::new(layout_result));
 
     let (logical_size, _) = convert_window_size(&window.state.size);
 
@@ -1058,6 +1864,44 @@ fn update_scroll_state<T>(
     should_scroll_render
 }
 
+/// Scrolls all scrollable parents of the node, so that the node is visible
+/// in each of them. Returns whether any scroll state changed.
+fn scroll_node_into_view<T>(
+    window: &mut Window<T>,
+    node_hierarchy: &NodeHierarchy,
+    layout_result: &LayoutResult,
+    node_id: NodeId,
+) -> bool {
+
+    use webrender::api::LayoutRect;
+
+    let node_rect = match (node_hierarchy.get(node_id), layout_result.get_node_rect(node_id)) {
+        (Some(_), Some(rect)) => rect,
+        _ => return false, // node_id out of range
+    };
+
+    let mut should_scroll_render = false;
+
+    let scrolled_nodes = &window.internal.last_scrolled_nodes;
+    let scroll_states = &mut window.scroll_states;
+
+    for scroll_node in node_id.ancestors(node_hierarchy).skip(1)
+        .filter_map(|parent_id| scrolled_nodes.overflowing_nodes.get(&parent_id)) {
+
+        let parent_bounds = scroll_node.parent_rect.bounds;
+        let rect_in_parent = LayoutRect::new(
+            LayoutPoint::new(node_rect.origin.x - parent_bounds.origin.x, node_rect.origin.y - parent_bounds.origin.y),
+            node_rect.size,
+        );
+
+        if scroll_states.scroll_rect_into_view(&scroll_node.parent_external_scroll_id, parent_bounds.size, rect_in_parent) {
+            should_scroll_render = true;
+        }
+    }
+
+    should_scroll_render
+}
+
 fn clean_up_unused_opengl_textures(pipeline_info: PipelineInfo) {
 
     use compositor::ACTIVE_GL_TEXTURES;
@@ -1406,3 +2250,119 @@ fn set_webrender_debug_flags(r: &mut Renderer, old_flags: &DebugState, new_flags
         r.set_debug_flag(DebugFlags::GPU_CACHE_DBG, new_flags.gpu_cache_dbg);
     }
 }
+
+#[test]
+fn test_resolve_window_requests() {
+
+    let window_id = unsafe { GliumWindowId::dummy() };
+
+    // Closing a window twice or closing a window that isn't open is ignored
+    let requests = vec![WindowRequest::<()>::Close(window_id), WindowRequest::Close(window_id)];
+    let (open, close) = resolve_window_requests(requests, &[window_id]);
+    assert!(open.is_empty());
+    assert_eq!(close, vec![window_id]);
+
+    let (_, close) = resolve_window_requests(vec![WindowRequest::<()>::Close(window_id)], &[]);
+    assert!(close.is_empty());
+
+    let requests = vec![WindowRequest::<()>::Open(WindowCreateOptions::default(), Css::new())];
+    let (open, close) = resolve_window_requests(requests, &[window_id]);
+    assert_eq!(open.len(), 1);
+    assert!(close.is_empty());
+
+    // Quitting closes all windows, including the ones that were opened in the same frame
+    let requests = vec![
+        WindowRequest::<()>::Open(WindowCreateOptions::default(), Css::new()),
+        WindowRequest::Quit,
+    ];
+    let (open, close) = resolve_window_requests(requests, &[window_id]);
+    assert!(open.is_empty());
+    assert_eq!(close, vec![window_id]);
+}
+
+#[test]
+fn test_close_requested_callback_can_veto_closing() {
+
+    use std::cell::Cell;
+
+    let close_requested = [WindowEvent::CloseRequested];
+    let destroyed = [WindowEvent::Destroyed];
+
+    // Without a callback, the window closes immediately
+    assert!(should_close_window::<fn() -> ShouldClose>(&close_requested, None));
+
+    assert!(should_close_window(&close_requested, Some(|| ShouldClose::Close)));
+    assert!(!should_close_window(&close_requested, Some(|| ShouldClose::DontClose)));
+
+    // A destroyed window can't veto being closed, the callback isn't invoked
+    let callback_invoked = Cell::new(false);
+    assert!(should_close_window(&destroyed, Some(|| { callback_invoked.set(true); ShouldClose::DontClose })));
+    assert!(!callback_invoked.get());
+}
+
+#[test]
+fn test_dispatch_event_order() {
+
+    // Button (node 1) inside of a node (node 0), both with a capture and a normal callback
+    let dispatch_order = [
+        (NodeId::new(0), EventPhase::Capture),
+        (NodeId::new(1), EventPhase::Capture),
+        (NodeId::new(1), EventPhase::Bubble),
+        (NodeId::new(0), EventPhase::Bubble),
+    ];
+
+    let dispatch = |event_propagates: bool, stop_at: Option<DispatchStep>| {
+        let mut steps = Vec::new();
+        dispatch_event::<(), _>(&dispatch_order, event_propagates, |step, dispatch_state| {
+            steps.push(step);
+            dispatch_state.propagation_stopped = stop_at == Some(step);
+            Ok(())
+        }).unwrap();
+        steps
+    };
+
+    let capture_0 = DispatchStep::Callback(NodeId::new(0), EventPhase::Capture);
+    let capture_1 = DispatchStep::Callback(NodeId::new(1), EventPhase::Capture);
+    let bubble_1 = DispatchStep::Callback(NodeId::new(1), EventPhase::Bubble);
+    let bubble_0 = DispatchStep::Callback(NodeId::new(0), EventPhase::Bubble);
+
+    assert_eq!(dispatch(true, None), vec![capture_0, capture_1, DispatchStep::DefaultCallbacks, bubble_1, bubble_0]);
+    assert_eq!(dispatch(true, Some(bubble_1)), vec![capture_0, capture_1, DispatchStep::DefaultCallbacks, bubble_1]);
+
+    // Stopping the propagation doesn't prevent the default callbacks
+    assert_eq!(dispatch(true, Some(capture_0)), vec![capture_0, DispatchStep::DefaultCallbacks]);
+
+    // Events that don't propagate ignore `stop_propagation`
+    assert_eq!(dispatch(false, Some(capture_0)), vec![capture_0, capture_1, DispatchStep::DefaultCallbacks, bubble_1, bubble_0]);
+}
+
+#[test]
+fn test_dispatch_event_text_input_default_callback() {
+
+    use widgets::text_input::TextInputState;
+
+    // `TextInput` (node 1) with a user-defined `On::TextInput` callback inside of a node (node 0)
+    // with a capture callback, the `TextInputState` already contains the text "a" and "b" is typed
+    let dispatch_order = [(NodeId::new(0), EventPhase::Capture), (NodeId::new(1), EventPhase::Bubble)];
+
+    let type_char = |capture_prevents_default: bool| {
+        let mut state = TextInputState::new("a");
+        let mut text_in_user_callback = None;
+        dispatch_event::<(), _>(&dispatch_order, true, |step, dispatch_state| {
+            match step {
+                DispatchStep::Callback(_, EventPhase::Capture) => if capture_prevents_default { dispatch_state.default_prevented = true; },
+                // The default callback of the `TextInput`, see `TextInputState::on_text_input`
+                DispatchStep::DefaultCallbacks => state.text.push('b'),
+                DispatchStep::Callback(_, EventPhase::Bubble) => text_in_user_callback = Some(state.text.clone()),
+            }
+            Ok(())
+        }).unwrap();
+        (state.text, text_in_user_callback)
+    };
+
+    // The user-defined callback already sees the edited text
+    assert_eq!(type_char(false), ("ab".to_string(), Some("ab".to_string())));
+
+    // A capture callback can prevent the edit, the user-defined callback is still invoked
+    assert_eq!(type_char(true), ("a".to_string(), Some("a".to_string())));
+}
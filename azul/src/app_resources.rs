@@ -500,7 +500,7 @@ fn scan_ui_description_for_font_keys<'a, T>(
         let display_rect = &display_list.rectangles[node_id];
 
         match node_data.node_type {
-            Text(_) | Label(_) => {
+            Text(_) | Label(_) | Span(_) => {
                 let css_font_id = ui_solver::get_font_id(&display_rect.style);
                 let font_id = match app_resources.css_ids_to_font_ids.get(css_font_id) {
                     Some(s) => ImmediateFontId::Resolved(*s),
@@ -934,7 +934,7 @@ enum LinuxNativeFontType { SansSerif, Monospace }
 #[cfg(target_os = "linux")]
 fn linux_get_native_font(font_type: LinuxNativeFontType) -> String {
 
-    use std::process::Command;
+    use azul_native_style::gsettings_get;
     use self::LinuxNativeFontType::*;
 
     let font_name = match font_type {
@@ -948,15 +948,7 @@ fn linux_get_native_font(font_type: LinuxNativeFontType) -> String {
     };
 
     // Execute "gsettings get org.gnome.desktop.interface font-name" and parse the output
-    let gsetting_cmd_result =
-        Command::new("gsettings")
-            .arg("get")
-            .arg("org.gnome.desktop.interface")
-            .arg(font_name)
-            .output()
-            .ok().map(|output| output.stdout)
-            .and_then(|stdout_bytes| String::from_utf8(stdout_bytes).ok())
-            .map(|stdout_string| stdout_string.lines().collect::<String>());
+    let gsetting_cmd_result = gsettings_get(font_name);
 
     match &gsetting_cmd_result {
         Some(s) => parse_gsettings_font(s).to_string(),
@@ -1,14 +1,32 @@
 use std::{
-    sync::{Arc, Mutex, Weak, atomic::{AtomicUsize, Ordering}},
+    any::Any,
+    sync::{Arc, Mutex, Condvar, mpsc::{channel, Sender}, atomic::{AtomicBool, AtomicUsize, Ordering}},
     thread::{self, JoinHandle},
     time::{Duration, Instant},
     fmt,
     hash::{Hash, Hasher},
+    future::Future,
+    mem::ManuallyDrop,
+    marker::PhantomData,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
 };
+use glium::glutin::EventsLoopProxy;
 use {
-    callbacks::{UpdateScreen, DontRedraw, TimerCallback, TimerCallbackType},
-    app_resources::AppResources,
+    callbacks::{UpdateScreen, DontRedraw, TimerCallback, TimerCallbackType, TimerClosureCallback, TimerCallbackVariant},
+    app::AppStateNoData,
 };
+#[cfg(test)]
+use app_resources::AppResources;
+
+/// Number of threads that run the futures of `Task::from_future`
+const THREAD_POOL_SIZE: usize = 4;
+
+lazy_static! {
+    /// Thread pool for running futures, only started when the first future is spawned
+    static ref THREAD_POOL: ThreadPool = ThreadPool::new(THREAD_POOL_SIZE);
+}
 
 /// Should a timer terminate or not - used to remove active timers
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -55,27 +73,37 @@ pub struct Timer<T> {
     pub delay: Option<Duration>,
     /// How frequently the timer should run, i.e. set this to `Some(Duration::from_millis(16))`
     /// to run the timer every 16ms. If this value is set to `None`, (the default), the timer
-    /// will execute the timer as-fast-as-possible (i.e. at a faster framerate
-    /// than the framework itself) - which might be  performance intensive.
+    /// is treated as an animation and runs on every frame, as fast as
+    /// `AppConfig::min_frame_time` allows - which might be performance intensive.
     pub interval: Option<Duration>,
     /// When to stop the timer (for example, you can stop the
     /// execution after 5s using `Some(Duration::from_secs(5))`).
     pub timeout: Option<Duration>,
     /// Callback to be called for this timer
-    pub callback: TimerCallback<T>,
+    pub callback: TimerCallbackVariant<T>,
 }
 
 impl<T> Timer<T> {
 
     /// Create a new timer
     pub fn new(callback: TimerCallbackType<T>,) -> Self {
+        Self::from_callback(TimerCallbackVariant::Fn(TimerCallback(callback)))
+    }
+
+    /// Create a new timer from a closure, which can capture state
+    /// (i.e. the ID of the object that should be animated)
+    pub fn from_closure(callback: TimerClosureCallback<T>) -> Self {
+        Self::from_callback(TimerCallbackVariant::Closure(callback))
+    }
+
+    fn from_callback(callback: TimerCallbackVariant<T>) -> Self {
         Timer {
             created: Instant::now(),
             last_run: None,
             delay: None,
             interval: None,
             timeout: None,
-            callback: TimerCallback(callback),
+            callback,
         }
     }
 
@@ -103,12 +131,35 @@ impl<T> Timer<T> {
         self
     }
 
+    /// Crate-internal: Returns the next point in time at which the timer has to be
+    /// invoked (or terminated because of its timeout), so that the event loop knows
+    /// when it has to wake up again.
+    ///
+    /// Returns `None` if the timer has no interval and is past its delay, i.e. it is an
+    /// animation that wants to run on every frame.
+    pub(crate) fn get_next_run_time(&self) -> Option<Instant> {
+        let delay = self.delay.unwrap_or_else(|| Duration::from_millis(0));
+        let timeout = self.timeout.map(|timeout| self.created + timeout);
+
+        let next_run = match (self.interval, self.last_run) {
+            (Some(interval), Some(last_run)) => Some(last_run + interval),
+            (Some(interval), None) => Some(self.created + delay + interval),
+            (None, _) if Instant::now() < self.created + delay => Some(self.created + delay),
+            (None, _) => None,
+        };
+
+        match (next_run, timeout) {
+            (Some(next_run), Some(timeout)) => Some(next_run.min(timeout)),
+            (next_run, _) => next_run,
+        }
+    }
+
     /// Crate-internal: Invokes the timer if the timer and
     /// the `self.timeout` allow it to
     pub(crate) fn invoke_callback_with_data(
         &mut self,
         data: &mut T,
-        app_resources: &mut AppResources)
+        app_state_no_data: &mut AppStateNoData<T>)
     -> (UpdateScreen, TerminateTimer)
     {
         let instant_now = Instant::now();
@@ -121,6 +172,11 @@ impl<T> Timer<T> {
             }
         }
 
+        // Check if the timer is still delayed
+        if instant_now < self.created + delay {
+            return (DontRedraw, TerminateTimer::Continue);
+        }
+
         if let Some(interval) = self.interval {
             let last_run = match self.last_run {
                 Some(s) => s,
@@ -131,7 +187,7 @@ impl<T> Timer<T> {
             }
         }
 
-        let res = (self.callback.0)(data, app_resources);
+        let res = self.callback.invoke(data, app_state_no_data);
 
         self.last_run = Some(instant_now);
 
@@ -139,7 +195,7 @@ impl<T> Timer<T> {
     }
 }
 
-// #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)] for Timer<T>
+// #[derive(Debug, Clone, PartialEq, Eq, Hash)] for Timer<T>
 
 impl<T> fmt::Debug for Timer<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -164,7 +220,14 @@ impl<T> fmt::Debug for Timer<T> {
 
 impl<T> Clone for Timer<T> {
     fn clone(&self) -> Self {
-        Timer { .. *self }
+        Timer {
+            created: self.created,
+            last_run: self.last_run,
+            delay: self.delay,
+            interval: self.interval,
+            timeout: self.timeout,
+            callback: self.callback.clone(),
+        }
     }
 }
 
@@ -192,87 +255,661 @@ impl<T> PartialEq for Timer<T> {
 
 impl<T> Eq for Timer<T> { }
 
-impl<T> Copy for Timer<T> { }
-
 /// Simple struct that is used by Azul internally to determine when the thread has finished executing.
-/// When this struct goes out of scope, Azul will call `.join()` on the thread (so in order to not
-/// block the main thread, simply let it go out of scope naturally.
-pub struct DropCheck(Arc<()>);
+/// When this struct goes out of scope, the task is marked as finished and Azul will call `.join()`
+/// on the thread (so in order to not block the main thread, simply let it go out of scope naturally).
+pub struct DropCheck(Arc<TaskState>);
+
+impl Drop for DropCheck {
+    fn drop(&mut self) {
+        // If the task panicked, the task thread marks the task as finished
+        // after the panic has been stored (see `run_task_callback`)
+        if !thread::panicking() {
+            self.0.finish();
+        }
+    }
+}
+
+/// Token that can be queried by a running task whether it should stop
+/// (because `TaskHandle::cancel` was called or the app is closing)
+#[derive(Clone)]
+pub struct CancellationToken(Arc<TaskState>);
+
+impl CancellationToken {
+    /// Returns true if the task should stop as soon as possible
+    pub fn is_cancelled(&self) -> bool {
+        self.0.is_cancelled()
+    }
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CancellationToken {{ is_cancelled: {:?} }}", self.is_cancelled())
+    }
+}
+
+/// Passed into the callback of a `Task::new_cancellable` task, in order to query whether the
+/// task was cancelled and to report the progress of the task (of type `P`) back to the UI.
+pub struct TaskContext<P> {
+    state: Arc<TaskState>,
+    marker: PhantomData<fn(P)>,
+}
 
-/// A `Task` is a seperate thread that is owned by the framework.
+impl<P: Send + 'static> TaskContext<P> {
+
+    /// Returns true if the task should stop as soon as possible
+    pub fn is_cancelled(&self) -> bool {
+        self.state.is_cancelled()
+    }
+
+    /// Returns a token that can be moved into other functions or threads, to check for cancellation
+    pub fn cancellation_token(&self) -> CancellationToken {
+        CancellationToken(self.state.clone())
+    }
+
+    /// Reports the current progress to the UI, overwriting the last progress. The progress
+    /// can be read via `TaskHandle::get_progress::<P>()` and the UI is redrawn automatically.
+    pub fn set_progress(&self, progress: P) {
+        self.state.set_progress(Box::new(progress));
+    }
+}
+
+/// Handle to a running task, returned by `AppState::add_task`.
+///
+/// The handle can be cloned and stored in the application data, so that
+/// callbacks can cancel the task and the layout can display the progress of the task.
+#[derive(Clone)]
+pub struct TaskHandle(Arc<TaskState>);
+
+impl TaskHandle {
+
+    /// Requests the task to stop. Futures (`Task::from_future`) are dropped before their next
+    /// `poll()`, tasks running on a thread have to check `TaskContext::is_cancelled` themselves.
+    ///
+    /// The `Task::then` timer of a cancelled task won't be run.
+    pub fn cancel(&self) {
+        self.0.cancel();
+    }
+
+    /// Returns true if `cancel()` was called on this task
+    pub fn is_cancelled(&self) -> bool {
+        self.0.is_cancelled()
+    }
+
+    /// Returns true if the task has been finished (or has stopped after being cancelled)
+    pub fn is_finished(&self) -> bool {
+        self.0.is_finished()
+    }
+
+    /// Returns the last progress reported via `TaskContext::set_progress`. Returns `None`
+    /// if no progress was reported yet or if the progress isn't of the type `P`.
+    pub fn get_progress<P: Any + Clone>(&self) -> Option<P> {
+        self.0.progress.lock().unwrap().as_ref()?.downcast_ref::<P>().cloned()
+    }
+
+    /// Returns the panic message if the task panicked, available in the `Task::or_else` timer
+    pub fn get_error(&self) -> Option<String> {
+        self.0.error.lock().unwrap().clone()
+    }
+}
+
+impl fmt::Debug for TaskHandle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,
+            "TaskHandle {{ is_finished: {:?}, is_cancelled: {:?} }}",
+            self.is_finished(),
+            self.is_cancelled(),
+        )
+    }
+}
+
+/// State that is shared between a `Task` and the thread or future that runs it
+struct TaskState {
+    finished: AtomicBool,
+    cancelled: AtomicBool,
+    /// Set when the progress changed since the last frame, so that the UI can be redrawn
+    progress_changed: AtomicBool,
+    /// Last progress reported via `TaskContext::set_progress`
+    progress: Mutex<Option<Box<dyn Any + Send>>>,
+    /// Panic payload, if the task panicked - re-thrown on the main thread when the task is removed
+    panic: Mutex<Option<Box<dyn Any + Send>>>,
+    /// Message of the panic, kept after the panic payload was taken
+    error: Mutex<Option<String>>,
+    /// Wakes up the event loop when the task is finished, set in `add_task`.
+    /// Also guards `finished`, so that `wait_until_finished` can't miss the notification
+    events_loop_proxy: Mutex<Option<EventsLoopProxy>>,
+    finished_condvar: Condvar,
+    /// The future of a `Task::from_future` task, keeps the future alive while it is waiting to
+    /// be woken up and allows dropping it on cancellation. Reset when the task is finished
+    /// (otherwise the `FutureTask` and the `TaskState` would keep each other alive).
+    future_task: Mutex<Option<Arc<FutureTask>>>,
+}
+
+impl TaskState {
+
+    fn new() -> Self {
+        Self {
+            finished: AtomicBool::new(false),
+            cancelled: AtomicBool::new(false),
+            progress_changed: AtomicBool::new(false),
+            progress: Mutex::new(None),
+            panic: Mutex::new(None),
+            error: Mutex::new(None),
+            events_loop_proxy: Mutex::new(None),
+            finished_condvar: Condvar::new(),
+            future_task: Mutex::new(None),
+        }
+    }
+
+    fn finish(&self) {
+        // Set the flag while holding the lock, so that `set_events_loop_proxy`
+        // can't miss the wakeup if the task finishes at the same time
+        let proxy = self.events_loop_proxy.lock().unwrap();
+        self.finished.store(true, Ordering::SeqCst);
+        self.finished_condvar.notify_all();
+        if let Some(proxy) = &*proxy {
+            // Fails only if the event loop is already closed
+            let _ = proxy.wakeup();
+        }
+        drop(proxy);
+        *self.future_task.lock().unwrap() = None;
+    }
+
+    fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::SeqCst)
+    }
+
+    /// Blocks the current thread until the task is finished or the `deadline` has passed,
+    /// returns whether the task is finished
+    fn wait_until_finished(&self, deadline: Instant) -> bool {
+        let mut proxy = self.events_loop_proxy.lock().unwrap();
+        while !self.is_finished() {
+            let now = Instant::now();
+            if now >= deadline {
+                return false;
+            }
+            proxy = self.finished_condvar.wait_timeout(proxy, deadline - now).unwrap().0;
+        }
+        true
+    }
+
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        // Re-schedule the future, so that it gets dropped even if it's waiting on something
+        let future_task = self.future_task.lock().unwrap().clone();
+        if let Some(future_task) = future_task {
+            future_task.schedule();
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    fn set_progress(&self, progress: Box<dyn Any + Send>) {
+        *self.progress.lock().unwrap() = Some(progress);
+        self.progress_changed.store(true, Ordering::SeqCst);
+        if let Some(proxy) = &*self.events_loop_proxy.lock().unwrap() {
+            let _ = proxy.wakeup();
+        }
+    }
+
+    fn set_events_loop_proxy(&self, new_proxy: EventsLoopProxy) {
+        let mut proxy = self.events_loop_proxy.lock().unwrap();
+        if self.is_finished() {
+            let _ = new_proxy.wakeup();
+        }
+        *proxy = Some(new_proxy);
+    }
+
+    fn set_panic(&self, payload: Box<dyn Any + Send>) {
+        *self.error.lock().unwrap() = Some(get_panic_message(&*payload));
+        *self.panic.lock().unwrap() = Some(payload);
+    }
+
+    fn has_panicked(&self) -> bool {
+        self.panic.lock().unwrap().is_some()
+    }
+
+    fn take_panic(&self) -> Option<Box<dyn Any + Send>> {
+        self.panic.lock().unwrap().take()
+    }
+}
+
+/// Returns the message of a `panic!()` (which is either a `&str` or a `String`)
+fn get_panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "task panicked".to_string()
+    }
+}
+
+/// A `Task` is a seperate thread or future that is owned by the framework.
 ///
 /// In difference to a `Thread`, you don't have to `await()` the result of a `Task`,
-/// you can just hand the task to the framework (via `AppResources::add_task`) and
+/// you can just hand the task to the framework (via `AppState::add_task`) and
 /// the framework will automatically update the UI when the task is finished.
 /// This is useful to offload actions such as loading long files, etc. to a background thread.
 ///
 /// Azul will join the thread automatically after it is finished (joining won't block the UI).
+/// Dropping a `Task` that is still running doesn't block either, the task is cancelled and
+/// detached instead. If the task panics, the panic is propagated to the main thread once
+/// the task is finished, unless the task has an `or_else` timer.
 pub struct Task<T> {
-    // Task is in progress
+    state: Arc<TaskState>,
+    /// Only set for tasks created with `Task::new`, futures run on the shared thread pool
     join_handle: Option<JoinHandle<()>>,
-    dropcheck: Weak<()>,
     /// Timer that will run directly after this task is completed.
     pub(crate) after_completion_timer: Option<Timer<T>>,
+    /// Timer that will run instead of `after_completion_timer` if the task panicked.
+    pub(crate) on_error_timer: Option<Timer<T>>,
 }
 
 impl<T> Task<T> {
 
     /// Creates a new task from a callback and a set of input data - which has to be wrapped in an `Arc<Mutex<T>>>`.
+    ///
+    /// Note that this spawns a new OS-level thread for every task, which is fine for a few
+    /// long-running, blocking jobs. For many small (i.e. IO-bound) jobs, use `Task::from_future`.
     pub fn new<U>(data: &Arc<Mutex<U>>, callback: fn(Arc<Mutex<U>>, DropCheck)) -> Self where U: Send + 'static {
 
-        let thread_check = Arc::new(());
-        let thread_weak = Arc::downgrade(&thread_check);
+        let state = Arc::new(TaskState::new());
+        let drop_check = DropCheck(state.clone());
+        let thread_state = state.clone();
         let app_state_clone = data.clone();
 
         let thread_handle = thread::spawn(move || {
-            callback(app_state_clone, DropCheck(thread_check))
+            run_task_callback(&thread_state, || callback(app_state_clone, drop_check))
         });
 
         Self {
+            state,
             join_handle: Some(thread_handle),
-            dropcheck: thread_weak,
             after_completion_timer: None,
+            on_error_timer: None,
+        }
+    }
+
+    /// Same as `Task::new`, but the callback gets a `TaskContext` instead of a `DropCheck`,
+    /// which can be used to check if the task was cancelled (via `TaskHandle::cancel`)
+    /// and to report the progress of type `P` to the UI.
+    ///
+    /// ```rust,ignore
+    /// fn download(data: Arc<Mutex<MyData>>, context: TaskContext<f32>) {
+    ///     for chunk in 0..100 {
+    ///         if context.is_cancelled() {
+    ///             return;
+    ///         }
+    ///         download_chunk(chunk);
+    ///         context.set_progress(chunk as f32 / 100.0);
+    ///     }
+    /// }
+    ///
+    /// let handle = app_state.add_task(Task::new_cancellable(&app_state.data, download));
+    /// // later, i.e. in the layout() function:
+    /// let progress = handle.get_progress::<f32>().unwrap_or(0.0);
+    /// ```
+    pub fn new_cancellable<U, P>(data: &Arc<Mutex<U>>, callback: fn(Arc<Mutex<U>>, TaskContext<P>)) -> Self
+    where U: Send + 'static, P: Send + 'static
+    {
+        let state = Arc::new(TaskState::new());
+        let drop_check = DropCheck(state.clone());
+        let context = TaskContext { state: state.clone(), marker: PhantomData };
+        let thread_state = state.clone();
+        let app_state_clone = data.clone();
+
+        let thread_handle = thread::spawn(move || {
+            run_task_callback(&thread_state, || {
+                // The task is finished when the drop_check goes out of scope
+                let _drop_check = drop_check;
+                callback(app_state_clone, context)
+            })
+        });
+
+        Self {
+            state,
+            join_handle: Some(thread_handle),
+            after_completion_timer: None,
+            on_error_timer: None,
+        }
+    }
+
+    /// Creates a new task that runs the given future on a small, shared thread pool
+    /// (instead of creating one thread per task). When the future is finished, the event
+    /// loop is woken up and the timer set via `Task::then` is run.
+    ///
+    /// To modify the application data from the future, move a clone
+    /// of the `Arc<Mutex<T>>` into the future. If the task is cancelled,
+    /// the future is dropped instead of being polled again.
+    ///
+    /// ```rust,ignore
+    /// let data = app_state.data.clone();
+    /// let task = Task::from_future(async move {
+    ///     let response = fetch_url("https://example.com").await;
+    ///     data.modify(|state| state.response = Some(response));
+    /// });
+    /// app_state.add_task(task.then(Timer::new(on_response_received)));
+    /// ```
+    pub fn from_future<F>(future: F) -> Self where F: Future<Output = ()> + Send + 'static {
+        Self::from_future_with_context(|_: TaskContext<()>| future)
+    }
+
+    /// Same as `Task::from_future`, but creates the future from a `TaskContext`,
+    /// so that the future can report its progress to the UI.
+    pub fn from_future_with_context<P, C, F>(create_future: C) -> Self
+    where P: Send + 'static, C: FnOnce(TaskContext<P>) -> F, F: Future<Output = ()> + Send + 'static
+    {
+        let state = Arc::new(TaskState::new());
+        let future = create_future(TaskContext { state: state.clone(), marker: PhantomData });
+
+        let future_task = Arc::new(FutureTask {
+            future: Mutex::new(Some(Box::pin(future))),
+            notified: AtomicBool::new(false),
+            state: state.clone(),
+        });
+
+        *state.future_task.lock().unwrap() = Some(future_task.clone());
+        future_task.schedule();
+
+        Self {
+            state,
+            join_handle: None,
+            after_completion_timer: None,
+            on_error_timer: None,
         }
     }
 
     /// Stores a `Timer` that will run after the task has finished.
     ///
     /// Often necessary to "clean up" or copy data from the background task into the UI.
+    /// The timer isn't run if the task was cancelled or panicked.
     #[inline]
     pub fn then(mut self, timer: Timer<T>) -> Self {
         self.after_completion_timer = Some(timer);
         self
     }
 
+    /// Stores a `Timer` that will run if the task panicked, instead of propagating the panic
+    /// to the main thread - i.e. in order to show an error message (see `widgets::toast`).
+    /// The panic message is available via `TaskHandle::get_error`.
+    #[inline]
+    pub fn or_else(mut self, timer: Timer<T>) -> Self {
+        self.on_error_timer = Some(timer);
+        self
+    }
+
+    /// Returns a handle to the task, which can be used to cancel the task or query its progress
+    pub fn get_handle(&self) -> TaskHandle {
+        TaskHandle(self.state.clone())
+    }
+
     /// Returns true if the task has been finished, false otherwise
     pub(crate) fn is_finished(&self) -> bool {
-        self.dropcheck.upgrade().is_none()
+        self.state.is_finished()
+    }
+
+    /// Returns true if the task was cancelled via `TaskHandle::cancel`
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.state.is_cancelled()
+    }
+
+    /// Returns true if the progress of the task changed since the last call to this function
+    pub(crate) fn take_progress_changed(&self) -> bool {
+        self.state.progress_changed.swap(false, Ordering::SeqCst)
+    }
+
+    /// Joins the finished task and returns the timer that should run now: the `then` timer
+    /// if the task succeeded or the `or_else` timer if the task panicked. If the task panicked
+    /// and has no `or_else` timer, the panic is re-thrown when the task is dropped.
+    pub(crate) fn take_completion_timer(&mut self) -> Option<Timer<T>> {
+
+        if let Some(thread_handle) = self.join_handle.take() {
+            if let Err(panic) = thread_handle.join() {
+                self.state.set_panic(panic);
+            }
+        }
+
+        if self.state.is_cancelled() {
+            return None;
+        }
+
+        if self.state.has_panicked() {
+            let timer = self.on_error_timer.take()?;
+            // The panic is handled by the timer
+            self.state.take_panic();
+            Some(timer)
+        } else {
+            self.after_completion_timer.take()
+        }
+    }
+
+    /// Wakes up the event loop via the `proxy` as soon as the task is finished
+    pub(crate) fn set_events_loop_proxy(&self, proxy: EventsLoopProxy) {
+        self.state.set_events_loop_proxy(proxy);
+    }
+
+    /// Cancels the tasks and waits until they are finished, used when the app is closing
+    /// (so that the tasks don't hold on to the application data anymore).
+    ///
+    /// A `Task::new` task can't be cancelled and might block for a long time (i.e. in a file
+    /// dialog), so tasks that are still running after the `timeout` are detached instead.
+    pub(crate) fn cancel_and_join_all(tasks: Vec<Self>, timeout: Duration) {
+
+        for task in &tasks {
+            task.state.cancel();
+        }
+
+        let deadline = Instant::now() + timeout;
+
+        for mut task in tasks {
+            if task.state.wait_until_finished(deadline) {
+                // The thread is about to exit, so this doesn't block
+                if let Some(thread_handle) = task.join_handle.take() {
+                    if let Err(panic) = thread_handle.join() {
+                        task.state.set_panic(panic);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Runs the callback of a `Task::new` / `Task::new_cancellable` task on the task thread.
+/// The panic of the callback is stored in the `TaskState` before the task is marked as finished,
+/// so that it isn't lost if the `Task` was dropped (and the thread detached) in the meantime.
+fn run_task_callback<F: FnOnce()>(state: &TaskState, callback: F) {
+    if let Err(panic) = panic::catch_unwind(AssertUnwindSafe(callback)) {
+        state.set_panic(panic);
+        state.finish();
     }
 }
 
 impl<T> Drop for Task<T> {
     fn drop(&mut self) {
+
+        if !self.state.is_finished() {
+            // Don't block the UI: let the task know it should stop, then detach it.
+            // If the task panics later on, the panic is available via `TaskHandle::get_error`
+            self.state.cancel();
+            return;
+        }
+
         if let Some(thread_handle) = self.join_handle.take() {
-            let _ = thread_handle.join().unwrap();
+            if let Err(panic) = thread_handle.join() {
+                self.state.set_panic(panic);
+            }
+        }
+
+        // Re-throw the panic of the task on the main thread (but don't panic while panicking)
+        if !thread::panicking() {
+            if let Some(panic) = self.state.take_panic() {
+                panic::resume_unwind(panic);
+            }
+        }
+    }
+}
+
+/// A future that was spawned with `Task::from_future`. The future is `None` while
+/// it is being polled and after it has been completed (or panicked / was cancelled).
+struct FutureTask {
+    future: Mutex<Option<Pin<Box<dyn Future<Output = ()> + Send>>>>,
+    /// Set when the task is woken up, so that a wake-up that happens while the
+    /// future is being polled (on another thread) isn't lost
+    notified: AtomicBool,
+    state: Arc<TaskState>,
+}
+
+impl FutureTask {
+
+    /// Marks the task as woken up and queues it on the thread pool
+    fn schedule(self: Arc<Self>) {
+        self.notified.store(true, Ordering::SeqCst);
+        THREAD_POOL.schedule(self);
+    }
+
+    /// Polls the future on the current thread pool thread, until it is pending
+    /// without having been woken up in the meantime
+    fn run(self: Arc<Self>) {
+
+        loop {
+
+            // Don't hold the lock while polling, otherwise a future that wakes itself up
+            // would block another thread pool thread until the poll is finished
+            let future = self.future.lock().unwrap().take();
+
+            let mut future = match future {
+                Some(s) => s,
+                // Finished or currently being polled by another thread,
+                // which will see the notified flag and poll again
+                None => return,
+            };
+
+            if self.state.is_cancelled() {
+                // Drop the future (and everything it captured) before marking the task as finished
+                drop(future);
+                self.state.finish();
+                return;
+            }
+
+            self.notified.store(false, Ordering::SeqCst);
+
+            let waker = new_future_task_waker(self.clone());
+            let mut context = Context::from_waker(&waker);
+
+            match panic::catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(&mut context))) {
+                Ok(Poll::Pending) => {
+                    *self.future.lock().unwrap() = Some(future);
+                    // Woken up while polling: the scheduled run might have found no future
+                    if !self.notified.load(Ordering::SeqCst) {
+                        return;
+                    }
+                },
+                Ok(Poll::Ready(())) => {
+                    drop(future);
+                    self.state.finish();
+                    return;
+                },
+                Err(panic) => {
+                    drop(future);
+                    self.state.set_panic(panic);
+                    self.state.finish();
+                    return;
+                },
+            }
         }
     }
 }
 
+/// Fixed-size pool of threads that poll the futures of all `Task::from_future` tasks
+struct ThreadPool {
+    sender: Mutex<Sender<Arc<FutureTask>>>,
+}
+
+impl ThreadPool {
+
+    fn new(num_threads: usize) -> Self {
+
+        let (sender, receiver) = channel::<Arc<FutureTask>>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        for thread_idx in 0..num_threads {
+            let receiver = receiver.clone();
+            thread::Builder::new()
+                .name(format!("azul-task-{}", thread_idx))
+                .spawn(move || loop {
+                    let next_task = receiver.lock().unwrap().recv();
+                    match next_task {
+                        Ok(task) => task.run(),
+                        Err(_) => break,
+                    }
+                })
+                .expect("Could not spawn thread pool thread");
+        }
+
+        Self { sender: Mutex::new(sender) }
+    }
+
+    /// Queues the task to be polled on the next free thread
+    fn schedule(&self, task: Arc<FutureTask>) {
+        // The receiver lives as long as the threads, which never exit
+        let _ = self.sender.lock().unwrap().send(task);
+    }
+}
+
+static FUTURE_TASK_WAKER_VTABLE: RawWakerVTable = RawWakerVTable::new(
+    clone_future_task_waker,
+    wake_future_task_waker,
+    wake_future_task_waker_by_ref,
+    drop_future_task_waker,
+);
+
+/// Creates a `Waker` that re-schedules the task on the thread pool when woken up.
+/// The waker owns one strong reference of the `Arc<FutureTask>`.
+fn new_future_task_waker(task: Arc<FutureTask>) -> Waker {
+    unsafe { Waker::from_raw(new_future_task_raw_waker(task)) }
+}
+
+fn new_future_task_raw_waker(task: Arc<FutureTask>) -> RawWaker {
+    RawWaker::new(Arc::into_raw(task) as *const (), &FUTURE_TASK_WAKER_VTABLE)
+}
+
+unsafe fn clone_future_task_waker(ptr: *const ()) -> RawWaker {
+    let task = ManuallyDrop::new(Arc::from_raw(ptr as *const FutureTask));
+    new_future_task_raw_waker(Arc::clone(&task))
+}
+
+unsafe fn wake_future_task_waker(ptr: *const ()) {
+    FutureTask::schedule(Arc::from_raw(ptr as *const FutureTask));
+}
+
+unsafe fn wake_future_task_waker_by_ref(ptr: *const ()) {
+    let task = ManuallyDrop::new(Arc::from_raw(ptr as *const FutureTask));
+    FutureTask::schedule(Arc::clone(&task));
+}
+
+unsafe fn drop_future_task_waker(ptr: *const ()) {
+    drop(Arc::from_raw(ptr as *const FutureTask));
+}
+
 /// A `Thread` is a simple abstraction over `std::thread` that allows to offload a pure
 /// function to a different thread (essentially emulating async / await for older compilers)
 pub struct Thread<T> {
-    data: Option<Arc<Mutex<T>>>,
-    join_handle: Option<JoinHandle<()>>,
+    join_handle: JoinHandle<T>,
 }
 
 /// Error that can happen while calling `.await()`
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum AwaitError {
     /// Arc::into_inner() failed
+    #[deprecated(note = "never returned, the result of a `Thread` isn't stored in an `Arc` anymore")]
     ArcUnlockError,
     /// The background thread panicked
     ThreadJoinError,
     /// Mutex::into_inner() failed
+    #[deprecated(note = "never returned, the result of a `Thread` isn't stored in a `Mutex` anymore")]
     MutexIntoInnerError,
 }
 
@@ -282,7 +919,8 @@ impl<T> Thread<T> {
     /// This is a workaround until `await` is implemented. Note that invoking this function
     /// will create an OS-level thread.
     ///
-    /// **Warning**: You *must* call `.await()`, otherwise the `Thread` will panic when it is dropped!
+    /// If the `Thread` is dropped without calling `.await()`, the thread is detached
+    /// and keeps running in the background, the result is discarded.
     ///
     /// # Example
     ///
@@ -306,46 +944,181 @@ impl<T> Thread<T> {
     /// assert_eq!(result_3, Ok(21));
     /// ```
     pub fn new<U>(initial_data: U, callback: fn(U) -> T) -> Self where T: Send + 'static, U: Send + 'static {
+        Self {
+            join_handle: thread::spawn(move || callback(initial_data)),
+        }
+    }
 
-        use std::mem;
+    /// Block until the internal thread has finished and return T.
+    ///
+    /// Returns `AwaitError::ThreadJoinError` if the thread panicked,
+    /// use `.join()` to get the panic payload instead.
+    pub fn await(self) -> Result<T, AwaitError> {
+        self.join_handle.join().map_err(|_| AwaitError::ThreadJoinError)
+    }
 
-        // Reserve memory for T and zero it
-        let data = Arc::new(Mutex::new(unsafe { mem::zeroed() }));
-        let data_arc = data.clone();
+    /// Block until the internal thread has finished, returns the panic
+    /// payload as an error if the thread panicked.
+    pub fn join(self) -> thread::Result<T> {
+        self.join_handle.join()
+    }
+}
 
-        // For some reason, Rust doesn't realize that we're *moving* the data into the
-        // child thread, which is why the 'static is unnecessary - that would only be necessary
-        // if we'd reference the data from the main thread
-        let thread_handle = thread::spawn(move || {
-            *data_arc.lock().unwrap() = callback(initial_data);
-        });
+#[cfg(test)]
+struct YieldNTimes(usize);
 
-        Self {
-            data: Some(data),
-            join_handle: Some(thread_handle),
+#[cfg(test)]
+impl Future for YieldNTimes {
+    type Output = ();
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<()> {
+        if self.0 == 0 {
+            Poll::Ready(())
+        } else {
+            self.0 -= 1;
+            context.waker().wake_by_ref();
+            Poll::Pending
         }
     }
+}
+
+#[test]
+fn test_future_tasks_finish() {
+    let tasks = (0..100).map(|i| Task::<()>::from_future(YieldNTimes(i % 5))).collect::<Vec<_>>();
+    while !tasks.iter().all(|task| task.is_finished()) {
+        thread::sleep(Duration::from_millis(1));
+    }
+}
 
-    /// Block until the internal thread has finished and return T
-    pub fn await(mut self) -> Result<T, AwaitError> {
+#[test]
+#[should_panic(expected = "task panicked")]
+fn test_task_panic_is_propagated() {
+    fn panicking_task(_: Arc<Mutex<()>>, _: DropCheck) { panic!("task panicked"); }
+    let task = Task::<()>::new(&Arc::new(Mutex::new(())), panicking_task);
+    while !task.is_finished() {
+        thread::sleep(Duration::from_millis(1));
+    }
+    drop(task);
+}
 
-        // .await() can only be called once, so these .unwrap()s are safe
-        let handle = self.join_handle.take().unwrap();
-        let data = self.data.take().unwrap();
+#[test]
+fn test_task_error_timer() {
 
-        handle.join().map_err(|_| AwaitError::ThreadJoinError)?;
+    fn panicking_task(_: Arc<Mutex<()>>, _: DropCheck) { panic!("file not found"); }
+    fn timer_callback(_: &mut (), _: &mut AppResources) -> (UpdateScreen, TerminateTimer) {
+        (DontRedraw, TerminateTimer::Terminate)
+    }
 
-        let data_arc = Arc::try_unwrap(data).map_err(|_| AwaitError::ArcUnlockError)?;
-        let data = data_arc.into_inner().map_err(|_| AwaitError::MutexIntoInnerError)?;
+    let mut task = Task::<()>::new(&Arc::new(Mutex::new(())), panicking_task)
+        .then(Timer::new(timer_callback))
+        .or_else(Timer::new(timer_callback).with_delay(Duration::from_secs(1)));
+    let handle = task.get_handle();
 
-        Ok(data)
+    while !task.is_finished() {
+        thread::sleep(Duration::from_millis(1));
     }
+
+    // The or_else timer is returned and the panic isn't propagated
+    let timer = task.take_completion_timer().unwrap();
+    assert_eq!(timer.delay, Some(Duration::from_secs(1)));
+    assert_eq!(handle.get_error(), Some("file not found".to_string()));
+    drop(task);
 }
 
-impl<T> Drop for Thread<T> {
-    fn drop(&mut self) {
-        if self.join_handle.take().is_some() {
-            panic!("Thread has not been await()-ed correctly!");
+#[test]
+fn test_cancel_pending_future() {
+
+    struct NeverReady;
+
+    impl Future for NeverReady {
+        type Output = ();
+        fn poll(self: Pin<&mut Self>, _: &mut Context) -> Poll<()> {
+            Poll::Pending
         }
     }
+
+    let task = Task::<()>::from_future(NeverReady);
+    let handle = task.get_handle();
+    assert!(!handle.is_finished());
+
+    Task::cancel_and_join_all(vec![task], Duration::from_secs(5));
+    assert!(handle.is_finished());
+    assert!(handle.is_cancelled());
+}
+
+#[test]
+fn test_cancel_and_join_all_detaches_blocked_tasks() {
+
+    static RELEASE_TASK: AtomicBool = AtomicBool::new(false);
+
+    fn blocking_task(_: Arc<Mutex<()>>, _: DropCheck) {
+        while !RELEASE_TASK.load(Ordering::SeqCst) {
+            thread::sleep(Duration::from_millis(1));
+        }
+        panic!("task panicked after being detached");
+    }
+
+    let task = Task::<()>::new(&Arc::new(Mutex::new(())), blocking_task);
+    let handle = task.get_handle();
+
+    let start = Instant::now();
+    Task::cancel_and_join_all(vec![task], Duration::from_millis(50));
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert!(!handle.is_finished());
+
+    // The panic of the detached task is still available
+    RELEASE_TASK.store(true, Ordering::SeqCst);
+    while !handle.is_finished() {
+        thread::sleep(Duration::from_millis(1));
+    }
+    assert_eq!(handle.get_error(), Some("task panicked after being detached".to_string()));
+}
+
+#[test]
+fn test_task_progress() {
+
+    fn report_progress(_: Arc<Mutex<()>>, context: TaskContext<usize>) {
+        context.set_progress(5);
+        while !context.is_cancelled() {
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    let task = Task::<()>::new_cancellable(&Arc::new(Mutex::new(())), report_progress);
+    let handle = task.get_handle();
+
+    while handle.get_progress::<usize>().is_none() {
+        thread::sleep(Duration::from_millis(1));
+    }
+
+    assert_eq!(handle.get_progress::<usize>(), Some(5));
+    assert_eq!(handle.get_progress::<f32>(), None);
+    assert!(task.take_progress_changed());
+    assert!(!task.take_progress_changed());
+
+    // Dropping a running task must not block
+    drop(task);
+    while !handle.is_finished() {
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+#[test]
+fn test_timer_next_run_time() {
+    fn timer_callback(_: &mut (), _: &mut AppResources) -> (UpdateScreen, TerminateTimer) {
+        (DontRedraw, TerminateTimer::Continue)
+    }
+
+    let timer = Timer::new(timer_callback);
+    assert_eq!(timer.get_next_run_time(), None);
+
+    let timer = Timer::new(timer_callback).with_delay(Duration::from_secs(10));
+    assert_eq!(timer.get_next_run_time(), Some(timer.created + Duration::from_secs(10)));
+
+    let mut timer = Timer::new(timer_callback).with_interval(Duration::from_secs(2));
+    assert_eq!(timer.get_next_run_time(), Some(timer.created + Duration::from_secs(2)));
+    timer.last_run = Some(timer.created + Duration::from_secs(3));
+    assert_eq!(timer.get_next_run_time(), Some(timer.created + Duration::from_secs(5)));
+
+    let timer = Timer::new(timer_callback).with_interval(Duration::from_secs(2)).with_timeout(Duration::from_secs(1));
+    assert_eq!(timer.get_next_run_time(), Some(timer.created + Duration::from_secs(1)));
 }
@@ -1,14 +1,17 @@
 use std::{
     fmt,
+    any::Any,
+    path::PathBuf,
     rc::Rc,
     hash::{Hash, Hasher},
+    collections::hash_map::DefaultHasher,
     collections::BTreeMap,
     sync::atomic::{AtomicUsize, Ordering},
 };
 use azul_css::CssPath;
 #[cfg(feature = "css_parser")]
 use azul_css_parser::CssPathParseError;
-use webrender::api::{HitTestItem, LayoutRect};
+use webrender::api::{HitTestItem, LayoutRect, LayoutSize};
 use {
     app::AppState,
     async::TerminateTimer,
@@ -18,6 +21,11 @@ use {
     id_tree::{NodeId, Node, NodeHierarchy},
     app_resources::AppResources,
     window::FakeWindow,
+    window_state::MouseState,
+    ui_solver::LayoutResult,
+    text_layout::TextMetrics,
+    overlay::{Overlay, OverlayAnchor, OverlayPlacement},
+    menu,
 };
 pub use stack_checked_pointer::StackCheckedPointer;
 pub use glium::texture::Texture2d;
@@ -84,13 +92,192 @@ pub struct IFrameCallback<T>(pub IFrameCallbackType<T>);
 impl_callback!(IFrameCallback<T>);
 
 pub type TimerCallbackType<T> = fn(&mut T, app_resources: &mut AppResources) -> (UpdateScreen, TerminateTimer);
-/// Callback that can runs on every frame on the main thread - can modify the app data model
+/// Callback that can runs on every frame on the main thread - can modify the app data model.
+/// Use a `TimerClosureCallback` if the timer needs access to the `AppStateNoData`
+/// (i.e. to open or close windows).
 pub struct TimerCallback<T>(pub TimerCallbackType<T>);
 impl_callback!(TimerCallback<T>);
 
+pub type LayoutCallbackType<T> = fn(&T, layout_info: LayoutInfo<T>) -> Dom<T>;
+/// Callback that maps the application data to the DOM of a window. By default,
+/// all windows use `Layout::layout`, see `WindowCreateOptions::layout_callback`.
+pub struct LayoutCallback<T>(pub LayoutCallbackType<T>);
+impl_callback!(LayoutCallback<T>);
+
+pub type CloseRequestedCallbackType<T> = fn(&mut AppState<T>, window_id: &GliumWindowId) -> ShouldClose;
+/// Callback that is invoked when the user tries to close a window (i.e. by clicking
+/// the "X" button), can veto closing the window, for example to ask the user whether
+/// unsaved changes should be discarded.
+///
+/// Not invoked for windows closed via `AppState::close_window` or `AppState::quit`.
+pub struct CloseRequestedCallback<T>(pub CloseRequestedCallbackType<T>);
+impl_callback!(CloseRequestedCallback<T>);
+
+/// Return value of a `CloseRequestedCallback`, whether the window should close or not
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ShouldClose {
+    /// Close the window
+    Close,
+    /// Keep the window open, the window is redrawn, so that changes
+    /// the callback made to the application data are visible
+    DontClose,
+}
+
+/// Identifies a closure callback. Closures can't be compared, so closure callbacks are
+/// compared and hashed by their key instead, i.e. when diffing two DOMs. Two closure
+/// callbacks with the same key are assumed to do the same thing, so the key should
+/// contain everything the closure captures, i.e. `CallbackKey::new(&("delete_row", row))`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CallbackKey(u64);
+
+impl CallbackKey {
+    /// Creates a key by hashing any hashable value, such as a string, a row index or a tuple of both
+    pub fn new<K: Hash + ?Sized>(key: &K) -> Self {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        CallbackKey(hasher.finish())
+    }
+}
+
+/// Closure version of `Callback<T>`, can capture state, such as the index
+/// of the row that the callback is attached to:
+///
+/// ```no_run,ignore
+/// let row_index = 5;
+/// Dom::div().with_callback(On::MouseUp, ClosureCallback::new(&("delete_row", row_index), move |app_state, _| {
+///     app_state.data.modify(|state| { state.rows.remove(row_index); });
+///     Redraw
+/// }))
+/// ```
+pub struct ClosureCallback<T> {
+    key: CallbackKey,
+    callback: Rc<dyn Fn(&mut AppState<T>, &mut CallbackInfo<T>) -> UpdateScreen>,
+}
+
+impl_closure_callback!(ClosureCallback<T>);
+
+impl<T> ClosureCallback<T> {
+    /// Creates a new closure callback, see `CallbackKey` for what the key is used for
+    pub fn new<K, F>(key: &K, callback: F) -> Self
+    where K: Hash + ?Sized, F: Fn(&mut AppState<T>, &mut CallbackInfo<T>) -> UpdateScreen + 'static
+    {
+        Self { key: CallbackKey::new(key), callback: Rc::new(callback) }
+    }
+}
+
+/// Callback attached to a DOM node - either a function pointer or a closure
+pub enum CallbackVariant<T> {
+    Fn(Callback<T>),
+    Closure(ClosureCallback<T>),
+}
+
+impl_callback_variant!(CallbackVariant<T>, Callback, ClosureCallback);
+
+impl<T> CallbackVariant<T> {
+    pub(crate) fn invoke(&self, app_state: &mut AppState<T>, callback_info: &mut CallbackInfo<T>) -> UpdateScreen {
+        match self {
+            CallbackVariant::Fn(callback) => (callback.0)(app_state, callback_info),
+            CallbackVariant::Closure(callback) => (callback.callback)(app_state, callback_info),
+        }
+    }
+}
+
+/// Closure version of `DefaultCallback<T>`. Instead of a `StackCheckedPointer`, the closure
+/// gets the whole application data, so it can capture which part of the data it should
+/// modify (i.e. an ID or an index) - no `unsafe` code necessary.
+///
+/// Register it via `FakeWindow::add_closure_callback`.
+pub struct DefaultClosureCallback<T> {
+    key: CallbackKey,
+    callback: Rc<dyn Fn(&mut T, &mut AppStateNoData<T>, &mut CallbackInfo<T>) -> UpdateScreen>,
+}
+
+impl_closure_callback!(DefaultClosureCallback<T>);
+
+impl<T> DefaultClosureCallback<T> {
+    /// Creates a new closure callback, see `CallbackKey` for what the key is used for
+    pub fn new<K, F>(key: &K, callback: F) -> Self
+    where K: Hash + ?Sized, F: Fn(&mut T, &mut AppStateNoData<T>, &mut CallbackInfo<T>) -> UpdateScreen + 'static
+    {
+        Self { key: CallbackKey::new(key), callback: Rc::new(callback) }
+    }
+}
+
+/// Closure version of `IFrameCallback<T>`, the closure gets read-only access to the
+/// application data. Use it via `Dom::iframe_closure`.
+pub struct IFrameClosureCallback<T> {
+    key: CallbackKey,
+    callback: Rc<dyn Fn(&T, LayoutInfo<T>, HidpiAdjustedBounds) -> Dom<T>>,
+}
+
+impl_closure_callback!(IFrameClosureCallback<T>);
+
+impl<T> IFrameClosureCallback<T> {
+    /// Creates a new closure callback, see `CallbackKey` for what the key is used for
+    pub fn new<K, F>(key: &K, callback: F) -> Self
+    where K: Hash + ?Sized, F: Fn(&T, LayoutInfo<T>, HidpiAdjustedBounds) -> Dom<T> + 'static
+    {
+        Self { key: CallbackKey::new(key), callback: Rc::new(callback) }
+    }
+
+    pub(crate) fn invoke(&self, app_data: &T, layout_info: LayoutInfo<T>, bounds: HidpiAdjustedBounds) -> Dom<T> {
+        (self.callback)(app_data, layout_info, bounds)
+    }
+}
+
+/// Closure version of `TimerCallback<T>`, use it via `Timer::from_closure`.
+/// Unlike `TimerCallback<T>`, the closure gets the entire `AppStateNoData`.
+pub struct TimerClosureCallback<T> {
+    key: CallbackKey,
+    callback: Rc<dyn Fn(&mut T, &mut AppStateNoData<T>) -> (UpdateScreen, TerminateTimer)>,
+}
+
+impl_closure_callback!(TimerClosureCallback<T>);
+
+impl<T> TimerClosureCallback<T> {
+    /// Creates a new closure callback, see `CallbackKey` for what the key is used for
+    pub fn new<K, F>(key: &K, callback: F) -> Self
+    where K: Hash + ?Sized, F: Fn(&mut T, &mut AppStateNoData<T>) -> (UpdateScreen, TerminateTimer) + 'static
+    {
+        Self { key: CallbackKey::new(key), callback: Rc::new(callback) }
+    }
+}
+
+/// Callback of a `Timer` - either a function pointer or a closure
+pub enum TimerCallbackVariant<T> {
+    Fn(TimerCallback<T>),
+    Closure(TimerClosureCallback<T>),
+}
+
+impl_callback_variant!(TimerCallbackVariant<T>, TimerCallback, TimerClosureCallback);
+
+impl<T> TimerCallbackVariant<T> {
+    pub(crate) fn invoke(&self, data: &mut T, app_state_no_data: &mut AppStateNoData<T>) -> (UpdateScreen, TerminateTimer) {
+        match self {
+            TimerCallbackVariant::Fn(callback) => (callback.0)(data, app_state_no_data.resources),
+            TimerCallbackVariant::Closure(callback) => (callback.callback)(data, app_state_no_data),
+        }
+    }
+}
+
+/// Default callback stored in the `DefaultCallbackSystem`
+enum DefaultCallbackVariant<T> {
+    Fn(StackCheckedPointer<T>, DefaultCallback<T>),
+    Closure(DefaultClosureCallback<T>),
+}
+
+impl<T> Clone for DefaultCallbackVariant<T> {
+    fn clone(&self) -> Self {
+        match self {
+            DefaultCallbackVariant::Fn(ptr, callback) => DefaultCallbackVariant::Fn(*ptr, *callback),
+            DefaultCallbackVariant::Closure(callback) => DefaultCallbackVariant::Closure(callback.clone()),
+        }
+    }
+}
+
 /// Wrapper for storing, inserting and registering default callbacks
 pub(crate) struct DefaultCallbackSystem<T> {
-    callbacks: BTreeMap<DefaultCallbackId, (StackCheckedPointer<T>, DefaultCallback<T>)>,
+    callbacks: BTreeMap<DefaultCallbackId, DefaultCallbackVariant<T>>,
 }
 
 impl<T> DefaultCallbackSystem<T> {
@@ -104,7 +291,12 @@ impl<T> DefaultCallbackSystem<T> {
 
     /// Registers a new callback
     pub fn add_callback(&mut self, id: DefaultCallbackId, ptr: StackCheckedPointer<T>, func: DefaultCallback<T>) {
-        self.callbacks.insert(id, (ptr, func));
+        self.callbacks.insert(id, DefaultCallbackVariant::Fn(ptr, func));
+    }
+
+    /// Registers a new closure callback
+    pub fn add_closure_callback(&mut self, id: DefaultCallbackId, callback: DefaultClosureCallback<T>) {
+        self.callbacks.insert(id, DefaultCallbackVariant::Closure(callback));
     }
 
     /// Invokes a certain default callback and returns its result
@@ -113,17 +305,21 @@ impl<T> DefaultCallbackSystem<T> {
     /// accidentally alias the data in `self.internal` (which could lead to UB).
     ///
     /// What we know is that the pointer (`self.internal`) points to somewhere
-    /// in `T`, so we know that `self.internal` isn't aliased
+    /// in `T`, so we know that `self.internal` isn't aliased. Closure callbacks
+    /// get `app_data` directly.
     pub(crate) fn run_callback(
         &self,
-        _app_data: &mut T,
+        app_data: &mut T,
         callback_id: &DefaultCallbackId,
         app_state_no_data: &mut AppStateNoData<T>,
         window_event: &mut CallbackInfo<T>)
     -> UpdateScreen
     {
-        if let Some((callback_ptr, callback_fn)) = self.callbacks.get(callback_id) {
-            (callback_fn.0)(callback_ptr, app_state_no_data, window_event)
+        if let Some(callback) = self.callbacks.get(callback_id) {
+            match callback {
+                DefaultCallbackVariant::Fn(callback_ptr, callback_fn) => (callback_fn.0)(callback_ptr, app_state_no_data, window_event),
+                DefaultCallbackVariant::Closure(callback) => (callback.callback)(app_data, app_state_no_data, window_event),
+            }
         } else {
             #[cfg(feature = "logging")] {
                 warn!("Calling default callback with invalid ID {:?}", callback_id);
@@ -150,6 +346,46 @@ pub struct LayoutInfo<'a, 'b, T: 'b> {
     pub resources: &'a AppResources,
 }
 
+impl<'a, 'b, T: 'b> LayoutInfo<'a, 'b, T> {
+
+    /// Returns the rectangle of the node in the **previous frame**, relative to the top
+    /// left of the window. Returns `None` on the first frame or if the node didn't exist.
+    ///
+    /// Note that the `NodeId` refers to the previous frames DOM, so this is only
+    /// useful if the DOM structure doesn't change between frames.
+    pub fn get_node_rect(&self, node_id: NodeId) -> Option<LayoutRect> {
+        self.window.last_layout_result.as_ref()?.get_node_rect(node_id)
+    }
+
+    /// Returns the size of the content of the node in the previous frame, see `get_node_rect`
+    pub fn get_content_size(&self, node_id: NodeId) -> Option<LayoutSize> {
+        self.window.last_layout_result.as_ref()?.get_content_size(node_id)
+    }
+
+    /// Returns the text metrics of a text node in the previous frame, see `get_node_rect`
+    pub fn get_text_metrics(&self, node_id: NodeId) -> Option<TextMetrics> {
+        self.window.last_layout_result.as_ref()?.get_text_metrics(node_id)
+    }
+
+    /// Adds a popup that is laid out outside of the returned DOM and drawn on top of it,
+    /// positioned next to the `anchor` according to the `placement`, see the `overlay` module.
+    ///
+    /// Overlays only exist for one frame, so they have to be re-added on every `layout()` call.
+    /// The nodes of the overlay are appended to the DOM, so the `NodeId`s of the returned DOM
+    /// don't change. Add a `EventFilter::Not(NotEventFilter::Hover(HoverEventFilter::MouseDown))`
+    /// callback to the overlay in order to dismiss it on outside clicks.
+    pub fn add_overlay(&mut self, anchor: OverlayAnchor, placement: OverlayPlacement, dom: Dom<T>) {
+        self.window.overlays.push(Overlay { anchor, placement, dom });
+    }
+
+    /// Renders the menu bar of the window (see `WindowCreateOptions::menu_bar`), usually
+    /// inserted as the first child of the root node. Opening the menus is handled internally,
+    /// the open menu is drawn as an overlay below the menu bar.
+    pub fn menu_bar_dom(&self) -> Dom<T> {
+        menu::menu_bar_dom(self.window.menu_bar.as_ref(), self.window.open_menu.as_ref())
+    }
+}
+
 /// Information about the callback that is passed to the callback whenever a callback is invoked
 pub struct CallbackInfo<'a, T: 'a> {
     /// The callback can change the focus - note that the focus is set before the
@@ -160,7 +396,18 @@ pub struct CallbackInfo<'a, T: 'a> {
     pub window_id: &'a GliumWindowId,
     /// The ID of the node that was hit. You can use this to query information about
     /// the node, but please don't hard-code any if / else statements based on the `NodeId`
+    ///
+    /// This is the node whose callback is currently invoked (the "current target"),
+    /// which can be a parent of the node the event was dispatched to, see `get_target`.
     pub hit_dom_node: NodeId,
+    /// Node that the event was originally dispatched to
+    pub(crate) target: NodeId,
+    /// Whether the event is currently traveling to or from the target
+    pub(crate) phase: EventPhase,
+    /// Set by `stop_propagation`
+    pub(crate) propagation_stopped: bool,
+    /// Set by `prevent_default`
+    pub(crate) default_prevented: bool,
     /// UiState containing the necessary data for testing what
     pub(crate) ui_state: &'a UiState<T>,
     /// What items are currently being hit
@@ -169,6 +416,12 @@ pub struct CallbackInfo<'a, T: 'a> {
     pub cursor_relative_to_item: Option<(f32, f32)>,
     /// The (x, y) position of the mouse cursor, **relative to top left of the window**.
     pub cursor_in_viewport: Option<(f32, f32)>,
+    /// Layout of the current frame, `None` if the window hasn't been laid out yet
+    pub(crate) layout_result: Option<&'a LayoutResult>,
+    /// Node that should be scrolled into view, see `scroll_into_view`
+    pub(crate) scroll_into_view: Option<NodeId>,
+    /// Set by `consume_scroll`
+    pub(crate) scroll_consumed: bool,
 }
 
 impl<'a, T: 'a> Clone for CallbackInfo<'a, T> {
@@ -177,10 +430,17 @@ impl<'a, T: 'a> Clone for CallbackInfo<'a, T> {
             focus: self.focus.clone(),
             window_id: self.window_id,
             hit_dom_node: self.hit_dom_node,
+            target: self.target,
+            phase: self.phase,
+            propagation_stopped: self.propagation_stopped,
+            default_prevented: self.default_prevented,
             ui_state: self.ui_state,
             hit_test_items: self.hit_test_items,
             cursor_relative_to_item: self.cursor_relative_to_item,
             cursor_in_viewport: self.cursor_in_viewport,
+            layout_result: self.layout_result,
+            scroll_into_view: self.scroll_into_view,
+            scroll_consumed: self.scroll_consumed,
         }
     }
 }
@@ -191,22 +451,45 @@ impl<'a, T: 'a> fmt::Debug for CallbackInfo<'a, T> {
             focus: {:?}, \
             window_id: {:?}, \
             hit_dom_node: {:?}, \
+            target: {:?}, \
+            phase: {:?}, \
+            propagation_stopped: {:?}, \
+            default_prevented: {:?}, \
             ui_state: {:?}, \
             hit_test_items: {:?}, \
             cursor_relative_to_item: {:?}, \
             cursor_in_viewport: {:?}, \
+            scroll_into_view: {:?}, \
+            scroll_consumed: {:?}, \
         }}",
             self.focus,
             self.window_id,
             self.hit_dom_node,
+            self.target,
+            self.phase,
+            self.propagation_stopped,
+            self.default_prevented,
             self.ui_state,
             self.hit_test_items,
             self.cursor_relative_to_item,
             self.cursor_in_viewport,
+            self.scroll_into_view,
+            self.scroll_consumed,
         )
     }
 }
 
+/// Phase of the event dispatch, see `CallbackInfo::get_event_phase`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EventPhase {
+    /// The event travels from the root to the target, invoking the
+    /// callbacks that were added via `Dom::with_capture_callback`
+    Capture,
+    /// The event travels from the target back to the root, invoking the
+    /// callbacks that were added via `Dom::with_callback`
+    Bubble,
+}
+
 /// Information about the bounds of a laid-out div rectangle.
 ///
 /// Necessary when invoking `IFrameCallbacks` and `GlTextureCallbacks`, so
@@ -456,6 +739,43 @@ pub enum FocusTarget {
     NoFocus,
 }
 
+/// Payload of the current drag-and-drop operation, stored in the `AppState`
+/// from `On::DragStart` until the `On::DragEnd` callbacks have run.
+pub enum DragData {
+    /// Files dragged from the OS into the window
+    Files(Vec<PathBuf>),
+    /// Any value set via `AppState::set_drag_data` (usually in the `On::DragStart` callback)
+    Custom(Box<dyn Any>),
+}
+
+impl DragData {
+
+    /// Returns the dragged files, if the payload was dragged in from the OS
+    pub fn get_files(&self) -> Option<&[PathBuf]> {
+        match self {
+            DragData::Files(files) => Some(files),
+            DragData::Custom(_) => None,
+        }
+    }
+
+    /// Returns the custom payload, if it is of type `U`
+    pub fn downcast_ref<U: Any>(&self) -> Option<&U> {
+        match self {
+            DragData::Files(_) => None,
+            DragData::Custom(data) => data.downcast_ref::<U>(),
+        }
+    }
+}
+
+impl fmt::Debug for DragData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DragData::Files(files) => write!(f, "DragData::Files({:?})", files),
+            DragData::Custom(_) => write!(f, "DragData::Custom(..)"),
+        }
+    }
+}
+
 impl<'a, T: 'a> CallbackInfo<'a, T> {
 
     /// Set the focus to a certain div by parsing a string.
@@ -486,4 +806,126 @@ impl<'a, T: 'a> CallbackInfo<'a, T> {
     pub fn clear_focus(&mut self) {
         self.focus = Some(FocusTarget::NoFocus);
     }
-}
\ No newline at end of file
+}
+
+impl<'a, T: 'a> CallbackInfo<'a, T> {
+
+    /// Returns the laid-out rectangle of the node, relative to the top left of the window
+    /// (without taking the scroll state of any parent nodes into account).
+    ///
+    /// Useful for positioning popups relative to the node that was clicked on.
+    pub fn get_node_rect(&self, node_id: NodeId) -> Option<LayoutRect> {
+        self.layout_result?.get_node_rect(node_id)
+    }
+
+    /// Returns the size of the content of the node, i.e. the size of the laid-out
+    /// text block for text nodes. Can be larger than the node itself if the content overflows.
+    pub fn get_content_size(&self, node_id: NodeId) -> Option<LayoutSize> {
+        self.layout_result?.get_content_size(node_id)
+    }
+
+    /// Returns the font size, number of lines and positions of the words of
+    /// a text node, `None` if the node isn't a text node
+    pub fn get_text_metrics(&self, node_id: NodeId) -> Option<TextMetrics> {
+        self.layout_result?.get_text_metrics(node_id)
+    }
+
+    /// Returns the cursor position relative to the top left of the window. Window callbacks
+    /// (i.e. `On::MouseOver` on the window while dragging) are invoked without a hit-test,
+    /// so `cursor_in_viewport` is `None` for them and the cursor of the `mouse_state` is used.
+    pub fn get_cursor_in_viewport(&self, mouse_state: &MouseState) -> Option<(f32, f32)> {
+        self.cursor_in_viewport.or_else(|| {
+            let cursor_pos = mouse_state.cursor_pos?;
+            Some((cursor_pos.x as f32, cursor_pos.y as f32))
+        })
+    }
+
+    /// Returns the node that the event was originally dispatched to, i.e. the front-most
+    /// node under the cursor for `Hover` events or the focused node for `Focus` events.
+    pub fn get_target(&self) -> NodeId {
+        self.target
+    }
+
+    /// Returns the node whose callback is currently invoked - during the capture and bubble
+    /// phase, this is a parent of the target (same as `hit_dom_node`).
+    pub fn get_current_target(&self) -> NodeId {
+        self.hit_dom_node
+    }
+
+    /// Returns the index of the child of the current target that contains the event target,
+    /// i.e. which item of a list was clicked if only the list itself has a callback.
+    /// Returns `None` if the event was dispatched to the current target itself.
+    pub fn get_target_child_index(&self) -> Option<usize> {
+        let mut node_id = self.target;
+        loop {
+            let parent = self.parent(node_id)?;
+            if parent == self.hit_dom_node {
+                return self.get_index_in_parent(node_id).map(|(index, _)| index);
+            }
+            node_id = parent;
+        }
+    }
+
+    /// Returns whether the callback was invoked in the capture or in the bubble phase
+    pub fn get_event_phase(&self) -> EventPhase {
+        self.phase
+    }
+
+    /// Stops the event from propagating any further, i.e. the callbacks of the parent
+    /// nodes (in the bubble phase) or the children (in the capture phase) aren't invoked.
+    ///
+    /// Events that don't propagate (`MouseEnter`, `MouseLeave`, `FocusReceived`,
+    /// `FocusLost` and all `Not` events) are not affected by this.
+    pub fn stop_propagation(&mut self) {
+        self.propagation_stopped = true;
+    }
+
+    /// Prevents the default callbacks of this event from being invoked, for example
+    /// to stop a `TextInput` from editing its text on a certain key press.
+    ///
+    /// Default callbacks are invoked after the capture callbacks, but before the normal
+    /// callbacks of an event (so that i.e. the `On::TextInput` callback of a `TextInput`
+    /// already sees the edited text). So only capture callbacks (`Dom::with_capture_callback`)
+    /// can prevent them, calling this from a normal callback has no effect.
+    pub fn prevent_default(&mut self) {
+        self.default_prevented = true;
+    }
+
+    /// Returns whether a callback has already called `prevent_default` for this event
+    pub fn is_default_prevented(&self) -> bool {
+        self.default_prevented
+    }
+
+    /// Scrolls all scrollable parents of the node so that the node is visible.
+    /// The scroll happens after all callbacks are run, if multiple callbacks
+    /// scroll to different nodes, the last one wins.
+    pub fn scroll_into_view(&mut self, node_id: NodeId) {
+        self.scroll_into_view = Some(node_id);
+    }
+
+    /// Marks the scroll amount of the current event (mouse wheel or touch pan) as handled,
+    /// so that the window doesn't scroll the scrollable nodes under the cursor by the same
+    /// amount. For nodes that scroll their content themselves, i.e. the `ListView`.
+    pub fn consume_scroll(&mut self) {
+        self.scroll_consumed = true;
+    }
+}
+
+#[test]
+fn test_closure_callbacks_compare_by_key() {
+    fn fn_callback(_: &mut AppState<()>, _: &mut CallbackInfo<()>) -> UpdateScreen { DontRedraw }
+
+    let row = 5;
+    let a = ClosureCallback::<()>::new(&("delete_row", row), move |_, _| if row == 5 { Redraw } else { DontRedraw });
+    let b = ClosureCallback::<()>::new(&("delete_row", 5), |_, _| DontRedraw);
+    let c = ClosureCallback::<()>::new(&("delete_row", 6), |_, _| DontRedraw);
+
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    assert_eq!(a.get_key(), CallbackKey::new(&("delete_row", 5)));
+
+    let closure_variant = CallbackVariant::from(a);
+    let fn_variant = CallbackVariant::from(Callback(fn_callback));
+    assert_ne!(closure_variant, fn_variant);
+    assert_eq!(closure_variant.clone(), CallbackVariant::from(b));
+}
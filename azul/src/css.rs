@@ -53,22 +53,72 @@ pub mod native_style {
     pub use azul_native_style::*;
 }
 
+#[cfg(feature = "native_style")]
+pub use azul_native_style::NativeTheme;
+
 #[cfg(feature = "css_parser")]
-use azul_css_parser::{self, CssParseError};
+use azul_css_parser::{self, CssParseError, CssParseWarning, CssPrintMode};
 
 /// Returns a style with the native appearance for the operating system. Convenience wrapper
 /// for functionality from the the `azul-native-style` crate.
+///
+/// Doesn't query the OS theme, use `native_with_system_theme` for that.
 #[cfg(feature = "native_style")]
 pub fn native() -> Css {
     azul_native_style::native()
 }
 
+/// Returns the native style in the theme the OS is set to, queries the OS on the first call.
+/// Convenience wrapper for `azul-native-style::native_with_system_theme`.
+#[cfg(feature = "native_style")]
+pub fn native_with_system_theme() -> Css {
+    azul_native_style::native_with_system_theme()
+}
+
+/// Returns the native style in the given theme, regardless of the OS theme.
+/// Convenience wrapper for `azul-native-style::native_with_theme`.
+#[cfg(feature = "native_style")]
+pub fn native_with_theme(theme: NativeTheme) -> Css {
+    azul_native_style::native_with_theme(theme)
+}
+
+/// Returns the theme (light, dark or high-contrast) the OS is currently set to.
+/// Convenience wrapper for `azul-native-style::get_system_theme`.
+///
+/// Note: This spawns a process and waits for it, don't call it from a callback -
+/// use `get_cached_system_theme` instead.
+#[cfg(feature = "native_style")]
+pub fn get_system_theme() -> NativeTheme {
+    azul_native_style::get_system_theme()
+}
+
+/// Returns the OS theme of the last `get_system_theme` call, only queries the OS once.
+/// Convenience wrapper for `azul-native-style::get_cached_system_theme`.
+#[cfg(feature = "native_style")]
+pub fn get_cached_system_theme() -> NativeTheme {
+    azul_native_style::get_cached_system_theme()
+}
+
 /// Parses CSS stylesheet from a string. Convenience wrapper for `azul-css-parser::new_from_str`.
 #[cfg(feature = "css_parser")]
 pub fn from_str(input: &str) -> Result<Css, CssParseError> {
     azul_css_parser::new_from_str(input)
 }
 
+/// Same as `from_str`, but skips invalid rules and declarations instead of failing,
+/// returning them as warnings. Convenience wrapper for `azul-css-parser::new_from_str_with_warnings`.
+#[cfg(feature = "css_parser")]
+pub fn from_str_with_warnings(input: &str) -> (Css, Vec<CssParseWarning>) {
+    azul_css_parser::new_from_str_with_warnings(input)
+}
+
+/// Prints a stylesheet back to CSS text that can be parsed again with `from_str`.
+/// Convenience wrapper for `azul-css-parser::print_css`.
+#[cfg(feature = "css_parser")]
+pub fn to_string(css: &Css, mode: CssPrintMode) -> String {
+    azul_css_parser::print_css(css, mode)
+}
+
 /// Appends a custom stylesheet to `css::native()`.
 #[cfg(all(feature = "css_parser", feature = "native_style"))]
 pub fn override_native(input: &str) -> Result<Css, CssParseError> {
@@ -77,6 +127,14 @@ pub fn override_native(input: &str) -> Result<Css, CssParseError> {
     Ok(css)
 }
 
+/// Same as `override_native`, but uses the native style in the given theme instead of the OS theme.
+#[cfg(all(feature = "css_parser", feature = "native_style"))]
+pub fn override_native_with_theme(input: &str, theme: NativeTheme) -> Result<Css, CssParseError> {
+    let mut css = native_with_theme(theme);
+    css.append(from_str(input)?);
+    Ok(css)
+}
+
 /// Allows dynamic reloading of a CSS file during an applications runtime, useful for
 /// changing the look & feel while the application is running.
 #[cfg(all(debug_assertions, feature = "css_parser"))]
@@ -1,4 +1,8 @@
 pub use tinyfiledialogs::{MessageBoxIcon, DefaultColorValue};
+use {
+    async::Task,
+    widgets::file_chooser::{FileChooser, FileChooserState},
+};
 
 /// Ok or cancel result, returned from the `msg_box_ok_cancel` function
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -125,7 +129,8 @@ impl<'a> Into<DefaultColorValue<'a>> for ColorValue<'a> {
     }
 }
 
-/// Opens the default color picker dialog
+/// Opens the default color picker dialog - this blocks the UI thread until the dialog
+/// is closed, `widgets::color_picker::ColorPicker` is an in-app alternative
 pub fn color_picker_dialog(title: &str, default_value: Option<ColorValue>)
 -> Option<(String, [u8; 3])>
 {
@@ -137,40 +142,122 @@ pub fn color_picker_dialog(title: &str, default_value: Option<ColorValue>)
 ///
 /// Filters are the file extensions, i.e. `Some(&["doc", "docx"])` to only allow
 /// "doc" and "docx" files
+///
+/// Like all file dialogs, this blocks the UI thread until the dialog is closed and returns
+/// `None` without showing anything if `!has_native_file_dialogs()` - use `show_file_chooser`
+/// for a non-blocking alternative that falls back to the built-in `FileChooser`.
 pub fn open_file_dialog(default_path: Option<&str>, filter_list: Option<&[&str]>)
 -> Option<String>
 {
+    open_file_dialog_with_title("Open File", default_path, filter_list)
+}
+
+/// Same as `open_file_dialog`, but with a custom title
+pub fn open_file_dialog_with_title(title: &str, default_path: Option<&str>, filter_list: Option<&[&str]>)
+-> Option<String>
+{
+    if !has_native_file_dialogs() {
+        return None;
+    }
     let filter_list = filter_list.map(|f| (f, ""));
     let path = default_path.unwrap_or("");
-    ::tinyfiledialogs::open_file_dialog("Open File", path, filter_list)
+    ::tinyfiledialogs::open_file_dialog(title, path, filter_list)
 }
 
-/// Open a directory, returns `None` if the user canceled the dialog
+/// Open a directory, returns `None` if the user canceled the dialog or if
+/// `!has_native_file_dialogs()` (see `show_file_chooser` for the fallback)
 pub fn open_directory_dialog(default_path: Option<&str>)
 -> Option<String>
 {
-    ::tinyfiledialogs::select_folder_dialog("Open Folder", default_path.unwrap_or(""))
+    open_directory_dialog_with_title("Open Folder", default_path)
 }
 
-/// Open multiple files at once, returns `None` if the user canceled the dialog,
-/// otherwise returns the `Vec<String>` with the given file paths
+/// Same as `open_directory_dialog`, but with a custom title
+pub fn open_directory_dialog_with_title(title: &str, default_path: Option<&str>)
+-> Option<String>
+{
+    if !has_native_file_dialogs() {
+        return None;
+    }
+    ::tinyfiledialogs::select_folder_dialog(title, default_path.unwrap_or(""))
+}
+
+/// Open multiple files at once, returns `None` if the user canceled the dialog or if
+/// `!has_native_file_dialogs()` (see `show_file_chooser` for the fallback), otherwise returns the `Vec<String>` with the given file paths
 ///
 /// Filters are the file extensions, i.e. `Some(&["doc", "docx"])` to only allow
 /// "doc" and "docx" files
 pub fn open_multiple_files_dialog(default_path: Option<&str>, filter_list: Option<&[&str]>)
 -> Option<Vec<String>>
 {
+    open_multiple_files_dialog_with_title("Open Files", default_path, filter_list)
+}
+
+/// Same as `open_multiple_files_dialog`, but with a custom title
+pub fn open_multiple_files_dialog_with_title(title: &str, default_path: Option<&str>, filter_list: Option<&[&str]>)
+-> Option<Vec<String>>
+{
+    if !has_native_file_dialogs() {
+        return None;
+    }
     let filter_list = filter_list.map(|f| (f, ""));
     let path = default_path.unwrap_or("");
-    ::tinyfiledialogs::open_file_dialog_multi("Open Folder", path, filter_list)
+    ::tinyfiledialogs::open_file_dialog_multi(title, path, filter_list)
 }
 
-/// Opens a save file dialog, returns `None` if the user canceled the dialog
+/// Opens a save file dialog, returns `None` if the user canceled the dialog or if
+/// `!has_native_file_dialogs()` (see `show_file_chooser` for the fallback)
 pub fn save_file_dialog(default_path: Option<&str>)
 -> Option<String>
 {
+    save_file_dialog_with_title("Save File", default_path)
+}
+
+/// Same as `save_file_dialog`, but with a custom title
+pub fn save_file_dialog_with_title(title: &str, default_path: Option<&str>)
+-> Option<String>
+{
+    if !has_native_file_dialogs() {
+        return None;
+    }
     let path = default_path.unwrap_or("");
-    ::tinyfiledialogs::save_file_dialog("Save File", path)
+    ::tinyfiledialogs::save_file_dialog(title, path)
+}
+
+/// Programs that tinyfiledialogs uses to show file dialogs on Linux and the BSDs
+#[cfg(all(unix, not(target_os = "macos")))]
+const DIALOG_PROGRAMS: &[&str] = &["zenity", "kdialog", "qarma", "matedialog", "Xdialog"];
+
+/// Returns whether the native file dialogs can be shown. On Linux and the BSDs, tinyfiledialogs
+/// needs zenity, kdialog (or a similar program) to be installed, which is missing on minimal systems.
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn has_native_file_dialogs() -> bool {
+    use std::env;
+    let path = match env::var_os("PATH") {
+        Some(s) => s,
+        None => return false,
+    };
+    env::split_paths(&path).any(|directory| DIALOG_PROGRAMS.iter().any(|program| directory.join(program).is_file()))
+}
+
+/// Returns whether the native file dialogs can be shown, always true on Windows and macOS
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+pub fn has_native_file_dialogs() -> bool {
+    true
+}
+
+/// Shows a file dialog without blocking the event loop: If `has_native_file_dialogs()`,
+/// the native dialog runs on a background thread, otherwise the built-in `FileChooser`
+/// is opened. The mode, title, directory and filter of the dialog are taken from the `state`.
+///
+/// The returned task has to be added via `AppState::add_task`. Once the dialog is closed,
+/// the result is passed to the `on_close` callback of the `chooser`.
+pub fn show_file_chooser<T: 'static>(chooser: &FileChooser<T>, state: &mut FileChooserState) -> Task<T> {
+    if has_native_file_dialogs() {
+        chooser.open_native(state)
+    } else {
+        chooser.open(state)
+    }
 }
 
 // TODO (at least on Windows):
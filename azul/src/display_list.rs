@@ -10,13 +10,13 @@ use webrender::api::{
     LayoutPixel, DisplayListBuilder, PrimitiveInfo, GradientStop,
     ColorF, PipelineId, Epoch, ImageData, ImageDescriptor,
     ResourceUpdate, AddImage, BorderRadius, ClipMode,
-    LayoutPoint, LayoutSize, GlyphOptions, LayoutRect, ExternalScrollId,
+    LayoutPoint, LayoutSize, LayoutVector2D, GlyphOptions, LayoutRect, ExternalScrollId,
     ComplexClipRegion, LayoutPrimitiveInfo, ExternalImageId,
     ExternalImageData, ImageFormat, ExternalImageType, TextureTarget,
     ImageRendering, AlphaType, FontInstanceFlags, FontRenderMode,
 };
 use azul_css::{
-    Css, LayoutPosition,CssProperty, LayoutOverflow,
+    Css, LayoutPosition, LayoutAlignItems, CssProperty, LayoutOverflow,
     StyleBorderRadius, LayoutMargin, LayoutPadding, BoxShadowClipMode,
     StyleTextColor, StyleBackground, StyleBoxShadow,
     StyleBackgroundSize, StyleBackgroundRepeat, StyleBorder, BoxShadowPreDisplayItem,
@@ -25,15 +25,16 @@ use azul_css::{
 use {
     FastHashMap,
     app_resources::AppResources,
-    callbacks::{IFrameCallback, GlTextureCallback, HidpiAdjustedBounds, StackCheckedPointer},
+    callbacks::{GlTextureCallback, HidpiAdjustedBounds, StackCheckedPointer},
     ui_state::UiState,
     ui_description::{UiDescription, StyledNode},
     id_tree::{NodeDataContainer, NodeId, NodeHierarchy},
     dom::{
         NodeData, ScrollTagId, DomHash, DomString, new_scroll_tag_id,
-        NodeType::{self, Div, Text, Image, GlTexture, IFrame, Label},
+        Dom, NodeType::{self, Div, Text, Span, Image, GlTexture, IFrame, IFrameClosure, Label},
     },
     ui_solver::{do_the_layout, LayoutResult, PositionedRectangle},
+    overlay::{OverlayNode, OverlayAnchor, place_overlay},
     app_resources::ImageId,
     compositor::new_opengl_texture_id,
     window::{Window, FakeWindow, ScrollStates},
@@ -42,17 +43,20 @@ use {
 };
 
 const DEFAULT_FONT_COLOR: StyleTextColor = StyleTextColor(StyleColorU { r: 0, b: 0, g: 0, a: 255 });
+const TRANSPARENT_BG: StyleColorU = StyleColorU { r: 0, g: 0, b: 0, a: 0 };
 
 pub(crate) struct DisplayList<'a, T: 'a> {
     pub(crate) ui_descr: &'a UiDescription<T>,
-    pub(crate) rectangles: NodeDataContainer<DisplayRectangle<'a>>
+    pub(crate) rectangles: NodeDataContainer<DisplayRectangle<'a>>,
+    /// Overlays that have to be moved next to their anchor after the layout
+    pub(crate) overlays: Vec<OverlayNode>,
 }
 
 impl<'a, T: 'a> fmt::Debug for DisplayList<'a, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f,
-            "DisplayList {{ ui_descr: {:?}, rectangles: {:?} }}",
-            self.ui_descr, self.rectangles
+            "DisplayList {{ ui_descr: {:?}, rectangles: {:?}, overlays: {:?} }}",
+            self.ui_descr, self.rectangles, self.overlays
         )
     }
 }
@@ -88,7 +92,7 @@ impl<'a, T: 'a> DisplayList<'a, T> {
     pub(crate) fn new_from_ui_description(ui_description: &'a UiDescription<T>, ui_state: &UiState<T>) -> Self {
         let arena = &ui_description.ui_descr_arena;
 
-        let display_rect_arena = arena.node_data.transform(|node, node_id| {
+        let mut display_rect_arena = arena.node_data.transform(|node, node_id| {
             let style = &ui_description.styled_nodes[node_id];
             let tag = ui_state.node_ids_to_tag_ids.get(&node_id).map(|tag| *tag);
             let mut rect = DisplayRectangle::new(tag, style);
@@ -96,9 +100,20 @@ impl<'a, T: 'a> DisplayList<'a, T> {
             rect
         });
 
+        // Overlay wrappers are taken out of the normal flow, so that they don't take space away
+        // from the regular DOM and so that they are drawn on top of it. The wrapper itself fills
+        // the window, but doesn't stretch the overlay, so that the overlay takes the size of its content.
+        for overlay in &ui_state.overlays {
+            if let Some(rect) = display_rect_arena.get_mut(overlay.node_id) {
+                rect.layout.position = Some(LayoutPosition::Absolute);
+                rect.layout.align_items = Some(LayoutAlignItems::Start);
+            }
+        }
+
         Self {
             ui_descr: ui_description,
             rectangles: display_rect_arena,
+            overlays: ui_state.overlays.clone(),
         }
     }
 
@@ -136,7 +151,7 @@ impl<'a, T: 'a> DisplayList<'a, T> {
         app_resources.add_fonts_and_images(&self);
 
         let window_size = window.state.size.get_reverse_logical_size();
-        let layout_result = do_the_layout(
+        let mut layout_result = do_the_layout(
             node_hierarchy,
             node_data,
             &self.rectangles,
@@ -145,6 +160,14 @@ impl<'a, T: 'a> DisplayList<'a, T> {
             LayoutPoint::new(0.0, 0.0),
         );
 
+        position_overlays(
+            &self.overlays,
+            node_hierarchy,
+            node_data,
+            &mut layout_result,
+            LayoutSize::new(window_size.width as f32, window_size.height as f32),
+        );
+
         // TODO: After the layout has been done, call all IFrameCallbacks and get and insert
         // their font keys / image keys
 
@@ -160,6 +183,25 @@ impl<'a, T: 'a> DisplayList<'a, T> {
         let mut builder = DisplayListBuilder::with_capacity(window.internal.pipeline_id, TypedSize2D::new(width as f32, height as f32), self.rectangles.len());
 
         let rects_in_rendering_order = determine_rendering_order(node_hierarchy, &self.rectangles, &layout_result.rects);
+        let drag_ghost = window.state.get_drag_ghost();
+
+        let referenced_content = DisplayListParametersRef {
+            pipeline_id: window.internal.pipeline_id,
+            node_hierarchy,
+            node_data,
+            display_rectangle_arena: &self.rectangles,
+            css: &window.css,
+            layout_result: &layout_result,
+        };
+
+        let mut referenced_mutable_content = DisplayListParametersMut {
+            app_data: app_data_access,
+            app_resources,
+            fake_window,
+            builder: &mut builder,
+            resource_updates: &mut resource_updates,
+            pipeline_id: window.internal.pipeline_id,
+        };
 
         push_rectangles_into_displaylist(
             window.internal.epoch,
@@ -167,28 +209,67 @@ impl<'a, T: 'a> DisplayList<'a, T> {
             rects_in_rendering_order,
             &mut scrollable_nodes,
             &mut window.scroll_states,
-            &DisplayListParametersRef {
-                pipeline_id: window.internal.pipeline_id,
-                node_hierarchy,
-                node_data,
-                display_rectangle_arena: &self.rectangles,
-                css: &window.css,
-                layout_result: &layout_result,
-            },
-            &mut DisplayListParametersMut {
-                app_data: app_data_access,
-                app_resources,
-                fake_window,
-                builder: &mut builder,
-                resource_updates: &mut resource_updates,
-                pipeline_id: window.internal.pipeline_id,
-            },
+            &referenced_content,
+            &mut referenced_mutable_content,
         );
 
+        // The dragged node is drawn last, so that it is on top of all other nodes
+        if let Some((drag_source, (offset_x, offset_y))) = drag_ghost {
+            push_drag_ghost(
+                window.internal.epoch,
+                window.state.size,
+                drag_source,
+                LayoutVector2D::new(offset_x as f32, offset_y as f32),
+                &referenced_content,
+                &mut referenced_mutable_content,
+            );
+        }
+
         (builder, scrollable_nodes, layout_result)
     }
 }
 
+/// Moves each overlay (and all of its children) from where the layout put it to its
+/// position next to its anchor - the anchor rectangle is taken from the same layout.
+///
+/// The size of the overlay is the size of the DOM that was passed to `add_overlay`,
+/// i.e. the first child of the `__azul-overlay` wrapper.
+fn position_overlays<T>(
+    overlays: &[OverlayNode],
+    node_hierarchy: &NodeHierarchy,
+    node_data: &NodeDataContainer<NodeData<T>>,
+    layout_result: &mut LayoutResult,
+    window_size: LayoutSize,
+) {
+    for overlay in overlays {
+
+        let overlay_rect = match node_hierarchy[overlay.node_id].first_child.and_then(|child| layout_result.get_node_rect(child)) {
+            Some(s) => s,
+            None => continue,
+        };
+
+        let anchor_node = match &overlay.anchor {
+            OverlayAnchor::Node(node_id) => Some(*node_id),
+            OverlayAnchor::NodeWithId(id) => node_data.linear_iter().find(|node_id| node_data[*node_id].ids.contains(id)),
+            OverlayAnchor::Rect(_) => None,
+        };
+
+        let anchor_rect = match (&overlay.anchor, anchor_node.and_then(|node_id| layout_result.get_node_rect(node_id))) {
+            (OverlayAnchor::Rect(rect), _) => *rect,
+            (_, Some(rect)) => rect,
+            (_, None) => continue,
+        };
+
+        let new_origin = place_overlay(anchor_rect, overlay_rect.size, window_size, overlay.placement);
+        let offset = new_origin - overlay_rect.origin;
+
+        for node_id in overlay.node_id.descendants(node_hierarchy) {
+            let bounds = &mut layout_result.rects[node_id].bounds;
+            bounds.origin = bounds.origin + offset;
+        }
+    }
+}
+
 /// In order to render rectangles in the correct order, we have to group them together:
 /// As long as there are no position:absolute items, items are inserted in a parents-then-child order
 ///
@@ -509,6 +590,7 @@ fn push_rectangles_into_displaylist<'a, 'b, 'c, 'd, 'e, 'f, T>(
             rect_idx: content_group.root.node_id,
             html_node: &referenced_content.node_data[content_group.root.node_id].node_type,
             window_size,
+            drag_ghost_offset: None,
         };
 
         // Push the root of the node
@@ -528,6 +610,7 @@ fn push_rectangles_into_displaylist<'a, 'b, 'c, 'd, 'e, 'f, T>(
                 rect_idx: item.node_id,
                 html_node: &referenced_content.node_data[item.node_id].node_type,
                 window_size,
+                drag_ghost_offset: None,
             };
 
             push_rectangles_into_displaylist_inner(
@@ -579,6 +662,42 @@ fn push_rectangles_into_displaylist_inner<'a,'b,'c,'d,'e,'f, T>(
 */
 }
 
+/// Pushes the node that is currently dragged (including its children) a second time,
+/// moved by how far the cursor moved since the drag started. The copy (the "drag ghost")
+/// doesn't have any hit-testing tags, so it doesn't hide the drop target from the hit-test.
+fn push_drag_ghost<'a,'b,'c,'d,'e,'f, T>(
+    epoch: Epoch,
+    window_size: WindowSize,
+    drag_source: NodeId,
+    offset: LayoutVector2D,
+    referenced_content: &DisplayListParametersRef<'a,'b,'c,'d,'e, T>,
+    referenced_mutable_content: &mut DisplayListParametersMut<'f, T>,
+) {
+    // NOTE: The source node can be out of range if the DOM shrunk since the drag started
+    if referenced_content.node_hierarchy.get(drag_source).is_none() {
+        return;
+    }
+
+    let mut ghost_scrollable_nodes = ScrolledNodes::default();
+
+    for node_id in drag_source.descendants(referenced_content.node_hierarchy) {
+        let rectangle = DisplayListRectParams {
+            epoch,
+            rect_idx: node_id,
+            html_node: &referenced_content.node_data[node_id].node_type,
+            window_size,
+            drag_ghost_offset: Some(offset),
+        };
+
+        displaylist_handle_rect(
+            &mut ghost_scrollable_nodes,
+            &rectangle,
+            referenced_content,
+            referenced_mutable_content
+        );
+    }
+}
+
 /// Parameters that apply to a single rectangle / div node
 #[derive(Copy, Clone)]
 pub(crate) struct DisplayListRectParams<'a, T: 'a> {
@@ -586,6 +705,8 @@ pub(crate) struct DisplayListRectParams<'a, T: 'a> {
     pub rect_idx: NodeId,
     pub html_node: &'a NodeType<T>,
     window_size: WindowSize,
+    /// `Some` if the rectangle is drawn as part of the drag ghost, offset from its laid out position
+    drag_ghost_offset: Option<LayoutVector2D>,
 }
 
 fn get_clip_region<'a>(bounds: LayoutRect, rect: &DisplayRectangle<'a>) -> Option<ComplexClipRegion> {
@@ -614,21 +735,37 @@ fn displaylist_handle_rect<'a,'b,'c,'d,'e,'f,'g, T>(
     } = referenced_content;
 
     let DisplayListRectParams {
-        epoch, rect_idx, html_node, window_size,
+        epoch, rect_idx, html_node, window_size, drag_ghost_offset,
     } = rectangle;
 
+    // Inline spans are drawn (and hit-tested) as a part of their parent text node
+    if let Span(_) = html_node {
+        return;
+    }
+
     let rect = &display_rectangle_arena[*rect_idx];
     let bounds = layout_result.rects[*rect_idx].bounds;
+    let bounds = match drag_ghost_offset {
+        Some(offset) => LayoutRect::new(bounds.origin + *offset, bounds.size),
+        None => bounds,
+    };
+
+    // The drag ghost must not be hit-tested, otherwise it would always be the drop target
+    let tag = if drag_ghost_offset.is_some() {
+        None
+    } else {
+        rect.tag.map(|tag| (tag, 0)).or({
+            scrollable_nodes.overflowing_nodes
+            .get(&rect_idx)
+            .map(|scrolled| (scrolled.scroll_tag_id.0, 0))
+        })
+    };
 
     let info = LayoutPrimitiveInfo {
         rect: bounds,
         clip_rect: bounds,
         is_backface_visible: false,
-        tag: rect.tag.map(|tag| (tag, 0)).or({
-            scrollable_nodes.overflowing_nodes
-            .get(&rect_idx)
-            .map(|scrolled| (scrolled.scroll_tag_id.0, 0))
-        }),
+        tag,
     };
 
     let clip_region_id = get_clip_region(bounds, &rect).map(|clip|
@@ -660,7 +797,6 @@ fn displaylist_handle_rect<'a,'b,'c,'d,'e,'f,'g, T>(
             &referenced_mutable_content.app_resources,
         );
     } else if info.tag.is_some() {
-        const TRANSPARENT_BG: StyleColorU = StyleColorU { r: 0, g: 0, b: 0, a: 0 };
         push_rect(
             &info,
             referenced_mutable_content.builder,
@@ -678,7 +814,9 @@ fn displaylist_handle_rect<'a,'b,'c,'d,'e,'f,'g, T>(
     }
 
     match html_node {
-        Div => { },
+        // Don't invoke the OpenGL or IFrame callbacks a second time for the drag ghost
+        GlTexture(_) | IFrame(_) | IFrameClosure(_) if drag_ghost_offset.is_some() => { },
+        Div | Span(_) => { },
         Text(_) | Label(_) => {
             // Text is laid out and positioned during the layout pass,
             // so this should succeed - if there were problems
@@ -688,9 +826,11 @@ fn displaylist_handle_rect<'a,'b,'c,'d,'e,'f,'g, T>(
                 &info,
                 referenced_mutable_content.builder,
                 layout_result,
+                display_rectangle_arena,
                 rect_idx,
                 &rect.style,
                 &rect.layout,
+                drag_ghost_offset.is_none(),
             )
         },
         Image(image_id) => push_image(
@@ -701,7 +841,14 @@ fn displaylist_handle_rect<'a,'b,'c,'d,'e,'f,'g, T>(
             LayoutSize::new(info.rect.size.width, info.rect.size.height)
         ),
         GlTexture(callback) => push_opengl_texture(callback, &info, rectangle, referenced_content, referenced_mutable_content),
-        IFrame(callback) => push_iframe(callback, &info, scrollable_nodes, rectangle, referenced_content, referenced_mutable_content),
+        IFrame((iframe_callback, iframe_pointer)) => push_iframe(
+            &|_, window_info, bounds| (iframe_callback.0)(iframe_pointer, window_info, bounds),
+            &info, scrollable_nodes, rectangle, referenced_content, referenced_mutable_content
+        ),
+        IFrameClosure(iframe_callback) => push_iframe(
+            &|app_data, window_info, bounds| iframe_callback.invoke(app_data, window_info, bounds),
+            &info, scrollable_nodes, rectangle, referenced_content, referenced_mutable_content
+        ),
     };
 
     // Push the inset shadow (if any)
@@ -795,8 +942,10 @@ fn push_opengl_texture<'a,'b,'c,'d,'e,'f, T>(
     );
 }
 
+/// `invoke_callback` invokes the `IFrameCallback` (or the closure of the `IFrameClosureCallback`)
+/// with the locked application data
 fn push_iframe<'a,'b,'c,'d,'e,'f, T>(
-    (iframe_callback, iframe_pointer): &(IFrameCallback<T>, StackCheckedPointer<T>),
+    invoke_callback: &dyn Fn(&T, LayoutInfo<T>, HidpiAdjustedBounds) -> Dom<T>,
     info: &LayoutPrimitiveInfo,
     parent_scrollable_nodes: &mut ScrolledNodes,
     rectangle: &DisplayListRectParams<'a, T>,
@@ -811,18 +960,19 @@ fn push_iframe<'a,'b,'c,'d,'e,'f, T>(
 
     let new_dom = {
         // Make sure that the app data is locked before invoking the callback
-        let _lock = referenced_mutable_content.app_data.lock().unwrap();
+        let lock = referenced_mutable_content.app_data.lock().unwrap();
 
         let window_info = LayoutInfo {
             window: referenced_mutable_content.fake_window,
             resources: &referenced_mutable_content.app_resources,
         };
 
-        (iframe_callback.0)(&iframe_pointer, window_info, bounds)
+        invoke_callback(&*lock, window_info, bounds)
     };
 
     // TODO: Right now, no focusing, hovering or :active allowed in iframes!
     let is_mouse_down = false;
+    let drop_target = None;
     let mut focused_node = None;
     let mut focus_target = None;
     let hovered_nodes = BTreeMap::new();
@@ -834,7 +984,8 @@ fn push_iframe<'a,'b,'c,'d,'e,'f, T>(
         &mut focused_node,
         &mut focus_target,
         &hovered_nodes,
-        is_mouse_down
+        is_mouse_down,
+        drop_target,
     );
 
     let display_list = DisplayList::new_from_ui_description(&ui_description, &ui_state);
@@ -877,6 +1028,15 @@ fn push_iframe<'a,'b,'c,'d,'e,'f, T>(
         .. *referenced_content
     };
 
+    // The content of the iframe is clipped to the bounds of the iframe, i.e. the rows
+    // of a list view that are scrolled out of the list shouldn't be drawn over its neighbours
+    let iframe_clip_id = referenced_mutable_content.builder.define_clip(info.rect, vec![ComplexClipRegion {
+        rect: info.rect,
+        radii: BorderRadius::zero(),
+        mode: ClipMode::Clip,
+    }], None);
+    referenced_mutable_content.builder.push_clip_id(iframe_clip_id);
+
     push_rectangles_into_displaylist(
         rectangle.epoch,
         rectangle.window_size,
@@ -887,6 +1047,8 @@ fn push_iframe<'a,'b,'c,'d,'e,'f, T>(
         referenced_mutable_content
     );
 
+    referenced_mutable_content.builder.pop_clip_id();
+
     parent_scrollable_nodes.overflowing_nodes.extend(scrollable_nodes.overflowing_nodes.into_iter());
     parent_scrollable_nodes.tags_to_node_ids.extend(scrollable_nodes.tags_to_node_ids.into_iter());
 }
@@ -939,15 +1101,17 @@ fn push_rect(
     builder.push_rect(&info, wr_translate_color_u(*color).into());
 }
 
-fn push_text(
+fn push_text<'a>(
     info: &PrimitiveInfo<LayoutPixel>,
     builder: &mut DisplayListBuilder,
     layout_result: &LayoutResult,
+    display_rectangle_arena: &NodeDataContainer<DisplayRectangle<'a>>,
     node_id: &NodeId,
     rect_style: &RectStyle,
     rect_layout: &RectLayout,
+    hit_test_spans: bool,
 ) {
-    use text_layout::get_layouted_glyphs;
+    use text_layout::{get_layouted_glyphs, split_glyphs_into_runs, get_run_bounds};
     use css::webrender_translate::wr_translate_color_u;
     use ui_solver::determine_text_alignment;
 
@@ -1022,16 +1186,55 @@ fn push_text(
         builder.push_clip_id(clip_id);
     }
 
-    builder.push_text(
-        &info,
-        &layouted_glyphs.glyphs,
-        *font_instance_key,
-        font_color.into(),
-        Some(GlyphOptions {
-            render_mode: FontRenderMode::Subpixel,
-            flags: flags,
-        })
-    );
+    let glyph_options = Some(GlyphOptions {
+        render_mode: FontRenderMode::Subpixel,
+        flags: flags,
+    });
+
+    let text_runs = layout_result.text_runs.get(node_id).map(|runs| runs.as_slice()).unwrap_or(&[]);
+
+    if text_runs.len() < 2 {
+        builder.push_text(
+            &info,
+            &layouted_glyphs.glyphs,
+            *font_instance_key,
+            font_color.into(),
+            glyph_options,
+        );
+    } else {
+        // Paragraph with inline spans: Every run is drawn with its own font and color
+        let run_glyphs = split_glyphs_into_runs(&layouted_glyphs.glyphs, scaled_words);
+        for ((scaled_run, text_run), glyphs) in scaled_words.runs.iter().zip(text_runs.iter()).zip(run_glyphs.into_iter()) {
+
+            let run_rect = &display_rectangle_arena[text_run.node_id];
+            let run_color = run_rect.style.font_color.unwrap_or(DEFAULT_FONT_COLOR).0;
+
+            builder.push_text(
+                &info,
+                glyphs,
+                text_run.font_instance_key,
+                wr_translate_color_u(run_color).into(),
+                glyph_options,
+            );
+
+            // Spans with callbacks get their own hit-testing rectangles, so that
+            // e.g. a link can be clicked without clicking the entire paragraph
+            let span_tag = match run_rect.tag {
+                Some(tag) if hit_test_spans && text_run.node_id != *node_id => tag,
+                _ => continue,
+            };
+
+            for run_bounds in get_run_bounds(scaled_run, glyphs, scaled_words) {
+                let span_info = LayoutPrimitiveInfo {
+                    rect: run_bounds,
+                    clip_rect: run_bounds,
+                    is_backface_visible: false,
+                    tag: Some((span_tag, 0)),
+                };
+                push_rect(&span_info, builder, &TRANSPARENT_BG);
+            }
+        }
+    }
 
     if text_bounds.is_some() {
         builder.pop_clip_id();
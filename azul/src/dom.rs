@@ -12,7 +12,7 @@ use {
     ui_state::UiState,
     callbacks::{
         DefaultCallbackId, StackCheckedPointer,
-        Callback, GlTextureCallback, IFrameCallback,
+        CallbackVariant, GlTextureCallback, IFrameCallback, IFrameClosureCallback,
     },
     app_resources::{ImageId, TextId},
     id_tree::{Arena, NodeDataContainer},
@@ -93,6 +93,10 @@ pub enum NodeType<T> {
     Label(DomString),
     /// Larger amount of text, that has to be cached
     Text(TextId),
+    /// Inline run of text - only valid as a child of a `Label` or `Text` node,
+    /// where it gets laid out in the same paragraph as its parent, but with
+    /// its own font, font size and text color
+    Span(DomString),
     /// An image that is rendered by WebRender. The id is acquired by the
     /// `AppState::add_image()` function
     Image(ImageId),
@@ -102,6 +106,8 @@ pub enum NodeType<T> {
     GlTexture((GlTextureCallback<T>, StackCheckedPointer<T>)),
     /// DOM that gets passed its width / height during the layout
     IFrame((IFrameCallback<T>, StackCheckedPointer<T>)),
+    /// Same as `IFrame`, but the DOM is created by a closure
+    IFrameClosure(IFrameClosureCallback<T>),
 }
 
 impl<T> NodeType<T> {
@@ -110,10 +116,12 @@ impl<T> NodeType<T> {
         match self {
             Div => None,
             Label(s) => Some(format!("{}", s)),
+            Span(s) => Some(format!("{}", s)),
             Image(id) => Some(format!("image({:?})", id)),
             Text(t) => Some(format!("textid({:?})", t)),
             GlTexture(g) => Some(format!("gltexture({:?})", g)),
             IFrame(i) => Some(format!("iframe({:?})", i)),
+            IFrameClosure(i) => Some(format!("iframe({:?})", i)),
         }
     }
 }
@@ -127,9 +135,11 @@ impl<T> fmt::Debug for NodeType<T> {
             Div => write!(f, "NodeType::Div"),
             Label(a) => write!(f, "NodeType::Label {{ {:?} }}", a),
             Text(a) => write!(f, "NodeType::Text {{ {:?} }}", a),
+            Span(a) => write!(f, "NodeType::Span {{ {:?} }}", a),
             Image(a) => write!(f, "NodeType::Image {{ {:?} }}", a),
             GlTexture((ptr, cb)) => write!(f, "NodeType::GlTexture {{ ptr: {:?}, callback: {:?} }}", ptr, cb),
             IFrame((ptr, cb)) => write!(f, "NodeType::IFrame {{ ptr: {:?}, callback: {:?} }}", ptr, cb),
+            IFrameClosure(cb) => write!(f, "NodeType::IFrameClosure {{ callback: {:?} }}", cb),
         }
     }
 }
@@ -141,9 +151,11 @@ impl<T> Clone for NodeType<T> {
             Div => Div,
             Label(a) => Label(a.clone()),
             Text(a) => Text(a.clone()),
+            Span(a) => Span(a.clone()),
             Image(a) => Image(a.clone()),
             GlTexture((ptr, a)) => GlTexture((ptr.clone(), a.clone())),
             IFrame((ptr, a)) => IFrame((ptr.clone(), a.clone())),
+            IFrameClosure(a) => IFrameClosure(a.clone()),
         }
     }
 }
@@ -157,6 +169,7 @@ impl<T> Hash for NodeType<T> {
             Div => { },
             Label(a) => a.hash(state),
             Text(a) => a.hash(state),
+            Span(a) => a.hash(state),
             Image(a) => a.hash(state),
             GlTexture((ptr, a)) => {
                 ptr.hash(state);
@@ -166,6 +179,7 @@ impl<T> Hash for NodeType<T> {
                 ptr.hash(state);
                 a.hash(state);
             },
+            IFrameClosure(a) => a.hash(state),
         }
     }
 }
@@ -177,6 +191,7 @@ impl<T> PartialEq for NodeType<T> {
             (Div, Div) => true,
             (Label(a), Label(b)) => a == b,
             (Text(a), Text(b)) => a == b,
+            (Span(a), Span(b)) => a == b,
             (Image(a), Image(b)) => a == b,
             (GlTexture((ptr_a, a)), GlTexture((ptr_b, b))) => {
                 a == b && ptr_a == ptr_b
@@ -184,6 +199,7 @@ impl<T> PartialEq for NodeType<T> {
             (IFrame((ptr_a, a)), IFrame((ptr_b, b))) => {
                 a == b && ptr_a == ptr_b
             },
+            (IFrameClosure(a), IFrameClosure(b)) => a == b,
             _ => false,
         }
     }
@@ -198,9 +214,10 @@ impl<T> NodeType<T> {
         match self {
             Div => NodeTypePath::Div,
             Label(_) | Text(_) => NodeTypePath::P,
+            Span(_) => NodeTypePath::Span,
             Image(_) => NodeTypePath::Img,
             GlTexture(_) => NodeTypePath::Texture,
-            IFrame(_) => NodeTypePath::IFrame,
+            IFrame(_) | IFrameClosure(_) => NodeTypePath::IFrame,
         }
     }
 }
@@ -257,6 +274,39 @@ pub enum On {
     DroppedFile,
     /// A file was hovered, but has exited the window
     HoveredFileCancelled,
+    /// A draggable element has been dragged further than the drag threshold.
+    /// Fires on the dragged element - set the payload with `AppState::set_drag_data` here.
+    DragStart,
+    /// Something is being dragged into the element (does not propagate to the parents)
+    DragEnter,
+    /// Something is being dragged over the element
+    DragOver,
+    /// Something is being dragged out of the element (does not propagate to the parents)
+    DragLeave,
+    /// Something has been dropped on the element, the payload is in `AppState::get_drag_data`.
+    /// Files dropped from the OS also fire this event, with a `DragData::Files` payload.
+    Drop,
+    /// The drag operation has finished (dropped or cancelled). Fires on the dragged element.
+    DragEnd,
+    /// A finger (or pen) has touched the element. The touch points are in `FakeWindow::get_touch_state`.
+    TouchStart,
+    /// A finger that touches the screen has moved over the element
+    TouchMove,
+    /// A finger has been lifted from the element
+    TouchEnd,
+    /// The OS has cancelled the touch (i.e. because the window lost focus)
+    TouchCancel,
+    /// A finger has touched the element shortly without moving
+    Tap,
+    /// A finger has touched the element for a longer time without moving
+    LongPress,
+    /// A single finger has moved while touching the screen. Also scrolls the scrollable
+    /// parents of the element. See `TouchState::gestures` for the moved distance.
+    Pan,
+    /// Two fingers have moved towards or away from each other, see `TouchState::gestures` for the scale
+    Pinch,
+    /// A single finger has quickly moved over the screen and was lifted, see `TouchState::gestures`
+    Swipe,
     /// Equivalent to `onfocus`
     FocusReceived,
     /// Equivalent to `onblur`
@@ -355,6 +405,21 @@ impl From<On> for EventFilter {
             HoveredFile          => EventFilter::Hover(HoverEventFilter::HoveredFile),
             DroppedFile          => EventFilter::Hover(HoverEventFilter::DroppedFile),
             HoveredFileCancelled => EventFilter::Hover(HoverEventFilter::HoveredFileCancelled),
+            DragStart            => EventFilter::Hover(HoverEventFilter::DragStart),
+            DragEnter            => EventFilter::Hover(HoverEventFilter::DragEnter),
+            DragOver             => EventFilter::Hover(HoverEventFilter::DragOver),
+            DragLeave            => EventFilter::Hover(HoverEventFilter::DragLeave),
+            Drop                 => EventFilter::Hover(HoverEventFilter::Drop),
+            DragEnd              => EventFilter::Hover(HoverEventFilter::DragEnd),
+            TouchStart           => EventFilter::Hover(HoverEventFilter::TouchStart),
+            TouchMove            => EventFilter::Hover(HoverEventFilter::TouchMove),
+            TouchEnd             => EventFilter::Hover(HoverEventFilter::TouchEnd),
+            TouchCancel          => EventFilter::Hover(HoverEventFilter::TouchCancel),
+            Tap                  => EventFilter::Hover(HoverEventFilter::Tap),
+            LongPress            => EventFilter::Hover(HoverEventFilter::LongPress),
+            Pan                  => EventFilter::Hover(HoverEventFilter::Pan),
+            Pinch                => EventFilter::Hover(HoverEventFilter::Pinch),
+            Swipe                => EventFilter::Hover(HoverEventFilter::Swipe),
             FocusReceived        => EventFilter::Focus(FocusEventFilter::FocusReceived),        // focus!
             FocusLost            => EventFilter::Focus(FocusEventFilter::FocusLost),            // focus!
         }
@@ -382,6 +447,21 @@ pub enum HoverEventFilter {
     HoveredFile,
     DroppedFile,
     HoveredFileCancelled,
+    DragStart,
+    DragEnter,
+    DragOver,
+    DragLeave,
+    Drop,
+    DragEnd,
+    TouchStart,
+    TouchMove,
+    TouchEnd,
+    TouchCancel,
+    Tap,
+    LongPress,
+    Pan,
+    Pinch,
+    Swipe,
 }
 
 impl HoverEventFilter {
@@ -406,6 +486,21 @@ impl HoverEventFilter {
             HoveredFile => None,
             DroppedFile => None,
             HoveredFileCancelled => None,
+            DragStart => None,
+            DragEnter => None,
+            DragOver => None,
+            DragLeave => None,
+            Drop => None,
+            DragEnd => None,
+            TouchStart => None,
+            TouchMove => None,
+            TouchEnd => None,
+            TouchCancel => None,
+            Tap => None,
+            LongPress => None,
+            Pan => None,
+            Pinch => None,
+            Swipe => None,
         }
     }
 }
@@ -466,6 +561,15 @@ pub enum WindowEventFilter {
     HoveredFile,
     DroppedFile,
     HoveredFileCancelled,
+    TouchStart,
+    TouchMove,
+    TouchEnd,
+    TouchCancel,
+    Tap,
+    LongPress,
+    Pan,
+    Pinch,
+    Swipe,
 }
 
 impl WindowEventFilter {
@@ -488,6 +592,15 @@ impl WindowEventFilter {
             HoveredFile => Some(HoverEventFilter::HoveredFile),
             DroppedFile => Some(HoverEventFilter::DroppedFile),
             HoveredFileCancelled => Some(HoverEventFilter::HoveredFileCancelled),
+            TouchStart => Some(HoverEventFilter::TouchStart),
+            TouchMove => Some(HoverEventFilter::TouchMove),
+            TouchEnd => Some(HoverEventFilter::TouchEnd),
+            TouchCancel => Some(HoverEventFilter::TouchCancel),
+            Tap => Some(HoverEventFilter::Tap),
+            LongPress => Some(HoverEventFilter::LongPress),
+            Pan => Some(HoverEventFilter::Pan),
+            Pinch => Some(HoverEventFilter::Pinch),
+            Swipe => Some(HoverEventFilter::Swipe),
             // MouseEnter and MouseLeave on the **window** - does not mean a mouseenter
             // and a mouseleave on the hovered element
             MouseEnter => None,
@@ -505,7 +618,11 @@ pub struct NodeData<T> {
     /// `.myclass .otherclass`
     pub classes: Vec<DomString>,
     /// `On::MouseUp` -> `Callback(my_button_click_handler)`
-    pub callbacks: Vec<(EventFilter, Callback<T>)>,
+    pub callbacks: Vec<(EventFilter, CallbackVariant<T>)>,
+    /// Same as `callbacks`, but the callbacks are invoked in the capture phase, i.e. on the
+    /// way from the root to the event target, before any callbacks in the bubble phase.
+    /// `Not` event filters are ignored, since they don't propagate.
+    pub capture_callbacks: Vec<(EventFilter, CallbackVariant<T>)>,
     /// Usually not set by the user directly - `FakeWindow::add_default_callback`
     /// returns a callback ID, so that we know which default callback(s) are attached
     /// to this node.
@@ -536,6 +653,13 @@ pub struct NodeData<T> {
     /// Note that without this, there can be no `On::FocusReceived` (equivalent to onfocus),
     /// `On::FocusLost` (equivalent to onblur), etc. events.
     pub tab_index: Option<TabIndex>,
+    /// Whether this node is checked, i.e. a checked checkbox or the selected option of
+    /// a radio group. Only affects styling via the `:checked` CSS pseudo-selector.
+    pub is_checked: bool,
+    /// Whether this node is disabled. Only affects styling via the `:disabled` CSS
+    /// pseudo-selector - the callbacks of the node are still invoked, so widgets
+    /// don't attach their callbacks to disabled nodes.
+    pub is_disabled: bool,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
@@ -591,10 +715,13 @@ impl<T> PartialEq for NodeData<T> {
         self.ids == other.ids &&
         self.classes == other.classes &&
         self.callbacks == other.callbacks &&
+        self.capture_callbacks == other.capture_callbacks &&
         self.default_callback_ids == other.default_callback_ids &&
         self.dynamic_css_overrides == other.dynamic_css_overrides &&
         self.is_draggable == other.is_draggable &&
-        self.tab_index == other.tab_index
+        self.tab_index == other.tab_index &&
+        self.is_checked == other.is_checked &&
+        self.is_disabled == other.is_disabled
     }
 }
 
@@ -618,6 +745,9 @@ impl<T> Hash for NodeData<T> {
         for callback in &self.callbacks {
             callback.hash(state);
         }
+        for capture_callback in &self.capture_callbacks {
+            capture_callback.hash(state);
+        }
         for default_callback_id in &self.default_callback_ids {
             default_callback_id.hash(state);
         }
@@ -626,6 +756,8 @@ impl<T> Hash for NodeData<T> {
         }
         self.is_draggable.hash(state);
         self.tab_index.hash(state);
+        self.is_checked.hash(state);
+        self.is_disabled.hash(state);
     }
 }
 
@@ -636,10 +768,13 @@ impl<T> Clone for NodeData<T> {
             ids: self.ids.clone(),
             classes: self.classes.clone(),
             callbacks: self.callbacks.clone(),
+            capture_callbacks: self.capture_callbacks.clone(),
             default_callback_ids: self.default_callback_ids.clone(),
             dynamic_css_overrides: self.dynamic_css_overrides.clone(),
             is_draggable: self.is_draggable.clone(),
             tab_index: self.tab_index.clone(),
+            is_checked: self.is_checked,
+            is_disabled: self.is_disabled,
         }
     }
 }
@@ -674,12 +809,30 @@ impl<T> fmt::Display for NodeData<T> {
             String::new()
         };
 
+        let checked = if self.is_checked {
+            format!(" checked=\"true\"")
+        } else {
+            String::new()
+        };
+
+        let disabled = if self.is_disabled {
+            format!(" disabled=\"true\"")
+        } else {
+            String::new()
+        };
+
         let callbacks = if self.callbacks.is_empty() {
             String::new()
         } else {
             format!(" callbacks=\"{}\"", self.callbacks.iter().map(|(evt, cb)| format!("({:?}={:?})", evt, cb)).collect::<Vec<String>>().join(" "))
         };
 
+        let capture_callbacks = if self.capture_callbacks.is_empty() {
+            String::new()
+        } else {
+            format!(" capture-callbacks=\"{}\"", self.capture_callbacks.iter().map(|(evt, cb)| format!("({:?}={:?})", evt, cb)).collect::<Vec<String>>().join(" "))
+        };
+
         let default_callbacks = if self.default_callback_ids.is_empty() {
             String::new()
         } else {
@@ -693,12 +846,12 @@ impl<T> fmt::Display for NodeData<T> {
         };
 
         if let Some(content) = text_content {
-            write!(f, "<{}{}{}{}{}{}{}{}>{}</{}>",
-                html_type, id_string, class_string, tabindex, draggable, callbacks, default_callbacks, css_overrides, content, html_type
+            write!(f, "<{}{}{}{}{}{}{}{}{}{}{}>{}</{}>",
+                html_type, id_string, class_string, tabindex, draggable, checked, disabled, callbacks, capture_callbacks, default_callbacks, css_overrides, content, html_type
             )
         } else {
-            write!(f, "<{}{}{}{}{}{}{}{}/>",
-                html_type, id_string, class_string, tabindex, draggable, callbacks, default_callbacks, css_overrides,
+            write!(f, "<{}{}{}{}{}{}{}{}{}{}{}/>",
+                html_type, id_string, class_string, tabindex, draggable, checked, disabled, callbacks, capture_callbacks, default_callbacks, css_overrides,
             )
         }
     }
@@ -712,19 +865,25 @@ impl<T> fmt::Debug for NodeData<T> {
                 \tids: {:?}, \
                 \tclasses: {:?}, \
                 \tcallbacks: {:?}, \
+                \tcapture_callbacks: {:?}, \
                 \tdefault_callback_ids: {:?}, \
                 \tdynamic_css_overrides: {:?}, \
                 \tis_draggable: {:?}, \
                 \ttab_index: {:?}, \
+                \tis_checked: {:?}, \
+                \tis_disabled: {:?}, \
             }}",
             self.node_type,
             self.ids,
             self.classes,
             self.callbacks,
+            self.capture_callbacks,
             self.default_callback_ids,
             self.dynamic_css_overrides,
             self.is_draggable,
             self.tab_index,
+            self.is_checked,
+            self.is_disabled,
         )
     }
 }
@@ -741,10 +900,13 @@ impl<T> NodeData<T> {
             ids: Vec::new(),
             classes: Vec::new(),
             callbacks: Vec::new(),
+            capture_callbacks: Vec::new(),
             default_callback_ids: Vec::new(),
             dynamic_css_overrides: Vec::new(),
             is_draggable: false,
             tab_index: None,
+            is_checked: false,
+            is_disabled: false,
         }
     }
 
@@ -1010,6 +1172,15 @@ impl<T> Dom<T> {
         Self::new(NodeType::Text(text_id))
     }
 
+    /// Shorthand for `Dom::new(NodeType::Span(value.into()))`
+    ///
+    /// Spans have to be appended to a `Dom::label` or `Dom::text_id`: The text of
+    /// the parent node and of all its spans are then laid out as one paragraph.
+    #[inline]
+    pub fn span<S: Into<DomString>>(value: S) -> Self {
+        Self::new(NodeType::Span(value.into()))
+    }
+
     /// Shorthand for `Dom::new(NodeType::Image(image_id))`
     #[inline]
     pub fn image(image: ImageId) -> Self {
@@ -1028,6 +1199,12 @@ impl<T> Dom<T> {
         Self::new(NodeType::IFrame((callback, ptr)))
     }
 
+    /// Shorthand for `Dom::new(NodeType::IFrameClosure(callback))`
+    #[inline]
+    pub fn iframe_closure(callback: IFrameClosureCallback<T>) -> Self {
+        Self::new(NodeType::IFrameClosure(callback))
+    }
+
     /// Parses and loads a DOM from an XML string
     #[inline]
     pub fn from_xml(xml: &str, component_map: &mut XmlComponentMap<T>) -> Result<Self, XmlParseError> {
@@ -1182,11 +1359,20 @@ impl<T> Dom<T> {
 
     /// Same as `event`, but easier to use for method chaining in a builder-style pattern
     #[inline]
-    pub fn with_callback<O: Into<EventFilter>>(mut self, on: O, callback: Callback<T>) -> Self {
+    pub fn with_callback<O: Into<EventFilter>, C: Into<CallbackVariant<T>>>(mut self, on: O, callback: C) -> Self {
         self.add_callback(on, callback);
         self
     }
 
+    /// Same as `with_callback`, but the callback is invoked in the capture phase, before
+    /// the callbacks of the children are invoked, so it can intercept events for the whole
+    /// subtree via `CallbackInfo::stop_propagation`.
+    #[inline]
+    pub fn with_capture_callback<O: Into<EventFilter>, C: Into<CallbackVariant<T>>>(mut self, on: O, callback: C) -> Self {
+        self.add_capture_callback(on, callback);
+        self
+    }
+
     #[inline]
     pub fn with_child(mut self, child: Self) -> Self {
         self.add_child(child);
@@ -1211,6 +1397,20 @@ impl<T> Dom<T> {
         self
     }
 
+    /// Sets whether the node matches the `:checked` CSS pseudo-selector
+    #[inline]
+    pub fn with_checked(mut self, checked: bool) -> Self {
+        self.set_checked(checked);
+        self
+    }
+
+    /// Sets whether the node matches the `:disabled` CSS pseudo-selector
+    #[inline]
+    pub fn with_disabled(mut self, disabled: bool) -> Self {
+        self.set_disabled(disabled);
+        self
+    }
+
     #[inline]
     pub fn add_id<S: Into<DomString>>(&mut self, id: S) {
         self.arena.node_data[self.head].ids.push(id.into());
@@ -1222,8 +1422,13 @@ impl<T> Dom<T> {
     }
 
     #[inline]
-    pub fn add_callback<O: Into<EventFilter>>(&mut self, on: O, callback: Callback<T>) {
-        self.arena.node_data[self.head].callbacks.push((on.into(), callback));
+    pub fn add_callback<O: Into<EventFilter>, C: Into<CallbackVariant<T>>>(&mut self, on: O, callback: C) {
+        self.arena.node_data[self.head].callbacks.push((on.into(), callback.into()));
+    }
+
+    #[inline]
+    pub fn add_capture_callback<O: Into<EventFilter>, C: Into<CallbackVariant<T>>>(&mut self, on: O, callback: C) {
+        self.arena.node_data[self.head].capture_callbacks.push((on.into(), callback.into()));
     }
 
     #[inline]
@@ -1246,6 +1451,16 @@ impl<T> Dom<T> {
         self.arena.node_data[self.head].is_draggable = draggable;
     }
 
+    #[inline]
+    pub fn set_checked(&mut self, checked: bool) {
+        self.arena.node_data[self.head].is_checked = checked;
+    }
+
+    #[inline]
+    pub fn set_disabled(&mut self, disabled: bool) {
+        self.arena.node_data[self.head].is_disabled = disabled;
+    }
+
     /// Returns a debug formatted version of the DOM for easier debugging
     pub fn debug_dump(&self) -> String {
         format!("{}", self.arena.print_tree(|t| format!("{}", t)))
@@ -1289,8 +1504,9 @@ impl<T> Dom<T> {
         let mut not_default_callbacks = BTreeMap::new();
         let mut window_callbacks = BTreeMap::new();
         let mut window_default_callbacks = BTreeMap::new();
+        let mut capture_callbacks = BTreeMap::new();
 
-        // data.callbacks, HoverEventFilter, Callback<T>, as_hover_event_filter, hover_callbacks, <node_tag_id> (optional)
+        // data.callbacks, HoverEventFilter, CallbackVariant<T>, as_hover_event_filter, hover_callbacks, <node_tag_id> (optional)
         macro_rules! filter_and_insert_callbacks {
             (
                     $node_id:ident,
@@ -1301,7 +1517,7 @@ impl<T> Dom<T> {
                     $final_callback_list:ident,
             ) => {
                 let node_hover_callbacks: BTreeMap<$event_filter, $callback_type> = $data_source.iter()
-                .filter_map(|(event_filter, cb)| event_filter.$filter_func().map(|not_evt| (not_evt, *cb)))
+                .filter_map(|(event_filter, cb)| event_filter.$filter_func().map(|not_evt| (not_evt, cb.clone())))
                 .collect();
 
                 if !node_hover_callbacks.is_empty() {
@@ -1318,7 +1534,7 @@ impl<T> Dom<T> {
                 $node_tag_id:ident,
             ) => {
                 let node_hover_callbacks: BTreeMap<$event_filter, $callback_type> = $data_source.iter()
-                .filter_map(|(event_filter, cb)| event_filter.$filter_func().map(|not_evt| (not_evt, *cb)))
+                .filter_map(|(event_filter, cb)| event_filter.$filter_func().map(|not_evt| (not_evt, cb.clone())))
                 .collect();
 
                 if !node_hover_callbacks.is_empty() {
@@ -1351,7 +1567,7 @@ impl<T> Dom<T> {
                         node_id,
                         node.callbacks,
                         HoverEventFilter,
-                        Callback<T>,
+                        CallbackVariant<T>,
                         as_hover_event_filter,
                         hover_callbacks,
                         node_tag_id,
@@ -1362,7 +1578,7 @@ impl<T> Dom<T> {
                         node_id,
                         node.callbacks,
                         FocusEventFilter,
-                        Callback<T>,
+                        CallbackVariant<T>,
                         as_focus_event_filter,
                         focus_callbacks,
                         node_tag_id,
@@ -1372,7 +1588,7 @@ impl<T> Dom<T> {
                         node_id,
                         node.callbacks,
                         NotEventFilter,
-                        Callback<T>,
+                        CallbackVariant<T>,
                         as_not_event_filter,
                         not_callbacks,
                         node_tag_id,
@@ -1382,12 +1598,25 @@ impl<T> Dom<T> {
                         node_id,
                         node.callbacks,
                         WindowEventFilter,
-                        Callback<T>,
+                        CallbackVariant<T>,
                         as_window_event_filter,
                         window_callbacks,
                     );
                 }
 
+                // Capture callbacks are only invoked for events that propagate from a child,
+                // so the node itself doesn't need a tag for hit-testing
+                if !node.capture_callbacks.is_empty() {
+                    let node_capture_callbacks: BTreeMap<EventFilter, CallbackVariant<T>> = node.capture_callbacks.iter()
+                        .filter(|(event_filter, _)| event_filter.as_not_event_filter().is_none())
+                        .cloned()
+                        .collect();
+
+                    if !node_capture_callbacks.is_empty() {
+                        capture_callbacks.insert(node_id, node_capture_callbacks);
+                    }
+                }
+
                 if !node.default_callback_ids.is_empty() {
 
                     // Filter and insert HoverEventFilter callbacks
@@ -1475,7 +1704,9 @@ impl<T> Dom<T> {
             not_default_callbacks,
             window_callbacks,
             window_default_callbacks,
+            capture_callbacks,
 
+            overlays: Vec::new(),
         }
     }
 }
@@ -229,6 +229,10 @@ impl<T> NodeDataContainer<T> {
         self.internal.get(id.index())
     }
 
+    pub fn get_mut(&mut self, id: NodeId) -> Option<&mut T> {
+        self.internal.get_mut(id.index())
+    }
+
     pub fn iter(&self) -> Iter<T> {
         self.internal.iter()
     }
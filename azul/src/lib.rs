@@ -197,8 +197,16 @@ pub mod dialogs;
 pub mod dom;
 /// Re-exports of errors
 pub mod error;
+/// Platform-neutral menu bar, context menus and keyboard accelerators
+pub mod menu;
+/// Popups that are drawn on top of the regular DOM (tooltips, context menus, dropdowns)
+pub mod overlay;
+/// Per-window keyboard shortcuts with chords, focus scopes and config files
+pub mod shortcuts;
 /// Handles text layout (modularized, can be used as a standalone module)
 pub mod text_layout;
+/// Touch points and gesture recognition (tap, long press, pan, pinch, swipe)
+pub mod touch;
 /// Main `Layout` trait definition + convenience traits for `Arc<Mutex<T>>`
 pub mod traits;
 /// Container for default widgets (`TextInput` / `Button` / `Label`, `TableView`, ...)
@@ -263,33 +271,43 @@ type FastHashSet<T> = ::std::collections::HashSet<T>;
 pub mod prelude {
     #[cfg(feature = "css_parser")]
     pub use azul_css::*;
-    pub use app::{App, AppConfig, AppState, AppResources};
-    pub use async::{Task, TerminateTimer, TimerId, Timer, DropCheck};
+    pub use app::{App, AppConfig, AppState, AppStateNoData, AppResources};
+    pub use async::{Task, TaskHandle, TaskContext, CancellationToken, TerminateTimer, TimerId, Timer, DropCheck};
     pub use resources::{
         RawImageFormat, ImageId, FontId, FontSource, ImageSource,
         TextCache, TextId,
     };
     pub use callbacks::{
         Callback, TimerCallback, IFrameCallback, GlTextureCallback,
+        LayoutCallback, CloseRequestedCallback, ShouldClose, GliumWindowId,
+        CallbackKey, ClosureCallback, DefaultClosureCallback, IFrameClosureCallback, TimerClosureCallback,
         UpdateScreen, Redraw, DontRedraw,
-        CallbackInfo, FocusTarget, LayoutInfo, HidpiAdjustedBounds, Texture,
+        CallbackInfo, EventPhase, FocusTarget, DragData, LayoutInfo, HidpiAdjustedBounds, Texture,
     };
     pub use dom::{
         Dom, DomHash, NodeType, NodeData, On, DomString, TabIndex,
         EventFilter, HoverEventFilter, FocusEventFilter, NotEventFilter, WindowEventFilter,
     };
     pub use traits::{Layout, Modify};
+    pub use overlay::{OverlayAnchor, OverlayPlacement, OverlaySide, OverlayAlign};
+    pub use menu::{MenuBar, Menu, MenuItem, MenuAction};
+    pub use shortcuts::{ShortcutRegistry, ShortcutBinding, ShortcutScope, Shortcut, KeyCombination};
+    pub use touch::{TouchState, TouchPoint, Gesture, SwipeDirection};
     pub use window::{
         MonitorIter, Window, WindowCreateOptions,
         WindowMonitorTarget, RendererType, ReadOnlyWindow
     };
+    #[cfg(feature = "native_style")]
+    pub use window::NativeThemeSetting;
+    #[cfg(feature = "native_style")]
+    pub use azul_native_style::NativeTheme;
     pub use window_state::{WindowState, KeyboardState, MouseState, DebugState, keymap, AcceleratorKey};
     pub use glium::glutin::{
         dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize},
         VirtualKeyCode, ScanCode, Icon,
     };
     pub use stack_checked_pointer::StackCheckedPointer;
-    pub use text_layout::{TextLayoutOptions, GlyphInstance};
+    pub use text_layout::{TextLayoutOptions, GlyphInstance, TextMetrics};
     pub use xml::{XmlComponent, XmlComponentMap};
 
     #[cfg(any(feature = "css_parser", feature = "native_style"))]
@@ -139,6 +139,140 @@ macro_rules! impl_callback {($callback_value:ident<$t:ident>) => (
     impl<$t> Copy for $callback_value<$t> { }
 )}
 
+/// Implements `Display`, `Debug`, `Clone`, `Hash`, `PartialEq`, `Eq`, `PartialOrd` and `Ord`
+/// for a closure callback (a struct with a `key: CallbackKey` and a `callback: Rc<dyn Fn(...)>`).
+///
+/// Closures can't be compared, so all comparisons only look at the `CallbackKey`.
+macro_rules! impl_closure_callback {($callback_value:ident<$t:ident>) => (
+
+    impl<$t> ::std::fmt::Display for $callback_value<$t> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{:?}", self)
+        }
+    }
+
+    impl<$t> ::std::fmt::Debug for $callback_value<$t> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let callback = stringify!($callback_value);
+            write!(f, "{} @ {:?}", callback, self.key)
+        }
+    }
+
+    impl<$t> Clone for $callback_value<$t> {
+        fn clone(&self) -> Self {
+            $callback_value { key: self.key, callback: self.callback.clone() }
+        }
+    }
+
+    impl<$t> ::std::hash::Hash for $callback_value<$t> {
+        fn hash<H>(&self, state: &mut H) where H: Hasher {
+            self.key.hash(state);
+        }
+    }
+
+    impl<$t> PartialEq for $callback_value<$t> {
+        fn eq(&self, rhs: &Self) -> bool {
+            self.key == rhs.key
+        }
+    }
+
+    impl<$t> PartialOrd for $callback_value<$t> {
+        fn partial_cmp(&self, other: &Self) -> Option<::std::cmp::Ordering> {
+            Some(self.key.cmp(&other.key))
+        }
+    }
+
+    impl<$t> Ord for $callback_value<$t> {
+        fn cmp(&self, other: &Self) -> ::std::cmp::Ordering {
+            self.key.cmp(&other.key)
+        }
+    }
+
+    impl<$t> Eq for $callback_value<$t> { }
+
+    impl<$t> $callback_value<$t> {
+        /// Returns the key that this callback is compared and hashed by
+        pub fn get_key(&self) -> CallbackKey {
+            self.key
+        }
+    }
+)}
+
+/// Implements `Debug`, `Clone`, `Hash`, `PartialEq`, `Eq`, `PartialOrd`, `Ord` and the
+/// `From` conversions for an enum with a `Fn(function pointer)` and a `Closure(closure)` variant.
+/// Function pointers are ordered before closures.
+macro_rules! impl_callback_variant {($variant:ident<$t:ident>, $fn_callback:ident, $closure_callback:ident) => (
+
+    impl<$t> ::std::fmt::Debug for $variant<$t> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                $variant::Fn(c) => write!(f, "{:?}", c),
+                $variant::Closure(c) => write!(f, "{:?}", c),
+            }
+        }
+    }
+
+    impl<$t> Clone for $variant<$t> {
+        fn clone(&self) -> Self {
+            match self {
+                $variant::Fn(c) => $variant::Fn(c.clone()),
+                $variant::Closure(c) => $variant::Closure(c.clone()),
+            }
+        }
+    }
+
+    impl<$t> ::std::hash::Hash for $variant<$t> {
+        fn hash<H>(&self, state: &mut H) where H: Hasher {
+            match self {
+                $variant::Fn(c) => { 0_u8.hash(state); c.hash(state); },
+                $variant::Closure(c) => { 1_u8.hash(state); c.hash(state); },
+            }
+        }
+    }
+
+    impl<$t> PartialEq for $variant<$t> {
+        fn eq(&self, rhs: &Self) -> bool {
+            match (self, rhs) {
+                ($variant::Fn(a), $variant::Fn(b)) => a == b,
+                ($variant::Closure(a), $variant::Closure(b)) => a == b,
+                _ => false,
+            }
+        }
+    }
+
+    impl<$t> PartialOrd for $variant<$t> {
+        fn partial_cmp(&self, other: &Self) -> Option<::std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl<$t> Ord for $variant<$t> {
+        fn cmp(&self, other: &Self) -> ::std::cmp::Ordering {
+            use std::cmp::Ordering;
+            match (self, other) {
+                ($variant::Fn(a), $variant::Fn(b)) => a.cmp(b),
+                ($variant::Closure(a), $variant::Closure(b)) => a.cmp(b),
+                ($variant::Fn(_), $variant::Closure(_)) => Ordering::Less,
+                ($variant::Closure(_), $variant::Fn(_)) => Ordering::Greater,
+            }
+        }
+    }
+
+    impl<$t> Eq for $variant<$t> { }
+
+    impl<$t> From<$fn_callback<$t>> for $variant<$t> {
+        fn from(callback: $fn_callback<$t>) -> Self {
+            $variant::Fn(callback)
+        }
+    }
+
+    impl<$t> From<$closure_callback<$t>> for $variant<$t> {
+        fn from(callback: $closure_callback<$t>) -> Self {
+            $variant::Closure(callback)
+        }
+    }
+)}
+
 macro_rules! image_api {($struct_name:ident::$struct_field:ident) => (
 
 impl<T> $struct_name<T> {
@@ -406,6 +540,57 @@ impl<T> $struct_name<T> {
 
 )}
 
+/// Implements functions to open and close windows at runtime, for `AppState` and `AppStateNoData`
+macro_rules! impl_window_api {() => (
+
+    /// Opens a new window once all callbacks and timers of the current frame have run.
+    /// The window is laid out with `options.layout_callback` if it is set, otherwise with
+    /// the `Layout::layout()` function of the application data.
+    ///
+    /// If the window can't be created, the error is logged and the app continues running.
+    pub fn open_window(&mut self, options: WindowCreateOptions<T>, css: Css) {
+        self.window_requests.push(WindowRequest::Open(options, css));
+    }
+
+    /// Closes the window with the given ID once all callbacks and timers of the current frame
+    /// have run. Unlike closing the window via the "close" button, this does **not** invoke the
+    /// `on_close_requested` callback of the window.
+    ///
+    /// The app exits once the last window is closed.
+    pub fn close_window(&mut self, window_id: GliumWindowId) {
+        self.window_requests.push(WindowRequest::Close(window_id));
+    }
+
+    /// Closes all windows (without invoking their `on_close_requested` callbacks),
+    /// so that `App::run()` returns at the end of the current frame.
+    pub fn quit(&mut self) {
+        self.window_requests.push(WindowRequest::Quit);
+    }
+)}
+
+/// Implements functions to access the payload of the current drag-and-drop
+/// operation, for `AppState` and `AppStateNoData`
+macro_rules! impl_drag_api {() => (
+
+    /// Sets the payload of the current drag operation, usually called in the
+    /// `On::DragStart` callback. The payload can be read in the `On::DragOver`
+    /// and `On::Drop` callbacks and is cleared after the `On::DragEnd` callbacks have run.
+    pub fn set_drag_data<U: Any>(&mut self, data: U) {
+        self.drag_data = Some(DragData::Custom(Box::new(data)));
+    }
+
+    /// Returns the payload of the current drag operation. Files dragged in from
+    /// the OS are stored as `DragData::Files`.
+    pub fn get_drag_data(&self) -> Option<&DragData> {
+        self.drag_data.as_ref()
+    }
+
+    /// Removes and returns the payload of the current drag operation
+    pub fn take_drag_data(&mut self) -> Option<DragData> {
+        self.drag_data.take()
+    }
+)}
+
 macro_rules! impl_deamon_api {() => (
 
     /// Insert a timer into the list of active timers.
@@ -437,7 +622,13 @@ macro_rules! impl_deamon_api {() => (
     ///
     /// While you can't modify the `SvgCache` from a different thread, you can
     /// modify other things in the `AppState` and leave the SVG cache alone.
-    pub fn add_task(&mut self, task: Task<T>) {
+    ///
+    /// The event loop is woken up as soon as the task is finished. The returned `TaskHandle`
+    /// can be stored in the application data, to cancel the task or to query its progress.
+    pub fn add_task(&mut self, task: Task<T>) -> TaskHandle {
+        task.set_events_loop_proxy(self.resources.fake_display.hidden_events_loop.create_proxy());
+        let handle = task.get_handle();
         self.tasks.push(task);
+        handle
     }
 )}
\ No newline at end of file
@@ -0,0 +1,645 @@
+//! Platform-neutral application menus
+//!
+//! A `MenuBar` consists of `Menu`s, which contain actions, separators and submenus.
+//! Every action is bound to a `Callback<T>` and optionally to an accelerator chord
+//! (i.e. `[Ctrl, Key(S)]`), which is dispatched globally by the window, regardless
+//! of which node is focused or hovered:
+//!
+//! ```no_run,ignore
+//! use azul::prelude::{AcceleratorKey::*, VirtualKeyCode::*};
+//!
+//! let menu_bar = MenuBar::new()
+//!     .with_menu(Menu::new("File")
+//!         .with_action(MenuAction::new("Open", Callback(open_file)).with_accelerator(vec![Ctrl, Key(O)]))
+//!         .with_action(MenuAction::new("Save", Callback(save_file)).with_accelerator(vec![Ctrl, Key(S)]))
+//!         .with_separator()
+//!         .with_submenu(Menu::new("Recent files").with_action(MenuAction::new("notes.txt", Callback(open_recent)))))
+//!     .with_menu(Menu::new("View")
+//!         .with_action(MenuAction::new("Show sidebar", Callback(toggle_sidebar)).with_checked(self.show_sidebar)));
+//! ```
+//!
+//! Since there is no native menu implementation yet, menus are rendered in-app: the menu bar
+//! of a window is set via `WindowCreateOptions::menu_bar` or `FakeWindow::set_menu_bar` and
+//! inserted into the DOM via `LayoutInfo::menu_bar_dom`. Opened menus are rendered as an overlay
+//! on top of the window. `FakeWindow::open_context_menu` opens any `Menu` as a context menu.
+
+use std::{
+    fmt,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use glium::glutin::VirtualKeyCode;
+use {
+    app::AppState,
+    dom::{Dom, DomString, On, EventFilter, NotEventFilter, HoverEventFilter},
+    callbacks::{Callback, CallbackInfo, UpdateScreen, Redraw, DontRedraw},
+    overlay::{OverlayAnchor, OverlayPlacement, OverlaySide, OverlayAlign},
+    shortcuts::KeyCombination,
+    window_state::{AcceleratorKey, KeyboardState},
+};
+
+static LAST_OPEN_MENU_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Horizontal bar at the top of a window, containing the top-level menus
+pub struct MenuBar<T> {
+    pub menus: Vec<Menu<T>>,
+}
+
+/// A list of menu items. Used for the top-level menus of a `MenuBar`,
+/// for submenus and for context menus
+pub struct Menu<T> {
+    /// Title of the menu in the menu bar or the parent menu
+    pub label: DomString,
+    pub items: Vec<MenuItem<T>>,
+    /// Disabled menus are greyed out and can't be opened
+    pub enabled: bool,
+}
+
+pub enum MenuItem<T> {
+    Action(MenuAction<T>),
+    Submenu(Menu<T>),
+    /// Horizontal line between two groups of items
+    Separator,
+}
+
+/// Clickable menu item
+pub struct MenuAction<T> {
+    pub label: DomString,
+    /// Invoked when the item is clicked or the accelerator is pressed
+    pub callback: Callback<T>,
+    /// Keys that have to be pressed at the same time to invoke the callback without
+    /// opening the menu, i.e. `[Ctrl, Shift, Key(S)]`. Empty if the item has no accelerator.
+    pub accelerator: Vec<AcceleratorKey>,
+    /// `Some(checked)` if the item can be checked, in which case the item shows a check mark.
+    /// Clicking the item doesn't toggle it, the callback has to update the menu.
+    pub checked: Option<bool>,
+    /// Disabled items are greyed out and neither invoked by clicks nor by the accelerator
+    pub enabled: bool,
+}
+
+impl<T> MenuBar<T> {
+
+    pub fn new() -> Self {
+        Self { menus: Vec::new() }
+    }
+
+    #[inline]
+    pub fn with_menu(mut self, menu: Menu<T>) -> Self {
+        self.menus.push(menu);
+        self
+    }
+
+    /// Returns the callback of the first enabled action whose accelerator is pressed,
+    /// see `Menu::find_accelerator`
+    pub fn find_accelerator(&self, keyboard_state: &KeyboardState, pressed_key: VirtualKeyCode) -> Option<Callback<T>> {
+        self.menus.iter().filter_map(|menu| menu.find_accelerator(keyboard_state, pressed_key)).next()
+    }
+}
+
+impl<T> Menu<T> {
+
+    pub fn new<S: Into<DomString>>(label: S) -> Self {
+        Self {
+            label: label.into(),
+            items: Vec::new(),
+            enabled: true,
+        }
+    }
+
+    #[inline]
+    pub fn with_action(mut self, action: MenuAction<T>) -> Self {
+        self.items.push(MenuItem::Action(action));
+        self
+    }
+
+    #[inline]
+    pub fn with_submenu(mut self, submenu: Menu<T>) -> Self {
+        self.items.push(MenuItem::Submenu(submenu));
+        self
+    }
+
+    #[inline]
+    pub fn with_separator(mut self) -> Self {
+        self.items.push(MenuItem::Separator);
+        self
+    }
+
+    #[inline]
+    pub fn with_enabled(self, enabled: bool) -> Self {
+        Self { enabled, .. self }
+    }
+
+    /// Returns the submenu at the given path of item indices, i.e. `[2, 0]` is the first
+    /// item of the third item of this menu. Returns `self` if the path is empty.
+    pub fn get_submenu(&self, path: &[usize]) -> Option<&Menu<T>> {
+        match path.split_first() {
+            None => Some(self),
+            Some((first, rest)) => match self.items.get(*first)? {
+                MenuItem::Submenu(submenu) => submenu.get_submenu(rest),
+                _ => None,
+            },
+        }
+    }
+
+    /// Returns the callback of the first enabled action (including the actions of enabled
+    /// submenus) whose accelerator is pressed. The `pressed_key` has to be the key of the
+    /// accelerator, so that the callback is only invoked once, when the last key is pressed.
+    ///
+    /// Modifiers are matched exactly, like a `KeyCombination`: `[Ctrl, Key(S)]` doesn't
+    /// match if Ctrl + Shift + S is pressed.
+    pub fn find_accelerator(&self, keyboard_state: &KeyboardState, pressed_key: VirtualKeyCode) -> Option<Callback<T>> {
+        self.find_key_combination(&KeyCombination::from_keyboard_state(keyboard_state, pressed_key))
+    }
+
+    fn find_key_combination(&self, pressed: &KeyCombination) -> Option<Callback<T>> {
+
+        if !self.enabled {
+            return None;
+        }
+
+        self.items.iter().filter_map(|item| match item {
+            MenuItem::Action(action) => {
+                let matches = action.enabled &&
+                    KeyCombination::from_accelerator(&action.accelerator).as_ref() == Some(pressed);
+                if matches { Some(action.callback) } else { None }
+            },
+            MenuItem::Submenu(submenu) => submenu.find_key_combination(pressed),
+            MenuItem::Separator => None,
+        }).next()
+    }
+}
+
+impl<T> MenuAction<T> {
+
+    pub fn new<S: Into<DomString>>(label: S, callback: Callback<T>) -> Self {
+        Self {
+            label: label.into(),
+            callback,
+            accelerator: Vec::new(),
+            checked: None,
+            enabled: true,
+        }
+    }
+
+    #[inline]
+    pub fn with_accelerator(self, accelerator: Vec<AcceleratorKey>) -> Self {
+        Self { accelerator, .. self }
+    }
+
+    #[inline]
+    pub fn with_checked(self, checked: bool) -> Self {
+        Self { checked: Some(checked), .. self }
+    }
+
+    #[inline]
+    pub fn with_enabled(self, enabled: bool) -> Self {
+        Self { enabled, .. self }
+    }
+}
+
+impl<T> Default for MenuBar<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for MenuBar<T> {
+    fn clone(&self) -> Self {
+        Self { menus: self.menus.clone() }
+    }
+}
+
+impl<T> Clone for Menu<T> {
+    fn clone(&self) -> Self {
+        Self {
+            label: self.label.clone(),
+            items: self.items.clone(),
+            enabled: self.enabled,
+        }
+    }
+}
+
+impl<T> Clone for MenuItem<T> {
+    fn clone(&self) -> Self {
+        use self::MenuItem::*;
+        match self {
+            Action(action) => Action(action.clone()),
+            Submenu(submenu) => Submenu(submenu.clone()),
+            Separator => Separator,
+        }
+    }
+}
+
+impl<T> Clone for MenuAction<T> {
+    fn clone(&self) -> Self {
+        Self {
+            label: self.label.clone(),
+            callback: self.callback,
+            accelerator: self.accelerator.clone(),
+            checked: self.checked,
+            enabled: self.enabled,
+        }
+    }
+}
+
+impl<T> fmt::Debug for MenuBar<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MenuBar {{ menus: {:?} }}", self.menus)
+    }
+}
+
+impl<T> fmt::Debug for Menu<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,
+            "Menu {{ label: {:?}, items: {:?}, enabled: {:?} }}",
+            self.label, self.items, self.enabled,
+        )
+    }
+}
+
+impl<T> fmt::Debug for MenuItem<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::MenuItem::*;
+        match self {
+            Action(action) => write!(f, "Action({:?})", action),
+            Submenu(submenu) => write!(f, "Submenu({:?})", submenu),
+            Separator => write!(f, "Separator"),
+        }
+    }
+}
+
+impl<T> fmt::Debug for MenuAction<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,
+            "MenuAction {{ label: {:?}, callback: {:?}, accelerator: {:?}, checked: {:?}, enabled: {:?} }}",
+            self.label, self.callback, self.accelerator, self.checked, self.enabled,
+        )
+    }
+}
+
+/// Formats an accelerator for displaying it next to the menu item, i.e. "Ctrl+Shift+S"
+pub fn format_accelerator(accelerator: &[AcceleratorKey]) -> String {
+    accelerator.iter().map(|key| match key {
+        AcceleratorKey::Ctrl => "Ctrl".to_string(),
+        AcceleratorKey::Alt => "Alt".to_string(),
+        AcceleratorKey::Shift => "Shift".to_string(),
+        AcceleratorKey::Key(key) => {
+            let key = format!("{:?}", key);
+            // VirtualKeyCode::Key1 => "1"
+            if key.len() == 4 && key.starts_with("Key") { key[3..].to_string() } else { key }
+        },
+    }).collect::<Vec<_>>().join("+")
+}
+
+/// The menu that is currently open in a window
+pub(crate) struct OpenMenu<T> {
+    /// Unique ID of the opened menu, so that a click outside of the menu only closes the menu
+    /// it was rendered for, not a menu that was opened by another callback of the same click
+    pub(crate) id: usize,
+    pub(crate) source: OpenMenuSource<T>,
+    /// Index of the opened submenu item, for every level of submenus
+    pub(crate) submenus: Vec<usize>,
+}
+
+pub(crate) enum OpenMenuSource<T> {
+    /// Index of the menu in the menu bar of the window
+    MenuBar(usize),
+    ContextMenu(Menu<T>, OverlayAnchor),
+}
+
+impl<T> Clone for OpenMenu<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            source: match &self.source {
+                OpenMenuSource::MenuBar(index) => OpenMenuSource::MenuBar(*index),
+                OpenMenuSource::ContextMenu(menu, anchor) => OpenMenuSource::ContextMenu(menu.clone(), anchor.clone()),
+            },
+            submenus: self.submenus.clone(),
+        }
+    }
+}
+
+impl<T> OpenMenu<T> {
+
+    pub(crate) fn new(source: OpenMenuSource<T>) -> Self {
+        Self {
+            id: LAST_OPEN_MENU_ID.fetch_add(1, Ordering::SeqCst),
+            source,
+            submenus: Vec::new(),
+        }
+    }
+
+    /// Returns the menu that is displayed at the given level (0 = the top-level menu)
+    pub(crate) fn get_menu<'a>(&'a self, menu_bar: Option<&'a MenuBar<T>>, level: usize) -> Option<&'a Menu<T>> {
+        let root = match &self.source {
+            OpenMenuSource::MenuBar(index) => menu_bar?.menus.get(*index)?,
+            OpenMenuSource::ContextMenu(menu, _) => menu,
+        };
+        root.get_submenu(self.submenus.get(..level)?)
+    }
+}
+
+/// ID of the menu bar item, so that the opened menu can be anchored to it
+fn menu_bar_item_id(index: usize) -> String {
+    format!("__azul-menu-bar-item-{}", index)
+}
+
+/// ID of the popup of an opened menu, see `OpenMenu::id`
+fn menu_popup_id(open_menu_id: usize) -> String {
+    format!("__azul-menu-popup-{}", open_menu_id)
+}
+
+/// Renders the menu bar, the opened menu is rendered separately, see `open_menu_overlay`
+pub(crate) fn menu_bar_dom<T>(menu_bar: Option<&MenuBar<T>>, open_menu: Option<&OpenMenu<T>>) -> Dom<T> {
+
+    let mut menu_bar_dom = Dom::div().with_class("__azul-native-menu-bar");
+
+    let open_index = match open_menu.map(|open_menu| &open_menu.source) {
+        Some(OpenMenuSource::MenuBar(index)) => Some(*index),
+        _ => None,
+    };
+
+    for (menu_index, menu) in menu_bar.iter().flat_map(|menu_bar| menu_bar.menus.iter()).enumerate() {
+        let mut item = Dom::div()
+            .with_class("__azul-native-menu-bar-item")
+            .with_id(menu_bar_item_id(menu_index))
+            .with_callback(On::MouseDown, Callback(on_menu_bar_item_click))
+            .with_callback(On::MouseEnter, Callback(on_menu_bar_item_hover))
+            .with_child(Dom::label(menu.label.clone()));
+        if !menu.enabled {
+            item.add_class("__azul-native-menu-bar-item-disabled");
+        }
+        if open_index == Some(menu_index) {
+            item.add_class("__azul-native-menu-bar-item-open");
+        }
+        menu_bar_dom.add_child(item);
+    }
+
+    menu_bar_dom
+}
+
+/// Renders the opened menu and all opened submenus next to each other in a single overlay,
+/// so that clicks on submenus aren't outside clicks of the parent menu
+pub(crate) fn open_menu_overlay<T>(open_menu: &OpenMenu<T>, menu_bar: Option<&MenuBar<T>>) -> Option<(OverlayAnchor, OverlayPlacement, Dom<T>)> {
+
+    let anchor = match &open_menu.source {
+        OpenMenuSource::MenuBar(index) => OverlayAnchor::NodeWithId(menu_bar_item_id(*index).into()),
+        OpenMenuSource::ContextMenu(_, anchor) => anchor.clone(),
+    };
+
+    let mut popup = Dom::div()
+        .with_class("__azul-native-menu-popup")
+        .with_id(menu_popup_id(open_menu.id))
+        .with_callback(EventFilter::Not(NotEventFilter::Hover(HoverEventFilter::MouseDown)), Callback(on_menu_dismiss));
+
+    for level in 0..=open_menu.submenus.len() {
+        let menu = open_menu.get_menu(menu_bar, level)?;
+        popup.add_child(menu_dom(menu, open_menu.submenus.get(level).cloned()));
+    }
+
+    let placement = OverlayPlacement::new(OverlaySide::Bottom).with_align(OverlayAlign::Start);
+    Some((anchor, placement, popup))
+}
+
+/// Renders the items of a single menu, the item at `open_submenu` is highlighted
+fn menu_dom<T>(menu: &Menu<T>, open_submenu: Option<usize>) -> Dom<T> {
+
+    let mut menu_dom = Dom::div().with_class("__azul-native-menu");
+
+    for (item_index, item) in menu.items.iter().enumerate() {
+
+        let (label, checked, accelerator, enabled) = match item {
+            MenuItem::Separator => {
+                menu_dom.add_child(Dom::div().with_class("__azul-native-menu-separator"));
+                continue;
+            },
+            MenuItem::Action(action) => (&action.label, action.checked, format_accelerator(&action.accelerator), action.enabled),
+            MenuItem::Submenu(submenu) => (&submenu.label, None, "\u{25B8}".to_string(), submenu.enabled),
+        };
+
+        let mut item_dom = Dom::div()
+            .with_class("__azul-native-menu-item")
+            .with_callback(On::MouseUp, Callback(on_menu_item_click))
+            .with_callback(On::MouseEnter, Callback(on_menu_item_hover))
+            .with_child(Dom::label(if checked == Some(true) { "\u{2713}" } else { "" }).with_class("__azul-native-menu-item-check"))
+            .with_child(Dom::label(label.clone()).with_class("__azul-native-menu-item-label"))
+            .with_child(Dom::label(accelerator).with_class("__azul-native-menu-item-accelerator"));
+
+        if !enabled {
+            item_dom.add_class("__azul-native-menu-item-disabled");
+        }
+        if open_submenu == Some(item_index) {
+            item_dom.add_class("__azul-native-menu-item-open");
+        }
+
+        menu_dom.add_child(item_dom);
+    }
+
+    menu_dom
+}
+
+/// Returns the (level, item index) of the menu item that the callback is invoked on:
+/// popup -> menu (level) -> item (item index)
+fn get_menu_item_position<T>(info: &CallbackInfo<T>) -> Option<(usize, usize)> {
+    let (item_index, menu) = info.get_index_in_parent(info.hit_dom_node)?;
+    let (level, _) = info.get_index_in_parent(menu)?;
+    Some((level, item_index))
+}
+
+fn on_menu_bar_item_click<T>(app_state: &mut AppState<T>, info: &mut CallbackInfo<T>) -> UpdateScreen {
+    let menu_index = info.target_index_in_parent()?;
+    let window = app_state.windows.get_mut(info.window_id)?;
+
+    if !window.menu_bar.as_ref()?.menus.get(menu_index)?.enabled {
+        return DontRedraw;
+    }
+
+    window.open_menu = match &window.open_menu {
+        // Clicking the title of the open menu closes it
+        Some(OpenMenu { source: OpenMenuSource::MenuBar(open_index), .. }) if *open_index == menu_index => None,
+        _ => Some(OpenMenu::new(OpenMenuSource::MenuBar(menu_index))),
+    };
+
+    Redraw
+}
+
+/// While a menu of the menu bar is open, hovering over another title opens that menu instead
+fn on_menu_bar_item_hover<T>(app_state: &mut AppState<T>, info: &mut CallbackInfo<T>) -> UpdateScreen {
+    let menu_index = info.target_index_in_parent()?;
+    let window = app_state.windows.get_mut(info.window_id)?;
+
+    if !window.menu_bar.as_ref()?.menus.get(menu_index)?.enabled {
+        return DontRedraw;
+    }
+
+    match &mut window.open_menu {
+        Some(open_menu) => match open_menu.source {
+            OpenMenuSource::MenuBar(open_index) if open_index != menu_index => {
+                open_menu.source = OpenMenuSource::MenuBar(menu_index);
+                open_menu.submenus.clear();
+                Redraw
+            },
+            _ => DontRedraw,
+        },
+        None => DontRedraw,
+    }
+}
+
+fn on_menu_item_click<T>(app_state: &mut AppState<T>, info: &mut CallbackInfo<T>) -> UpdateScreen {
+    let (level, item_index) = get_menu_item_position(info)?;
+    let window = app_state.windows.get_mut(info.window_id)?;
+
+    let callback = {
+        let open_menu = window.open_menu.as_ref()?;
+        match open_menu.get_menu(window.menu_bar.as_ref(), level)?.items.get(item_index)? {
+            MenuItem::Action(action) if action.enabled => Some(action.callback),
+            MenuItem::Submenu(submenu) if submenu.enabled => None,
+            _ => return DontRedraw,
+        }
+    };
+
+    match callback {
+        Some(callback) => {
+            window.open_menu = None;
+            // The closed menu has to be redrawn, even if the callback didn't change anything
+            (callback.0)(app_state, info).or(Redraw)
+        },
+        None => {
+            let open_menu = window.open_menu.as_mut()?;
+            open_menu.submenus.truncate(level);
+            open_menu.submenus.push(item_index);
+            Redraw
+        },
+    }
+}
+
+/// Hovering over a submenu item opens the submenu, hovering over any other item closes it
+fn on_menu_item_hover<T>(app_state: &mut AppState<T>, info: &mut CallbackInfo<T>) -> UpdateScreen {
+    let (level, item_index) = get_menu_item_position(info)?;
+    let window = app_state.windows.get_mut(info.window_id)?;
+
+    let opens_submenu = match window.open_menu.as_ref()?.get_menu(window.menu_bar.as_ref(), level)?.items.get(item_index)? {
+        MenuItem::Submenu(submenu) => submenu.enabled,
+        _ => false,
+    };
+
+    let open_menu = window.open_menu.as_mut()?;
+    let previous_submenus = open_menu.submenus.clone();
+    open_menu.submenus.truncate(level);
+    if opens_submenu {
+        open_menu.submenus.push(item_index);
+    }
+
+    if open_menu.submenus != previous_submenus { Redraw } else { DontRedraw }
+}
+
+/// Closes the menu on clicks outside of the menu. The result doesn't depend on whether this
+/// callback runs before or after the callback of the clicked node, which might open a new menu.
+fn on_menu_dismiss<T>(app_state: &mut AppState<T>, info: &mut CallbackInfo<T>) -> UpdateScreen {
+
+    // Clicks on the menu bar are handled by `on_menu_bar_item_click`
+    if is_menu_bar_click(info) {
+        return DontRedraw;
+    }
+
+    let window = app_state.windows.get_mut(info.window_id)?;
+
+    // The menu was already replaced by another callback of the same click
+    if !info.target_has_id(&menu_popup_id(window.open_menu.as_ref()?.id)) {
+        return DontRedraw;
+    }
+
+    window.open_menu = None;
+    Redraw
+}
+
+/// Returns whether the cursor is over an item of the menu bar
+fn is_menu_bar_click<T>(info: &CallbackInfo<T>) -> bool {
+    info.hit_test_items.iter()
+        .filter_map(|item| info.ui_state.tag_ids_to_node_ids.get(&item.tag.0))
+        .filter_map(|node_id| info.get_node_content(*node_id))
+        .any(|node| node.has_class("__azul-native-menu-bar-item"))
+}
+
+#[test]
+fn test_menu_find_accelerator() {
+
+    use window_state::AcceleratorKey::*;
+
+    struct Mock;
+
+    fn save(_: &mut AppState<Mock>, _: &mut CallbackInfo<Mock>) -> UpdateScreen { DontRedraw }
+    fn save_as(_: &mut AppState<Mock>, _: &mut CallbackInfo<Mock>) -> UpdateScreen { DontRedraw }
+    fn export(_: &mut AppState<Mock>, _: &mut CallbackInfo<Mock>) -> UpdateScreen { DontRedraw }
+
+    let menu_bar = MenuBar::new()
+        .with_menu(Menu::new("File")
+            .with_action(MenuAction::new("Save as", Callback(save_as)).with_accelerator(vec![Ctrl, Shift, Key(VirtualKeyCode::S)]))
+            .with_action(MenuAction::new("Save", Callback(save)).with_accelerator(vec![Ctrl, Key(VirtualKeyCode::S)]))
+            .with_separator()
+            .with_submenu(Menu::new("Export")
+                .with_action(MenuAction::new("PDF", Callback(export)).with_accelerator(vec![Ctrl, Key(VirtualKeyCode::E)]).with_enabled(false))));
+
+    let mut keyboard_state = KeyboardState::default();
+    keyboard_state.ctrl_down = true;
+    keyboard_state.current_virtual_keycodes.insert(VirtualKeyCode::S);
+
+    assert_eq!(menu_bar.find_accelerator(&keyboard_state, VirtualKeyCode::S), Some(Callback(save)));
+    assert_eq!(menu_bar.find_accelerator(&keyboard_state, VirtualKeyCode::LControl), None);
+
+    keyboard_state.shift_down = true;
+    assert_eq!(menu_bar.find_accelerator(&keyboard_state, VirtualKeyCode::S), Some(Callback(save_as)));
+
+    // Additional modifiers don't match
+    keyboard_state.alt_down = true;
+    assert_eq!(menu_bar.find_accelerator(&keyboard_state, VirtualKeyCode::S), None);
+    keyboard_state.alt_down = false;
+
+    // Disabled items are ignored
+    keyboard_state.shift_down = false;
+    keyboard_state.current_virtual_keycodes.insert(VirtualKeyCode::E);
+    assert_eq!(menu_bar.find_accelerator(&keyboard_state, VirtualKeyCode::E), None);
+
+    assert_eq!(menu_bar.menus[0].get_submenu(&[3]).map(|menu| menu.label.as_str()), Some("Export"));
+    assert!(menu_bar.menus[0].get_submenu(&[0]).is_none());
+    assert_eq!(format_accelerator(&[Ctrl, Shift, Key(VirtualKeyCode::S)]), "Ctrl+Shift+S");
+    assert_eq!(format_accelerator(&[Alt, Key(VirtualKeyCode::Key1)]), "Alt+1");
+}
+
+#[test]
+fn test_menu_ui() {
+
+    struct Mock;
+
+    fn callback(_: &mut AppState<Mock>, _: &mut CallbackInfo<Mock>) -> UpdateScreen { DontRedraw }
+
+    let menu = Menu::new("Edit")
+        .with_action(MenuAction::new("Undo", Callback(callback)).with_enabled(false))
+        .with_separator()
+        .with_submenu(Menu::new("Find"));
+
+    let expected = r#"
+        <div class="__azul-native-menu">
+            <div class="__azul-native-menu-item __azul-native-menu-item-disabled">
+                <p class="__azul-native-menu-item-check"></p>
+                <p class="__azul-native-menu-item-label">Undo</p>
+                <p class="__azul-native-menu-item-accelerator"></p>
+            </div>
+            <div class="__azul-native-menu-separator"></div>
+            <div class="__azul-native-menu-item __azul-native-menu-item-open">
+                <p class="__azul-native-menu-item-check"></p>
+                <p class="__azul-native-menu-item-label">Find</p>
+                <p class="__azul-native-menu-item-accelerator">▸</p>
+            </div>
+        </div>
+    "#;
+
+    let mut dom: Dom<Mock> = menu_dom(&menu, Some(2));
+
+    // Callbacks can't be expressed in XML
+    for node in dom.arena.node_data.iter_mut() {
+        node.callbacks.clear();
+    }
+
+    dom.assert_eq(expected);
+}
@@ -0,0 +1,280 @@
+//! Popups (tooltips, context menus, dropdowns, ...) that are laid out outside of the normal
+//! document flow and are drawn on top of all other content.
+//!
+//! Since every window only renders a single DOM, a popup that is a child of a regular node
+//! would get clipped or moved by the layout of its parents. Instead, overlays are added via
+//! `LayoutInfo::add_overlay` and positioned relative to an anchor rectangle after the
+//! layout of the regular DOM is done:
+//!
+//! ```no_run,ignore
+//! fn layout(&self, mut info: LayoutInfo<Self>) -> Dom<Self> {
+//!     if let Some((x, y)) = self.context_menu_position {
+//!         let placement = OverlayPlacement::new(OverlaySide::Bottom).with_align(OverlayAlign::Start);
+//!         info.add_overlay(OverlayAnchor::point(x, y), placement, context_menu_dom());
+//!     }
+//!     Dom::div()
+//! }
+//! ```
+//!
+//! Unlike regular nodes, the root node of an overlay isn't stretched to fill the available
+//! space, it takes the size of its content unless its CSS sets a `width` / `height`.
+
+use std::fmt;
+use webrender::api::{LayoutRect, LayoutSize, LayoutPoint};
+use {
+    dom::{Dom, DomString},
+    id_tree::NodeId,
+};
+
+/// CSS class of the wrapper node that every overlay DOM is inserted into
+pub const OVERLAY_CLASS: &str = "__azul-overlay";
+
+/// What the overlay should be positioned relative to
+#[derive(Debug, Clone, PartialEq)]
+pub enum OverlayAnchor {
+    /// Anchored to the laid-out rectangle of a node of the current DOM
+    Node(NodeId),
+    /// Anchored to the first node of the current DOM that has the given HTML id, useful
+    /// if the `NodeId` isn't known when the overlay is added (i.e. inside of a widget)
+    NodeWithId(DomString),
+    /// Anchored to a rectangle, relative to the top left of the window
+    Rect(LayoutRect),
+}
+
+impl OverlayAnchor {
+    /// Anchors the overlay to a single point (for example the cursor position for context menus)
+    pub fn point(x: f32, y: f32) -> Self {
+        OverlayAnchor::Rect(LayoutRect::new(LayoutPoint::new(x, y), LayoutSize::zero()))
+    }
+}
+
+/// On which side of the anchor the overlay is placed
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum OverlaySide {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl OverlaySide {
+    /// Returns the side on the other side of the anchor
+    pub fn opposite(&self) -> Self {
+        use self::OverlaySide::*;
+        match self {
+            Top => Bottom,
+            Bottom => Top,
+            Left => Right,
+            Right => Left,
+        }
+    }
+
+    fn is_vertical(&self) -> bool {
+        *self == OverlaySide::Top || *self == OverlaySide::Bottom
+    }
+}
+
+/// How the overlay is aligned along the side of the anchor
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum OverlayAlign {
+    /// Aligns the left (or top) edges of the overlay and the anchor
+    Start,
+    /// Centers the overlay on the anchor
+    Center,
+    /// Aligns the right (or bottom) edges of the overlay and the anchor
+    End,
+}
+
+/// Where an overlay should be placed relative to its anchor
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct OverlayPlacement {
+    /// Preferred side of the anchor
+    pub side: OverlaySide,
+    /// Alignment along the preferred side
+    pub align: OverlayAlign,
+    /// Distance between the anchor and the overlay, in logical pixels
+    pub offset: f32,
+    /// If the overlay doesn't fit on the preferred side, place it on the opposite side instead
+    pub flip: bool,
+    /// If the overlay still overflows the window, move it back into the window
+    pub shift: bool,
+}
+
+impl Default for OverlayPlacement {
+    fn default() -> Self {
+        Self::new(OverlaySide::Bottom)
+    }
+}
+
+impl OverlayPlacement {
+
+    /// Creates a new, start-aligned placement that flips and shifts if necessary
+    pub fn new(side: OverlaySide) -> Self {
+        Self {
+            side,
+            align: OverlayAlign::Start,
+            offset: 0.0,
+            flip: true,
+            shift: true,
+        }
+    }
+
+    #[inline]
+    pub fn with_align(self, align: OverlayAlign) -> Self {
+        Self { align, .. self }
+    }
+
+    #[inline]
+    pub fn with_offset(self, offset: f32) -> Self {
+        Self { offset, .. self }
+    }
+
+    #[inline]
+    pub fn with_flip(self, flip: bool) -> Self {
+        Self { flip, .. self }
+    }
+
+    #[inline]
+    pub fn with_shift(self, shift: bool) -> Self {
+        Self { shift, .. self }
+    }
+}
+
+/// An overlay that was added in the `layout()` function, but not yet inserted into the DOM
+pub(crate) struct Overlay<T> {
+    pub(crate) anchor: OverlayAnchor,
+    pub(crate) placement: OverlayPlacement,
+    pub(crate) dom: Dom<T>,
+}
+
+impl<T> Clone for Overlay<T> {
+    fn clone(&self) -> Self {
+        Self {
+            anchor: self.anchor.clone(),
+            placement: self.placement,
+            dom: self.dom.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Overlay<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,
+            "Overlay {{ anchor: {:?}, placement: {:?}, dom: {:?} }}",
+            self.anchor, self.placement, self.dom,
+        )
+    }
+}
+
+/// An overlay after it was inserted into the DOM of the window
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct OverlayNode {
+    /// ID of the `__azul-overlay` wrapper node
+    pub(crate) node_id: NodeId,
+    pub(crate) anchor: OverlayAnchor,
+    pub(crate) placement: OverlayPlacement,
+}
+
+/// Calculates the top left corner of an overlay with the given `size`, placed next to
+/// the `anchor` rectangle. The overlay is flipped to the opposite side and / or shifted
+/// back into the window if it would otherwise overflow the `window_size`.
+pub fn place_overlay(anchor: LayoutRect, size: LayoutSize, window_size: LayoutSize, placement: OverlayPlacement) -> LayoutPoint {
+
+    let mut origin = position_on_side(anchor, size, placement.side, placement.align, placement.offset);
+
+    if placement.flip && overflows_side(origin, size, window_size, placement.side) {
+        let flipped_side = placement.side.opposite();
+        let flipped_origin = position_on_side(anchor, size, flipped_side, placement.align, placement.offset);
+        if !overflows_side(flipped_origin, size, window_size, flipped_side) {
+            origin = flipped_origin;
+        }
+    }
+
+    if placement.shift {
+        origin.x = shift_into_range(origin.x, size.width, window_size.width);
+        origin.y = shift_into_range(origin.y, size.height, window_size.height);
+    }
+
+    origin
+}
+
+fn position_on_side(anchor: LayoutRect, size: LayoutSize, side: OverlaySide, align: OverlayAlign, offset: f32) -> LayoutPoint {
+
+    fn align_axis(anchor_start: f32, anchor_size: f32, size: f32, align: OverlayAlign) -> f32 {
+        match align {
+            OverlayAlign::Start => anchor_start,
+            OverlayAlign::Center => anchor_start + (anchor_size - size) / 2.0,
+            OverlayAlign::End => anchor_start + anchor_size - size,
+        }
+    }
+
+    match side {
+        OverlaySide::Top => LayoutPoint::new(
+            align_axis(anchor.origin.x, anchor.size.width, size.width, align),
+            anchor.origin.y - offset - size.height,
+        ),
+        OverlaySide::Bottom => LayoutPoint::new(
+            align_axis(anchor.origin.x, anchor.size.width, size.width, align),
+            anchor.origin.y + anchor.size.height + offset,
+        ),
+        OverlaySide::Left => LayoutPoint::new(
+            anchor.origin.x - offset - size.width,
+            align_axis(anchor.origin.y, anchor.size.height, size.height, align),
+        ),
+        OverlaySide::Right => LayoutPoint::new(
+            anchor.origin.x + anchor.size.width + offset,
+            align_axis(anchor.origin.y, anchor.size.height, size.height, align),
+        ),
+    }
+}
+
+/// Returns whether the overlay overflows the window on the axis that `side` is on
+fn overflows_side(origin: LayoutPoint, size: LayoutSize, window_size: LayoutSize, side: OverlaySide) -> bool {
+    if side.is_vertical() {
+        origin.y < 0.0 || origin.y + size.height > window_size.height
+    } else {
+        origin.x < 0.0 || origin.x + size.width > window_size.width
+    }
+}
+
+/// If the overlay is larger than the window, it is aligned to the top / left edge
+fn shift_into_range(start: f32, size: f32, max: f32) -> f32 {
+    start.min(max - size).max(0.0)
+}
+
+#[test]
+fn test_place_overlay_sides() {
+    let anchor = LayoutRect::new(LayoutPoint::new(100.0, 100.0), LayoutSize::new(50.0, 20.0));
+    let size = LayoutSize::new(30.0, 10.0);
+    let window_size = LayoutSize::new(800.0, 600.0);
+
+    let place = |side, align| place_overlay(anchor, size, window_size, OverlayPlacement::new(side).with_align(align));
+
+    assert_eq!(place(OverlaySide::Bottom, OverlayAlign::Start), LayoutPoint::new(100.0, 120.0));
+    assert_eq!(place(OverlaySide::Bottom, OverlayAlign::Center), LayoutPoint::new(110.0, 120.0));
+    assert_eq!(place(OverlaySide::Bottom, OverlayAlign::End), LayoutPoint::new(120.0, 120.0));
+    assert_eq!(place(OverlaySide::Top, OverlayAlign::Start), LayoutPoint::new(100.0, 90.0));
+    assert_eq!(place(OverlaySide::Left, OverlayAlign::Start), LayoutPoint::new(70.0, 100.0));
+    assert_eq!(place(OverlaySide::Right, OverlayAlign::End), LayoutPoint::new(150.0, 110.0));
+}
+
+#[test]
+fn test_place_overlay_flip_and_shift() {
+    let window_size = LayoutSize::new(800.0, 600.0);
+    let size = LayoutSize::new(200.0, 100.0);
+
+    // Not enough space below the anchor, so the overlay is flipped to the top
+    let anchor = LayoutRect::new(LayoutPoint::new(10.0, 550.0), LayoutSize::new(50.0, 20.0));
+    let placement = OverlayPlacement::new(OverlaySide::Bottom).with_offset(5.0);
+    assert_eq!(place_overlay(anchor, size, window_size, placement), LayoutPoint::new(10.0, 445.0));
+    assert_eq!(place_overlay(anchor, size, window_size, placement.with_flip(false).with_shift(false)), LayoutPoint::new(10.0, 575.0));
+
+    // Overflows the right edge of the window, so the overlay is shifted to the left
+    let anchor = LayoutRect::new(LayoutPoint::new(700.0, 10.0), LayoutSize::new(50.0, 20.0));
+    let placement = OverlayPlacement::new(OverlaySide::Bottom);
+    assert_eq!(place_overlay(anchor, size, window_size, placement), LayoutPoint::new(600.0, 30.0));
+
+    // Doesn't fit on either side, so the overlay stays on the preferred side and is shifted
+    let anchor = LayoutRect::new(LayoutPoint::new(10.0, 50.0), LayoutSize::new(50.0, 500.0));
+    assert_eq!(place_overlay(anchor, size, window_size, placement), LayoutPoint::new(10.0, 500.0));
+}
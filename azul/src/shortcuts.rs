@@ -0,0 +1,742 @@
+//! Per-window registry of keyboard shortcuts
+//!
+//! Unlike `window_state::keymap`, which has to be called from inside of a callback, shortcuts
+//! are registered once per window and dispatched by the window itself:
+//!
+//! ```no_run,ignore
+//! let shortcuts = ShortcutRegistry::new()
+//!     .with_binding(ShortcutBinding::new("file.save", "Ctrl+S".parse()?, Callback(save))
+//!         .with_description("Save the current file"))
+//!     .with_binding(ShortcutBinding::new("file.save_all", "Ctrl+K Ctrl+S".parse()?, Callback(save_all))
+//!         .with_description("Save all open files"))
+//!     .with_binding(ShortcutBinding::new("editor.duplicate_line", "Ctrl+D".parse()?, Callback(duplicate_line))
+//!         .with_scope(ShortcutScope::Id("editor".into())));
+//!
+//! let window_options = WindowCreateOptions { shortcuts, .. Default::default() };
+//! ```
+//!
+//! - Modifiers are matched exactly: `Ctrl+S` doesn't fire when `Ctrl+Shift+S` is pressed.
+//! - A shortcut can be a chord of multiple key combinations (`Ctrl+K Ctrl+S`), which
+//!   have to be pressed one after another.
+//! - Scoped shortcuts are only active while the focused node is inside of the scope and
+//!   take precedence over global shortcuts with the same keys.
+//!
+//! The shortcuts of the bindings can be changed at runtime (`ShortcutRegistry::rebind`) or
+//! loaded from a config file (`ShortcutRegistry::load_config_file`), which contains one
+//! `action = shortcut` line per binding:
+//!
+//! ```text
+//! # Comments start with a "#"
+//! file.save = Ctrl+S
+//! file.save_all = Ctrl+K Ctrl+S
+//! # An empty shortcut removes the binding
+//! editor.duplicate_line =
+//! ```
+
+use std::{
+    fmt,
+    fs,
+    str::FromStr,
+    path::{Path, PathBuf},
+    io::Error as IoError,
+};
+use glium::glutin::VirtualKeyCode;
+use {
+    dom::{DomString, NodeData},
+    callbacks::Callback,
+    id_tree::{Arena, NodeId},
+    window_state::{KeyboardState, AcceleratorKey},
+};
+
+/// A single key, pressed together with exactly the given modifiers
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct KeyCombination {
+    pub key: VirtualKeyCode,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+    /// `Super / Windows / Command` key
+    pub super_key: bool,
+}
+
+impl KeyCombination {
+
+    /// Creates a key combination without modifiers
+    pub fn new(key: VirtualKeyCode) -> Self {
+        Self { key, ctrl: false, alt: false, shift: false, super_key: false }
+    }
+
+    #[inline]
+    pub fn with_ctrl(self) -> Self {
+        Self { ctrl: true, .. self }
+    }
+
+    #[inline]
+    pub fn with_alt(self) -> Self {
+        Self { alt: true, .. self }
+    }
+
+    #[inline]
+    pub fn with_shift(self) -> Self {
+        Self { shift: true, .. self }
+    }
+
+    #[inline]
+    pub fn with_super(self) -> Self {
+        Self { super_key: true, .. self }
+    }
+
+    /// Converts a menu accelerator (i.e. `[Ctrl, Shift, Key(S)]`) into a key combination.
+    /// Returns `None` if the accelerator doesn't contain exactly one non-modifier key.
+    pub fn from_accelerator(accelerator: &[AcceleratorKey]) -> Option<Self> {
+
+        let mut keys = accelerator.iter().filter_map(|key| match key {
+            AcceleratorKey::Key(key) => Some(*key),
+            _ => None,
+        });
+
+        let key = keys.next()?;
+        if keys.next().is_some() {
+            return None;
+        }
+
+        Some(Self {
+            key,
+            ctrl: accelerator.contains(&AcceleratorKey::Ctrl),
+            alt: accelerator.contains(&AcceleratorKey::Alt),
+            shift: accelerator.contains(&AcceleratorKey::Shift),
+            super_key: false,
+        })
+    }
+
+    /// Returns the combination of the `pressed_key` and the modifiers that are currently pressed
+    pub fn from_keyboard_state(keyboard_state: &KeyboardState, pressed_key: VirtualKeyCode) -> Self {
+        Self {
+            key: pressed_key,
+            ctrl: keyboard_state.ctrl_down,
+            alt: keyboard_state.alt_down,
+            shift: keyboard_state.shift_down,
+            super_key: keyboard_state.super_down,
+        }
+    }
+}
+
+impl fmt::Display for KeyCombination {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.ctrl { write!(f, "Ctrl+")?; }
+        if self.alt { write!(f, "Alt+")?; }
+        if self.shift { write!(f, "Shift+")?; }
+        if self.super_key { write!(f, "Super+")?; }
+        match KEY_NAMES.iter().find(|(_, key)| *key == self.key) {
+            Some((name, _)) => write!(f, "{}", name),
+            None => write!(f, "{:?}", self.key),
+        }
+    }
+}
+
+impl FromStr for KeyCombination {
+    type Err = ShortcutParseError;
+
+    /// Parses a combination such as `"Ctrl+Shift+S"`. Modifiers and keys are case-insensitive.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+
+        let mut parts = s.split('+').map(|part| part.trim()).collect::<Vec<_>>();
+        let key_name = parts.pop().filter(|key| !key.is_empty()).ok_or_else(|| ShortcutParseError::MissingKey(s.to_string()))?;
+
+        let key = KEY_NAMES.iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key_name))
+            .map(|(_, key)| *key)
+            .ok_or_else(|| if parse_modifier(key_name).is_some() {
+                ShortcutParseError::MissingKey(s.to_string())
+            } else {
+                ShortcutParseError::UnknownKey(key_name.to_string())
+            })?;
+
+        let mut combination = KeyCombination::new(key);
+
+        for modifier in parts {
+            match parse_modifier(modifier).ok_or_else(|| ShortcutParseError::UnknownModifier(modifier.to_string()))? {
+                Modifier::Ctrl => combination.ctrl = true,
+                Modifier::Alt => combination.alt = true,
+                Modifier::Shift => combination.shift = true,
+                Modifier::Super => combination.super_key = true,
+            }
+        }
+
+        Ok(combination)
+    }
+}
+
+enum Modifier {
+    Ctrl,
+    Alt,
+    Shift,
+    Super,
+}
+
+fn parse_modifier(name: &str) -> Option<Modifier> {
+    match name.to_ascii_lowercase().as_str() {
+        "ctrl" | "control" => Some(Modifier::Ctrl),
+        "alt" | "option" => Some(Modifier::Alt),
+        "shift" => Some(Modifier::Shift),
+        "super" | "cmd" | "command" | "win" | "meta" => Some(Modifier::Super),
+        _ => None,
+    }
+}
+
+/// Modifier keys can't be the last key of a combination, so they don't start or break chords
+fn is_modifier_key(key: VirtualKeyCode) -> bool {
+    use glium::glutin::VirtualKeyCode::*;
+    match key {
+        LControl | RControl | LAlt | RAlt | LShift | RShift | LWin | RWin => true,
+        _ => false,
+    }
+}
+
+/// Names of the keys in config files and in the `Display` output of a `Shortcut`
+const KEY_NAMES: &[(&str, VirtualKeyCode)] = {
+    use glium::glutin::VirtualKeyCode::*;
+    &[
+        ("A", A), ("B", B), ("C", C), ("D", D), ("E", E), ("F", F), ("G", G), ("H", H), ("I", I),
+        ("J", J), ("K", K), ("L", L), ("M", M), ("N", N), ("O", O), ("P", P), ("Q", Q), ("R", R),
+        ("S", S), ("T", T), ("U", U), ("V", V), ("W", W), ("X", X), ("Y", Y), ("Z", Z),
+        ("0", Key0), ("1", Key1), ("2", Key2), ("3", Key3), ("4", Key4),
+        ("5", Key5), ("6", Key6), ("7", Key7), ("8", Key8), ("9", Key9),
+        ("F1", F1), ("F2", F2), ("F3", F3), ("F4", F4), ("F5", F5), ("F6", F6),
+        ("F7", F7), ("F8", F8), ("F9", F9), ("F10", F10), ("F11", F11), ("F12", F12),
+        ("Escape", Escape), ("Tab", Tab), ("Space", Space), ("Enter", Return), ("Backspace", Back),
+        ("Insert", Insert), ("Delete", Delete), ("Home", Home), ("End", End),
+        ("PageUp", PageUp), ("PageDown", PageDown),
+        ("Left", Left), ("Right", Right), ("Up", Up), ("Down", Down),
+        ("Comma", Comma), ("Period", Period), ("Slash", Slash), ("Backslash", Backslash),
+        ("Semicolon", Semicolon), ("Apostrophe", Apostrophe), ("Grave", Grave),
+        ("LBracket", LBracket), ("RBracket", RBracket), ("Minus", Minus), ("Equals", Equals),
+        ("Plus", Add), ("Subtract", Subtract),
+    ]
+};
+
+/// One or more key combinations that have to be pressed one after another
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    /// Never empty
+    keys: Vec<KeyCombination>,
+}
+
+impl Shortcut {
+
+    /// Creates a chord of multiple key combinations, returns `None` if `keys` is empty
+    pub fn chord(keys: Vec<KeyCombination>) -> Option<Self> {
+        if keys.is_empty() { None } else { Some(Self { keys }) }
+    }
+
+    pub fn get_keys(&self) -> &[KeyCombination] {
+        &self.keys
+    }
+}
+
+impl From<KeyCombination> for Shortcut {
+    fn from(key: KeyCombination) -> Self {
+        Self { keys: vec![key] }
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let keys = self.keys.iter().map(|key| key.to_string()).collect::<Vec<_>>();
+        write!(f, "{}", keys.join(" "))
+    }
+}
+
+impl FromStr for Shortcut {
+    type Err = ShortcutParseError;
+
+    /// Parses a shortcut such as `"Ctrl+S"` or a chord such as `"Ctrl+K Ctrl+S"`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let keys = s.split_whitespace().map(|key| key.parse()).collect::<Result<Vec<_>, _>>()?;
+        Shortcut::chord(keys).ok_or(ShortcutParseError::Empty)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShortcutParseError {
+    /// The string doesn't contain any keys
+    Empty,
+    /// A key combination only consists of modifiers, i.e. `"Ctrl+"`
+    MissingKey(String),
+    UnknownKey(String),
+    UnknownModifier(String),
+}
+
+impl_display!(ShortcutParseError, {
+    Empty => "Shortcut is empty",
+    MissingKey(s) => format!("Key combination \"{}\" has no key", s),
+    UnknownKey(s) => format!("Unknown key: \"{}\"", s),
+    UnknownModifier(s) => format!("Unknown modifier: \"{}\"", s),
+});
+
+/// Where a shortcut is active
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ShortcutScope {
+    /// Active regardless of which node is focused
+    Global,
+    /// Active while the focused node or one of its parents has the given HTML id
+    Id(DomString),
+    /// Active while the focused node or one of its parents has the given CSS class
+    Class(DomString),
+}
+
+impl Default for ShortcutScope {
+    fn default() -> Self {
+        ShortcutScope::Global
+    }
+}
+
+impl ShortcutScope {
+
+    /// Returns whether the `focused_node` is inside of this scope
+    pub(crate) fn is_active<T>(&self, focused_node: Option<NodeId>, arena: &Arena<NodeData<T>>) -> bool {
+
+        let (name, check_id) = match self {
+            ShortcutScope::Global => return true,
+            ShortcutScope::Id(id) => (id, true),
+            ShortcutScope::Class(class) => (class, false),
+        };
+
+        let focused_node = match focused_node.filter(|node_id| arena.node_layout.get(*node_id).is_some()) {
+            Some(s) => s,
+            None => return false,
+        };
+
+        focused_node.ancestors(&arena.node_layout).any(|node_id| {
+            let node_data = &arena.node_data[node_id];
+            if check_id { node_data.ids.contains(name) } else { node_data.classes.contains(name) }
+        })
+    }
+}
+
+/// Binds a shortcut to a callback
+pub struct ShortcutBinding<T> {
+    /// Unique name of the action, used to rebind the shortcut (i.e. `"file.save"`)
+    pub action: String,
+    /// Human-readable description for help screens
+    pub description: String,
+    /// `None` if the action is currently unbound
+    pub shortcut: Option<Shortcut>,
+    pub scope: ShortcutScope,
+    pub callback: Callback<T>,
+}
+
+impl<T> ShortcutBinding<T> {
+
+    /// Creates a global binding without description
+    pub fn new<S: Into<String>>(action: S, shortcut: Shortcut, callback: Callback<T>) -> Self {
+        Self {
+            action: action.into(),
+            description: String::new(),
+            shortcut: Some(shortcut),
+            scope: ShortcutScope::Global,
+            callback,
+        }
+    }
+
+    #[inline]
+    pub fn with_description<S: Into<String>>(self, description: S) -> Self {
+        Self { description: description.into(), .. self }
+    }
+
+    #[inline]
+    pub fn with_scope(self, scope: ShortcutScope) -> Self {
+        Self { scope, .. self }
+    }
+}
+
+impl<T> Clone for ShortcutBinding<T> {
+    fn clone(&self) -> Self {
+        Self {
+            action: self.action.clone(),
+            description: self.description.clone(),
+            shortcut: self.shortcut.clone(),
+            scope: self.scope.clone(),
+            callback: self.callback,
+        }
+    }
+}
+
+impl<T> fmt::Debug for ShortcutBinding<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,
+            "ShortcutBinding {{ action: {:?}, description: {:?}, shortcut: {:?}, scope: {:?}, callback: {:?} }}",
+            self.action, self.description, self.shortcut, self.scope, self.callback,
+        )
+    }
+}
+
+/// All shortcuts of a window, see the module documentation
+pub struct ShortcutRegistry<T> {
+    bindings: Vec<ShortcutBinding<T>>,
+    /// Key combinations of a chord that has been started, but not finished yet
+    pending: Vec<KeyCombination>,
+}
+
+impl<T> Default for ShortcutRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for ShortcutRegistry<T> {
+    fn clone(&self) -> Self {
+        Self {
+            bindings: self.bindings.clone(),
+            pending: self.pending.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for ShortcutRegistry<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ShortcutRegistry {{ bindings: {:?}, pending: {:?} }}", self.bindings, self.pending)
+    }
+}
+
+/// Result of looking up the keys that were pressed so far
+enum ChordMatch<T> {
+    Complete(Callback<T>),
+    /// The keys are the start of at least one chord
+    Prefix,
+    NoMatch,
+}
+
+impl<T> ShortcutRegistry<T> {
+
+    pub fn new() -> Self {
+        Self {
+            bindings: Vec::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn with_binding(mut self, binding: ShortcutBinding<T>) -> Self {
+        self.add_binding(binding);
+        self
+    }
+
+    /// Adds a binding, replacing the binding with the same action (if any)
+    pub fn add_binding(&mut self, binding: ShortcutBinding<T>) {
+        match self.bindings.iter_mut().find(|b| b.action == binding.action) {
+            Some(existing) => *existing = binding,
+            None => self.bindings.push(binding),
+        }
+    }
+
+    /// Removes the binding of the action, returns the removed binding
+    pub fn remove_binding(&mut self, action: &str) -> Option<ShortcutBinding<T>> {
+        let index = self.bindings.iter().position(|b| b.action == action)?;
+        self.pending.clear();
+        Some(self.bindings.remove(index))
+    }
+
+    /// Changes the shortcut of an action (`None` unbinds it).
+    /// Returns `false` if there is no binding for the action.
+    pub fn rebind(&mut self, action: &str, shortcut: Option<Shortcut>) -> bool {
+        match self.bindings.iter_mut().find(|b| b.action == action) {
+            Some(binding) => {
+                binding.shortcut = shortcut;
+                self.pending.clear();
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Returns all bindings in the order they were added, i.e. for displaying a help screen
+    pub fn get_bindings(&self) -> &[ShortcutBinding<T>] {
+        &self.bindings
+    }
+
+    pub fn get_binding(&self, action: &str) -> Option<&ShortcutBinding<T>> {
+        self.bindings.iter().find(|b| b.action == action)
+    }
+
+    /// Returns the pairs of bindings that use the same shortcut in the same scope,
+    /// i.e. to warn the user after rebinding
+    pub fn get_conflicts(&self) -> Vec<(&ShortcutBinding<T>, &ShortcutBinding<T>)> {
+        let mut conflicts = Vec::new();
+        for (index, a) in self.bindings.iter().enumerate() {
+            for b in &self.bindings[(index + 1)..] {
+                let same_shortcut = a.shortcut.is_some() && a.shortcut == b.shortcut;
+                let same_scope = a.scope == b.scope;
+                if same_shortcut && same_scope {
+                    conflicts.push((a, b));
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// Returns the key combinations of a chord that was started, but not finished yet,
+    /// i.e. `[Ctrl+K]` after Ctrl+K was pressed for the chord `Ctrl+K Ctrl+S`
+    pub fn get_pending_chord(&self) -> &[KeyCombination] {
+        &self.pending
+    }
+
+    /// Cancels the current chord, i.e. when the window loses focus
+    pub fn cancel_pending_chord(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Loads `action = shortcut` lines from a config file, see `load_config`
+    pub fn load_config_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), ShortcutConfigError> {
+        let path = path.as_ref();
+        let config = fs::read_to_string(path).map_err(|e| ShortcutConfigError::Io(e, path.to_path_buf()))?;
+        self.load_config(&config)
+    }
+
+    /// Rebinds the actions listed in the config (one `action = shortcut` line per action,
+    /// empty lines and lines starting with `#` are ignored). Actions that aren't listed keep
+    /// their shortcut. Nothing is changed if any line of the config is invalid.
+    pub fn load_config(&mut self, config: &str) -> Result<(), ShortcutConfigError> {
+
+        use self::ShortcutConfigError::*;
+
+        let mut new_shortcuts = Vec::new();
+
+        for (line_index, line) in config.lines().enumerate() {
+
+            let line_number = line_index + 1;
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut split = line.splitn(2, '=');
+            let action = split.next().unwrap_or("").trim();
+            let shortcut = match split.next() {
+                Some(s) => s.trim(),
+                None => return Err(MissingEquals(line_number, line.to_string())),
+            };
+
+            if self.get_binding(action).is_none() {
+                return Err(UnknownAction(line_number, action.to_string()));
+            }
+
+            let shortcut = if shortcut.is_empty() {
+                None
+            } else {
+                Some(shortcut.parse().map_err(|e| InvalidShortcut(line_number, e))?)
+            };
+
+            new_shortcuts.push((action, shortcut));
+        }
+
+        for (action, shortcut) in new_shortcuts {
+            self.rebind(action, shortcut);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the current shortcuts in the format of `load_config`, i.e. to save them after
+    /// the user has rebound them
+    pub fn to_config(&self) -> String {
+        self.bindings.iter().map(|binding| match &binding.shortcut {
+            Some(shortcut) => format!("{} = {}\n", binding.action, shortcut),
+            None => format!("{} =\n", binding.action),
+        }).collect()
+    }
+
+    /// Invoked on every key press: returns the callback of the binding whose shortcut was
+    /// completed by the `pressed_key`. `is_scope_active` decides whether the bindings of a
+    /// scope are currently active.
+    pub(crate) fn handle_key<F: Fn(&ShortcutScope) -> bool>(
+        &mut self,
+        keyboard_state: &KeyboardState,
+        pressed_key: VirtualKeyCode,
+        is_scope_active: F,
+    ) -> Option<Callback<T>> {
+
+        if is_modifier_key(pressed_key) {
+            return None;
+        }
+
+        let combination = KeyCombination::from_keyboard_state(keyboard_state, pressed_key);
+        self.pending.push(combination);
+
+        match self.find_chord(&is_scope_active) {
+            ChordMatch::Complete(callback) => {
+                self.pending.clear();
+                return Some(callback);
+            },
+            ChordMatch::Prefix => return None,
+            ChordMatch::NoMatch => { },
+        }
+
+        // A key that breaks a chord can still start a new shortcut
+        let broke_chord = self.pending.len() > 1;
+        self.pending.clear();
+
+        if broke_chord {
+            self.pending.push(combination);
+            match self.find_chord(&is_scope_active) {
+                ChordMatch::Complete(callback) => {
+                    self.pending.clear();
+                    return Some(callback);
+                },
+                ChordMatch::Prefix => return None,
+                ChordMatch::NoMatch => self.pending.clear(),
+            }
+        }
+
+        None
+    }
+
+    /// Looks up the pending keys in the active bindings. Scoped bindings
+    /// take precedence over global bindings.
+    fn find_chord<F: Fn(&ShortcutScope) -> bool>(&self, is_scope_active: &F) -> ChordMatch<T> {
+
+        let active_bindings = || self.bindings.iter()
+            .filter(|binding| is_scope_active(&binding.scope))
+            .filter_map(|binding| Some((binding.shortcut.as_ref()?, binding)));
+
+        let complete = active_bindings()
+            .filter(|(shortcut, _)| shortcut.keys == self.pending)
+            .min_by_key(|(_, binding)| binding.scope == ShortcutScope::Global);
+
+        if let Some((_, binding)) = complete {
+            return ChordMatch::Complete(binding.callback);
+        }
+
+        if active_bindings().any(|(shortcut, _)| shortcut.keys.starts_with(&self.pending)) {
+            ChordMatch::Prefix
+        } else {
+            ChordMatch::NoMatch
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ShortcutConfigError {
+    Io(IoError, PathBuf),
+    /// Line number and content of a line without `=`
+    MissingEquals(usize, String),
+    /// Line number and name of an action that has no binding
+    UnknownAction(usize, String),
+    InvalidShortcut(usize, ShortcutParseError),
+}
+
+impl_display!(ShortcutConfigError, {
+    Io(err, path_buf) => format!("Could not load \"{}\" - IO error: {}", path_buf.as_path().to_string_lossy(), err),
+    MissingEquals(line, content) => format!("Line {}: expected \"action = shortcut\", got \"{}\"", line, content),
+    UnknownAction(line, action) => format!("Line {}: unknown action \"{}\"", line, action),
+    InvalidShortcut(line, err) => format!("Line {}: {}", line, err),
+});
+
+#[cfg(test)]
+struct Mock;
+
+#[cfg(test)]
+use {app::AppState, callbacks::{CallbackInfo, UpdateScreen, DontRedraw}};
+
+#[cfg(test)]
+fn save(_: &mut AppState<Mock>, _: &mut CallbackInfo<Mock>) -> UpdateScreen { DontRedraw }
+#[cfg(test)]
+fn save_all(_: &mut AppState<Mock>, _: &mut CallbackInfo<Mock>) -> UpdateScreen { DontRedraw }
+#[cfg(test)]
+fn duplicate_line(_: &mut AppState<Mock>, _: &mut CallbackInfo<Mock>) -> UpdateScreen { DontRedraw }
+
+#[cfg(test)]
+fn test_registry() -> ShortcutRegistry<Mock> {
+    ShortcutRegistry::new()
+        .with_binding(ShortcutBinding::new("file.save", "Ctrl+S".parse().unwrap(), Callback(save)))
+        .with_binding(ShortcutBinding::new("file.save_all", "Ctrl+K Ctrl+S".parse().unwrap(), Callback(save_all)))
+        .with_binding(ShortcutBinding::new("editor.duplicate_line", "Ctrl+S".parse().unwrap(), Callback(duplicate_line))
+            .with_scope(ShortcutScope::Id("editor".into())))
+}
+
+#[cfg(test)]
+fn ctrl_down() -> KeyboardState {
+    let mut keyboard_state = KeyboardState::default();
+    keyboard_state.ctrl_down = true;
+    keyboard_state
+}
+
+#[test]
+fn test_parse_shortcut() {
+    use glium::glutin::VirtualKeyCode::*;
+    let shortcut: Shortcut = "ctrl+shift+F5".parse().unwrap();
+    assert_eq!(shortcut, Shortcut::from(KeyCombination::new(F5).with_ctrl().with_shift()));
+    assert_eq!(shortcut.to_string(), "Ctrl+Shift+F5");
+    assert_eq!("Ctrl+K  Ctrl+1".parse::<Shortcut>().unwrap().to_string(), "Ctrl+K Ctrl+1");
+
+    assert_eq!("".parse::<Shortcut>(), Err(ShortcutParseError::Empty));
+    assert_eq!("Ctrl+".parse::<Shortcut>(), Err(ShortcutParseError::MissingKey("Ctrl+".into())));
+    assert_eq!("Ctrl+Shift".parse::<Shortcut>(), Err(ShortcutParseError::MissingKey("Ctrl+Shift".into())));
+    assert_eq!("Ctrl+Foo".parse::<Shortcut>(), Err(ShortcutParseError::UnknownKey("Foo".into())));
+    assert_eq!("Hyper+S".parse::<Shortcut>(), Err(ShortcutParseError::UnknownModifier("Hyper".into())));
+}
+
+#[test]
+fn test_shortcut_exact_modifiers() {
+    use glium::glutin::VirtualKeyCode::*;
+    let mut registry = test_registry();
+
+    assert_eq!(registry.handle_key(&ctrl_down(), S, |_| false), None);
+    assert_eq!(registry.handle_key(&ctrl_down(), S, |scope| *scope == ShortcutScope::Global), Some(Callback(save)));
+
+    let mut ctrl_shift = ctrl_down();
+    ctrl_shift.shift_down = true;
+    assert_eq!(registry.handle_key(&ctrl_shift, S, |_| true), None);
+    assert_eq!(registry.handle_key(&KeyboardState::default(), S, |_| true), None);
+}
+
+#[test]
+fn test_shortcut_scopes() {
+    use glium::glutin::VirtualKeyCode::S;
+    let mut registry = test_registry();
+    // The scoped binding wins over the global binding with the same keys
+    assert_eq!(registry.handle_key(&ctrl_down(), S, |_| true), Some(Callback(duplicate_line)));
+}
+
+#[test]
+fn test_shortcut_chords() {
+    use glium::glutin::VirtualKeyCode::*;
+    let mut registry = test_registry();
+    let is_global = |scope: &ShortcutScope| *scope == ShortcutScope::Global;
+
+    // Modifier keys don't break the chord
+    assert_eq!(registry.handle_key(&ctrl_down(), LControl, is_global), None);
+    assert_eq!(registry.handle_key(&ctrl_down(), K, is_global), None);
+    assert_eq!(registry.get_pending_chord(), &[KeyCombination::new(K).with_ctrl()]);
+    assert_eq!(registry.handle_key(&ctrl_down(), LControl, is_global), None);
+    assert_eq!(registry.handle_key(&ctrl_down(), S, is_global), Some(Callback(save_all)));
+    assert!(registry.get_pending_chord().is_empty());
+
+    // A key that breaks the chord starts a new shortcut
+    assert_eq!(registry.handle_key(&ctrl_down(), K, is_global), None);
+    assert_eq!(registry.handle_key(&KeyboardState::default(), A, is_global), None);
+    assert!(registry.get_pending_chord().is_empty());
+    assert_eq!(registry.handle_key(&ctrl_down(), K, is_global), None);
+    assert_eq!(registry.handle_key(&ctrl_down(), K, is_global), None);
+    assert_eq!(registry.handle_key(&ctrl_down(), S, is_global), Some(Callback(save_all)));
+}
+
+#[test]
+fn test_shortcut_config() {
+    let mut registry = test_registry();
+
+    registry.load_config("# comment\n\nfile.save = Ctrl+Alt+S\neditor.duplicate_line =\n").unwrap();
+    assert_eq!(registry.to_config(), "file.save = Ctrl+Alt+S\nfile.save_all = Ctrl+K Ctrl+S\neditor.duplicate_line =\n");
+
+    // Invalid configs don't change anything
+    assert!(registry.load_config("file.save = Ctrl+X\nfile.open = Ctrl+O").is_err());
+    assert!(registry.load_config("file.save Ctrl+X").is_err());
+    assert!(registry.load_config("file.save = Ctrl+Foo").is_err());
+    assert_eq!(registry.get_binding("file.save").unwrap().shortcut, Some("Ctrl+Alt+S".parse().unwrap()));
+
+    registry.rebind("file.save_all", Some("Ctrl+Alt+S".parse().unwrap()));
+    let conflicts = registry.get_conflicts();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!((conflicts[0].0.action.as_str(), conflicts[0].1.action.as_str()), ("file.save", "file.save_all"));
+}
@@ -74,6 +74,13 @@ impl<T> StackCheckedPointer<T> {
         callback(&mut *(self.internal as *mut U), app_state_no_data, window_event)
     }
 
+    /// **UNSAFE**: Returns a shared reference to the value, i.e. to read the value in
+    /// an `IFrameClosureCallback` while the application data is locked. Same as for
+    /// `invoke_mut`, `U` **must be the same type** that the pointer was created with.
+    pub unsafe fn get<U: Sized>(&self) -> &U {
+        &*(self.internal as *const U)
+    }
+
     pub unsafe fn invoke_mut_iframe<U: Sized>(
         &self,
         callback: fn(&mut U, LayoutInfo<T>, HidpiAdjustedBounds) -> Dom<T>,
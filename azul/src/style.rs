@@ -22,6 +22,7 @@ pub(crate) struct HtmlCascadeInfo<'a, T: 'a> {
     pub is_hovered_over: bool,
     pub is_focused: bool,
     pub is_active: bool,
+    pub is_drag_over: bool,
 }
 
 impl<'a, T: 'a> fmt::Debug for HtmlCascadeInfo<'a, T> {
@@ -33,6 +34,7 @@ impl<'a, T: 'a> fmt::Debug for HtmlCascadeInfo<'a, T> {
             is_hovered_over: {:?}, \
             is_focused: {:?}, \
             is_active: {:?}, \
+            is_drag_over: {:?}, \
          }}",
             self.node_data,
             self.index_in_parent,
@@ -40,6 +42,7 @@ impl<'a, T: 'a> fmt::Debug for HtmlCascadeInfo<'a, T> {
             self.is_hovered_over,
             self.is_focused,
             self.is_active,
+            self.is_drag_over,
          )
     }
 }
@@ -175,7 +178,8 @@ fn construct_html_cascade_tree<'a, T>(
     node_depths_sorted: &[(usize, NodeId)],
     focused_item: Option<NodeId>,
     hovered_items: &BTreeMap<NodeId, HitTestItem>,
-    is_mouse_down: bool
+    is_mouse_down: bool,
+    drop_target: Option<NodeId>,
 ) -> NodeDataContainer<HtmlCascadeInfo<'a, T>> {
 
     let mut nodes = (0..node_hierarchy.len()).map(|_| HtmlCascadeInfo {
@@ -185,6 +189,7 @@ fn construct_html_cascade_tree<'a, T>(
         is_hovered_over: false,
         is_active: false,
         is_focused: false,
+        is_drag_over: false,
     }).collect::<Vec<_>>();
 
     for (_depth, parent_id) in node_depths_sorted {
@@ -200,6 +205,7 @@ fn construct_html_cascade_tree<'a, T>(
             is_hovered_over: is_parent_hovered_over,
             is_active: is_parent_hovered_over && is_mouse_down,
             is_focused: focused_item == Some(*parent_id),
+            is_drag_over: drop_target == Some(*parent_id),
         };
 
         nodes[parent_id.index()] = parent_html_matcher;
@@ -213,6 +219,7 @@ fn construct_html_cascade_tree<'a, T>(
                 is_hovered_over: is_child_hovered_over,
                 is_active: is_child_hovered_over && is_mouse_down,
                 is_focused: focused_item == Some(child_id),
+                is_drag_over: drop_target == Some(child_id),
             };
 
             nodes[child_id.index()] = child_html_matcher;
@@ -229,7 +236,7 @@ pub struct HoverGroup {
     /// Whether any property in the hover group will trigger a re-layout.
     /// This is important for creating
     pub affects_layout: bool,
-    /// Whether this path ends with `:active` or with `:hover`
+    /// Whether this path ends with `:active`, `:hover` or `:drag-over`
     pub active_or_hover: ActiveHover,
 }
 
@@ -238,20 +245,22 @@ pub struct HoverGroup {
 pub enum ActiveHover {
     Active,
     Hover,
+    DragOver,
 }
 
-/// Returns all CSS paths that have a `:hover` or `:active` in their path
+/// Returns all CSS paths that have a `:hover`, `:active` or `:drag-over` in their path
 /// (since they need to have tags for hit-testing)
 fn collect_hover_groups(css: &Css) -> BTreeMap<CssPath, HoverGroup> {
     use azul_css::{CssPathSelector::*, CssPathPseudoSelector::*};
 
     let hover_rule = PseudoSelector(Hover);
     let active_rule = PseudoSelector(Active);
+    let drag_over_rule = PseudoSelector(DragOver);
 
-    // Filter out all :hover and :active rules, since we need to create tags
+    // Filter out all :hover, :active and :drag-over rules, since we need to create tags
     // for them after the main CSS styling has been done
     css.rules().filter_map(|rule_block| {
-        let pos = rule_block.path.selectors.iter().position(|x| *x == hover_rule || *x == active_rule)?;
+        let pos = rule_block.path.selectors.iter().position(|x| *x == hover_rule || *x == active_rule || *x == drag_over_rule)?;
         if rule_block.declarations.is_empty() {
             return None;
         }
@@ -259,6 +268,7 @@ fn collect_hover_groups(css: &Css) -> BTreeMap<CssPath, HoverGroup> {
         let active_or_hover = match rule_block.path.selectors.get(pos)? {
             PseudoSelector(Hover) => ActiveHover::Hover,
             PseudoSelector(Active) => ActiveHover::Active,
+            PseudoSelector(DragOver) => ActiveHover::DragOver,
             _ => return None,
         };
 
@@ -344,6 +354,15 @@ fn selector_group_matches<'a, T>(selectors: &[&CssPathSelector], html_node: &Htm
             PseudoSelector(CssPathPseudoSelector::Focus) => {
                 if !html_node.is_focused { return false; }
             },
+            PseudoSelector(CssPathPseudoSelector::DragOver) => {
+                if !html_node.is_drag_over { return false; }
+            },
+            PseudoSelector(CssPathPseudoSelector::Checked) => {
+                if !html_node.node_data.is_checked { return false; }
+            },
+            PseudoSelector(CssPathPseudoSelector::Disabled) => {
+                if !html_node.node_data.is_disabled { return false; }
+            },
             DirectChildren | Children => {
                 panic!("Unreachable: DirectChildren or Children in CSS path!");
             },
@@ -360,6 +379,7 @@ pub(crate) fn match_dom_selectors<T>(
     pending_focus_target: &mut Option<FocusTarget>,
     hovered_nodes: &BTreeMap<NodeId, HitTestItem>,
     is_mouse_down: bool,
+    drop_target: Option<NodeId>,
 ) -> UiDescription<T> {
 
     use azul_css::CssDeclaration;
@@ -373,6 +393,7 @@ pub(crate) fn match_dom_selectors<T>(
         *focused_node,
         hovered_nodes,
         is_mouse_down,
+        drop_target,
     );
 
     // Update the current focused field if the callbacks of the
@@ -531,6 +552,7 @@ fn test_case_issue_93() {
         None,
         &BTreeMap::new(),
         false,
+        false,
     );
 
     //  rules: [
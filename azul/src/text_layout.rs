@@ -19,6 +19,9 @@ const DEFAULT_LINE_HEIGHT: f32 = 1.0;
 const DEFAULT_WORD_SPACING: f32 = 1.0;
 const DEFAULT_LETTER_SPACING: f32 = 0.0;
 const DEFAULT_TAB_WIDTH: f32 = 4.0;
+/// Approximate descent of a run (relative to its font size), so that the
+/// hit-testing rectangles of a run also cover the descenders of the glyphs
+const DEFAULT_DESCENT: f32 = 0.25;
 
 /// Text broken up into `Tab`, `Word()`, `Return` characters
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -43,6 +46,19 @@ impl Words {
     pub fn get_char(&self, idx: usize) -> Option<char> {
         self.internal_chars.get(idx).cloned()
     }
+
+    /// Appends the words of `other` to the end of this text, used to join the
+    /// runs of a paragraph with inline spans into a single paragraph
+    pub fn append(&mut self, other: &Words) {
+        let offset = self.internal_chars.len();
+        self.items.extend(other.items.iter().map(|word| Word {
+            start: word.start + offset,
+            end: word.end + offset,
+            word_type: word.word_type,
+        }));
+        self.internal_str.push_str(&other.internal_str);
+        self.internal_chars.extend(other.internal_chars.iter().cloned());
+    }
 }
 
 /// Section of a certain type
@@ -81,6 +97,52 @@ pub struct ScaledWords {
     pub space_advance_px: f32,
     /// Glyph index of the space character
     pub space_codepoint: u32,
+    /// Sections of the paragraph that were scaled with their own font or font size
+    /// (i.e. inline spans). Words that are not part of any run use `self.font_size_px`.
+    pub runs: Vec<ScaledRun>,
+}
+
+/// Styled run of words inside of a paragraph, i.e. the text of an inline span
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ScaledRun {
+    /// Index of the first word of this run in `ScaledWords::items`
+    pub start: usize,
+    /// Index of the word after the last word of this run in `ScaledWords::items`
+    pub end: usize,
+    /// Font size (in pixels) that the words of this run were scaled to
+    pub font_size_px: f32,
+}
+
+impl ScaledWords {
+
+    /// Appends the words of another run (usually shaped with a different font
+    /// or font size) to the end of this paragraph.
+    ///
+    /// The spacing of the paragraph (space width, line height, tab width)
+    /// is still determined by the font of the first run.
+    pub fn append_run(&mut self, run: ScaledWords) {
+        let start = self.items.len();
+        self.longest_word_width = self.longest_word_width.max(run.longest_word_width);
+        self.items.extend(run.items.into_iter());
+        self.runs.push(ScaledRun {
+            start,
+            end: self.items.len(),
+            font_size_px: run.font_size_px,
+        });
+    }
+
+    /// Returns the font size (in pixels) of the word at `word_idx`
+    pub fn get_word_font_size(&self, word_idx: usize) -> f32 {
+        self.runs.iter()
+            .find(|run| word_idx >= run.start && word_idx < run.end)
+            .map(|run| run.font_size_px)
+            .unwrap_or(self.font_size_px)
+    }
+
+    /// Returns whether any run has a different font size than the rest of the paragraph
+    pub fn has_mixed_font_sizes(&self) -> bool {
+        self.runs.iter().any(|run| run.font_size_px != self.font_size_px)
+    }
 }
 
 /// Word that is scaled (to a font / font instance), but not yet positioned
@@ -125,6 +187,23 @@ pub struct WordPositions {
     pub content_size: LayoutSize,
 }
 
+/// Metrics of a laid-out text node, see `CallbackInfo::get_text_metrics`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextMetrics {
+    /// Font size (in pixels) that was used to lay out the text
+    pub font_size_px: f32,
+    /// Width and height of the laid-out text block
+    pub content_size: LayoutSize,
+    /// How many words are in the text
+    pub number_of_words: usize,
+    /// How many (virtual) lines the text was broken into
+    pub number_of_lines: usize,
+    /// Width of the longest word, i.e. the minimum width the text can be wrapped to
+    pub longest_word_width: f32,
+    /// Position of each word, relative to the top left of the text block
+    pub word_positions: Vec<LayoutPoint>,
+}
+
 /// Width and height of the scrollbars at the side of the text field.
 ///
 /// This information is necessary in order to reserve space at
@@ -409,10 +488,14 @@ pub fn words_to_scaled_words(
 
         let is_space = glyph_info.codepoint == space_codepoint;
         if is_space {
-            shaped_word_positions.push(current_word_positions.clone());
-            shaped_word_infos.push(current_word_infos.clone());
-            current_word_positions.clear();
-            current_word_infos.clear();
+            // Leading or consecutive spaces (i.e. the " or " of an inline span) would
+            // otherwise push empty words, which shifts the glyphs of all following words
+            if !current_word_positions.is_empty() {
+                shaped_word_positions.push(current_word_positions.clone());
+                shaped_word_infos.push(current_word_infos.clone());
+                current_word_positions.clear();
+                current_word_infos.clear();
+            }
         } else {
             current_word_positions.push(glyph_position);
             current_word_infos.push(glyph_info);
@@ -445,7 +528,9 @@ pub fn words_to_scaled_words(
                 glyph_positions: hb_glyph_positions,
                 word_width: hb_word_width,
             })
-        }).collect();
+        }).collect::<Vec<ScaledWord>>();
+
+    let runs = vec![ScaledRun { start: 0, end: scaled_words.len(), font_size_px }];
 
     ScaledWords {
         items: scaled_words,
@@ -453,11 +538,15 @@ pub fn words_to_scaled_words(
         space_advance_px,
         space_codepoint,
         font_size_px,
+        runs,
     }
 }
 
 /// Positions the words on the screen (does not layout any glyph positions!), necessary for estimating
 /// the intrinsic width + height of the text content.
+///
+/// If the `scaled_words` contain runs with different font sizes, each line is
+/// as high as the largest run on that line (but at least as high as `font_size_px`).
 pub fn position_words(
     words: &Words,
     scaled_words: &ScaledWords,
@@ -476,6 +565,9 @@ pub fn position_words(
 
     let mut line_breaks = Vec::new();
     let mut word_positions = Vec::new();
+    // Line number of each positioned word, necessary to correct the
+    // line heights for paragraphs with mixed font sizes
+    let mut word_line_numbers = Vec::new();
 
     let mut line_number = 0;
     let mut line_caret_x = 0.0;
@@ -550,6 +642,7 @@ pub fn position_words(
         if !is_line_break {
             let line_caret_y = get_line_y_position(line_number, font_size_px, line_height_px);
             word_positions.push(LayoutPoint::new(line_caret_x, line_caret_y));
+            word_line_numbers.push(line_number);
         }
 
         // Correct and advance the line caret position
@@ -565,6 +658,7 @@ pub fn position_words(
         if is_line_break {
             let line_caret_y = get_line_y_position(line_number, font_size_px, line_height_px);
             word_positions.push(LayoutPoint::new(line_caret_x, line_caret_y));
+            word_line_numbers.push(line_number);
             // important! - if the word is pushed onto the next line, the caret has to be
             // advanced by that words width!
             line_caret_x += word_advance_x;
@@ -615,7 +709,21 @@ pub fn position_words(
     let number_of_words = current_word_idx + 1;
 
     let longest_line_width = line_breaks.iter().map(|(_word_idx, line_length)| *line_length).fold(0.0_f32, f32::max);
-    let content_size_y = get_line_y_position(line_number, font_size_px, line_height_px);
+    let mut content_size_y = get_line_y_position(line_number, font_size_px, line_height_px);
+
+    // Push the baseline of every line down by the largest font size on that line
+    if scaled_words.has_mixed_font_sizes() {
+        let mut line_font_sizes = vec![font_size_px; number_of_lines];
+        for (word_idx, line) in word_line_numbers.iter().enumerate() {
+            line_font_sizes[*line] = line_font_sizes[*line].max(scaled_words.get_word_font_size(word_idx));
+        }
+        let line_baselines = get_line_baselines(&line_font_sizes, line_height_px);
+        for (word_position, line) in word_positions.iter_mut().zip(word_line_numbers.iter()) {
+            word_position.y = line_baselines[*line];
+        }
+        content_size_y = line_baselines.last().cloned().unwrap_or(content_size_y);
+    }
+
     let content_size_x = text_layout_options.max_horizontal_width.unwrap_or(longest_line_width);
     let content_size = LayoutSize::new(content_size_x, content_size_y);
 
@@ -751,6 +859,69 @@ pub fn get_line_y_position(line_number: usize, font_size_px: f32, line_height_px
     ((font_size_px + line_height_px) * line_number as f32) + font_size_px
 }
 
+/// For lines with different font sizes (**NOTE: 0-indexed!**), calculates the Y
+/// position of the bottom left corner of each line
+pub fn get_line_baselines(line_font_sizes: &[f32], line_height_px: f32) -> Vec<f32> {
+    let mut baselines = Vec::with_capacity(line_font_sizes.len());
+    let mut current_y = 0.0;
+    for (line_number, font_size_px) in line_font_sizes.iter().enumerate() {
+        if line_number != 0 {
+            current_y += line_height_px;
+        }
+        current_y += font_size_px;
+        baselines.push(current_y);
+    }
+    baselines
+}
+
+/// Splits the laid out glyphs of a paragraph into the glyphs of each run
+/// (in the order of `scaled_words.runs`), so that every run can be drawn
+/// with its own font instance and text color
+pub fn split_glyphs_into_runs<'a>(glyphs: &'a [GlyphInstance], scaled_words: &ScaledWords) -> Vec<&'a [GlyphInstance]> {
+    let mut glyph_idx = 0;
+    scaled_words.runs.iter().map(|run| {
+        let glyph_count: usize = scaled_words.items[run.start..run.end].iter().map(|w| w.glyph_infos.len()).sum();
+        let start = glyph_idx.min(glyphs.len());
+        let end = (glyph_idx + glyph_count).min(glyphs.len());
+        glyph_idx += glyph_count;
+        &glyphs[start..end]
+    }).collect()
+}
+
+/// Returns the rectangles that a run of words occupies (one rectangle per line),
+/// given the already laid out glyphs of the run (see `split_glyphs_into_runs`).
+/// Used for hit-testing inline spans, for example links inside of a paragraph.
+pub fn get_run_bounds(run: &ScaledRun, run_glyphs: &[GlyphInstance], scaled_words: &ScaledWords) -> Vec<LayoutRect> {
+
+    let mut line_bounds = Vec::<LayoutRect>::new();
+    let mut glyph_idx = 0;
+
+    for scaled_word in &scaled_words.items[run.start..run.end] {
+
+        let first_glyph = match run_glyphs.get(glyph_idx) {
+            Some(s) => s,
+            None => break,
+        };
+
+        glyph_idx += scaled_word.glyph_infos.len();
+
+        let word_bounds = LayoutRect::new(
+            LayoutPoint::new(first_glyph.point.x, first_glyph.point.y - run.font_size_px),
+            LayoutSize::new(scaled_word.word_width, run.font_size_px * (1.0 + DEFAULT_DESCENT)),
+        );
+
+        // Words on the same line are merged, so that the space between them is also hit-testable
+        match line_bounds.last_mut() {
+            Some(last) if last.origin.y == word_bounds.origin.y && last.origin.x <= word_bounds.origin.x => {
+                *last = last.union(&word_bounds);
+            },
+            _ => line_bounds.push(word_bounds),
+        }
+    }
+
+    line_bounds
+}
+
 #[derive(Debug, Copy, Clone, PartialOrd, PartialEq)]
 enum LineCaretIntersection {
     /// OK: Caret does not interset any elements
@@ -1072,6 +1243,169 @@ fn test_get_line_y_position() {
     assert_eq!(get_line_y_position(2, 20.0, 5.0), 70.0);
 }
 
+#[test]
+fn test_get_line_baselines() {
+
+    // Same font size on every line: same as get_line_y_position
+    assert_eq!(get_line_baselines(&[20.0, 20.0, 20.0], 5.0), vec![20.0, 45.0, 70.0]);
+
+    // lines:
+    // 0 - height 20, padding 5 = 20.0
+    // 1 - height 40 (large span), padding 5 = 65.0 ( = 20 + 5 + 40)
+    // 2 - height 20, padding 5 = 90.0 ( = 20 + 5 + 40 + 5 + 20)
+    assert_eq!(get_line_baselines(&[20.0, 40.0, 20.0], 5.0), vec![20.0, 65.0, 90.0]);
+    assert_eq!(get_line_baselines(&[], 5.0), Vec::<f32>::new());
+}
+
+#[test]
+fn test_words_append() {
+
+    let mut words = split_text_into_words("Click ");
+    words.append(&split_text_into_words("this link"));
+
+    assert_eq!(words.get_str(), "Click this link");
+    assert_eq!(words.items, vec![
+        Word { start: 0,    end: 5,     word_type: WordType::Word   }, // "Click"
+        Word { start: 5,    end: 6,     word_type: WordType::Space  }, // " "
+        Word { start: 6,    end: 10,    word_type: WordType::Word   }, // "this"
+        Word { start: 10,   end: 11,    word_type: WordType::Space  }, // " "
+        Word { start: 11,   end: 15,    word_type: WordType::Word   }, // "link"
+    ]);
+    assert_eq!(words.get_substr(&words.items[4]), "link");
+}
+
+#[test]
+fn test_scaled_words_runs() {
+
+    let mut scaled_words = ScaledWords {
+        font_size_px: 16.0,
+        items: Vec::new(),
+        longest_word_width: 0.0,
+        space_advance_px: 4.0,
+        space_codepoint: 0,
+        runs: vec![ScaledRun { start: 0, end: 0, font_size_px: 16.0 }],
+    };
+
+    assert!(!scaled_words.has_mixed_font_sizes());
+
+    scaled_words.append_run(ScaledWords {
+        font_size_px: 32.0,
+        items: Vec::new(),
+        longest_word_width: 50.0,
+        space_advance_px: 8.0,
+        space_codepoint: 0,
+        runs: Vec::new(),
+    });
+
+    assert!(scaled_words.has_mixed_font_sizes());
+    assert_eq!(scaled_words.longest_word_width, 50.0);
+    assert_eq!(scaled_words.runs[1], ScaledRun { start: 0, end: 0, font_size_px: 32.0 });
+    assert_eq!(scaled_words.space_advance_px, 4.0);
+    assert_eq!(scaled_words.get_word_font_size(0), 16.0);
+}
+
+#[cfg(test)]
+fn test_scaled_word(glyph_count: usize, word_width: f32) -> ScaledWord {
+    ScaledWord {
+        glyph_infos: (0..glyph_count).map(|_| unsafe { ::std::mem::zeroed() }).collect(),
+        glyph_positions: (0..glyph_count).map(|_| unsafe { ::std::mem::zeroed() }).collect(),
+        word_width,
+    }
+}
+
+#[cfg(test)]
+fn test_mixed_scaled_words() -> ScaledWords {
+    // "ab" at 16px, followed by "cd" at 32px
+    ScaledWords {
+        font_size_px: 16.0,
+        items: vec![test_scaled_word(2, 10.0), test_scaled_word(2, 10.0)],
+        longest_word_width: 10.0,
+        space_advance_px: 4.0,
+        space_codepoint: 0,
+        runs: vec![
+            ScaledRun { start: 0, end: 1, font_size_px: 16.0 },
+            ScaledRun { start: 1, end: 2, font_size_px: 32.0 },
+        ],
+    }
+}
+
+#[test]
+fn test_words_to_scaled_words_leading_space() {
+
+    let font_bytes = include_bytes!("../../assets/fonts/KoHo-Light.ttf");
+
+    let scaled_words = words_to_scaled_words(&split_text_into_words(" or "), font_bytes, 0, 16.0);
+    assert_eq!(scaled_words.items.len(), 1);
+    assert_eq!(scaled_words.items[0].glyph_infos.len(), 2);
+
+    let scaled_words = words_to_scaled_words(&split_text_into_words("a  bc"), font_bytes, 0, 16.0);
+    assert_eq!(scaled_words.items.len(), 2);
+    assert_eq!(scaled_words.items[1].glyph_infos.len(), 2);
+}
+
+#[test]
+fn test_position_words_mixed_font_sizes() {
+
+    let words = split_text_into_words("ab cd");
+    let scaled_words = test_mixed_scaled_words();
+
+    // Both words on one line: the line is as high as the larger run
+    let word_positions = position_words(&words, &scaled_words, &TextLayoutOptions::default(), 16.0);
+    assert_eq!(word_positions.number_of_lines, 1);
+    assert_eq!(word_positions.word_positions, vec![LayoutPoint::new(0.0, 32.0), LayoutPoint::new(14.0, 32.0)]);
+    assert_eq!(word_positions.content_size.height, 32.0);
+
+    // "cd" wraps onto the second line, only the second line is pushed down by the larger run
+    let options = TextLayoutOptions { max_horizontal_width: Some(15.0), .. Default::default() };
+    let word_positions = position_words(&words, &scaled_words, &options, 16.0);
+    assert_eq!(word_positions.number_of_lines, 2);
+    assert_eq!(word_positions.word_positions, vec![LayoutPoint::new(0.0, 16.0), LayoutPoint::new(0.0, 52.0)]);
+    assert_eq!(word_positions.content_size.height, 52.0);
+}
+
+#[test]
+fn test_split_glyphs_into_runs() {
+
+    let scaled_words = test_mixed_scaled_words();
+    let glyphs = (0..4).map(|index| GlyphInstance { index, point: LayoutPoint::new(index as f32 * 5.0, 16.0) }).collect::<Vec<_>>();
+
+    let runs = split_glyphs_into_runs(&glyphs, &scaled_words);
+    assert_eq!(runs.len(), 2);
+    assert_eq!(runs[0], &glyphs[0..2]);
+    assert_eq!(runs[1], &glyphs[2..4]);
+
+    // Missing glyphs result in shorter (or empty) runs instead of a panic
+    let runs = split_glyphs_into_runs(&glyphs[..1], &scaled_words);
+    assert_eq!(runs[0], &glyphs[0..1]);
+    assert!(runs[1].is_empty());
+}
+
+#[test]
+fn test_get_run_bounds() {
+
+    // The 32px run contains "cd", "ef" and "gh", where "gh" wrapped onto the second line
+    let mut scaled_words = test_mixed_scaled_words();
+    scaled_words.items.push(test_scaled_word(2, 10.0));
+    scaled_words.items.push(test_scaled_word(2, 10.0));
+    scaled_words.runs[1].end = 4;
+
+    let run_glyphs = [
+        GlyphInstance { index: 0, point: LayoutPoint::new(0.0, 32.0) },
+        GlyphInstance { index: 1, point: LayoutPoint::new(5.0, 32.0) },
+        GlyphInstance { index: 2, point: LayoutPoint::new(14.0, 32.0) },
+        GlyphInstance { index: 3, point: LayoutPoint::new(19.0, 32.0) },
+        GlyphInstance { index: 4, point: LayoutPoint::new(0.0, 68.0) },
+        GlyphInstance { index: 5, point: LayoutPoint::new(5.0, 68.0) },
+    ];
+
+    // Words on the same line are merged into one rectangle, including the space between them
+    let bounds = get_run_bounds(&scaled_words.runs[1], &run_glyphs, &scaled_words);
+    assert_eq!(bounds, vec![
+        LayoutRect::new(LayoutPoint::new(0.0, 0.0), LayoutSize::new(24.0, 40.0)),
+        LayoutRect::new(LayoutPoint::new(0.0, 36.0), LayoutSize::new(10.0, 40.0)),
+    ]);
+}
+
 // Scenario 1:
 //
 // +---------+
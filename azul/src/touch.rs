@@ -0,0 +1,354 @@
+//! Touch input and gesture recognition
+//!
+//! Touch screens send one event per finger and frame (`On::TouchStart`, `On::TouchMove`,
+//! `On::TouchEnd`, `On::TouchCancel`). From these, the window recognizes the gestures
+//! `On::Tap`, `On::LongPress`, `On::Pan`, `On::Pinch` and `On::Swipe`. The current touch
+//! points and the gestures of the current event are stored in the `TouchState` of the window:
+//!
+//! ```no_run,ignore
+//! fn on_pinch(app_state: &mut AppState<MyApp>, event: &mut CallbackInfo<MyApp>) -> UpdateScreen {
+//!     let touch_state = app_state.windows[event.window_id].get_touch_state().clone();
+//!     for gesture in &touch_state.gestures {
+//!         if let Gesture::Pinch { scale, .. } = gesture {
+//!             app_state.data.modify(|state| state.zoom *= scale)?;
+//!         }
+//!     }
+//!     Redraw
+//! }
+//! ```
+//!
+//! The first finger that touches the screen also moves the cursor, so that touch events and
+//! gestures are sent to the node under the first finger (and its parents). Panning with a
+//! single finger scrolls the scrollable nodes under the finger.
+
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+use glium::glutin::{TouchPhase, dpi::LogicalPosition};
+
+/// How far (in logical pixels) a finger can move before a tap or long press becomes a pan
+const TAP_SLOP: f64 = 10.0;
+/// How long a finger has to rest on the screen for a long press
+pub(crate) const LONG_PRESS_DURATION: Duration = Duration::from_millis(500);
+/// Minimum distance (in logical pixels) of a swipe
+const SWIPE_MIN_DISTANCE: f64 = 50.0;
+/// Minimum average speed (in logical pixels per second) of a swipe
+const SWIPE_MIN_VELOCITY: f64 = 300.0;
+
+/// A single finger (or pen) that touches the screen
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TouchPoint {
+    /// Unique ID of the finger, stays the same from `TouchStart` until `TouchEnd`
+    pub id: u64,
+    /// Current position, relative to the top left of the window
+    pub position: LogicalPosition,
+    /// Position where the finger touched the screen
+    pub start_position: LogicalPosition,
+    /// Pressure of the touch from 0.0 to 1.0, `None` if the device or the
+    /// windowing backend doesn't report the pressure
+    pub pressure: Option<f64>,
+}
+
+/// Fingers that currently touch the window
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TouchState {
+    /// All current touches, by finger ID
+    pub touches: BTreeMap<u64, TouchPoint>,
+    /// ID of the first finger that touched the screen, this finger moves the cursor
+    pub primary_touch: Option<u64>,
+    /// The finger of the current touch event - unlike `touches`, this is also
+    /// set for `TouchEnd` and `TouchCancel`, when the finger was already removed
+    pub changed_touch: Option<TouchPoint>,
+    /// Gestures that were recognized from the current touch event
+    pub gestures: Vec<Gesture>,
+}
+
+impl TouchState {
+
+    /// Returns the touch point of the first finger that touched the screen
+    pub fn get_primary_touch(&self) -> Option<&TouchPoint> {
+        self.touches.get(&self.primary_touch?)
+    }
+
+    /// Returns whether any finger touches the screen
+    pub fn is_touching(&self) -> bool {
+        !self.touches.is_empty()
+    }
+}
+
+/// A recognized touch gesture. All positions are relative to the top left of the window.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Gesture {
+    /// A finger touched the screen shortly without moving
+    Tap { position: LogicalPosition },
+    /// A finger rested on the screen for a longer time without moving
+    LongPress { position: LogicalPosition },
+    /// A single finger moved by `delta` (x, y) since the last `Pan`
+    Pan { position: LogicalPosition, delta: (f64, f64) },
+    /// Two fingers moved, `scale` is the change of the distance between them since the last
+    /// `Pinch` (> 1.0 if the fingers moved apart), `center` is the point between the fingers
+    Pinch { center: LogicalPosition, scale: f64 },
+    /// A single finger quickly moved in `direction` and was lifted,
+    /// `velocity` is the average speed in logical pixels per second
+    Swipe { direction: SwipeDirection, velocity: f64 },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// Finger that is tracked by the `GestureRecognizer`
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct TrackedTouch {
+    start_position: LogicalPosition,
+    start_time: Instant,
+    position: LogicalPosition,
+    /// Moved further than the `TAP_SLOP`, so the touch can't be a tap or long press anymore
+    moved: bool,
+}
+
+/// Recognizes gestures from the touch events of a window
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct GestureRecognizer {
+    touches: BTreeMap<u64, TrackedTouch>,
+    /// Distance between the two fingers at the last `Pinch`, if two fingers touch the screen
+    pinch_distance: Option<f64>,
+    /// Set when more than one finger touched the screen or the long press was already
+    /// recognized - no tap, long press or swipe until all fingers are lifted
+    gesture_finished: bool,
+}
+
+impl GestureRecognizer {
+
+    /// Updates the tracked fingers and returns the gestures recognized from the touch event.
+    ///
+    /// Long presses are recognized on the first touch event after the `LONG_PRESS_DURATION`
+    /// or when the finger is lifted. Since a resting finger doesn't necessarily send any events,
+    /// the window repeats the last touch event when a long press is pending, see
+    /// `get_pending_long_press`.
+    pub(crate) fn on_touch(&mut self, id: u64, phase: TouchPhase, position: LogicalPosition, time: Instant) -> Vec<Gesture> {
+
+        let mut gestures = Vec::new();
+
+        match phase {
+            TouchPhase::Started => {
+                self.touches.insert(id, TrackedTouch { start_position: position, start_time: time, position, moved: false });
+                if self.touches.len() > 1 {
+                    self.gesture_finished = true;
+                    self.pinch_distance = self.get_pinch().map(|(_, distance)| distance);
+                }
+            },
+            TouchPhase::Moved => {
+
+                let previous_position = {
+                    let touch = match self.touches.get_mut(&id) {
+                        Some(s) => s,
+                        None => return gestures,
+                    };
+                    let previous_position = touch.position;
+                    touch.position = position;
+                    if distance(touch.start_position, position) > TAP_SLOP {
+                        touch.moved = true;
+                    }
+                    previous_position
+                };
+
+                if self.touches.len() == 2 {
+                    if let (Some((center, new_distance)), Some(old_distance)) = (self.get_pinch(), self.pinch_distance) {
+                        if old_distance > 0.0 && new_distance > 0.0 {
+                            gestures.push(Gesture::Pinch { center, scale: new_distance / old_distance });
+                        }
+                        self.pinch_distance = Some(new_distance);
+                    }
+                } else if self.touches.len() == 1 {
+                    let touch = self.touches[&id];
+                    if touch.moved {
+                        gestures.push(Gesture::Pan {
+                            position,
+                            delta: (position.x - previous_position.x, position.y - previous_position.y),
+                        });
+                    } else if !self.gesture_finished && time.duration_since(touch.start_time) >= LONG_PRESS_DURATION {
+                        self.gesture_finished = true;
+                        gestures.push(Gesture::LongPress { position: touch.start_position });
+                    }
+                }
+            },
+            TouchPhase::Ended => {
+                if let Some(touch) = self.touches.remove(&id) {
+                    if !self.gesture_finished && self.touches.is_empty() {
+                        gestures.extend(recognize_release_gesture(&touch, position, time));
+                    }
+                }
+            },
+            TouchPhase::Cancelled => {
+                self.touches.remove(&id);
+                self.gesture_finished = true;
+            },
+        }
+
+        if self.touches.len() != 2 {
+            self.pinch_distance = None;
+        }
+
+        if self.touches.is_empty() {
+            self.gesture_finished = false;
+        }
+
+        gestures
+    }
+
+    /// Returns the ID of the finger that rested on the screen for the `LONG_PRESS_DURATION`,
+    /// if the long press wasn't recognized yet
+    pub(crate) fn get_pending_long_press(&self, time: Instant) -> Option<u64> {
+
+        if self.gesture_finished || self.touches.len() != 1 {
+            return None;
+        }
+
+        let (id, touch) = self.touches.iter().next()?;
+
+        if !touch.moved && time.duration_since(touch.start_time) >= LONG_PRESS_DURATION {
+            Some(*id)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the center between and the distance of the first two fingers
+    fn get_pinch(&self) -> Option<(LogicalPosition, f64)> {
+        let mut touches = self.touches.values();
+        let a = touches.next()?.position;
+        let b = touches.next()?.position;
+        Some((LogicalPosition::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0), distance(a, b)))
+    }
+}
+
+/// Recognizes a tap, long press or swipe when the last finger is lifted
+fn recognize_release_gesture(touch: &TrackedTouch, position: LogicalPosition, time: Instant) -> Option<Gesture> {
+
+    let duration = time.duration_since(touch.start_time);
+
+    if !touch.moved {
+        return Some(if duration >= LONG_PRESS_DURATION {
+            Gesture::LongPress { position: touch.start_position }
+        } else {
+            Gesture::Tap { position: touch.start_position }
+        });
+    }
+
+    let seconds = duration.as_secs() as f64 + f64::from(duration.subsec_nanos()) / 1_000_000_000.0;
+    let (dx, dy) = (position.x - touch.start_position.x, position.y - touch.start_position.y);
+    let swipe_distance = dx.abs().max(dy.abs());
+    let velocity = if seconds > 0.0 { swipe_distance / seconds } else { 0.0 };
+
+    if swipe_distance < SWIPE_MIN_DISTANCE || velocity < SWIPE_MIN_VELOCITY {
+        return None;
+    }
+
+    let direction = match (dx.abs() > dy.abs(), dx > 0.0, dy > 0.0) {
+        (true, true, _) => SwipeDirection::Right,
+        (true, false, _) => SwipeDirection::Left,
+        (false, _, true) => SwipeDirection::Down,
+        (false, _, false) => SwipeDirection::Up,
+    };
+
+    Some(Gesture::Swipe { direction, velocity })
+}
+
+fn distance(a: LogicalPosition, b: LogicalPosition) -> f64 {
+    ((a.x - b.x) * (a.x - b.x) + (a.y - b.y) * (a.y - b.y)).sqrt()
+}
+
+#[test]
+fn test_recognize_tap_and_long_press() {
+    let start = Instant::now();
+    let pos = |x, y| LogicalPosition::new(x, y);
+    let mut recognizer = GestureRecognizer::default();
+
+    assert_eq!(recognizer.on_touch(0, TouchPhase::Started, pos(10.0, 10.0), start), vec![]);
+    assert_eq!(recognizer.on_touch(0, TouchPhase::Moved, pos(12.0, 11.0), start + Duration::from_millis(50)), vec![]);
+    assert_eq!(recognizer.on_touch(0, TouchPhase::Ended, pos(12.0, 11.0), start + Duration::from_millis(100)),
+        vec![Gesture::Tap { position: pos(10.0, 10.0) }]);
+
+    // The long press is recognized once, while the finger still touches the screen
+    assert_eq!(recognizer.on_touch(1, TouchPhase::Started, pos(10.0, 10.0), start), vec![]);
+    assert_eq!(recognizer.on_touch(1, TouchPhase::Moved, pos(11.0, 10.0), start + Duration::from_millis(600)),
+        vec![Gesture::LongPress { position: pos(10.0, 10.0) }]);
+    assert_eq!(recognizer.on_touch(1, TouchPhase::Moved, pos(11.0, 11.0), start + Duration::from_millis(700)), vec![]);
+    assert_eq!(recognizer.on_touch(1, TouchPhase::Ended, pos(11.0, 11.0), start + Duration::from_millis(800)), vec![]);
+
+    // Cancelled touches are no taps
+    assert_eq!(recognizer.on_touch(2, TouchPhase::Started, pos(10.0, 10.0), start), vec![]);
+    assert_eq!(recognizer.on_touch(2, TouchPhase::Cancelled, pos(10.0, 10.0), start), vec![]);
+}
+
+#[test]
+fn test_pending_long_press() {
+    let start = Instant::now();
+    let pos = |x, y| LogicalPosition::new(x, y);
+    let mut recognizer = GestureRecognizer::default();
+
+    recognizer.on_touch(0, TouchPhase::Started, pos(10.0, 10.0), start);
+    assert_eq!(recognizer.get_pending_long_press(start + Duration::from_millis(100)), None);
+    assert_eq!(recognizer.get_pending_long_press(start + Duration::from_millis(500)), Some(0));
+
+    // Repeating the touch event recognizes the long press, after that it isn't pending anymore
+    assert_eq!(recognizer.on_touch(0, TouchPhase::Moved, pos(10.0, 10.0), start + Duration::from_millis(500)),
+        vec![Gesture::LongPress { position: pos(10.0, 10.0) }]);
+    assert_eq!(recognizer.get_pending_long_press(start + Duration::from_millis(600)), None);
+    recognizer.on_touch(0, TouchPhase::Ended, pos(10.0, 10.0), start + Duration::from_millis(600));
+
+    // Moved fingers and multiple fingers are no long press
+    recognizer.on_touch(1, TouchPhase::Started, pos(10.0, 10.0), start);
+    recognizer.on_touch(1, TouchPhase::Moved, pos(50.0, 10.0), start + Duration::from_millis(100));
+    assert_eq!(recognizer.get_pending_long_press(start + Duration::from_millis(500)), None);
+    recognizer.on_touch(1, TouchPhase::Ended, pos(50.0, 10.0), start + Duration::from_millis(600));
+
+    recognizer.on_touch(2, TouchPhase::Started, pos(10.0, 10.0), start);
+    recognizer.on_touch(3, TouchPhase::Started, pos(50.0, 10.0), start);
+    assert_eq!(recognizer.get_pending_long_press(start + Duration::from_millis(500)), None);
+}
+
+#[test]
+fn test_recognize_pan_and_swipe() {
+    let start = Instant::now();
+    let pos = |x, y| LogicalPosition::new(x, y);
+    let mut recognizer = GestureRecognizer::default();
+
+    assert_eq!(recognizer.on_touch(0, TouchPhase::Started, pos(100.0, 100.0), start), vec![]);
+    assert_eq!(recognizer.on_touch(0, TouchPhase::Moved, pos(105.0, 100.0), start + Duration::from_millis(10)), vec![]);
+    assert_eq!(recognizer.on_touch(0, TouchPhase::Moved, pos(100.0, 80.0), start + Duration::from_millis(20)),
+        vec![Gesture::Pan { position: pos(100.0, 80.0), delta: (-5.0, -20.0) }]);
+    assert_eq!(recognizer.on_touch(0, TouchPhase::Moved, pos(100.0, 20.0), start + Duration::from_millis(50)),
+        vec![Gesture::Pan { position: pos(100.0, 20.0), delta: (0.0, -60.0) }]);
+    assert_eq!(recognizer.on_touch(0, TouchPhase::Ended, pos(100.0, 20.0), start + Duration::from_millis(100)),
+        vec![Gesture::Swipe { direction: SwipeDirection::Up, velocity: 800.0 }]);
+
+    // Slow pans are no swipes
+    assert_eq!(recognizer.on_touch(1, TouchPhase::Started, pos(100.0, 100.0), start), vec![]);
+    recognizer.on_touch(1, TouchPhase::Moved, pos(200.0, 100.0), start + Duration::from_millis(500));
+    assert_eq!(recognizer.on_touch(1, TouchPhase::Ended, pos(200.0, 100.0), start + Duration::from_millis(1000)), vec![]);
+}
+
+#[test]
+fn test_recognize_pinch() {
+    let start = Instant::now();
+    let pos = |x, y| LogicalPosition::new(x, y);
+    let mut recognizer = GestureRecognizer::default();
+
+    recognizer.on_touch(0, TouchPhase::Started, pos(100.0, 100.0), start);
+    recognizer.on_touch(1, TouchPhase::Started, pos(200.0, 100.0), start);
+    assert_eq!(recognizer.on_touch(1, TouchPhase::Moved, pos(300.0, 100.0), start),
+        vec![Gesture::Pinch { center: pos(200.0, 100.0), scale: 2.0 }]);
+    assert_eq!(recognizer.on_touch(0, TouchPhase::Moved, pos(200.0, 100.0), start),
+        vec![Gesture::Pinch { center: pos(250.0, 100.0), scale: 0.5 }]);
+
+    // Lifting the fingers after a pinch is no tap
+    assert_eq!(recognizer.on_touch(1, TouchPhase::Ended, pos(300.0, 100.0), start), vec![]);
+    assert_eq!(recognizer.on_touch(0, TouchPhase::Ended, pos(200.0, 100.0), start), vec![]);
+}
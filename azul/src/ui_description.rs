@@ -63,6 +63,7 @@ impl<T> Default for UiDescription<T> {
         let default_dom = Dom::new(NodeType::Div);
         let hovered_nodes = BTreeMap::new();
         let is_mouse_down = false;
+        let drop_target = None;
         let mut focused_node = None;
         let mut focus_target = None;
         Self::match_css_to_dom(
@@ -72,6 +73,7 @@ impl<T> Default for UiDescription<T> {
             &mut focus_target,
             &hovered_nodes,
             is_mouse_down,
+            drop_target,
         )
     }
 }
@@ -87,6 +89,7 @@ impl<T> UiDescription<T> {
         pending_focus_target: &mut Option<FocusTarget>,
         hovered_nodes: &BTreeMap<NodeId, HitTestItem>,
         is_mouse_down: bool,
+        drop_target: Option<NodeId>,
     ) -> Self
     {
         let ui_description = ::style::match_dom_selectors(
@@ -95,7 +98,8 @@ impl<T> UiDescription<T> {
             focused_node,
             pending_focus_target,
             hovered_nodes,
-            is_mouse_down
+            is_mouse_down,
+            drop_target,
         );
 
         // Important: Create all the tags for the :hover and :active selectors
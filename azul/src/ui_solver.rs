@@ -10,7 +10,7 @@ use {
     display_list::DisplayRectangle,
     dom::{NodeData, NodeType},
     app_resources::AppResources,
-    text_layout::{Words, ScaledWords, TextLayoutOptions, WordPositions},
+    text_layout::{Words, ScaledWords, TextLayoutOptions, WordPositions, TextMetrics},
 };
 use webrender::api::{LayoutRect, LayoutPoint, LayoutSize, FontInstanceKey};
 
@@ -1189,15 +1189,70 @@ pub struct PositionedRectangle {
     pub content_height: Option<f32>,
 }
 
+/// Source node and font of a run of text inside of a paragraph - the first run is
+/// the text of the paragraph node itself, the following runs are its inline spans.
+///
+/// The runs of a node are in the same order as the `ScaledWords::runs` of that node.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TextRun {
+    /// Node that the text of this run comes from
+    pub node_id: NodeId,
+    /// Font instance that the words of this run were scaled with
+    pub font_instance_key: FontInstanceKey,
+}
+
 #[derive(Debug, Clone)]
 pub struct LayoutResult {
     pub rects: NodeDataContainer<PositionedRectangle>,
     pub word_cache: BTreeMap<NodeId, Words>,
     pub scaled_words: BTreeMap<NodeId, (ScaledWords, FontInstanceKey)>,
     pub positioned_word_cache: BTreeMap<NodeId, (WordPositions, FontInstanceKey)>,
+    pub text_runs: BTreeMap<NodeId, Vec<TextRun>>,
     pub node_depths: Vec<(usize, NodeId)>,
 }
 
+impl LayoutResult {
+
+    /// Returns the laid-out rectangle of the node, relative to the top left of the window
+    /// (without taking the scroll state of any parent nodes into account)
+    pub fn get_node_rect(&self, node_id: NodeId) -> Option<LayoutRect> {
+        self.rects.get(node_id).map(|rect| rect.bounds)
+    }
+
+    /// Returns the size of the content of the node - for text nodes, this is the size
+    /// of the laid-out text block, for all other nodes the content size falls back to
+    /// the size of the node itself if the node has no intrinsic content size.
+    pub fn get_content_size(&self, node_id: NodeId) -> Option<LayoutSize> {
+        if let Some((word_positions, _)) = self.positioned_word_cache.get(&node_id) {
+            return Some(word_positions.content_size);
+        }
+
+        let rect = self.rects.get(node_id)?;
+        Some(LayoutSize::new(
+            rect.content_width.unwrap_or(rect.bounds.size.width),
+            rect.content_height.unwrap_or(rect.bounds.size.height),
+        ))
+    }
+
+    /// Returns the font size, line count and word positions of a text node,
+    /// `None` if the node isn't a text node
+    pub fn get_text_metrics(&self, node_id: NodeId) -> Option<TextMetrics> {
+        let (word_positions, _) = self.positioned_word_cache.get(&node_id)?;
+        let longest_word_width = self.scaled_words.get(&node_id)
+            .map(|(scaled_words, _)| scaled_words.longest_word_width)
+            .unwrap_or(0.0);
+
+        Some(TextMetrics {
+            font_size_px: word_positions.font_size_px,
+            content_size: word_positions.content_size,
+            number_of_words: word_positions.number_of_words,
+            number_of_lines: word_positions.number_of_lines,
+            longest_word_width,
+            word_positions: word_positions.word_positions.clone(),
+        })
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 struct InlineText {
     /// Horizontal padding of the text in pixels
@@ -1237,9 +1292,10 @@ pub(crate) fn do_the_layout<'a,'b, T>(
     let inline_text_blocks = BTreeMap::<NodeId, InlineText>::new();
 
     // Resolve cached text IDs or break new, uncached strings into words / text runs
-    let word_cache = create_word_cache(app_resources, node_data);
+    let inline_runs = create_inline_runs(app_resources, node_hierarchy, node_data);
+    let word_cache = create_word_cache(&inline_runs);
     // Scale the words to the correct size - TODO: Caching / GC!
-    let scaled_words = create_scaled_words(app_resources, &word_cache, display_rects);
+    let (scaled_words, text_runs) = create_scaled_words(app_resources, &inline_runs, display_rects);
     // Layout all words as if there was no max-width constraint
     let word_positions_no_max_width = create_word_positions(
         &word_cache,
@@ -1322,62 +1378,135 @@ pub(crate) fn do_the_layout<'a,'b, T>(
         word_cache,
         scaled_words,
         positioned_word_cache: word_positions_with_max_width,
+        text_runs,
         node_depths: solved_widths.non_leaf_nodes_sorted_by_depth,
     }
 }
 
-fn create_word_cache<T>(
+/// For every text node, returns the words of the node itself, followed by
+/// the words of each of its inline `Span` children (one entry per run)
+fn create_inline_runs<T>(
     app_resources: &AppResources,
+    node_hierarchy: &NodeHierarchy,
     node_data: &NodeDataContainer<NodeData<T>>,
-) -> BTreeMap<NodeId, Words>
+) -> BTreeMap<NodeId, Vec<(NodeId, Words)>>
 {
     use text_layout::split_text_into_words;
     node_data
     .linear_iter()
     .filter_map(|node_id| {
-        match &node_data[node_id].node_type {
-            NodeType::Label(string) => Some((node_id, split_text_into_words(string.as_str()))),
-            NodeType::Text(text_id) => {
-                app_resources.get_text(text_id).map(|words| (node_id, words.clone()))
-            },
-            _ => None,
+        let words = match &node_data[node_id].node_type {
+            NodeType::Label(string) => split_text_into_words(string.as_str()),
+            NodeType::Text(text_id) => app_resources.get_text(text_id)?.clone(),
+            _ => return None,
+        };
+
+        let mut runs = vec![(node_id, words)];
+        runs.extend(node_id.children(node_hierarchy).filter_map(|child_id| {
+            match &node_data[child_id].node_type {
+                NodeType::Span(string) => Some((child_id, split_text_into_words(string.as_str()))),
+                _ => None,
+            }
+        }));
+
+        Some((node_id, runs))
+    }).collect()
+}
+
+/// Joins the runs of each text node into one paragraph
+fn create_word_cache(
+    inline_runs: &BTreeMap<NodeId, Vec<(NodeId, Words)>>,
+) -> BTreeMap<NodeId, Words>
+{
+    inline_runs.iter().filter_map(|(node_id, runs)| {
+        let mut runs = runs.iter();
+        let (_, first_run) = runs.next()?;
+        let mut words = first_run.clone();
+        for (_, run) in runs {
+            words.append(run);
         }
+        Some((*node_id, words))
     }).collect()
 }
 
+/// Scales the words of each run with the font of the node that the run comes from,
+/// returns the scaled paragraphs and the fonts of their runs
 fn create_scaled_words<'a>(
     app_resources: &AppResources,
-    words: &BTreeMap<NodeId, Words>,
+    inline_runs: &BTreeMap<NodeId, Vec<(NodeId, Words)>>,
     display_rects: &NodeDataContainer<DisplayRectangle<'a>>,
-) -> BTreeMap<NodeId, (ScaledWords, FontInstanceKey)> {
+) -> (BTreeMap<NodeId, (ScaledWords, FontInstanceKey)>, BTreeMap<NodeId, Vec<TextRun>>) {
 
-    use text_layout::words_to_scaled_words;
-    use app_resources::ImmediateFontId;
+    let mut scaled_words_map = BTreeMap::new();
+    let mut text_runs_map = BTreeMap::new();
 
-    words.iter().filter_map(|(node_id, words)| {
-        let style = &display_rects[*node_id].style;
-        let font_size = get_font_size(&style);
-        let font_size_au = font_size_to_au(font_size);
-        let css_font_id = get_font_id(&style);
-        let font_id = match app_resources.get_css_font_id(css_font_id) {
-            Some(s) => ImmediateFontId::Resolved(*s),
-            None => ImmediateFontId::Unresolved(css_font_id.to_string()),
+    for (node_id, runs) in inline_runs.iter() {
+
+        let mut runs = runs.iter();
+
+        let (mut scaled_words, font_instance_key) = match runs.next().and_then(|(run_node_id, words)| {
+            scale_words(app_resources, words, &display_rects[*run_node_id].style)
+        }) {
+            Some(s) => s,
+            None => continue,
         };
 
-        let loaded_font = app_resources.get_loaded_font(&font_id)?;
-        let font_instance_key = loaded_font.font_instances.get(&font_size_au)?;
+        let mut text_runs = vec![TextRun { node_id: *node_id, font_instance_key }];
+
+        for (run_node_id, words) in runs {
+            // If the font of the span isn't loaded, fall back to the font of the paragraph,
+            // so that the words of the paragraph and the scaled words don't go out of sync
+            let (run_scaled_words, run_font_instance_key) =
+                match scale_words(app_resources, words, &display_rects[*run_node_id].style) {
+                    Some(s) => s,
+                    None => match scale_words(app_resources, words, &display_rects[*node_id].style) {
+                        Some(s) => s,
+                        None => continue,
+                    },
+                };
+            scaled_words.append_run(run_scaled_words);
+            text_runs.push(TextRun { node_id: *run_node_id, font_instance_key: run_font_instance_key });
+        }
 
-        let font_bytes = &loaded_font.font_bytes;
-        let font_index = loaded_font.font_index as u32;
+        scaled_words_map.insert(*node_id, (scaled_words, font_instance_key));
+        text_runs_map.insert(*node_id, text_runs);
+    }
 
-        let scaled_words = words_to_scaled_words(
-            words,
-            font_bytes,
-            font_index,
-            font_size.0.to_pixels(),
-        );
-        Some((*node_id, (scaled_words, *font_instance_key)))
-    }).collect()
+    (scaled_words_map, text_runs_map)
+}
+
+/// Scales the words to the font family and font size of the given style
+fn scale_words(
+    app_resources: &AppResources,
+    words: &Words,
+    style: &RectStyle,
+) -> Option<(ScaledWords, FontInstanceKey)> {
+
+    use text_layout::words_to_scaled_words;
+    use app_resources::ImmediateFontId;
+
+    let font_size = get_font_size(&style);
+    let font_size_au = font_size_to_au(font_size);
+    let css_font_id = get_font_id(&style);
+    let font_id = match app_resources.get_css_font_id(css_font_id) {
+        Some(s) => ImmediateFontId::Resolved(*s),
+        None => ImmediateFontId::Unresolved(css_font_id.to_string()),
+    };
+
+    let loaded_font = app_resources.get_loaded_font(&font_id)?;
+    let font_instance_key = loaded_font.font_instances.get(&font_size_au)?;
+
+    let font_bytes = &loaded_font.font_bytes;
+    let font_index = loaded_font.font_index as u32;
+
+    let scaled_words = words_to_scaled_words(
+        words,
+        font_bytes,
+        font_index,
+        font_size.0.to_pixels(),
+    );
+
+    Some((scaled_words, *font_instance_key))
 }
 
 fn create_word_positions<'a>(
@@ -1759,4 +1888,27 @@ mod layout_tests {
             space_added: window_width - 200.0,
         });
     }
+
+    #[test]
+    fn test_layout_result_geometry_queries() {
+        let rect = |x, y, w, h| LayoutRect::new(LayoutPoint::new(x, y), LayoutSize::new(w, h));
+
+        let layout_result = LayoutResult {
+            rects: NodeDataContainer::new(vec![
+                PositionedRectangle { bounds: rect(0.0, 0.0, 400.0, 300.0), content_width: None, content_height: None },
+                PositionedRectangle { bounds: rect(10.0, 20.0, 100.0, 50.0), content_width: Some(250.0), content_height: None },
+            ]),
+            word_cache: BTreeMap::new(),
+            scaled_words: BTreeMap::new(),
+            positioned_word_cache: BTreeMap::new(),
+            text_runs: BTreeMap::new(),
+            node_depths: Vec::new(),
+        };
+
+        assert_eq!(layout_result.get_node_rect(NodeId::new(1)), Some(rect(10.0, 20.0, 100.0, 50.0)));
+        assert_eq!(layout_result.get_node_rect(NodeId::new(2)), None);
+        assert_eq!(layout_result.get_content_size(NodeId::new(0)), Some(LayoutSize::new(400.0, 300.0)));
+        assert_eq!(layout_result.get_content_size(NodeId::new(1)), Some(LayoutSize::new(250.0, 50.0)));
+        assert_eq!(layout_result.get_text_metrics(NodeId::new(1)), None);
+    }
 }
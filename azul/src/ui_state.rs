@@ -9,13 +9,15 @@ use {
     app::RuntimeError,
     dom::{
         Dom, TagId, TabIndex, DomString,
-        HoverEventFilter, FocusEventFilter, NotEventFilter,
+        EventFilter, HoverEventFilter, FocusEventFilter, NotEventFilter,
         WindowEventFilter
     },
     app::AppState,
     id_tree::NodeId,
     style::HoverGroup,
-    callbacks::{Callback, LayoutInfo, DefaultCallbackId},
+    callbacks::{CallbackVariant, LayoutInfo, DefaultCallbackId},
+    overlay::{Overlay, OverlayNode, OVERLAY_CLASS},
+    menu::open_menu_overlay,
 };
 
 pub struct UiState<T> {
@@ -43,14 +45,18 @@ pub struct UiState<T> {
     // There are two maps per event, one for the regular callbacks and one for
     // the default callbacks. This is done for consistency, since otherwise the
     // event filtering logic gets much more complicated than it already is.
-    pub hover_callbacks:                BTreeMap<NodeId, BTreeMap<HoverEventFilter, Callback<T>>>,
+    pub hover_callbacks:                BTreeMap<NodeId, BTreeMap<HoverEventFilter, CallbackVariant<T>>>,
     pub hover_default_callbacks:        BTreeMap<NodeId, BTreeMap<HoverEventFilter, DefaultCallbackId>>,
-    pub focus_callbacks:                BTreeMap<NodeId, BTreeMap<FocusEventFilter, Callback<T>>>,
+    pub focus_callbacks:                BTreeMap<NodeId, BTreeMap<FocusEventFilter, CallbackVariant<T>>>,
     pub focus_default_callbacks:        BTreeMap<NodeId, BTreeMap<FocusEventFilter, DefaultCallbackId>>,
-    pub not_callbacks:                  BTreeMap<NodeId, BTreeMap<NotEventFilter, Callback<T>>>,
+    pub not_callbacks:                  BTreeMap<NodeId, BTreeMap<NotEventFilter, CallbackVariant<T>>>,
     pub not_default_callbacks:          BTreeMap<NodeId, BTreeMap<NotEventFilter, DefaultCallbackId>>,
-    pub window_callbacks:               BTreeMap<NodeId, BTreeMap<WindowEventFilter, Callback<T>>>,
+    pub window_callbacks:               BTreeMap<NodeId, BTreeMap<WindowEventFilter, CallbackVariant<T>>>,
     pub window_default_callbacks:       BTreeMap<NodeId, BTreeMap<WindowEventFilter, DefaultCallbackId>>,
+    /// Callbacks that are invoked in the capture phase (before the event reaches the target)
+    pub capture_callbacks:              BTreeMap<NodeId, BTreeMap<EventFilter, CallbackVariant<T>>>,
+    /// Overlays that were appended to the DOM, positioned after the layout is done
+    pub(crate) overlays: Vec<OverlayNode>,
 }
 
 impl<T> fmt::Debug for UiState<T> {
@@ -73,6 +79,8 @@ impl<T> fmt::Debug for UiState<T> {
                 not_default_callbacks: {:?}, \
                 window_callbacks: {:?}, \
                 window_default_callbacks: {:?}, \
+                capture_callbacks: {:?}, \
+                overlays: {:?}, \
             }}",
 
             self.dom,
@@ -90,6 +98,8 @@ impl<T> fmt::Debug for UiState<T> {
             self.not_default_callbacks,
             self.window_callbacks,
             self.window_default_callbacks,
+            self.capture_callbacks,
+            self.overlays,
         )
     }
 }
@@ -114,7 +124,7 @@ impl<T> UiState<T> {
         };
 
         // Only shortly lock the data to get the dom out
-        let dom: Dom<T> = {
+        let mut dom: Dom<T> = {
             #[cfg(test)]{
                 Dom::<T>::new(NodeType::Div)
             }
@@ -125,7 +135,30 @@ impl<T> UiState<T> {
             }
         };
 
-        Ok(dom.into_ui_state())
+        // Lets the native style draw the window background in the dark / high-contrast themes
+        #[cfg(feature = "native_style")] {
+            dom.add_class(::azul_native_style::NATIVE_ROOT_CLASS);
+        }
+
+        // The open menu is drawn on top of all other overlays
+        let menu_overlay = fake_window.open_menu.as_ref()
+            .and_then(|open_menu| open_menu_overlay(open_menu, fake_window.menu_bar.as_ref()));
+        if let Some((anchor, placement, menu_dom)) = menu_overlay {
+            fake_window.overlays.push(Overlay { anchor, placement, dom: menu_dom });
+        }
+
+        // Append the overlays to the root node, so that the NodeIds of the regular DOM
+        // don't change. The overlays are positioned in the display list, after the layout.
+        let mut overlays = Vec::new();
+        for Overlay { anchor, placement, dom: overlay_dom } in fake_window.overlays.drain(..) {
+            let node_id = NodeId::new(dom.len());
+            dom.add_child(Dom::div().with_class(OVERLAY_CLASS).with_child(overlay_dom));
+            overlays.push(OverlayNode { node_id, anchor, placement });
+        }
+
+        let mut ui_state = dom.into_ui_state();
+        ui_state.overlays = overlays;
+        Ok(ui_state)
     }
 
     pub(crate) fn create_tags_for_hover_nodes(&mut self, hover_nodes: &BTreeMap<NodeId, HoverGroup>) {
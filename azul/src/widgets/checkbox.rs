@@ -0,0 +1,214 @@
+//! Checkbox with an optional third, indeterminate state (two-way data binding)
+
+use {
+    callbacks::{UpdateScreen, Redraw, DontRedraw},
+    dom::{Dom, DomString, On, EventFilter, FocusEventFilter, TabIndex},
+    window::FakeWindow,
+    prelude::VirtualKeyCode,
+    callbacks::{CallbackInfo, StackCheckedPointer, DefaultCallback, DefaultCallbackId},
+    app::AppStateNoData,
+};
+
+#[derive(Debug, Default, Clone, Hash, PartialEq, Eq)]
+pub struct Checkbox {
+    label: Option<DomString>,
+    on_toggle_callback: Option<(DefaultCallbackId, DefaultCallbackId)>,
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum CheckState {
+    Unchecked,
+    Checked,
+    /// Neither checked nor unchecked, i.e. for a "select all" checkbox if only some items are selected
+    Indeterminate,
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct CheckboxState {
+    pub state: CheckState,
+    /// If set, clicking the checkbox cycles from unchecked to checked to indeterminate -
+    /// otherwise the indeterminate state can only be set by the application
+    pub is_tri_state: bool,
+    /// Disabled checkboxes can't be focused or toggled
+    pub disabled: bool,
+}
+
+impl Default for CheckboxState {
+    fn default() -> Self {
+        CheckboxState {
+            state: CheckState::Unchecked,
+            is_tri_state: false,
+            disabled: false,
+        }
+    }
+}
+
+impl CheckboxState {
+
+    pub fn new(checked: bool) -> Self {
+        Self {
+            state: if checked { CheckState::Checked } else { CheckState::Unchecked },
+            .. Default::default()
+        }
+    }
+
+    /// Creates a checkbox that cycles through all three states when it is clicked
+    pub fn tri_state(state: CheckState) -> Self {
+        Self {
+            state,
+            is_tri_state: true,
+            .. Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn with_disabled(self, disabled: bool) -> Self {
+        Self { disabled, .. self }
+    }
+
+    /// Returns whether the checkbox is checked (`false` for the indeterminate state)
+    pub fn is_checked(&self) -> bool {
+        self.state == CheckState::Checked
+    }
+
+    /// Advances the checkbox to the next state, as if it was clicked
+    pub fn toggle(&mut self) {
+        use self::CheckState::*;
+        self.state = match (self.state, self.is_tri_state) {
+            (Unchecked, _) => Checked,
+            (Checked, true) => Indeterminate,
+            (Checked, false) | (Indeterminate, _) => Unchecked,
+        };
+    }
+}
+
+impl Checkbox {
+
+    pub fn new() -> Self {
+        Checkbox { label: None, on_toggle_callback: None }
+    }
+
+    pub fn with_label<S: Into<DomString>>(label: S) -> Self {
+        Checkbox { label: Some(label.into()), on_toggle_callback: None }
+    }
+
+    pub fn bind<T>(self, window: &mut FakeWindow<T>, field: &CheckboxState, data: &T) -> Self {
+        let ptr = StackCheckedPointer::new(data, field);
+        let on_toggle_callback = ptr.map(|ptr|{(
+            window.add_callback(ptr, DefaultCallback(CheckboxState::on_click_private)),
+            window.add_callback(ptr, DefaultCallback(CheckboxState::on_virtual_key_down_private))
+        )});
+
+        Self {
+            on_toggle_callback,
+            .. self
+        }
+    }
+
+    pub fn dom<T>(&self, field: &CheckboxState) -> Dom<T> {
+
+        let mut parent_div =
+            Dom::div()
+            .with_class("__azul-native-checkbox")
+            .with_checked(field.is_checked())
+            .with_disabled(field.disabled);
+
+        let mark = match field.state {
+            CheckState::Unchecked => "",
+            CheckState::Checked => "\u{2713}",
+            CheckState::Indeterminate => {
+                parent_div.add_class("__azul-native-checkbox-indeterminate");
+                "\u{2212}"
+            },
+        };
+
+        if !field.disabled {
+            parent_div.set_tab_index(TabIndex::Auto);
+            if let Some((click_callback, vk_callback)) = self.on_toggle_callback {
+                parent_div.add_default_callback_id(On::LeftMouseUp, click_callback);
+                parent_div.add_default_callback_id(EventFilter::Focus(FocusEventFilter::VirtualKeyDown), vk_callback);
+            }
+        }
+
+        parent_div.add_child(
+            Dom::div()
+            .with_class("__azul-native-checkbox-box")
+            .with_child(Dom::label(mark).with_class("__azul-native-checkbox-mark"))
+        );
+
+        if let Some(label) = &self.label {
+            parent_div.add_child(Dom::label(label.clone()).with_class("__azul-native-checkbox-label"));
+        }
+
+        parent_div
+    }
+}
+
+impl CheckboxState {
+
+    fn on_click_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_click, app_state_no_data, window_event) }
+    }
+
+    fn on_virtual_key_down_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_virtual_key_down, app_state_no_data, window_event) }
+    }
+
+    pub fn on_click<T>(&mut self, _app_state_no_data: &mut AppStateNoData<T>, _event: &mut CallbackInfo<T>) -> UpdateScreen {
+        if self.disabled {
+            return DontRedraw;
+        }
+        self.toggle();
+        Redraw
+    }
+
+    /// Toggles the checkbox when Space is pressed
+    pub fn on_virtual_key_down<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        let keyboard_state = app_state_no_data.windows[event.window_id].get_keyboard_state();
+        match keyboard_state.latest_virtual_keycode {
+            Some(VirtualKeyCode::Space) if !self.disabled => {
+                self.toggle();
+                Redraw
+            },
+            _ => DontRedraw,
+        }
+    }
+}
+
+#[test]
+fn test_checkbox_toggle() {
+    let mut checkbox = CheckboxState::new(false);
+    checkbox.toggle();
+    assert!(checkbox.is_checked());
+    checkbox.toggle();
+    assert_eq!(checkbox.state, CheckState::Unchecked);
+
+    // Indeterminate checkboxes become unchecked, tri-state checkboxes cycle through all states
+    checkbox.state = CheckState::Indeterminate;
+    checkbox.toggle();
+    assert_eq!(checkbox.state, CheckState::Unchecked);
+
+    let mut tri_state = CheckboxState::tri_state(CheckState::Checked);
+    tri_state.toggle();
+    assert_eq!(tri_state.state, CheckState::Indeterminate);
+    tri_state.toggle();
+    assert_eq!(tri_state.state, CheckState::Unchecked);
+}
+
+#[test]
+fn test_checkbox_ui_1() {
+
+    struct Mock;
+
+    let expected = r#"
+        <div class="__azul-native-checkbox" checked="true" tabindex="0">
+            <div class="__azul-native-checkbox-box">
+                <p class="__azul-native-checkbox-mark">✓</p>
+            </div>
+            <p class="__azul-native-checkbox-label">Hello</p>
+        </div>
+    "#;
+    let checkbox: Dom<Mock> = Checkbox::with_label("Hello").dom(&CheckboxState::new(true));
+
+    checkbox.assert_eq(expected);
+}
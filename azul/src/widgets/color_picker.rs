@@ -0,0 +1,528 @@
+//! HSV color picker with a saturation / value square, hue and alpha sliders
+//! and a text field for hex (or any other CSS) colors (two-way data binding)
+
+use azul_css::{
+    CssProperty, StyleBackground, LinearGradient, GradientStopPre,
+    Direction, DirectionCorner, ExtendMode, PercentageValue, ColorU, ColorF,
+};
+use azul_css_parser::CssColor;
+use {
+    callbacks::{UpdateScreen, Redraw, DontRedraw},
+    dom::{Dom, On, EventFilter, FocusEventFilter, WindowEventFilter, TabIndex},
+    window::FakeWindow,
+    prelude::VirtualKeyCode,
+    callbacks::{CallbackInfo, StackCheckedPointer, DefaultCallback, DefaultCallbackId},
+    app::AppStateNoData,
+    widgets::{flex_grow_div, text_input::{TextInputState, Selection}},
+};
+
+/// Size of the thumbs in pixels, has to match the `__azul-native-color-picker-thumb`
+/// in the native stylesheets
+pub const THUMB_SIZE: f32 = 12.0;
+/// How far the arrow keys move the hue (in degrees)
+const HUE_KEYBOARD_STEP: f32 = 1.0;
+
+/// Part of the color picker that can be dragged with the mouse
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ColorPickerArea {
+    /// Saturation (horizontal) and value (vertical)
+    Square,
+    Hue,
+    Alpha,
+}
+
+impl ColorPickerArea {
+    /// Index of the area in the children of the color picker
+    fn get_index(self) -> usize {
+        match self {
+            ColorPickerArea::Square => 0,
+            ColorPickerArea::Hue => 1,
+            ColorPickerArea::Alpha => 2,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+struct ColorPickerCallbacks {
+    mouse_down: DefaultCallbackId,
+    mouse_over: DefaultCallbackId,
+    mouse_up: DefaultCallbackId,
+    text_input: DefaultCallbackId,
+    virtual_key_down: DefaultCallbackId,
+    focus_lost: DefaultCallbackId,
+}
+
+#[derive(Debug, Default, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct ColorPicker {
+    callbacks: Option<ColorPickerCallbacks>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorPickerState {
+    /// Hue in degrees, from 0.0 to 360.0
+    pub hue: f32,
+    /// Saturation from 0.0 to 1.0
+    pub saturation: f32,
+    /// Value (brightness) from 0.0 to 1.0
+    pub value: f32,
+    /// Opacity from 0.0 to 1.0
+    pub alpha: f32,
+    /// Whether the alpha slider is shown, otherwise the color is always opaque
+    pub show_alpha: bool,
+    /// Disabled color pickers can't be focused or changed
+    pub disabled: bool,
+    /// Text that is currently edited, parsed into the color on Return or when the focus is lost
+    pub input: TextInputState,
+    /// Whether the `input` is a valid CSS color
+    is_valid: bool,
+    /// Area that is currently dragged with the mouse
+    dragging: Option<ColorPickerArea>,
+}
+
+impl Default for ColorPickerState {
+    fn default() -> Self {
+        Self::new(ColorU { r: 255, g: 255, b: 255, a: 255 }.into())
+    }
+}
+
+impl ColorPickerState {
+
+    pub fn new(color: CssColor) -> Self {
+        let mut picker = Self {
+            hue: 0.0,
+            saturation: 0.0,
+            value: 0.0,
+            alpha: 1.0,
+            show_alpha: true,
+            disabled: false,
+            input: TextInputState::default(),
+            is_valid: true,
+            dragging: None,
+        };
+        picker.set_color(color);
+        picker
+    }
+
+    /// Hides the alpha slider and makes the color opaque
+    #[inline]
+    pub fn without_alpha(self) -> Self {
+        let mut picker = Self { show_alpha: false, .. self };
+        picker.set_alpha(1.0);
+        picker
+    }
+
+    #[inline]
+    pub fn with_disabled(self, disabled: bool) -> Self {
+        Self { disabled, .. self }
+    }
+
+    /// Returns the currently picked color
+    pub fn get_color(&self) -> CssColor {
+        // Rounding instead of truncating (as `ColorU::from(ColorF)` does), so that
+        // setting a color and reading it back returns the same color
+        let to_u8 = |component: f32| (component.min(1.0).max(0.0) * 255.0).round() as u8;
+        let (r, g, b) = hsv_to_rgb(self.hue, self.saturation, self.value);
+        CssColor::from(ColorU { r: to_u8(r), g: to_u8(g), b: to_u8(b), a: to_u8(self.alpha) })
+    }
+
+    /// Sets the color and replaces the edited text. The hue is kept for gray colors
+    /// (and the saturation for black), so that it doesn't jump while dragging.
+    pub fn set_color(&mut self, color: CssColor) {
+        let color = color.to_color_f();
+        let (hue, saturation, value) = rgb_to_hsv(color.r, color.g, color.b);
+        if value > 0.0 {
+            if saturation > 0.0 {
+                self.hue = hue;
+            }
+            self.saturation = saturation;
+        }
+        self.value = value;
+        self.alpha = if self.show_alpha { color.a } else { 1.0 };
+        self.update_input();
+    }
+
+    /// Sets the hue (in degrees, wrapped to 0 - 360), saturation and value (clamped to 0 - 1)
+    pub fn set_hsv(&mut self, hue: f32, saturation: f32, value: f32) {
+        self.hue = wrap_hue(hue);
+        self.saturation = saturation.min(1.0).max(0.0);
+        self.value = value.min(1.0).max(0.0);
+        self.update_input();
+    }
+
+    pub fn set_alpha(&mut self, alpha: f32) {
+        self.alpha = alpha.min(1.0).max(0.0);
+        self.update_input();
+    }
+
+    /// Returns whether the edited text is a valid CSS color
+    pub fn is_valid(&self) -> bool {
+        self.is_valid
+    }
+
+    /// Returns the area that is currently dragged with the mouse
+    pub fn get_dragged_area(&self) -> Option<ColorPickerArea> {
+        self.dragging
+    }
+
+    /// Parses the edited text into the color - if the text is not a valid CSS color,
+    /// the text is reset to the current color. Returns whether the text was valid.
+    pub fn commit(&mut self) -> bool {
+        let color = CssColor::from_str(&self.input.text).ok();
+        match color {
+            Some(color) => {
+                self.set_color(color);
+                true
+            },
+            None => {
+                self.update_input();
+                false
+            },
+        }
+    }
+
+    /// Replaces the edited text with the hex code of the current color
+    fn update_input(&mut self) {
+        let prefix_hash = true;
+        self.input = TextInputState::new(self.get_color().to_string(prefix_hash));
+        self.is_valid = true;
+    }
+
+    /// Sets the color from a position within an area, both `x` and `y` from 0.0 to 1.0
+    fn set_from_area(&mut self, area: ColorPickerArea, x: f32, y: f32) {
+        let (x, y) = (x.min(1.0).max(0.0), y.min(1.0).max(0.0));
+        match area {
+            ColorPickerArea::Square => {
+                let hue = self.hue;
+                self.set_hsv(hue, x, 1.0 - y);
+            },
+            ColorPickerArea::Hue => {
+                // 360 degrees would wrap around to 0 and move the thumb to the start
+                let (saturation, value) = (self.saturation, self.value);
+                self.set_hsv(x * 359.99, saturation, value);
+            },
+            ColorPickerArea::Alpha => self.set_alpha(x),
+        }
+    }
+}
+
+impl ColorPicker {
+
+    pub fn new() -> Self {
+        ColorPicker { callbacks: None }
+    }
+
+    pub fn bind<T>(self, window: &mut FakeWindow<T>, field: &ColorPickerState, data: &T) -> Self {
+        let ptr = StackCheckedPointer::new(data, field);
+        let callbacks = ptr.map(|ptr| ColorPickerCallbacks {
+            mouse_down: window.add_callback(ptr, DefaultCallback(ColorPickerState::on_mouse_down_private)),
+            mouse_over: window.add_callback(ptr, DefaultCallback(ColorPickerState::on_mouse_over_private)),
+            mouse_up: window.add_callback(ptr, DefaultCallback(ColorPickerState::on_mouse_up_private)),
+            text_input: window.add_callback(ptr, DefaultCallback(ColorPickerState::on_text_input_private)),
+            virtual_key_down: window.add_callback(ptr, DefaultCallback(ColorPickerState::on_virtual_key_down_private)),
+            focus_lost: window.add_callback(ptr, DefaultCallback(ColorPickerState::on_focus_lost_private)),
+        });
+
+        Self { callbacks }
+    }
+
+    pub fn dom<T>(&self, field: &ColorPickerState) -> Dom<T> {
+
+        let mut parent_div =
+            Dom::div()
+            .with_class("__azul-native-color-picker")
+            .with_disabled(field.disabled);
+
+        if field.dragging.is_some() {
+            parent_div.add_class("__azul-native-color-picker-dragging");
+        }
+
+        if !field.disabled {
+            parent_div.set_tab_index(TabIndex::Auto);
+            if let Some(callbacks) = self.callbacks {
+                parent_div.add_default_callback_id(On::LeftMouseDown, callbacks.mouse_down);
+                // Dragging has to continue when the mouse leaves the color picker
+                parent_div.add_default_callback_id(EventFilter::Window(WindowEventFilter::MouseOver), callbacks.mouse_over);
+                parent_div.add_default_callback_id(EventFilter::Window(WindowEventFilter::MouseUp), callbacks.mouse_up);
+                parent_div.add_default_callback_id(EventFilter::Focus(FocusEventFilter::TextInput), callbacks.text_input);
+                parent_div.add_default_callback_id(EventFilter::Focus(FocusEventFilter::VirtualKeyDown), callbacks.virtual_key_down);
+                parent_div.add_default_callback_id(On::FocusLost, callbacks.focus_lost);
+            }
+        }
+
+        let color = field.get_color().to_color_u();
+        let (hue_r, hue_g, hue_b) = hsv_to_rgb(field.hue, 1.0, 1.0);
+        let hue_color = ColorU::from(ColorF { r: hue_r, g: hue_g, b: hue_b, a: 1.0 });
+
+        // The square is the pure hue, overlaid with a white gradient from the left
+        // (saturation) and a black gradient from the bottom (value)
+        let square = Dom::div()
+            .with_class("__azul-native-color-picker-square")
+            .with_css_override("__azul_color_picker_hue", CssProperty::Background(StyleBackground::Color(hue_color)))
+            .with_child(
+                Dom::div()
+                .with_class("__azul-native-color-picker-square-saturation")
+                .with_child(
+                    Dom::div()
+                    .with_class("__azul-native-color-picker-square-value")
+                    .with_child(flex_grow_div("__azul-native-color-picker-value-before", "__azul_color_picker_value_before", 1.0 - field.value))
+                    .with_child(thumb_row(field.saturation).with_class("__azul-native-color-picker-square-row"))
+                    .with_child(flex_grow_div("__azul-native-color-picker-value-after", "__azul_color_picker_value_after", field.value))
+                )
+            );
+
+        // Order has to match ColorPickerArea::get_index
+        parent_div.add_child(square);
+        parent_div.add_child(thumb_row(field.hue / 360.0).with_class("__azul-native-color-picker-hue"));
+
+        if field.show_alpha {
+            let transparent = ColorU { a: 0, .. color };
+            let opaque = ColorU { a: 255, .. color };
+            let alpha_gradient = LinearGradient {
+                direction: Direction::FromTo(DirectionCorner::Left, DirectionCorner::Right),
+                extend_mode: ExtendMode::Clamp,
+                stops: vec![
+                    GradientStopPre { offset: Some(PercentageValue::new(0.0)), color: transparent },
+                    GradientStopPre { offset: Some(PercentageValue::new(100.0)), color: opaque },
+                ],
+            };
+            parent_div.add_child(
+                thumb_row(field.alpha)
+                .with_class("__azul-native-color-picker-alpha")
+                .with_css_override("__azul_color_picker_alpha", CssProperty::Background(StyleBackground::LinearGradient(alpha_gradient)))
+            );
+        }
+
+        let mut input = Dom::label(field.input.text.clone()).with_class("__azul-native-color-picker-input");
+        if !field.is_valid {
+            input.add_class("__azul-native-color-picker-input-invalid");
+        }
+
+        parent_div.with_child(
+            Dom::div()
+            .with_class("__azul-native-color-picker-footer")
+            .with_child(
+                Dom::div()
+                .with_class("__azul-native-color-picker-preview")
+                .with_css_override("__azul_color_picker_color", CssProperty::Background(StyleBackground::Color(color)))
+            )
+            .with_child(input)
+        )
+    }
+}
+
+impl ColorPickerState {
+
+    fn on_mouse_down_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_mouse_down, app_state_no_data, window_event) }
+    }
+
+    fn on_mouse_over_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_mouse_over, app_state_no_data, window_event) }
+    }
+
+    fn on_mouse_up_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_mouse_up, app_state_no_data, window_event) }
+    }
+
+    fn on_text_input_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_text_input, app_state_no_data, window_event) }
+    }
+
+    fn on_virtual_key_down_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_virtual_key_down, app_state_no_data, window_event) }
+    }
+
+    fn on_focus_lost_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_focus_lost, app_state_no_data, window_event) }
+    }
+
+    /// Starts dragging the square, the hue or the alpha slider, depending on which one was clicked
+    pub fn on_mouse_down<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        if self.disabled {
+            return DontRedraw;
+        }
+
+        let area = match event.get_target_child_index()? {
+            0 => ColorPickerArea::Square,
+            1 => ColorPickerArea::Hue,
+            2 if self.show_alpha => ColorPickerArea::Alpha,
+            _ => return DontRedraw,
+        };
+
+        self.dragging = Some(area);
+        self.set_color_from_cursor(area, app_state_no_data, event);
+        Redraw
+    }
+
+    pub fn on_mouse_over<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        let area = self.dragging?;
+        self.set_color_from_cursor(area, app_state_no_data, event)
+    }
+
+    pub fn on_mouse_up<T>(&mut self, _app_state_no_data: &mut AppStateNoData<T>, _event: &mut CallbackInfo<T>) -> UpdateScreen {
+        self.dragging.take()?;
+        Redraw
+    }
+
+    /// Edits the hex text, ignoring characters that can't be part of a CSS color
+    pub fn on_text_input<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        let current_char = app_state_no_data.windows[event.window_id].get_keyboard_state().current_char;
+        match current_char {
+            Some(c) if !self.disabled && (c.is_ascii_alphanumeric() || ['#', '(', ')', ',', '.', ' ', '%', '-'].contains(&c)) => { },
+            _ => return DontRedraw,
+        }
+
+        let update = self.input.on_text_input(app_state_no_data, event);
+        self.is_valid = CssColor::from_str(&self.input.text).is_ok();
+        update
+    }
+
+    /// Return commits the edited text and Escape reverts it. Up / Down change the hue,
+    /// all other keys are handled like in a `TextInput`.
+    pub fn on_virtual_key_down<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        if self.disabled {
+            return DontRedraw;
+        }
+
+        let keyboard_state = app_state_no_data.windows[event.window_id].get_keyboard_state();
+        let (hue, saturation, value) = (self.hue, self.saturation, self.value);
+
+        match keyboard_state.latest_virtual_keycode {
+            Some(VirtualKeyCode::Up) => self.set_hsv(hue + HUE_KEYBOARD_STEP, saturation, value),
+            Some(VirtualKeyCode::Down) => self.set_hsv(hue - HUE_KEYBOARD_STEP, saturation, value),
+            Some(VirtualKeyCode::Return) => {
+                self.commit();
+                self.input.selection = Some(Selection::All);
+            },
+            Some(VirtualKeyCode::Escape) => self.update_input(),
+            _ => {
+                let update = self.input.on_virtual_key_down(app_state_no_data, event);
+                self.is_valid = CssColor::from_str(&self.input.text).is_ok();
+                return update;
+            },
+        }
+
+        Redraw
+    }
+
+    /// Commits the edited text when the color picker loses the focus
+    pub fn on_focus_lost<T>(&mut self, _app_state_no_data: &mut AppStateNoData<T>, _event: &mut CallbackInfo<T>) -> UpdateScreen {
+        let prefix_hash = true;
+        if self.input.text == self.get_color().to_string(prefix_hash) {
+            return DontRedraw;
+        }
+        self.commit();
+        Redraw
+    }
+
+    fn set_color_from_cursor<T>(&mut self, area: ColorPickerArea, app_state_no_data: &AppStateNoData<T>, event: &CallbackInfo<T>) -> UpdateScreen {
+
+        // The callbacks are invoked on the color picker itself, the areas are its children
+        let area_node = event.hit_dom_node.children(event.get_node_hierarchy()).nth(area.get_index())?;
+        let rect = event.get_node_rect(area_node)?;
+
+        let (x, y) = event.get_cursor_in_viewport(app_state_no_data.windows[event.window_id].get_mouse_state())?;
+
+        // The center of a thumb can only move between the edges of the area minus half a thumb
+        let x = (x - rect.origin.x - THUMB_SIZE / 2.0) / (rect.size.width - THUMB_SIZE);
+        let y = (y - rect.origin.y - THUMB_SIZE / 2.0) / (rect.size.height - THUMB_SIZE);
+
+        if !x.is_finite() {
+            return DontRedraw;
+        }
+
+        let old_color = self.get_color();
+        self.set_from_area(area, x, if y.is_finite() { y } else { 0.0 });
+        if self.get_color() != old_color { Redraw } else { DontRedraw }
+    }
+}
+
+/// Returns a row with a thumb that is moved to the `fraction` (0.0 - 1.0) of the row
+fn thumb_row<T>(fraction: f32) -> Dom<T> {
+    Dom::div()
+    .with_child(flex_grow_div("__azul-native-color-picker-before", "__azul_color_picker_before", fraction))
+    .with_child(Dom::div().with_class("__azul-native-color-picker-thumb"))
+    .with_child(flex_grow_div("__azul-native-color-picker-after", "__azul_color_picker_after", 1.0 - fraction))
+}
+
+/// Wraps a hue in degrees to 0 - 360
+fn wrap_hue(hue: f32) -> f32 {
+    let hue = hue % 360.0 + 360.0;
+    if hue >= 360.0 { hue - 360.0 } else { hue }
+}
+
+/// Converts a color from HSV (hue in degrees, saturation and value from 0.0 to 1.0) to RGB (0.0 - 1.0)
+pub fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> (f32, f32, f32) {
+    let chroma = value * saturation;
+    let sector = wrap_hue(hue) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = value - chroma;
+    (r + m, g + m, b + m)
+}
+
+/// Converts a color from RGB (0.0 - 1.0) to HSV (hue in degrees, saturation and value from 0.0 to 1.0)
+pub fn rgb_to_hsv(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let chroma = max - min;
+    let hue = if chroma == 0.0 {
+        0.0
+    } else if max == r {
+        wrap_hue(60.0 * (g - b) / chroma)
+    } else if max == g {
+        60.0 * ((b - r) / chroma + 2.0)
+    } else {
+        60.0 * ((r - g) / chroma + 4.0)
+    };
+    let saturation = if max == 0.0 { 0.0 } else { chroma / max };
+    (hue, saturation, max)
+}
+
+#[test]
+fn test_color_picker_hsv_round_trip() {
+    for color in &["#ff0000", "#00ff00", "#0000ff", "#336699", "#ffffff", "#000000", "#80808080"] {
+        let picker = ColorPickerState::new(CssColor::from_str(color).unwrap());
+        assert_eq!(picker.get_color().to_string(true), color.to_string());
+        assert_eq!(picker.input.text, color.to_string());
+    }
+
+    let (h, s, v) = rgb_to_hsv(0.2, 0.4, 0.6);
+    assert!((h - 210.0).abs() < 0.01);
+    assert!((s - 0.6667).abs() < 0.001);
+    assert!((v - 0.6).abs() < 0.001);
+}
+
+#[test]
+fn test_color_picker_input() {
+    let mut picker = ColorPickerState::new(CssColor::from_str("#ff0000").unwrap());
+
+    picker.input = TextInputState::new("not a color");
+    assert!(!picker.commit());
+    assert_eq!(picker.input.text, "#ff0000");
+
+    picker.input = TextInputState::new("rgba(0, 0, 255, 0.5)");
+    assert!(picker.commit());
+    assert_eq!(picker.input.text, "#0000ff80");
+    assert_eq!(picker.hue, 240.0);
+
+    // Dragging into the gray / black corner keeps the hue
+    picker.set_from_area(ColorPickerArea::Square, 0.0, 1.0);
+    assert_eq!(picker.get_color().to_string(false), "00000080");
+    assert_eq!(picker.hue, 240.0);
+    picker.set_from_area(ColorPickerArea::Alpha, 2.0, 0.0);
+    assert_eq!(picker.alpha, 1.0);
+
+    let opaque = ColorPickerState::new(CssColor::from_str("#12345678").unwrap()).without_alpha();
+    assert_eq!(opaque.input.text, "#123456");
+}
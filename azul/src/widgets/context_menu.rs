@@ -0,0 +1,171 @@
+//! Popup menu with a list of clickable items, usually opened by a right click
+
+use std::fmt;
+use {
+    dom::{Dom, DomString, On, EventFilter, NotEventFilter, HoverEventFilter},
+    callbacks::{Callback, LayoutInfo},
+    overlay::{OverlayAnchor, OverlayPlacement, OverlaySide, OverlayAlign},
+};
+
+pub struct ContextMenu<T> {
+    pub items: Vec<ContextMenuItem<T>>,
+    /// Invoked when the user clicks anywhere outside of the menu, should hide the menu
+    pub on_dismiss: Option<Callback<T>>,
+    pub placement: OverlayPlacement,
+}
+
+pub enum ContextMenuItem<T> {
+    /// Item with a label, invoking the callback when clicked
+    Item(DomString, Callback<T>),
+    /// Horizontal line between two groups of items
+    Separator,
+}
+
+impl<T> Clone for ContextMenu<T> {
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items.clone(),
+            on_dismiss: self.on_dismiss,
+            placement: self.placement,
+        }
+    }
+}
+
+impl<T> fmt::Debug for ContextMenu<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,
+            "ContextMenu {{ items: {:?}, on_dismiss: {:?}, placement: {:?} }}",
+            self.items, self.on_dismiss, self.placement,
+        )
+    }
+}
+
+impl<T> Clone for ContextMenuItem<T> {
+    fn clone(&self) -> Self {
+        use self::ContextMenuItem::*;
+        match self {
+            Item(label, callback) => Item(label.clone(), *callback),
+            Separator => Separator,
+        }
+    }
+}
+
+impl<T> fmt::Debug for ContextMenuItem<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::ContextMenuItem::*;
+        match self {
+            Item(label, callback) => write!(f, "Item({:?}, {:?})", label, callback),
+            Separator => write!(f, "Separator"),
+        }
+    }
+}
+
+impl<T> Default for ContextMenu<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> ContextMenu<T> {
+
+    /// Creates an empty menu that opens to the bottom right of its anchor
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            on_dismiss: None,
+            placement: OverlayPlacement::new(OverlaySide::Bottom).with_align(OverlayAlign::Start),
+        }
+    }
+
+    #[inline]
+    pub fn with_item<S: Into<DomString>>(mut self, label: S, callback: Callback<T>) -> Self {
+        self.items.push(ContextMenuItem::Item(label.into(), callback));
+        self
+    }
+
+    #[inline]
+    pub fn with_separator(mut self) -> Self {
+        self.items.push(ContextMenuItem::Separator);
+        self
+    }
+
+    #[inline]
+    pub fn with_dismiss_callback(self, on_dismiss: Callback<T>) -> Self {
+        Self { on_dismiss: Some(on_dismiss), .. self }
+    }
+
+    #[inline]
+    pub fn with_placement(self, placement: OverlayPlacement) -> Self {
+        Self { placement, .. self }
+    }
+
+    pub fn dom(self) -> Dom<T> {
+        use self::ContextMenuItem::*;
+
+        let mut menu = Dom::div().with_class("__azul-native-context-menu");
+
+        if let Some(on_dismiss) = self.on_dismiss {
+            menu.add_callback(EventFilter::Not(NotEventFilter::Hover(HoverEventFilter::MouseDown)), on_dismiss);
+        }
+
+        for item in self.items {
+            menu.add_child(match item {
+                Item(label, callback) => Dom::div()
+                    .with_class("__azul-native-context-menu-item")
+                    .with_callback(On::MouseUp, callback)
+                    .with_child(Dom::label(label)),
+                Separator => Dom::div().with_class("__azul-native-context-menu-separator"),
+            });
+        }
+
+        menu
+    }
+
+    /// Adds the menu as an overlay, usually anchored to the cursor position
+    /// (`OverlayAnchor::point`) at the time of the right click
+    pub fn show(self, anchor: OverlayAnchor, info: &mut LayoutInfo<T>) {
+        let placement = self.placement;
+        info.add_overlay(anchor, placement, self.dom());
+    }
+}
+
+#[test]
+fn test_context_menu_ui() {
+
+    use callbacks::{CallbackInfo, UpdateScreen, DontRedraw};
+    use app::AppState;
+
+    struct Mock;
+
+    fn callback(_: &mut AppState<Mock>, _: &mut CallbackInfo<Mock>) -> UpdateScreen { DontRedraw }
+
+    let expected = r#"
+        <div class="__azul-native-context-menu">
+            <div class="__azul-native-context-menu-item">
+                <p>Copy</p>
+            </div>
+            <div class="__azul-native-context-menu-separator"></div>
+            <div class="__azul-native-context-menu-item">
+                <p>Paste</p>
+            </div>
+        </div>
+    "#;
+
+    let mut menu: Dom<Mock> = ContextMenu::new()
+        .with_item("Copy", Callback(callback))
+        .with_separator()
+        .with_item("Paste", Callback(callback))
+        .with_dismiss_callback(Callback(callback))
+        .dom();
+
+    let root = &menu.arena.node_data[menu.head];
+    assert_eq!(root.callbacks.len(), 1);
+    assert_eq!(root.callbacks[0].0, EventFilter::Not(NotEventFilter::Hover(HoverEventFilter::MouseDown)));
+
+    // Callbacks can't be written in XML
+    for node in menu.arena.node_data.iter_mut() {
+        node.callbacks.clear();
+    }
+
+    menu.assert_eq(expected);
+}
@@ -0,0 +1,585 @@
+//! Calendar for picking a date with month navigation and optional min / max dates (two-way data binding)
+
+use std::time::{SystemTime, UNIX_EPOCH};
+use {
+    callbacks::{UpdateScreen, Redraw, DontRedraw},
+    dom::{Dom, On, EventFilter, FocusEventFilter, TabIndex},
+    window::FakeWindow,
+    prelude::VirtualKeyCode,
+    callbacks::{CallbackInfo, StackCheckedPointer, DefaultCallback, DefaultCallbackId},
+    app::AppStateNoData,
+};
+
+/// Number of weeks that the calendar shows, six weeks fit every month regardless of the week start
+const WEEKS_SHOWN: usize = 6;
+const DAYS_PER_WEEK: usize = 7;
+
+const MONTH_NAMES: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June",
+    "July", "August", "September", "October", "November", "December",
+];
+
+/// Regions that start the week on Sunday, according to the Unicode CLDR
+const SUNDAY_REGIONS: &[&str] = &[
+    "AG", "AS", "BD", "BR", "BS", "BT", "BW", "BZ", "CA", "CN", "CO", "DM", "DO", "ET", "GT",
+    "GU", "HK", "HN", "ID", "IL", "IN", "JM", "JP", "KE", "KH", "KR", "LA", "MH", "MM", "MO",
+    "MT", "MX", "MZ", "NI", "NP", "PA", "PE", "PH", "PK", "PR", "PT", "PY", "SA", "SG", "SV",
+    "TH", "TT", "TW", "UM", "US", "VE", "VI", "WS", "YE", "ZA", "ZW",
+];
+
+/// Regions that start the week on Saturday, according to the Unicode CLDR
+const SATURDAY_REGIONS: &[&str] = &[
+    "AE", "AF", "BH", "DJ", "DZ", "EG", "IQ", "IR", "JO", "KW", "LY", "OM", "QA", "SD", "SY",
+];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+
+    const ALL: [Weekday; 7] = [
+        Weekday::Monday, Weekday::Tuesday, Weekday::Wednesday, Weekday::Thursday,
+        Weekday::Friday, Weekday::Saturday, Weekday::Sunday,
+    ];
+
+    /// Returns the weekday `days` days after this one
+    pub fn add_days(self, days: i64) -> Self {
+        Self::ALL[floor_mod(self as i64 + days, 7) as usize]
+    }
+
+    /// Two-letter abbreviation, used in the header of the calendar
+    pub fn short_name(self) -> &'static str {
+        match self {
+            Weekday::Monday => "Mo",
+            Weekday::Tuesday => "Tu",
+            Weekday::Wednesday => "We",
+            Weekday::Thursday => "Th",
+            Weekday::Friday => "Fr",
+            Weekday::Saturday => "Sa",
+            Weekday::Sunday => "Su",
+        }
+    }
+
+    /// Returns the first day of the week for a locale such as `"en-US"` or `"de_DE.UTF-8"`.
+    /// Locales without a (known) region start the week on Monday, as in ISO 8601.
+    pub fn first_day_of_week(locale: &str) -> Self {
+        let locale = locale.split(|c| c == '.' || c == '@').next().unwrap_or("");
+        let region = match locale.split(|c| c == '-' || c == '_').nth(1) {
+            Some(s) => s.to_uppercase(),
+            None => return Weekday::Monday,
+        };
+        if SUNDAY_REGIONS.contains(&region.as_str()) {
+            Weekday::Sunday
+        } else if SATURDAY_REGIONS.contains(&region.as_str()) {
+            Weekday::Saturday
+        } else if region == "MV" {
+            Weekday::Friday
+        } else {
+            Weekday::Monday
+        }
+    }
+}
+
+/// Calendar date in the proleptic Gregorian calendar, without a time zone.
+/// Dates are ordered chronologically.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: i32,
+    /// Month from 1 to 12
+    pub month: u8,
+    /// Day of the month from 1 to 31
+    pub day: u8,
+}
+
+impl Date {
+
+    /// Returns `None` if the day doesn't exist in the given month
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Self> {
+        if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+            return None;
+        }
+        Some(Self { year, month, day })
+    }
+
+    /// Returns the current date in UTC
+    pub fn today() -> Self {
+        let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        Self::from_days_since_epoch((seconds / 86_400) as i64)
+    }
+
+    /// Returns the first day of the month of this date
+    pub fn first_of_month(self) -> Self {
+        Self { day: 1, .. self }
+    }
+
+    /// Returns the last day of the month of this date
+    pub fn last_of_month(self) -> Self {
+        Self { day: days_in_month(self.year, self.month), .. self }
+    }
+
+    pub fn weekday(self) -> Weekday {
+        // 1970-01-01 was a Thursday
+        Weekday::Thursday.add_days(self.to_days_since_epoch())
+    }
+
+    pub fn add_days(self, days: i64) -> Self {
+        Self::from_days_since_epoch(self.to_days_since_epoch() + days)
+    }
+
+    /// Adds a number of months, the day is clamped to the length of the resulting month
+    /// (i.e. one month after January 31st is the last day of February)
+    pub fn add_months(self, months: i32) -> Self {
+        let month_index = i64::from(self.year) * 12 + i64::from(self.month) - 1 + i64::from(months);
+        let year = floor_div(month_index, 12) as i32;
+        let month = floor_mod(month_index, 12) as u8 + 1;
+        Self { year, month, day: self.day.min(days_in_month(year, month)) }
+    }
+
+    /// Returns the number of days since 1970-01-01 (negative for earlier dates)
+    pub fn to_days_since_epoch(self) -> i64 {
+        // See http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+        let month = i64::from(self.month);
+        let year = i64::from(self.year) - if month <= 2 { 1 } else { 0 };
+        let era = floor_div(year, 400);
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// Inverse of `to_days_since_epoch`
+    pub fn from_days_since_epoch(days: i64) -> Self {
+        // See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let days = days + 719_468;
+        let era = floor_div(days, 146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_from_march = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+        let month = if month_from_march < 10 { month_from_march + 3 } else { month_from_march - 9 };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        Self { year: year as i32, month: month as u8, day: day as u8 }
+    }
+}
+
+pub fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+pub fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Division rounding towards negative infinity (for a positive `divisor`),
+/// so that dates before 1970 work the same as later ones
+fn floor_div(value: i64, divisor: i64) -> i64 {
+    let quotient = value / divisor;
+    if value % divisor < 0 { quotient - 1 } else { quotient }
+}
+
+/// Remainder of `floor_div`, always in `0..divisor`
+fn floor_mod(value: i64, divisor: i64) -> i64 {
+    value - floor_div(value, divisor) * divisor
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+struct DatePickerCallbacks {
+    previous_month: DefaultCallbackId,
+    next_month: DefaultCallbackId,
+    day_click: DefaultCallbackId,
+    virtual_key_down: DefaultCallbackId,
+    focus_lost: DefaultCallbackId,
+}
+
+#[derive(Debug, Default, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct DatePicker {
+    callbacks: Option<DatePickerCallbacks>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DatePickerState {
+    /// Selected date, always between `min` and `max` if set via `select`
+    pub selected: Option<Date>,
+    /// Earliest date that can be selected
+    pub min: Option<Date>,
+    /// Latest date that can be selected
+    pub max: Option<Date>,
+    /// First column of the calendar, see `Weekday::first_day_of_week` for locale defaults
+    pub first_weekday: Weekday,
+    /// Disabled date pickers can't be focused or changed
+    pub disabled: bool,
+    /// First day of the month that is currently shown
+    shown_month: Date,
+    /// Day that is highlighted via the keyboard
+    highlighted: Option<Date>,
+}
+
+impl Default for DatePickerState {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl DatePickerState {
+
+    /// Creates a date picker that shows the month of the `selected` date, or the current month
+    pub fn new(selected: Option<Date>) -> Self {
+        Self {
+            selected,
+            min: None,
+            max: None,
+            first_weekday: Weekday::Monday,
+            disabled: false,
+            shown_month: selected.unwrap_or_else(Date::today).first_of_month(),
+            highlighted: None,
+        }
+    }
+
+    #[inline]
+    pub fn with_min(self, min: Date) -> Self {
+        Self { min: Some(min), .. self }
+    }
+
+    #[inline]
+    pub fn with_max(self, max: Date) -> Self {
+        Self { max: Some(max), .. self }
+    }
+
+    #[inline]
+    pub fn with_first_weekday(self, first_weekday: Weekday) -> Self {
+        Self { first_weekday, .. self }
+    }
+
+    /// Starts the week on the first day of the week of the locale (i.e. `"en-US"`)
+    #[inline]
+    pub fn with_locale(self, locale: &str) -> Self {
+        self.with_first_weekday(Weekday::first_day_of_week(locale))
+    }
+
+    #[inline]
+    pub fn with_disabled(self, disabled: bool) -> Self {
+        Self { disabled, .. self }
+    }
+
+    /// Returns whether the date is between `min` and `max`
+    pub fn is_selectable(&self, date: Date) -> bool {
+        self.min.map_or(true, |min| date >= min) && self.max.map_or(true, |max| date <= max)
+    }
+
+    /// Selects the date and shows its month, ignored if the date isn't selectable.
+    /// Returns whether the date was selected.
+    pub fn select(&mut self, date: Date) -> bool {
+        if !self.is_selectable(date) {
+            return false;
+        }
+        self.selected = Some(date);
+        self.shown_month = date.first_of_month();
+        true
+    }
+
+    /// Returns the first day of the month that is currently shown
+    pub fn get_shown_month(&self) -> Date {
+        self.shown_month
+    }
+
+    /// Shows the month of the date, without selecting it
+    pub fn show_month(&mut self, date: Date) {
+        self.shown_month = date.first_of_month();
+    }
+
+    /// Whether the month before the shown month contains selectable dates
+    pub fn has_previous_month(&self) -> bool {
+        self.min.map_or(true, |min| self.shown_month > min.first_of_month())
+    }
+
+    /// Whether the month after the shown month contains selectable dates
+    pub fn has_next_month(&self) -> bool {
+        self.max.map_or(true, |max| self.shown_month < max.first_of_month())
+    }
+
+    /// Returns the days that the calendar shows, starting with the
+    /// `first_weekday` of the week that contains the first day of the month
+    pub fn get_visible_days(&self) -> Vec<Date> {
+        let offset = floor_mod(self.shown_month.weekday() as i64 - self.first_weekday as i64, 7);
+        let first = self.shown_month.add_days(-offset);
+        (0..(WEEKS_SHOWN * DAYS_PER_WEEK) as i64).map(|day| first.add_days(day)).collect()
+    }
+
+    /// Moves the keyboard highlight by a number of days (or months), clamped to
+    /// `min` and `max`, and shows the month of the highlighted day
+    fn move_highlight<F: Fn(Date) -> Date>(&mut self, move_fn: F) {
+        let start = self.highlighted.or(self.selected).unwrap_or(self.shown_month);
+        let mut target = move_fn(start);
+        if let Some(min) = self.min {
+            target = target.max(min);
+        }
+        if let Some(max) = self.max {
+            target = target.min(max);
+        }
+        self.highlighted = Some(target);
+        self.shown_month = target.first_of_month();
+    }
+}
+
+impl DatePicker {
+
+    pub fn new() -> Self {
+        DatePicker { callbacks: None }
+    }
+
+    pub fn bind<T>(self, window: &mut FakeWindow<T>, field: &DatePickerState, data: &T) -> Self {
+        let ptr = StackCheckedPointer::new(data, field);
+        let callbacks = ptr.map(|ptr| DatePickerCallbacks {
+            previous_month: window.add_callback(ptr, DefaultCallback(DatePickerState::on_previous_month_private)),
+            next_month: window.add_callback(ptr, DefaultCallback(DatePickerState::on_next_month_private)),
+            day_click: window.add_callback(ptr, DefaultCallback(DatePickerState::on_day_click_private)),
+            virtual_key_down: window.add_callback(ptr, DefaultCallback(DatePickerState::on_virtual_key_down_private)),
+            focus_lost: window.add_callback(ptr, DefaultCallback(DatePickerState::on_focus_lost_private)),
+        });
+
+        Self { callbacks }
+    }
+
+    pub fn dom<T>(&self, field: &DatePickerState) -> Dom<T> {
+
+        let callbacks = self.callbacks.filter(|_| !field.disabled);
+
+        let mut parent_div =
+            Dom::div()
+            .with_class("__azul-native-date-picker")
+            .with_disabled(field.disabled);
+
+        if !field.disabled {
+            parent_div.set_tab_index(TabIndex::Auto);
+            if let Some(callbacks) = callbacks {
+                parent_div.add_default_callback_id(EventFilter::Focus(FocusEventFilter::VirtualKeyDown), callbacks.virtual_key_down);
+                parent_div.add_default_callback_id(On::FocusLost, callbacks.focus_lost);
+            }
+        }
+
+        let mut previous_button = Dom::label("\u{2039}")
+            .with_class("__azul-native-date-picker-nav")
+            .with_disabled(field.disabled || !field.has_previous_month());
+        let mut next_button = Dom::label("\u{203A}")
+            .with_class("__azul-native-date-picker-nav")
+            .with_disabled(field.disabled || !field.has_next_month());
+
+        if let Some(callbacks) = callbacks {
+            previous_button.add_default_callback_id(On::LeftMouseUp, callbacks.previous_month);
+            next_button.add_default_callback_id(On::LeftMouseUp, callbacks.next_month);
+        }
+
+        let title = format!("{} {}", MONTH_NAMES[field.shown_month.month as usize - 1], field.shown_month.year);
+
+        let header = Dom::div()
+            .with_class("__azul-native-date-picker-header")
+            .with_child(previous_button)
+            .with_child(Dom::label(title).with_class("__azul-native-date-picker-title"))
+            .with_child(next_button);
+
+        let weekdays = (0..DAYS_PER_WEEK as i64)
+            .map(|day| Dom::label(field.first_weekday.add_days(day).short_name()).with_class("__azul-native-date-picker-weekday"))
+            .collect::<Dom<T>>()
+            .with_class("__azul-native-date-picker-weekdays");
+
+        let today = Date::today();
+        let visible_days = field.get_visible_days();
+
+        // Every week is a row, the clicked day is found via the index of the row and the column
+        let weeks = visible_days.chunks(DAYS_PER_WEEK).map(|week| {
+            let mut week_div = week.iter().map(|date| {
+                let mut day_div = Dom::div()
+                    .with_class("__azul-native-date-picker-day")
+                    .with_disabled(field.disabled || !field.is_selectable(*date))
+                    .with_child(Dom::label(format!("{}", date.day)));
+                if date.month != field.shown_month.month {
+                    day_div.add_class("__azul-native-date-picker-day-other-month");
+                }
+                if *date == today {
+                    day_div.add_class("__azul-native-date-picker-day-today");
+                }
+                if Some(*date) == field.selected {
+                    day_div.add_class("__azul-native-date-picker-day-selected");
+                }
+                if Some(*date) == field.highlighted {
+                    day_div.add_class("__azul-native-date-picker-day-highlighted");
+                }
+                day_div
+            })
+            .collect::<Dom<T>>()
+            .with_class("__azul-native-date-picker-week");
+
+            if let Some(callbacks) = callbacks {
+                week_div.add_default_callback_id(On::LeftMouseUp, callbacks.day_click);
+            }
+
+            week_div
+        })
+        .collect::<Dom<T>>()
+        .with_class("__azul-native-date-picker-days");
+
+        parent_div
+            .with_child(header)
+            .with_child(weekdays)
+            .with_child(weeks)
+    }
+}
+
+impl DatePickerState {
+
+    fn on_previous_month_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_previous_month, app_state_no_data, window_event) }
+    }
+
+    fn on_next_month_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_next_month, app_state_no_data, window_event) }
+    }
+
+    fn on_day_click_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_day_click, app_state_no_data, window_event) }
+    }
+
+    fn on_virtual_key_down_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_virtual_key_down, app_state_no_data, window_event) }
+    }
+
+    fn on_focus_lost_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_focus_lost, app_state_no_data, window_event) }
+    }
+
+    pub fn on_previous_month<T>(&mut self, _app_state_no_data: &mut AppStateNoData<T>, _event: &mut CallbackInfo<T>) -> UpdateScreen {
+        if self.disabled || !self.has_previous_month() {
+            return DontRedraw;
+        }
+        self.shown_month = self.shown_month.add_months(-1);
+        self.highlighted = None;
+        Redraw
+    }
+
+    pub fn on_next_month<T>(&mut self, _app_state_no_data: &mut AppStateNoData<T>, _event: &mut CallbackInfo<T>) -> UpdateScreen {
+        if self.disabled || !self.has_next_month() {
+            return DontRedraw;
+        }
+        self.shown_month = self.shown_month.add_months(1);
+        self.highlighted = None;
+        Redraw
+    }
+
+    /// Selects the clicked day, the callback is attached to every week (row) of the calendar
+    pub fn on_day_click<T>(&mut self, _app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        if self.disabled {
+            return DontRedraw;
+        }
+
+        let (week, _) = event.get_index_in_parent(event.hit_dom_node)?;
+        let day = event.get_target_child_index()?;
+        let date = *self.get_visible_days().get(week * DAYS_PER_WEEK + day)?;
+
+        self.highlighted = None;
+        if self.select(date) { Redraw } else { DontRedraw }
+    }
+
+    /// The arrow keys move the highlighted day by a day / week, PageUp / PageDown by a month
+    /// (with Shift by a year) and Home / End to the first / last day of the month.
+    /// Return or Space select the highlighted day.
+    pub fn on_virtual_key_down<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        if self.disabled {
+            return DontRedraw;
+        }
+
+        let keyboard_state = app_state_no_data.windows[event.window_id].get_keyboard_state();
+        let months = if keyboard_state.shift_down { 12 } else { 1 };
+
+        match keyboard_state.latest_virtual_keycode {
+            Some(VirtualKeyCode::Left) => self.move_highlight(|date| date.add_days(-1)),
+            Some(VirtualKeyCode::Right) => self.move_highlight(|date| date.add_days(1)),
+            Some(VirtualKeyCode::Up) => self.move_highlight(|date| date.add_days(-(DAYS_PER_WEEK as i64))),
+            Some(VirtualKeyCode::Down) => self.move_highlight(|date| date.add_days(DAYS_PER_WEEK as i64)),
+            Some(VirtualKeyCode::PageUp) => self.move_highlight(|date| date.add_months(-months)),
+            Some(VirtualKeyCode::PageDown) => self.move_highlight(|date| date.add_months(months)),
+            Some(VirtualKeyCode::Home) => self.move_highlight(Date::first_of_month),
+            Some(VirtualKeyCode::End) => self.move_highlight(Date::last_of_month),
+            Some(VirtualKeyCode::Return) | Some(VirtualKeyCode::Space) => {
+                let highlighted = self.highlighted?;
+                self.select(highlighted);
+            },
+            _ => return DontRedraw,
+        }
+
+        Redraw
+    }
+
+    /// Removes the keyboard highlight when the date picker loses the focus
+    pub fn on_focus_lost<T>(&mut self, _app_state_no_data: &mut AppStateNoData<T>, _event: &mut CallbackInfo<T>) -> UpdateScreen {
+        if self.highlighted.is_none() {
+            return DontRedraw;
+        }
+        self.highlighted = None;
+        Redraw
+    }
+}
+
+#[test]
+fn test_date_arithmetic() {
+    let date = Date::new(2024, 1, 31).unwrap();
+    assert_eq!(date.to_days_since_epoch(), 19_753);
+    assert_eq!(Date::from_days_since_epoch(19_753), date);
+    assert_eq!(Date::from_days_since_epoch(0), Date::new(1970, 1, 1).unwrap());
+    assert_eq!(date.weekday(), Weekday::Wednesday);
+    assert_eq!(date.add_months(1), Date::new(2024, 2, 29).unwrap());
+    assert_eq!(date.add_months(-13), Date::new(2022, 12, 31).unwrap());
+    assert_eq!(date.add_days(30), Date::new(2024, 3, 1).unwrap());
+    assert_eq!(Date::new(2023, 2, 29), None);
+    assert_eq!(Date::new(1900, 3, 1).unwrap().add_days(-1), Date::new(1900, 2, 28).unwrap());
+}
+
+#[test]
+fn test_date_picker_week_start() {
+    assert_eq!(Weekday::first_day_of_week("en-US"), Weekday::Sunday);
+    assert_eq!(Weekday::first_day_of_week("de_DE.UTF-8"), Weekday::Monday);
+    assert_eq!(Weekday::first_day_of_week("ar_EG"), Weekday::Saturday);
+    assert_eq!(Weekday::first_day_of_week("fr"), Weekday::Monday);
+
+    // October 2026 starts on a Thursday
+    let october = Date::new(2026, 10, 1).unwrap();
+    let monday_first = DatePickerState::new(Some(october));
+    assert_eq!(monday_first.get_visible_days()[0], Date::new(2026, 9, 28).unwrap());
+    let sunday_first = DatePickerState::new(Some(october)).with_locale("en-US");
+    assert_eq!(sunday_first.get_visible_days()[0], Date::new(2026, 9, 27).unwrap());
+    assert_eq!(sunday_first.get_visible_days().len(), WEEKS_SHOWN * DAYS_PER_WEEK);
+}
+
+#[test]
+fn test_date_picker_min_max() {
+    let min = Date::new(2026, 10, 10).unwrap();
+    let max = Date::new(2026, 11, 20).unwrap();
+    let mut picker = DatePickerState::new(Some(Date::new(2026, 10, 15).unwrap())).with_min(min).with_max(max);
+
+    assert!(!picker.has_previous_month());
+    assert!(picker.has_next_month());
+    assert!(!picker.select(Date::new(2026, 10, 9).unwrap()));
+    assert_eq!(picker.selected, Date::new(2026, 10, 15));
+
+    picker.move_highlight(|date| date.add_months(-1));
+    assert_eq!(picker.highlighted, Some(min));
+    picker.move_highlight(|date| date.add_months(12));
+    assert_eq!(picker.highlighted, Some(max));
+    assert_eq!(picker.get_shown_month(), Date::new(2026, 11, 1).unwrap());
+    assert!(!picker.has_next_month());
+}
@@ -0,0 +1,604 @@
+//! Select box / combo box (two-way data binding) and the popup list of options that
+//! opens below it, which can also be used on its own
+
+use std::fmt;
+use {
+    dom::{Dom, DomString, On, EventFilter, NotEventFilter, HoverEventFilter, FocusEventFilter, TabIndex},
+    callbacks::{Callback, LayoutInfo, UpdateScreen, Redraw, DontRedraw},
+    callbacks::{CallbackInfo, StackCheckedPointer, DefaultCallback, DefaultCallbackId},
+    overlay::{OverlayAnchor, OverlayPlacement, OverlaySide, OverlayAlign},
+    window::FakeWindow,
+    prelude::VirtualKeyCode,
+    app::AppStateNoData,
+    widgets::text_input::{TextInputState, Selection},
+    id_tree::NodeId,
+};
+
+/// How many options PageUp / PageDown move the highlight
+const PAGE_OPTIONS: isize = 10;
+/// Index of the arrow in the closed dropdown, clicking it opens editable combo boxes
+const ARROW_INDEX: usize = 1;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DropdownList {
+    pub options: Vec<DomString>,
+    /// Index of the option that is highlighted as the current value
+    pub selected: Option<usize>,
+    /// Index of the option that is highlighted via the keyboard
+    pub highlighted: Option<usize>,
+    pub placement: OverlayPlacement,
+}
+
+impl DropdownList {
+
+    /// Creates a list that opens below its anchor and flips above it if there isn't enough space
+    pub fn new<S: Into<DomString>>(options: Vec<S>) -> Self {
+        Self {
+            options: options.into_iter().map(|option| option.into()).collect(),
+            selected: None,
+            highlighted: None,
+            placement: OverlayPlacement::new(OverlaySide::Bottom).with_align(OverlayAlign::Start),
+        }
+    }
+
+    #[inline]
+    pub fn with_selected(self, selected: Option<usize>) -> Self {
+        Self { selected, .. self }
+    }
+
+    #[inline]
+    pub fn with_highlighted(self, highlighted: Option<usize>) -> Self {
+        Self { highlighted, .. self }
+    }
+
+    #[inline]
+    pub fn with_placement(self, placement: OverlayPlacement) -> Self {
+        Self { placement, .. self }
+    }
+
+    /// `on_select` is invoked when an option is clicked - the index of the clicked option is
+    /// `CallbackInfo::target_index_in_parent()`. `on_dismiss` is invoked on clicks outside of the list.
+    pub fn dom<T>(self, on_select: Callback<T>, on_dismiss: Callback<T>) -> Dom<T> {
+        self.list_dom(Some(on_select))
+            .with_callback(EventFilter::Not(NotEventFilter::Hover(HoverEventFilter::MouseDown)), on_dismiss)
+    }
+
+    /// Same as `dom`, but with default callbacks: `on_select` is invoked on the list itself,
+    /// the index of the clicked option is `CallbackInfo::get_target_child_index()`
+    pub fn dom_with_default_callbacks<T>(self, on_select: DefaultCallbackId, on_dismiss: DefaultCallbackId) -> Dom<T> {
+        let mut list = self.list_dom(None);
+        list.add_default_callback_id(On::MouseUp, on_select);
+        list.add_default_callback_id(EventFilter::Not(NotEventFilter::Hover(HoverEventFilter::MouseDown)), on_dismiss);
+        list
+    }
+
+    fn list_dom<T>(self, on_select: Option<Callback<T>>) -> Dom<T> {
+
+        let mut list = Dom::div().with_class("__azul-native-dropdown-list");
+
+        for (option_idx, option) in self.options.into_iter().enumerate() {
+            let mut option_dom = Dom::div()
+                .with_class("__azul-native-dropdown-option")
+                .with_child(Dom::label(option));
+            if let Some(on_select) = on_select {
+                option_dom.add_callback(On::MouseUp, on_select);
+            }
+            if self.selected == Some(option_idx) {
+                option_dom.add_class("__azul-native-dropdown-option-selected");
+            }
+            if self.highlighted == Some(option_idx) {
+                option_dom.add_class("__azul-native-dropdown-option-highlighted");
+            }
+            list.add_child(option_dom);
+        }
+
+        list
+    }
+
+    /// Adds the list as an overlay below the `anchor` node, usually the
+    /// node that displays the current value of the dropdown
+    pub fn show<T>(self, anchor: NodeId, on_select: Callback<T>, on_dismiss: Callback<T>, info: &mut LayoutInfo<T>) {
+        let placement = self.placement;
+        info.add_overlay(OverlayAnchor::Node(anchor), placement, self.dom(on_select, on_dismiss));
+    }
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+struct DropdownCallbacks {
+    toggle: DefaultCallbackId,
+    select: DefaultCallbackId,
+    dismiss: DefaultCallbackId,
+    text_input: DefaultCallbackId,
+    virtual_key_down: DefaultCallbackId,
+    focus_lost: DefaultCallbackId,
+}
+
+/// Select box that shows the selected item and opens a `DropdownList` with all items when
+/// clicked. The list is drawn as an overlay on top of the other content and closes on
+/// outside clicks or on Escape. Typing while the dropdown is focused filters the list.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct Dropdown {
+    /// HTML id of the dropdown, the list is anchored to the node with this id
+    id: DomString,
+    callbacks: Option<DropdownCallbacks>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DropdownState<I> {
+    pub items: Vec<I>,
+    /// Index of the selected item, `None` if nothing (or, for editable combo boxes,
+    /// a text that doesn't match any item) is selected
+    pub selected: Option<usize>,
+    /// Disabled dropdowns can't be focused or opened
+    pub disabled: bool,
+    /// Text of an editable combo box, `None` for regular select boxes
+    pub input: Option<TextInputState>,
+    is_open: bool,
+    /// Text typed while the list is open, only items containing it are shown
+    filter: String,
+    /// Index of the item (in `items`) that is highlighted via the keyboard
+    highlighted: Option<usize>,
+}
+
+impl<I> Default for DropdownState<I> {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl<I> DropdownState<I> {
+
+    pub fn new(items: Vec<I>) -> Self {
+        Self {
+            items,
+            selected: None,
+            disabled: false,
+            input: None,
+            is_open: false,
+            filter: String::new(),
+            highlighted: None,
+        }
+    }
+
+    #[inline]
+    pub fn with_disabled(self, disabled: bool) -> Self {
+        Self { disabled, .. self }
+    }
+
+    /// Returns the selected item
+    pub fn get_selected(&self) -> Option<&I> {
+        self.items.get(self.selected?)
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    /// Opens the list and highlights the selected item
+    pub fn open(&mut self) {
+        if self.disabled || self.items.is_empty() {
+            return;
+        }
+        self.is_open = true;
+        self.filter.clear();
+        self.highlighted = self.selected;
+    }
+
+    /// Closes the list without changing the selection
+    pub fn close(&mut self) {
+        self.is_open = false;
+        self.highlighted = None;
+        self.filter.clear();
+    }
+}
+
+impl<I: fmt::Display> DropdownState<I> {
+
+    #[inline]
+    pub fn with_selected(self, selected: usize) -> Self {
+        let mut dropdown = self;
+        dropdown.select(selected);
+        dropdown
+    }
+
+    /// Turns the dropdown into a combo box with a text field, which filters the
+    /// list while typing and also accepts text that doesn't match any item
+    #[inline]
+    pub fn editable(self) -> Self {
+        let text = self.get_selected().map(|item| item.to_string()).unwrap_or_default();
+        Self { input: Some(TextInputState::new(text)), .. self }
+    }
+
+    /// Selects the item at the `index` and closes the list, ignored if the `index` is out of range
+    pub fn select(&mut self, index: usize) {
+        if index >= self.items.len() {
+            return;
+        }
+        self.selected = Some(index);
+        if let Some(input) = &mut self.input {
+            *input = TextInputState::new(self.items[index].to_string());
+        }
+        self.close();
+    }
+
+    /// Returns the indices of the items that contain the typed text (case-insensitive)
+    pub fn get_visible_items(&self) -> Vec<usize> {
+        let filter = self.filter.to_lowercase();
+        self.items.iter().enumerate()
+            .filter(|(_, item)| filter.is_empty() || item.to_string().to_lowercase().contains(&filter))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Moves the highlight by `offset` visible items - opens the list if it is closed
+    pub fn move_highlight(&mut self, offset: isize) {
+        if !self.is_open {
+            self.open();
+            return;
+        }
+        let visible = self.get_visible_items();
+        if visible.is_empty() {
+            return;
+        }
+        let last = visible.len() as isize - 1;
+        let next = match self.highlighted.and_then(|highlighted| visible.iter().position(|index| *index == highlighted)) {
+            Some(position) => (position as isize + offset).max(0).min(last),
+            None if offset < 0 => last,
+            None => 0,
+        };
+        self.highlighted = Some(visible[next as usize]);
+    }
+
+    /// Sets the text that the items are filtered by and highlights the first match
+    fn set_filter(&mut self, filter: String) {
+        self.filter = filter;
+        self.is_open = true;
+        self.highlighted = self.get_visible_items().first().cloned();
+    }
+
+    /// Selects the highlighted item - in editable combo boxes, text without a
+    /// highlighted item is accepted as is, unless it matches an item exactly
+    fn commit(&mut self) {
+        if let Some(highlighted) = self.highlighted.filter(|_| self.is_open) {
+            self.select(highlighted);
+            return;
+        }
+        if let Some(input) = &self.input {
+            let matching_item = self.items.iter().position(|item| item.to_string() == input.text);
+            self.selected = matching_item;
+        }
+        self.close();
+    }
+}
+
+impl Dropdown {
+
+    /// Creates a dropdown with the given HTML id, which has to be unique within the window
+    pub fn new<S: Into<DomString>>(id: S) -> Self {
+        Self { id: id.into(), callbacks: None }
+    }
+
+    pub fn bind<T, I: fmt::Display>(self, window: &mut FakeWindow<T>, field: &DropdownState<I>, data: &T) -> Self {
+        let ptr = StackCheckedPointer::new(data, field);
+        let callbacks = ptr.map(|ptr| DropdownCallbacks {
+            toggle: window.add_callback(ptr, DefaultCallback(DropdownState::<I>::on_mouse_down_private)),
+            select: window.add_callback(ptr, DefaultCallback(DropdownState::<I>::on_select_private)),
+            dismiss: window.add_callback(ptr, DefaultCallback(DropdownState::<I>::on_dismiss_private)),
+            text_input: window.add_callback(ptr, DefaultCallback(DropdownState::<I>::on_text_input_private)),
+            virtual_key_down: window.add_callback(ptr, DefaultCallback(DropdownState::<I>::on_virtual_key_down_private)),
+            focus_lost: window.add_callback(ptr, DefaultCallback(DropdownState::<I>::on_focus_lost_private)),
+        });
+
+        Self { callbacks, .. self }
+    }
+
+    /// Renders the closed dropdown and, if the dropdown is open, adds the list as an overlay
+    pub fn dom<T, I: fmt::Display>(&self, field: &DropdownState<I>, info: &mut LayoutInfo<T>) -> Dom<T> {
+
+        let mut parent_div =
+            Dom::div()
+            .with_class("__azul-native-dropdown")
+            .with_id(self.id.clone())
+            .with_disabled(field.disabled);
+
+        if field.input.is_some() {
+            parent_div.add_class("__azul-native-dropdown-editable");
+        }
+
+        if field.is_open {
+            parent_div.add_class("__azul-native-dropdown-open");
+        }
+
+        if !field.disabled {
+            parent_div.set_tab_index(TabIndex::Auto);
+            if let Some(callbacks) = self.callbacks {
+                parent_div.add_default_callback_id(On::LeftMouseDown, callbacks.toggle);
+                parent_div.add_default_callback_id(EventFilter::Focus(FocusEventFilter::TextInput), callbacks.text_input);
+                parent_div.add_default_callback_id(EventFilter::Focus(FocusEventFilter::VirtualKeyDown), callbacks.virtual_key_down);
+                parent_div.add_default_callback_id(On::FocusLost, callbacks.focus_lost);
+            }
+        }
+
+        let value = match &field.input {
+            Some(input) => input.text.clone(),
+            None => field.get_selected().map(|item| item.to_string()).unwrap_or_default(),
+        };
+
+        // Order has to match ARROW_INDEX
+        parent_div.add_child(Dom::label(value).with_class("__azul-native-dropdown-value"));
+        parent_div.add_child(Dom::label("\u{25BE}").with_class("__azul-native-dropdown-arrow"));
+
+        if let (Some(list), Some(callbacks)) = (self.list_dom(field), self.callbacks) {
+            let placement = list.placement;
+            let list_dom = list.dom_with_default_callbacks(callbacks.select, callbacks.dismiss);
+            info.add_overlay(OverlayAnchor::NodeWithId(self.id.clone()), placement, list_dom);
+        }
+
+        parent_div
+    }
+
+    /// Returns the list of visible items if the dropdown is open
+    fn list_dom<I: fmt::Display>(&self, field: &DropdownState<I>) -> Option<DropdownList> {
+
+        if !field.is_open || field.disabled {
+            return None;
+        }
+
+        let visible = field.get_visible_items();
+        if visible.is_empty() {
+            return None;
+        }
+
+        let position_of = |index: Option<usize>| index.and_then(|index| visible.iter().position(|visible| *visible == index));
+
+        Some(
+            DropdownList::new(visible.iter().map(|index| field.items[*index].to_string()).collect())
+            .with_selected(position_of(field.selected))
+            .with_highlighted(position_of(field.highlighted))
+        )
+    }
+}
+
+impl<I: fmt::Display> DropdownState<I> {
+
+    fn on_mouse_down_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_mouse_down, app_state_no_data, window_event) }
+    }
+
+    fn on_select_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_select, app_state_no_data, window_event) }
+    }
+
+    fn on_dismiss_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_dismiss, app_state_no_data, window_event) }
+    }
+
+    fn on_text_input_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_text_input, app_state_no_data, window_event) }
+    }
+
+    fn on_virtual_key_down_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_virtual_key_down, app_state_no_data, window_event) }
+    }
+
+    fn on_focus_lost_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_focus_lost, app_state_no_data, window_event) }
+    }
+
+    /// Opens or closes the list - editable combo boxes only open when the arrow is clicked,
+    /// so that the text can be edited with the mouse
+    pub fn on_mouse_down<T>(&mut self, _app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        if self.disabled || (self.input.is_some() && event.get_target_child_index() != Some(ARROW_INDEX)) {
+            return DontRedraw;
+        }
+        // If the list was open, the click on the dropdown was also an outside click on the list,
+        // but hover callbacks run before `on_dismiss`, which then sees that the list is closed
+        if self.is_open {
+            self.close();
+        } else {
+            self.open();
+        }
+        Redraw
+    }
+
+    /// Selects the clicked item of the list
+    pub fn on_select<T>(&mut self, _app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        let position = event.get_target_child_index()?;
+        let index = *self.get_visible_items().get(position)?;
+        self.select(index);
+        Redraw
+    }
+
+    /// Closes the list when the user clicks outside of it
+    pub fn on_dismiss<T>(&mut self, _app_state_no_data: &mut AppStateNoData<T>, _event: &mut CallbackInfo<T>) -> UpdateScreen {
+        if !self.is_open {
+            return DontRedraw;
+        }
+        self.commit_or_close();
+        Redraw
+    }
+
+    /// Typed characters filter the list (type-ahead), editable combo boxes also edit their text
+    pub fn on_text_input<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        if self.disabled {
+            return DontRedraw;
+        }
+
+        if let Some(input) = &mut self.input {
+            let update = input.on_text_input(app_state_no_data, event);
+            let filter = input.text.clone();
+            self.set_filter(filter);
+            return update;
+        }
+
+        let current_char = app_state_no_data.windows[event.window_id].get_keyboard_state().current_char;
+        match current_char {
+            // Space opens the list (see on_virtual_key_down), unless the user is already typing
+            Some(' ') if self.filter.is_empty() => DontRedraw,
+            Some(c) if !c.is_control() => {
+                let mut filter = self.filter.clone();
+                filter.push(c);
+                self.set_filter(filter);
+                Redraw
+            },
+            _ => DontRedraw,
+        }
+    }
+
+    /// Up / Down (and PageUp / PageDown, Home / End) move the highlight, opening the list
+    /// if necessary. Return selects the highlighted item, Escape closes the list.
+    pub fn on_virtual_key_down<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        if self.disabled {
+            return DontRedraw;
+        }
+
+        let keyboard_state = app_state_no_data.windows[event.window_id].get_keyboard_state();
+        let is_editable = self.input.is_some();
+
+        match keyboard_state.latest_virtual_keycode {
+            Some(VirtualKeyCode::Down) if keyboard_state.alt_down && !self.is_open => self.open(),
+            Some(VirtualKeyCode::Up) if keyboard_state.alt_down && self.is_open => self.commit_or_close(),
+            Some(VirtualKeyCode::Down) => self.move_highlight(1),
+            Some(VirtualKeyCode::Up) => self.move_highlight(-1),
+            Some(VirtualKeyCode::PageDown) => self.move_highlight(PAGE_OPTIONS),
+            Some(VirtualKeyCode::PageUp) => self.move_highlight(-PAGE_OPTIONS),
+            Some(VirtualKeyCode::Home) if !is_editable && self.is_open => self.move_highlight(-(self.items.len() as isize)),
+            Some(VirtualKeyCode::End) if !is_editable && self.is_open => self.move_highlight(self.items.len() as isize),
+            Some(VirtualKeyCode::Space) if !is_editable && !self.is_open => self.open(),
+            Some(VirtualKeyCode::Space) if !is_editable && self.filter.is_empty() => self.commit(),
+            Some(VirtualKeyCode::Return) => {
+                self.commit();
+                if let Some(input) = &mut self.input {
+                    input.selection = Some(Selection::All);
+                }
+            },
+            Some(VirtualKeyCode::Escape) if self.is_open => self.close(),
+            Some(VirtualKeyCode::Back) if !is_editable && !self.filter.is_empty() => {
+                let mut filter = self.filter.clone();
+                filter.pop();
+                self.set_filter(filter);
+            },
+            _ => {
+                let update = match &mut self.input {
+                    Some(input) => input.on_virtual_key_down(app_state_no_data, event),
+                    None => return DontRedraw,
+                };
+                let filter = self.input.as_ref().map(|input| input.text.clone()).unwrap_or_default();
+                if filter != self.filter {
+                    self.set_filter(filter);
+                }
+                return update;
+            },
+        }
+
+        Redraw
+    }
+
+    /// Closes the list when the focus moves to another node via the keyboard. Clicks on the
+    /// list also remove the focus from the dropdown, these are handled by `on_select` / `on_dismiss`.
+    pub fn on_focus_lost<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        if app_state_no_data.windows[event.window_id].get_mouse_state().mouse_down() {
+            return DontRedraw;
+        }
+        if !self.is_open && self.input.is_none() {
+            return DontRedraw;
+        }
+        self.commit_or_close();
+        Redraw
+    }
+
+    /// Editable combo boxes keep the typed text when the list is closed, select boxes discard the highlight
+    fn commit_or_close(&mut self) {
+        if self.input.is_some() {
+            self.highlighted = None;
+            self.commit();
+        } else {
+            self.close();
+        }
+    }
+}
+
+#[test]
+fn test_dropdown_list_ui() {
+
+    use callbacks::{CallbackInfo, UpdateScreen, DontRedraw};
+    use app::AppState;
+
+    struct Mock;
+
+    fn callback(_: &mut AppState<Mock>, _: &mut CallbackInfo<Mock>) -> UpdateScreen { DontRedraw }
+
+    let expected = r#"
+        <div class="__azul-native-dropdown-list">
+            <div class="__azul-native-dropdown-option">
+                <p>A</p>
+            </div>
+            <div class="__azul-native-dropdown-option __azul-native-dropdown-option-selected">
+                <p>B</p>
+            </div>
+            <div class="__azul-native-dropdown-option">
+                <p>C</p>
+            </div>
+        </div>
+    "#;
+
+    let mut list: Dom<Mock> = DropdownList::new(vec!["A", "B", "C"])
+        .with_selected(Some(1))
+        .dom(Callback(callback), Callback(callback));
+
+    let root = &list.arena.node_data[list.head];
+    assert_eq!(root.callbacks.len(), 1);
+    assert_eq!(root.callbacks[0].0, EventFilter::Not(NotEventFilter::Hover(HoverEventFilter::MouseDown)));
+
+    // Callbacks can't be written in XML
+    for node in list.arena.node_data.iter_mut() {
+        node.callbacks.clear();
+    }
+
+    list.assert_eq(expected);
+}
+
+#[test]
+fn test_dropdown_filter_and_highlight() {
+    let mut dropdown = DropdownState::new(vec!["Apple", "Banana", "Cherry", "Pineapple"]).with_selected(1);
+    assert_eq!(dropdown.get_selected(), Some(&"Banana"));
+
+    // The first key press opens the list with the selected item highlighted
+    dropdown.move_highlight(1);
+    assert!(dropdown.is_open());
+    assert_eq!(dropdown.highlighted, Some(1));
+    dropdown.move_highlight(PAGE_OPTIONS);
+    assert_eq!(dropdown.highlighted, Some(3));
+
+    dropdown.set_filter("APPLE".to_string());
+    assert_eq!(dropdown.get_visible_items(), vec![0, 3]);
+    assert_eq!(dropdown.highlighted, Some(0));
+    dropdown.move_highlight(1);
+    dropdown.commit();
+    assert_eq!(dropdown.selected, Some(3));
+    assert!(!dropdown.is_open());
+    assert_eq!(dropdown.get_visible_items().len(), 4);
+}
+
+#[test]
+fn test_dropdown_editable_commit() {
+    let mut combo = DropdownState::new(vec!["Red", "Green", "Blue"]).with_selected(0).editable();
+    assert_eq!(combo.input.as_ref().unwrap().text, "Red");
+
+    // Text that doesn't match an item is kept, but deselects the item
+    combo.input = Some(TextInputState::new("Purple"));
+    combo.commit();
+    assert_eq!(combo.selected, None);
+    assert_eq!(combo.input.as_ref().unwrap().text, "Purple");
+
+    combo.input = Some(TextInputState::new("Blue"));
+    combo.commit();
+    assert_eq!(combo.selected, Some(2));
+
+    combo.set_filter("gr".to_string());
+    combo.commit();
+    assert_eq!(combo.get_selected(), Some(&"Green"));
+    assert_eq!(combo.input.as_ref().unwrap().text, "Green");
+}
@@ -0,0 +1,246 @@
+//! Collapsible panels: a single `Expander` with a clickable header, and an `Accordion` of
+//! panels of which only one is expanded at a time (two-way data binding)
+//!
+//! The content of a panel is only created while the panel is expanded.
+
+use {
+    callbacks::{UpdateScreen, Redraw, DontRedraw},
+    dom::{Dom, DomString, On, EventFilter, FocusEventFilter, TabIndex},
+    window::FakeWindow,
+    prelude::VirtualKeyCode,
+    callbacks::{CallbackInfo, StackCheckedPointer, DefaultCallback, DefaultCallbackId},
+    app::AppStateNoData,
+};
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct Expander {
+    header: DomString,
+    on_toggle_callback: Option<(DefaultCallbackId, DefaultCallbackId)>,
+}
+
+#[derive(Debug, Default, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct ExpanderState {
+    pub is_expanded: bool,
+    /// Disabled expanders can't be focused or toggled
+    pub disabled: bool,
+}
+
+impl ExpanderState {
+
+    pub fn new(is_expanded: bool) -> Self {
+        Self { is_expanded, disabled: false }
+    }
+
+    #[inline]
+    pub fn with_disabled(self, disabled: bool) -> Self {
+        Self { disabled, .. self }
+    }
+}
+
+/// Renders the header (with the expand arrow) of an expander or an accordion panel
+fn header_dom<T>(header: DomString, is_expanded: bool) -> Dom<T> {
+    Dom::div()
+    .with_class("__azul-native-expander-header")
+    .with_child(Dom::label(if is_expanded { "\u{25BE}" } else { "\u{25B8}" }).with_class("__azul-native-expander-arrow"))
+    .with_child(Dom::label(header).with_class("__azul-native-expander-title"))
+}
+
+/// Returns whether Return or Space was pressed, which toggles the focused header
+fn is_toggle_key<T>(app_state_no_data: &AppStateNoData<T>, event: &CallbackInfo<T>) -> bool {
+    let key = app_state_no_data.windows[event.window_id].get_keyboard_state().latest_virtual_keycode;
+    key == Some(VirtualKeyCode::Return) || key == Some(VirtualKeyCode::Space)
+}
+
+impl Expander {
+
+    pub fn new<S: Into<DomString>>(header: S) -> Self {
+        Expander { header: header.into(), on_toggle_callback: None }
+    }
+
+    pub fn bind<T>(self, window: &mut FakeWindow<T>, field: &ExpanderState, data: &T) -> Self {
+        let ptr = StackCheckedPointer::new(data, field);
+        let on_toggle_callback = ptr.map(|ptr|{(
+            window.add_callback(ptr, DefaultCallback(ExpanderState::on_click_private)),
+            window.add_callback(ptr, DefaultCallback(ExpanderState::on_virtual_key_down_private))
+        )});
+
+        Self {
+            on_toggle_callback,
+            .. self
+        }
+    }
+
+    /// `content` is only invoked if the expander is expanded
+    pub fn dom<T, F: FnOnce() -> Dom<T>>(&self, field: &ExpanderState, content: F) -> Dom<T> {
+
+        let mut header = header_dom(self.header.clone(), field.is_expanded).with_disabled(field.disabled);
+
+        if !field.disabled {
+            header.set_tab_index(TabIndex::Auto);
+            if let Some((click_callback, vk_callback)) = self.on_toggle_callback {
+                header.add_default_callback_id(On::LeftMouseUp, click_callback);
+                header.add_default_callback_id(EventFilter::Focus(FocusEventFilter::VirtualKeyDown), vk_callback);
+            }
+        }
+
+        let mut parent_div =
+            Dom::div()
+            .with_class("__azul-native-expander")
+            .with_checked(field.is_expanded)
+            .with_disabled(field.disabled)
+            .with_child(header);
+
+        if field.is_expanded {
+            parent_div.add_child(Dom::div().with_class("__azul-native-expander-content").with_child(content()));
+        }
+
+        parent_div
+    }
+}
+
+impl ExpanderState {
+
+    fn on_click_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_click, app_state_no_data, window_event) }
+    }
+
+    fn on_virtual_key_down_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_virtual_key_down, app_state_no_data, window_event) }
+    }
+
+    pub fn on_click<T>(&mut self, _app_state_no_data: &mut AppStateNoData<T>, _event: &mut CallbackInfo<T>) -> UpdateScreen {
+        if self.disabled {
+            return DontRedraw;
+        }
+        self.is_expanded = !self.is_expanded;
+        Redraw
+    }
+
+    /// Return or Space toggle the expander
+    pub fn on_virtual_key_down<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        if self.disabled || !is_toggle_key(app_state_no_data, event) {
+            return DontRedraw;
+        }
+        self.is_expanded = !self.is_expanded;
+        Redraw
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct Accordion {
+    headers: Vec<DomString>,
+    on_toggle_callback: Option<(DefaultCallbackId, DefaultCallbackId)>,
+}
+
+#[derive(Debug, Default, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct AccordionState {
+    /// Index of the expanded panel, `None` if all panels are collapsed
+    pub expanded: Option<usize>,
+}
+
+impl AccordionState {
+
+    pub fn new(expanded: Option<usize>) -> Self {
+        Self { expanded }
+    }
+
+    /// Expands the panel at the `index` and collapses all other panels,
+    /// collapses the panel if it is already expanded
+    pub fn toggle(&mut self, index: usize) {
+        self.expanded = if self.expanded == Some(index) { None } else { Some(index) };
+    }
+}
+
+impl Accordion {
+
+    pub fn new<S: Into<DomString>, I: IntoIterator<Item = S>>(headers: I) -> Self {
+        Accordion {
+            headers: headers.into_iter().map(|header| header.into()).collect(),
+            on_toggle_callback: None,
+        }
+    }
+
+    pub fn bind<T>(self, window: &mut FakeWindow<T>, field: &AccordionState, data: &T) -> Self {
+        let ptr = StackCheckedPointer::new(data, field);
+        let on_toggle_callback = ptr.map(|ptr|{(
+            window.add_callback(ptr, DefaultCallback(AccordionState::on_click_private)),
+            window.add_callback(ptr, DefaultCallback(AccordionState::on_virtual_key_down_private))
+        )});
+
+        Self {
+            on_toggle_callback,
+            .. self
+        }
+    }
+
+    /// `content` is only invoked for the expanded panel, with the index of the panel
+    pub fn dom<T, F: FnOnce(usize) -> Dom<T>>(&self, field: &AccordionState, content: F) -> Dom<T> {
+
+        let mut parent_div = Dom::div().with_class("__azul-native-accordion");
+        let mut content = Some(content);
+
+        for (index, header) in self.headers.iter().enumerate() {
+
+            let is_expanded = field.expanded == Some(index);
+            let mut header = header_dom(header.clone(), is_expanded).with_tab_index(TabIndex::Auto);
+
+            // The callbacks are on the headers, so that clicks on the content don't toggle the panel
+            if let Some((click_callback, vk_callback)) = self.on_toggle_callback {
+                header.add_default_callback_id(On::LeftMouseUp, click_callback);
+                header.add_default_callback_id(EventFilter::Focus(FocusEventFilter::VirtualKeyDown), vk_callback);
+            }
+
+            let mut panel = Dom::div()
+                .with_class("__azul-native-expander")
+                .with_checked(is_expanded)
+                .with_child(header);
+
+            if is_expanded {
+                if let Some(content) = content.take() {
+                    panel.add_child(Dom::div().with_class("__azul-native-expander-content").with_child(content(index)));
+                }
+            }
+
+            parent_div.add_child(panel);
+        }
+
+        parent_div
+    }
+}
+
+impl AccordionState {
+
+    fn on_click_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_click, app_state_no_data, window_event) }
+    }
+
+    fn on_virtual_key_down_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_virtual_key_down, app_state_no_data, window_event) }
+    }
+
+    /// Toggles the panel whose header was clicked
+    pub fn on_click<T>(&mut self, _app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        // header -> panel -> accordion
+        let panel = event.parent(event.hit_dom_node)?;
+        let (index, _) = event.get_index_in_parent(panel)?;
+        self.toggle(index);
+        Redraw
+    }
+
+    /// Return or Space toggle the panel of the focused header
+    pub fn on_virtual_key_down<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        if !is_toggle_key(app_state_no_data, event) {
+            return DontRedraw;
+        }
+        self.on_click(app_state_no_data, event)
+    }
+}
+
+#[test]
+fn test_accordion_toggle() {
+    let mut accordion = AccordionState::new(Some(0));
+    accordion.toggle(2);
+    assert_eq!(accordion.expanded, Some(2));
+    accordion.toggle(2);
+    assert_eq!(accordion.expanded, None);
+}
@@ -0,0 +1,1086 @@
+//! File chooser that is rendered by azul itself, as an overlay or inside of its own window
+//!
+//! Unlike the functions in the `dialogs` module, the file chooser doesn't block the event
+//! loop and doesn't need zenity or kdialog on Linux. Directories are read on a background
+//! thread via a `Task`. `dialogs::show_file_chooser` opens the native dialog (on a background
+//! thread) if one is available and falls back to the file chooser otherwise:
+//!
+//! ```no_run,ignore
+//! fn get_chooser(data: &mut Editor) -> &mut FileChooserState { &mut data.chooser }
+//!
+//! fn on_file_chosen(data: &mut Editor, _: &mut AppStateNoData<Editor>, result: FileChooserResult) -> UpdateScreen {
+//!     if let FileChooserResult::Accepted(paths) = result { data.open_files(paths); }
+//!     Redraw
+//! }
+//!
+//! fn open_file(app_state: &mut AppState<Editor>, _: &mut CallbackInfo<Editor>) -> UpdateScreen {
+//!     let chooser = FileChooser::new(get_chooser).with_on_close(on_file_chosen);
+//!     let task = app_state.data.modify_clone(|data| dialogs::show_file_chooser(&chooser, &mut data.chooser))?;
+//!     app_state.add_task(task);
+//!     Redraw
+//! }
+//!
+//! // in the layout() function:
+//! FileChooser::new(get_chooser).with_on_close(on_file_chosen).add_overlay(&self.chooser, self, &mut info);
+//! ```
+//!
+//! The entries are shown in a virtualized list, so the arrow keys, Shift / Ctrl + click and
+//! Ctrl + A work like in the `ListView`. Return or a double click opens the directory or accepts
+//! the file under the cursor, Backspace or Alt + Up goes to the parent directory, Ctrl + H shows
+//! hidden files, Escape cancels the chooser and typing a letter jumps to the next entry
+//! that starts with it.
+
+use std::{
+    env, fmt, fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+use azul_css::{CssProperty, LayoutWidth, LayoutHeight};
+use {
+    dom::{Dom, On, EventFilter, FocusEventFilter, TabIndex},
+    callbacks::{LayoutInfo, StackCheckedPointer, IFrameClosureCallback},
+    callbacks::{DefaultClosureCallback, DefaultCallbackId, TimerClosureCallback, UpdateScreen, Redraw, DontRedraw},
+    async::{Task, Timer, TerminateTimer, DropCheck},
+    overlay::{OverlayAnchor, OverlayPlacement, OverlaySide},
+    window::FakeWindow,
+    prelude::VirtualKeyCode,
+    app::AppStateNoData,
+    widgets::{
+        button::Button,
+        text_input::{TextInput, TextInputState},
+        list_view::{ListViewState, RowHeight, SelectionMode},
+        date_picker::Date,
+        DoubleClick,
+    },
+};
+
+/// Height of the rows in the list of entries
+const ENTRY_HEIGHT: f32 = 24.0;
+/// How many entries are rendered above and below the visible entries
+const OVERSCAN: usize = 5;
+
+/// What the user can choose with the file chooser
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FileChooserMode {
+    /// Choose a single existing file
+    OpenFile,
+    /// Choose one or more existing files
+    OpenFiles,
+    /// Choose a file name to save to, the file doesn't have to exist
+    SaveFile,
+    /// Choose a directory
+    OpenDirectory,
+}
+
+impl FileChooserMode {
+
+    /// Returns the default title of the chooser
+    pub fn get_title(&self) -> &'static str {
+        use self::FileChooserMode::*;
+        match self {
+            OpenFile => "Open File",
+            OpenFiles => "Open Files",
+            SaveFile => "Save File",
+            OpenDirectory => "Select Folder",
+        }
+    }
+
+    fn get_accept_label(&self) -> &'static str {
+        use self::FileChooserMode::*;
+        match self {
+            OpenFile | OpenFiles => "Open",
+            SaveFile => "Save",
+            OpenDirectory => "Select",
+        }
+    }
+}
+
+/// How the file chooser was closed
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FileChooserResult {
+    /// The chosen files or directories, always contains at least one path
+    Accepted(Vec<PathBuf>),
+    Cancelled,
+}
+
+/// Named list of glob patterns (i.e. `*.rs`), only files that match one of the
+/// patterns of the active filter are shown. Directories are always shown.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FileFilter {
+    pub name: String,
+    pub patterns: Vec<String>,
+}
+
+impl FileFilter {
+
+    pub fn new<S: Into<String>>(name: S, patterns: &[&str]) -> Self {
+        Self {
+            name: name.into(),
+            patterns: patterns.iter().map(|pattern| pattern.to_string()).collect(),
+        }
+    }
+
+    /// Returns whether the file name matches one of the patterns (case-insensitive)
+    pub fn matches(&self, file_name: &str) -> bool {
+        self.patterns.iter().any(|pattern| matches_glob(pattern, file_name))
+    }
+}
+
+/// Shortcut to a directory in the sidebar of the file chooser
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Place {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl Place {
+    pub fn new<S: Into<String>, P: Into<PathBuf>>(name: S, path: P) -> Self {
+        Self { name: name.into(), path: path.into() }
+    }
+}
+
+/// Returns the home directory and the Desktop, Documents and Downloads folders
+/// inside of it (if they exist), followed by the file system roots
+pub fn get_default_places() -> Vec<Place> {
+
+    let mut places = Vec::new();
+
+    if let Some(home) = get_home_directory() {
+        places.push(Place::new("Home", home.clone()));
+        for name in &["Desktop", "Documents", "Downloads"] {
+            let path = home.join(name);
+            if path.is_dir() {
+                places.push(Place::new(*name, path));
+            }
+        }
+    }
+
+    if cfg!(windows) {
+        // A: and B: are skipped, checking floppy drives is slow
+        for letter in b'C'..=b'Z' {
+            let drive = format!("{}:\\", letter as char);
+            if Path::new(&drive).exists() {
+                places.push(Place::new(format!("{}:", letter as char), drive));
+            }
+        }
+    } else {
+        places.push(Place::new("File System", "/"));
+    }
+
+    places
+}
+
+fn get_home_directory() -> Option<PathBuf> {
+    let variable = if cfg!(windows) { "USERPROFILE" } else { "HOME" };
+    env::var_os(variable).map(PathBuf::from).filter(|home| home.is_dir())
+}
+
+/// File or directory in the listed directory
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FileEntry {
+    pub name: String,
+    pub path: PathBuf,
+    pub is_directory: bool,
+    /// Size in bytes, `0` for directories
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+}
+
+impl FileEntry {
+
+    fn from_dir_entry(entry: fs::DirEntry) -> Self {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().into_owned();
+        // fs::metadata follows symlinks, so that links to directories can be opened
+        let metadata = fs::metadata(&path).or_else(|_| entry.metadata()).ok();
+        let is_directory = metadata.as_ref().map(|metadata| metadata.is_dir()) == Some(true);
+        Self {
+            name,
+            path,
+            is_directory,
+            size: metadata.as_ref().filter(|_| !is_directory).map(|metadata| metadata.len()).unwrap_or(0),
+            modified: metadata.and_then(|metadata| metadata.modified().ok()),
+        }
+    }
+
+    /// Entries starting with a dot are hidden on Unix
+    pub fn is_hidden(&self) -> bool {
+        self.name.starts_with('.')
+    }
+}
+
+/// Directory that is read on a background thread
+#[derive(Debug)]
+struct DirectoryListing {
+    directory: PathBuf,
+    entries: Option<Result<Vec<FileEntry>, String>>,
+}
+
+/// Native dialog that is shown on a background thread, see `dialogs::show_file_chooser`
+#[derive(Debug)]
+pub(crate) struct NativeDialogRequest {
+    mode: FileChooserMode,
+    title: String,
+    directory: PathBuf,
+    patterns: Vec<String>,
+    result: Option<FileChooserResult>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FileChooserState {
+    pub mode: FileChooserMode,
+    pub title: String,
+    /// Directory that is currently shown
+    pub directory: PathBuf,
+    pub filters: Vec<FileFilter>,
+    /// Index of the filter in `filters` that is applied to the entries
+    pub active_filter: usize,
+    pub show_hidden: bool,
+    pub places: Vec<Place>,
+    /// Name of the file to save to, only used in `FileChooserMode::SaveFile`
+    pub file_name: TextInputState,
+    /// Entries of the `directory` that pass the active filter, sorted by name (directories first)
+    pub entries: Vec<FileEntry>,
+    pub list: ListViewState,
+    /// Error that occurred while reading the `directory`
+    pub error: Option<String>,
+    /// Whether the built-in chooser should be shown
+    is_open: bool,
+    /// Whether a native dialog was started by `dialogs::show_file_chooser` and is still open
+    is_native_open: bool,
+    /// All entries of the `directory`, before filtering
+    all_entries: Vec<FileEntry>,
+    loading: Option<Arc<Mutex<DirectoryListing>>>,
+    /// Double-clicking an entry opens it
+    double_click: DoubleClick<usize>,
+    result: Option<FileChooserResult>,
+}
+
+impl FileChooserState {
+
+    /// Creates a closed file chooser that starts in the current working directory
+    pub fn new(mode: FileChooserMode) -> Self {
+        let selection_mode = if mode == FileChooserMode::OpenFiles { SelectionMode::Multiple } else { SelectionMode::Single };
+        Self {
+            mode,
+            title: mode.get_title().to_string(),
+            directory: env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+            filters: Vec::new(),
+            active_filter: 0,
+            show_hidden: false,
+            places: get_default_places(),
+            file_name: TextInputState::default(),
+            entries: Vec::new(),
+            list: ListViewState::new(0, RowHeight::Fixed(ENTRY_HEIGHT)).with_selection_mode(selection_mode),
+            error: None,
+            is_open: false,
+            is_native_open: false,
+            all_entries: Vec::new(),
+            loading: None,
+            double_click: DoubleClick::default(),
+            result: None,
+        }
+    }
+
+    #[inline]
+    pub fn with_title<S: Into<String>>(self, title: S) -> Self {
+        Self { title: title.into(), .. self }
+    }
+
+    #[inline]
+    pub fn with_directory<P: Into<PathBuf>>(self, directory: P) -> Self {
+        Self { directory: directory.into(), .. self }
+    }
+
+    /// Adds a filter, the first filter is active by default
+    #[inline]
+    pub fn with_filter(mut self, filter: FileFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    #[inline]
+    pub fn with_places(self, places: Vec<Place>) -> Self {
+        Self { places, .. self }
+    }
+
+    #[inline]
+    pub fn with_show_hidden(self, show_hidden: bool) -> Self {
+        Self { show_hidden, .. self }
+    }
+
+    /// Sets the file name that the `SaveFile` chooser starts with
+    #[inline]
+    pub fn with_file_name<S: Into<String>>(self, file_name: S) -> Self {
+        Self { file_name: TextInputState::new(file_name), .. self }
+    }
+
+    /// Returns whether the built-in chooser is shown
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    /// Returns whether the built-in chooser or the native dialog is open
+    pub fn is_active(&self) -> bool {
+        self.is_open || self.is_native_open
+    }
+
+    /// Returns whether the `directory` is still being read
+    pub fn is_loading(&self) -> bool {
+        self.loading.is_some()
+    }
+
+    /// Returns the result of the last chooser that was closed, if the `FileChooser` has no `on_close` callback
+    pub fn take_result(&mut self) -> Option<FileChooserResult> {
+        self.result.take()
+    }
+
+    pub fn get_active_filter(&self) -> Option<&FileFilter> {
+        self.filters.get(self.active_filter)
+    }
+
+    /// Returns the name and path of the `directory` and all of its parents, starting at the root
+    pub fn get_breadcrumbs(&self) -> Vec<(String, PathBuf)> {
+        let mut breadcrumbs = self.directory.ancestors().map(|path| {
+            let name = match path.file_name() {
+                Some(name) => name.to_string_lossy().into_owned(),
+                None => path.display().to_string(),
+            };
+            (name, path.to_path_buf())
+        }).filter(|(name, _)| !name.is_empty()).collect::<Vec<_>>();
+        breadcrumbs.reverse();
+        breadcrumbs
+    }
+
+    /// Returns the selected entries
+    pub fn get_selected_entries(&self) -> Vec<&FileEntry> {
+        self.list.get_selected_rows().filter_map(|row| self.entries.get(row)).collect()
+    }
+
+    /// Replaces the entries with the entries of the `directory`, i.e. after the
+    /// directory was read. The entries are sorted and filtered and the selection is cleared.
+    pub fn set_entries(&mut self, directory: PathBuf, mut entries: Vec<FileEntry>) {
+        entries.sort_by(|a, b| b.is_directory.cmp(&a.is_directory).then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase())));
+        self.directory = directory;
+        self.all_entries = entries;
+        self.error = None;
+        self.loading = None;
+        self.apply_filter();
+    }
+
+    /// Selects the next filter in `filters`
+    pub fn cycle_filter(&mut self) {
+        if !self.filters.is_empty() {
+            self.active_filter = (self.active_filter + 1) % self.filters.len();
+            self.apply_filter();
+        }
+    }
+
+    pub fn toggle_hidden(&mut self) {
+        self.show_hidden = !self.show_hidden;
+        self.apply_filter();
+    }
+
+    /// Filters the `entries` again, i.e. after the `active_filter` or `show_hidden` was changed
+    pub fn apply_filter(&mut self) {
+        let filter = self.filters.get(self.active_filter);
+        let show_hidden = self.show_hidden;
+        let show_files = self.mode != FileChooserMode::OpenDirectory;
+        self.entries = self.all_entries.iter().filter(|entry| {
+            (show_hidden || !entry.is_hidden()) &&
+            (entry.is_directory || (show_files && filter.map(|filter| filter.matches(&entry.name)) != Some(false)))
+        }).cloned().collect();
+
+        let selection_mode = self.list.selection_mode;
+        self.list = ListViewState::new(self.entries.len(), RowHeight::Fixed(ENTRY_HEIGHT)).with_selection_mode(selection_mode);
+        self.double_click.reset();
+    }
+
+    /// Starts reading the `directory`, the entries are replaced once the returned listing is finished
+    fn start_loading(&mut self, directory: PathBuf) -> Arc<Mutex<DirectoryListing>> {
+        let listing = Arc::new(Mutex::new(DirectoryListing { directory: directory.clone(), entries: None }));
+        self.directory = directory;
+        self.all_entries.clear();
+        self.entries.clear();
+        self.error = None;
+        self.list = ListViewState::new(0, RowHeight::Fixed(ENTRY_HEIGHT)).with_selection_mode(self.list.selection_mode);
+        self.double_click.reset();
+        self.loading = Some(listing.clone());
+        listing
+    }
+
+    /// Takes the entries out of the `listing`, if it is still the directory that should be shown
+    fn finish_loading(&mut self, listing: &Arc<Mutex<DirectoryListing>>) -> UpdateScreen {
+        if !self.loading.as_ref().map(|loading| Arc::ptr_eq(loading, listing)).unwrap_or(false) {
+            return DontRedraw;
+        }
+        self.loading = None;
+
+        let (directory, entries) = {
+            let mut listing = listing.lock().ok()?;
+            (listing.directory.clone(), listing.entries.take()?)
+        };
+
+        match entries {
+            Ok(entries) => self.set_entries(directory, entries),
+            Err(error) => self.error = Some(error),
+        }
+        Redraw
+    }
+
+    /// What should happen if the entry under the cursor is activated (double click or Return)
+    fn activate_cursor(&self) -> FileChooserAction {
+        let entry = match self.list.cursor.and_then(|row| self.entries.get(row)) {
+            Some(s) => s,
+            None => return self.accept(),
+        };
+        if entry.is_directory {
+            FileChooserAction::Navigate(entry.path.clone())
+        } else {
+            self.accept()
+        }
+    }
+
+    /// What should happen if the "Open" / "Save" button is pressed
+    fn accept(&self) -> FileChooserAction {
+        use self::FileChooserMode::*;
+        let selected = self.get_selected_entries();
+        match self.mode {
+            OpenFile | OpenFiles => {
+                let files = selected.iter().filter(|entry| !entry.is_directory).map(|entry| entry.path.clone()).collect::<Vec<_>>();
+                if !files.is_empty() {
+                    FileChooserAction::Finish(FileChooserResult::Accepted(files))
+                } else if let [directory] = selected.as_slice() {
+                    FileChooserAction::Navigate(directory.path.clone())
+                } else {
+                    FileChooserAction::None
+                }
+            },
+            SaveFile => {
+                let file_name = self.file_name.text.trim();
+                if file_name.is_empty() {
+                    return FileChooserAction::None;
+                }
+                let path = self.directory.join(file_name);
+                if path.is_dir() {
+                    FileChooserAction::Navigate(path)
+                } else {
+                    FileChooserAction::Finish(FileChooserResult::Accepted(vec![path]))
+                }
+            },
+            OpenDirectory => {
+                let directory = match selected.as_slice() {
+                    [directory] => directory.path.clone(),
+                    _ => self.directory.clone(),
+                };
+                FileChooserAction::Finish(FileChooserResult::Accepted(vec![directory]))
+            },
+        }
+    }
+
+    /// Returns whether the "Open" / "Save" button does anything
+    pub fn can_accept(&self) -> bool {
+        self.accept() != FileChooserAction::None
+    }
+
+    /// Copies the name of the selected file into the `file_name` of a `SaveFile` chooser
+    fn update_file_name(&mut self) {
+        if self.mode != FileChooserMode::SaveFile {
+            return;
+        }
+        let name = match self.get_selected_entries().as_slice() {
+            [entry] if !entry.is_directory => entry.name.clone(),
+            _ => return,
+        };
+        self.file_name = TextInputState::new(name);
+    }
+
+    /// Moves the cursor to the next entry that starts with the character `c`
+    pub fn jump_to_char(&mut self, c: char) -> UpdateScreen {
+        if self.entries.is_empty() {
+            return DontRedraw;
+        }
+        let prefix = c.to_lowercase().collect::<String>();
+        let start = self.list.cursor.map(|cursor| cursor + 1).unwrap_or(0);
+        let count = self.entries.len();
+        let row = (0..count).map(|offset| (start + offset) % count).find(|row| self.entries[*row].name.to_lowercase().starts_with(&prefix))?;
+        self.list.move_cursor(row, false);
+        self.update_file_name();
+        Redraw
+    }
+}
+
+/// What a click or key press in the file chooser does
+#[derive(Debug, Clone, PartialEq)]
+enum FileChooserAction {
+    None,
+    Navigate(PathBuf),
+    Finish(FileChooserResult),
+}
+
+/// Renders a `FileChooserState` and handles its events. Since the state has to be modified
+/// when a directory was read on a background thread, the state is accessed via a function.
+pub struct FileChooser<T> {
+    get_state: fn(&mut T) -> &mut FileChooserState,
+    on_close: Option<fn(&mut T, &mut AppStateNoData<T>, FileChooserResult) -> UpdateScreen>,
+}
+
+// #[derive(Debug, Copy, Clone)] for FileChooser<T>
+
+impl<T> fmt::Debug for FileChooser<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FileChooser {{ get_state: {:x}, on_close: {:?} }}",
+            self.get_state as usize, self.on_close.map(|on_close| on_close as usize))
+    }
+}
+
+impl<T> Clone for FileChooser<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for FileChooser<T> { }
+
+impl<T: 'static> FileChooser<T> {
+
+    pub fn new(get_state: fn(&mut T) -> &mut FileChooserState) -> Self {
+        Self { get_state, on_close: None }
+    }
+
+    /// Sets the function that is called with the result when the chooser is closed.
+    /// Without it, the result is stored in the state, see `FileChooserState::take_result`.
+    #[inline]
+    pub fn with_on_close(self, on_close: fn(&mut T, &mut AppStateNoData<T>, FileChooserResult) -> UpdateScreen) -> Self {
+        Self { on_close: Some(on_close), .. self }
+    }
+
+    /// Opens the built-in chooser and returns the task that reads its `directory`,
+    /// which has to be added via `AppState::add_task`
+    pub fn open(&self, state: &mut FileChooserState) -> Task<T> {
+        state.is_open = true;
+        state.result = None;
+        let directory = state.directory.clone();
+        self.load_directory(state, directory)
+    }
+
+    /// Returns a task that reads the `directory` and shows it once it is finished
+    fn load_directory(&self, state: &mut FileChooserState, directory: PathBuf) -> Task<T> {
+        let get_state = self.get_state;
+        let listing = state.start_loading(directory);
+        let key = format!("{:p}", &*listing);
+        let listing_clone = listing.clone();
+        Task::new(&listing, read_directory)
+            .then(Timer::from_closure(TimerClosureCallback::new(&("__azul_file_chooser_listing", key), move |data, _| {
+                (get_state(data).finish_loading(&listing_clone), TerminateTimer::Terminate)
+            })))
+    }
+
+    /// Shows the `state` as a native dialog on a background thread, see `dialogs::show_file_chooser`
+    pub(crate) fn open_native(&self, state: &mut FileChooserState) -> Task<T> {
+        let get_state = self.get_state;
+        let on_close = self.on_close;
+        let request = Arc::new(Mutex::new(NativeDialogRequest {
+            mode: state.mode,
+            title: state.title.clone(),
+            directory: state.directory.join(&state.file_name.text),
+            patterns: state.get_active_filter().map(|filter| filter.patterns.clone()).unwrap_or_default(),
+            result: None,
+        }));
+        state.is_native_open = true;
+        state.result = None;
+        let key = format!("{:p}", &*request);
+        let request_clone = request.clone();
+        Task::new(&request, run_native_dialog)
+            .then(Timer::from_closure(TimerClosureCallback::new(&("__azul_file_chooser_native", key), move |data, app_state_no_data| {
+                let result = request_clone.lock().ok().and_then(|mut request| request.result.take()).unwrap_or(FileChooserResult::Cancelled);
+                get_state(data).is_native_open = false;
+                (close_chooser(get_state, on_close, data, app_state_no_data, result), TerminateTimer::Terminate)
+            })))
+    }
+
+    /// Adds the chooser as a modal overlay that covers the whole window, if it is open
+    pub fn add_overlay(&self, state: &FileChooserState, data: &T, info: &mut LayoutInfo<T>) {
+
+        if !state.is_open {
+            return;
+        }
+
+        let window_size = info.window.state.size.dimensions;
+        let backdrop = Dom::div()
+            .with_class("__azul-native-file-chooser-backdrop")
+            .with_css_override("__azul_file_chooser_backdrop_width", CssProperty::Width(LayoutWidth::px(window_size.width as f32)))
+            .with_css_override("__azul_file_chooser_backdrop_height", CssProperty::Height(LayoutHeight::px(window_size.height as f32)))
+            .with_child(self.dom(state, data, info.window));
+
+        let placement = OverlayPlacement::new(OverlaySide::Bottom).with_flip(false).with_shift(false);
+        info.add_overlay(OverlayAnchor::point(0.0, 0.0), placement, backdrop);
+    }
+
+    /// Renders the chooser, i.e. to show it inside of its own window. The `state` has
+    /// to be stored inside of the application data `data`.
+    pub fn dom(&self, state: &FileChooserState, data: &T, window: &mut FakeWindow<T>) -> Dom<T> {
+
+        // div.__azul-native-file-chooser
+        //     |-> label.__azul-native-file-chooser-title
+        //     |-> div.__azul-native-file-chooser-header
+        //     |    |-> label.__azul-native-file-chooser-up
+        //     |    '-> div.__azul-native-file-chooser-breadcrumbs
+        //     |         '-> label.__azul-native-file-chooser-breadcrumb (.__azul-native-file-chooser-breadcrumb-current)
+        //     |-> div.__azul-native-file-chooser-body
+        //     |    |-> div.__azul-native-file-chooser-places
+        //     |    |    '-> label.__azul-native-file-chooser-place (.__azul-native-file-chooser-place-active)
+        //     |    '-> iframe.__azul-native-list-view.__azul-native-file-chooser-list (or a loading / error message)
+        //     '-> div.__azul-native-file-chooser-footer
+        //          |-> label.__azul-native-file-chooser-name-label + text input (only when saving)
+        //          |-> label.__azul-native-file-chooser-filter
+        //          '-> two buttons (cancel, accept)
+
+        let mut header = Dom::div().with_class("__azul-native-file-chooser-header");
+
+        let mut up = Dom::label("\u{2191}").with_class("__azul-native-file-chooser-up");
+        match state.directory.parent() {
+            Some(parent) => up.add_default_callback_id(On::LeftMouseUp, self.add_navigate_callback(window, "__azul_file_chooser_up", parent.to_path_buf())),
+            None => up.set_disabled(true),
+        }
+        header.add_child(up);
+
+        let breadcrumbs = state.get_breadcrumbs();
+        let last_breadcrumb = breadcrumbs.len().saturating_sub(1);
+        let mut breadcrumbs_dom = Dom::div().with_class("__azul-native-file-chooser-breadcrumbs");
+        for (index, (name, path)) in breadcrumbs.into_iter().enumerate() {
+            if index > 0 {
+                breadcrumbs_dom.add_child(Dom::label("\u{203A}").with_class("__azul-native-file-chooser-breadcrumb-separator"));
+            }
+            let mut breadcrumb = Dom::label(name).with_class("__azul-native-file-chooser-breadcrumb");
+            if index == last_breadcrumb {
+                breadcrumb.add_class("__azul-native-file-chooser-breadcrumb-current");
+            } else {
+                breadcrumb.add_default_callback_id(On::LeftMouseUp, self.add_navigate_callback(window, "__azul_file_chooser_breadcrumb", path));
+            }
+            breadcrumbs_dom.add_child(breadcrumb);
+        }
+        header.add_child(breadcrumbs_dom);
+
+        let mut places = Dom::div().with_class("__azul-native-file-chooser-places");
+        for place in &state.places {
+            let mut place_dom = Dom::label(place.name.clone()).with_class("__azul-native-file-chooser-place");
+            if place.path == state.directory {
+                place_dom.add_class("__azul-native-file-chooser-place-active");
+            }
+            place_dom.add_default_callback_id(On::LeftMouseUp, self.add_navigate_callback(window, "__azul_file_chooser_place", place.path.clone()));
+            places.add_child(place_dom);
+        }
+
+        let list = if state.is_loading() {
+            Dom::label("Loading\u{2026}").with_class("__azul-native-file-chooser-message")
+        } else if let Some(error) = &state.error {
+            Dom::label(error.clone()).with_class("__azul-native-file-chooser-message")
+        } else {
+            self.list_dom(state, data, window)
+        };
+
+        let body = Dom::div()
+            .with_class("__azul-native-file-chooser-body")
+            .with_child(places)
+            .with_child(list);
+
+        let mut footer = Dom::div().with_class("__azul-native-file-chooser-footer");
+
+        if state.mode == FileChooserMode::SaveFile {
+            let mut file_name = TextInput::new().bind(window, &state.file_name, data).dom(&state.file_name);
+            let on_key_down = self.add_key_callback(window, "__azul_file_chooser_name_key_down", false);
+            file_name.add_default_callback_id(EventFilter::Focus(FocusEventFilter::VirtualKeyDown), on_key_down);
+            footer.add_child(Dom::label("Name:").with_class("__azul-native-file-chooser-name-label"));
+            footer.add_child(file_name.with_class("__azul-native-file-chooser-name"));
+        } else {
+            footer.add_child(Dom::div().with_class("__azul-native-file-chooser-spacer"));
+        }
+
+        // Clicking the filter selects the next filter
+        if let Some(filter) = state.get_active_filter() {
+            let label = format!("{} ({})", filter.name, filter.patterns.join(", "));
+            let mut filter_dom = Dom::label(label).with_class("__azul-native-file-chooser-filter");
+            if state.filters.len() > 1 {
+                let get_state = self.get_state;
+                let on_click = window.add_closure_callback(DefaultClosureCallback::new("__azul_file_chooser_filter", move |data, _, _| {
+                    get_state(data).cycle_filter();
+                    Redraw
+                }));
+                filter_dom.add_default_callback_id(On::LeftMouseUp, on_click);
+            }
+            footer.add_child(filter_dom);
+        }
+
+        let get_state = self.get_state;
+        let on_close = self.on_close;
+        let on_cancel = window.add_closure_callback(DefaultClosureCallback::new("__azul_file_chooser_cancel", move |data, app_state_no_data, _| {
+            close_chooser(get_state, on_close, data, app_state_no_data, FileChooserResult::Cancelled)
+        }));
+        let on_accept = self.add_action_callback(window, "__azul_file_chooser_accept", FileChooserState::accept);
+
+        let mut cancel = Button::with_label("Cancel").dom().with_class("__azul-native-file-chooser-cancel");
+        cancel.add_default_callback_id(On::LeftMouseUp, on_cancel);
+        let mut accept = Button::with_label(state.mode.get_accept_label()).dom()
+            .with_class("__azul-native-file-chooser-accept")
+            .with_disabled(!state.can_accept());
+        accept.add_default_callback_id(On::LeftMouseUp, on_accept);
+        footer.add_child(cancel);
+        footer.add_child(accept);
+
+        Dom::div()
+            .with_class("__azul-native-file-chooser")
+            .with_child(Dom::label(state.title.clone()).with_class("__azul-native-file-chooser-title"))
+            .with_child(header)
+            .with_child(body)
+            .with_child(footer)
+    }
+
+    /// Renders the entries as a virtualized list
+    fn list_dom(&self, state: &FileChooserState, data: &T, window: &mut FakeWindow<T>) -> Dom<T> {
+
+        let ptr = match StackCheckedPointer::new(data, state) {
+            Some(ptr) => ptr,
+            None => return Dom::label(
+                "Cannot create file chooser from heap-allocated FileChooserState, \
+                 please store the FileChooserState in the application data"
+            ),
+        };
+
+        // The state is only read while the application data is locked for the IFrame callback
+        let mut dom = Dom::iframe_closure(IFrameClosureCallback::new(&("__azul_file_chooser_list", &ptr), move |_data, _info, dimensions| {
+            let state = unsafe { ptr.get::<FileChooserState>() };
+            state.list.render_with(|row| render_entry(&state.entries[row]), OVERSCAN, dimensions)
+        }))
+        .with_class("__azul-native-list-view")
+        .with_class("__azul-native-file-chooser-list")
+        .with_tab_index(TabIndex::Auto);
+
+        let get_state = self.get_state;
+        let on_scroll = window.add_closure_callback(DefaultClosureCallback::new("__azul_file_chooser_scroll", move |data, app_state_no_data, event| {
+            get_state(data).list.on_scroll(app_state_no_data, event)
+        }));
+        let on_pan = window.add_closure_callback(DefaultClosureCallback::new("__azul_file_chooser_pan", move |data, app_state_no_data, event| {
+            get_state(data).list.on_pan(app_state_no_data, event)
+        }));
+        let on_mouse_down = self.add_mouse_down_callback(window);
+        let on_key_down = self.add_key_callback(window, "__azul_file_chooser_list_key_down", true);
+        let on_text_input = window.add_closure_callback(DefaultClosureCallback::new("__azul_file_chooser_text_input", move |data, app_state_no_data, event| {
+            let keyboard_state = app_state_no_data.windows[event.window_id].get_keyboard_state();
+            match keyboard_state.current_char {
+                Some(c) if !c.is_control() && !c.is_whitespace() && !keyboard_state.ctrl_down => get_state(data).jump_to_char(c),
+                _ => DontRedraw,
+            }
+        }));
+
+        dom.add_default_callback_id(On::Scroll, on_scroll);
+        dom.add_default_callback_id(On::Pan, on_pan);
+        dom.add_default_callback_id(On::MouseDown, on_mouse_down);
+        dom.add_default_callback_id(EventFilter::Focus(FocusEventFilter::VirtualKeyDown), on_key_down);
+        dom.add_default_callback_id(EventFilter::Focus(FocusEventFilter::TextInput), on_text_input);
+        dom
+    }
+
+    /// Registers a callback that reads the directory at the `path`
+    fn add_navigate_callback(&self, window: &mut FakeWindow<T>, key: &str, path: PathBuf) -> DefaultCallbackId {
+        let chooser = *self;
+        window.add_closure_callback(DefaultClosureCallback::new(&(key, &path), move |data, app_state_no_data, _| {
+            let task = chooser.load_directory((chooser.get_state)(data), path.clone());
+            app_state_no_data.add_task(task);
+            Redraw
+        }))
+    }
+
+    /// Registers a callback that runs the `action` of the state (i.e. accepting the selected files)
+    fn add_action_callback(&self, window: &mut FakeWindow<T>, key: &str, action: fn(&FileChooserState) -> FileChooserAction) -> DefaultCallbackId {
+        let chooser = *self;
+        window.add_closure_callback(DefaultClosureCallback::new(key, move |data, app_state_no_data, _| {
+            let action = action((chooser.get_state)(data));
+            chooser.run_action(action, data, app_state_no_data)
+        }))
+    }
+
+    /// Selects the clicked entry, a double click opens it
+    fn add_mouse_down_callback(&self, window: &mut FakeWindow<T>) -> DefaultCallbackId {
+        let chooser = *self;
+        window.add_closure_callback(DefaultClosureCallback::new("__azul_file_chooser_mouse_down", move |data, app_state_no_data, event| {
+            let state = (chooser.get_state)(data);
+            state.list.on_mouse_down(app_state_no_data, event)?;
+            state.update_file_name();
+
+            let cursor = state.list.cursor?;
+            if !state.double_click.click(cursor, Instant::now()) {
+                return Redraw;
+            }
+
+            let action = state.activate_cursor();
+            chooser.run_action(action, data, app_state_no_data);
+            Redraw
+        }))
+    }
+
+    /// Handles Return, Escape, Backspace / Alt + Up and Ctrl + H, other keys are
+    /// forwarded to the list if `is_list` is set
+    fn add_key_callback(&self, window: &mut FakeWindow<T>, key: &str, is_list: bool) -> DefaultCallbackId {
+        let chooser = *self;
+        window.add_closure_callback(DefaultClosureCallback::new(key, move |data, app_state_no_data, event| {
+
+            let keyboard_state = app_state_no_data.windows[event.window_id].get_keyboard_state().clone();
+            let state = (chooser.get_state)(data);
+
+            let action = match keyboard_state.latest_virtual_keycode {
+                Some(VirtualKeyCode::Return) if is_list => state.activate_cursor(),
+                Some(VirtualKeyCode::Return) => state.accept(),
+                Some(VirtualKeyCode::Escape) => FileChooserAction::Finish(FileChooserResult::Cancelled),
+                Some(VirtualKeyCode::Back) if is_list => navigate_to_parent(state),
+                Some(VirtualKeyCode::Up) if keyboard_state.alt_down => navigate_to_parent(state),
+                Some(VirtualKeyCode::H) if keyboard_state.ctrl_down => {
+                    state.toggle_hidden();
+                    return Redraw;
+                },
+                _ if is_list => {
+                    state.list.on_virtual_key_down(app_state_no_data, event)?;
+                    state.update_file_name();
+                    return Redraw;
+                },
+                _ => return DontRedraw,
+            };
+
+            chooser.run_action(action, data, app_state_no_data)
+        }))
+    }
+
+    fn run_action(&self, action: FileChooserAction, data: &mut T, app_state_no_data: &mut AppStateNoData<T>) -> UpdateScreen {
+        match action {
+            FileChooserAction::None => DontRedraw,
+            FileChooserAction::Navigate(directory) => {
+                let task = self.load_directory((self.get_state)(data), directory);
+                app_state_no_data.add_task(task);
+                Redraw
+            },
+            FileChooserAction::Finish(result) => close_chooser(self.get_state, self.on_close, data, app_state_no_data, result),
+        }
+    }
+}
+
+fn navigate_to_parent(state: &FileChooserState) -> FileChooserAction {
+    match state.directory.parent() {
+        Some(parent) => FileChooserAction::Navigate(parent.to_path_buf()),
+        None => FileChooserAction::None,
+    }
+}
+
+/// Closes the chooser and hands the result to the `on_close` callback (or stores it in the state)
+fn close_chooser<T>(
+    get_state: fn(&mut T) -> &mut FileChooserState,
+    on_close: Option<fn(&mut T, &mut AppStateNoData<T>, FileChooserResult) -> UpdateScreen>,
+    data: &mut T,
+    app_state_no_data: &mut AppStateNoData<T>,
+    result: FileChooserResult)
+-> UpdateScreen
+{
+    let state = get_state(data);
+    state.is_open = false;
+    state.loading = None;
+    match on_close {
+        Some(on_close) => { on_close(data, app_state_no_data, result); },
+        None => state.result = Some(result),
+    }
+    Redraw
+}
+
+/// Runs on a background thread, reads the directory of the `listing`
+fn read_directory(listing: Arc<Mutex<DirectoryListing>>, _: DropCheck) {
+
+    let directory = match listing.lock() {
+        Ok(listing) => listing.directory.clone(),
+        Err(_) => return,
+    };
+
+    // Entries that can't be read (i.e. because they were deleted in the meantime) are skipped
+    let entries = fs::read_dir(&directory)
+        .map(|dir| dir.filter_map(|entry| entry.ok()).map(FileEntry::from_dir_entry).collect())
+        .map_err(|e| format!("Could not read {}: {}", directory.display(), e));
+
+    if let Ok(mut listing) = listing.lock() {
+        listing.entries = Some(entries);
+    }
+}
+
+/// Runs on a background thread, shows the native dialog of the `request`
+fn run_native_dialog(request: Arc<Mutex<NativeDialogRequest>>, _: DropCheck) {
+
+    use dialogs::{open_file_dialog_with_title, open_multiple_files_dialog_with_title, save_file_dialog_with_title, open_directory_dialog_with_title};
+
+    let (mode, title, path, patterns) = match request.lock() {
+        Ok(request) => (request.mode, request.title.clone(), request.directory.to_string_lossy().into_owned(), request.patterns.clone()),
+        Err(_) => return,
+    };
+
+    let patterns = patterns.iter().map(|pattern| pattern.as_str()).collect::<Vec<_>>();
+    let filter = if patterns.is_empty() { None } else { Some(patterns.as_slice()) };
+
+    let paths = match mode {
+        FileChooserMode::OpenFile => open_file_dialog_with_title(&title, Some(&path), filter).map(|path| vec![path]),
+        FileChooserMode::OpenFiles => open_multiple_files_dialog_with_title(&title, Some(&path), filter),
+        FileChooserMode::SaveFile => save_file_dialog_with_title(&title, Some(&path)).map(|path| vec![path]),
+        FileChooserMode::OpenDirectory => open_directory_dialog_with_title(&title, Some(&path)).map(|path| vec![path]),
+    };
+
+    let result = match paths {
+        Some(ref paths) if !paths.is_empty() => FileChooserResult::Accepted(paths.iter().map(PathBuf::from).collect()),
+        _ => FileChooserResult::Cancelled,
+    };
+
+    if let Ok(mut request) = request.lock() {
+        request.result = Some(result);
+    }
+}
+
+fn render_entry<T>(entry: &FileEntry) -> Dom<T> {
+
+    let icon_class = if entry.is_directory {
+        "__azul-native-file-chooser-icon-directory"
+    } else {
+        "__azul-native-file-chooser-icon-file"
+    };
+
+    let size = if entry.is_directory { String::new() } else { format_file_size(entry.size) };
+    let modified = entry.modified
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|since_epoch| {
+            let date = Date::from_days_since_epoch((since_epoch.as_secs() / 86_400) as i64);
+            format!("{:04}-{:02}-{:02}", date.year, date.month, date.day)
+        })
+        .unwrap_or_default();
+
+    Dom::div()
+        .with_class("__azul-native-file-chooser-entry")
+        .with_child(Dom::div().with_class("__azul-native-file-chooser-icon").with_class(icon_class))
+        .with_child(Dom::label(entry.name.clone()).with_class("__azul-native-file-chooser-entry-name"))
+        .with_child(Dom::label(size).with_class("__azul-native-file-chooser-entry-size"))
+        .with_child(Dom::label(modified).with_class("__azul-native-file-chooser-entry-modified"))
+}
+
+/// Formats a file size in bytes as i.e. "12 B", "3.4 KB" or "1.2 GB"
+pub fn format_file_size(size: u64) -> String {
+    const UNITS: &[&str] = &["KB", "MB", "GB", "TB"];
+    if size < 1024 {
+        return format!("{} B", size);
+    }
+    let mut size = size as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Returns whether the `name` matches the glob `pattern`, ignoring case. `*` matches
+/// any number of characters and `?` matches exactly one character.
+pub fn matches_glob(pattern: &str, name: &str) -> bool {
+
+    let pattern = pattern.to_lowercase().chars().collect::<Vec<_>>();
+    let name = name.to_lowercase().chars().collect::<Vec<_>>();
+
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` in the pattern and the position in the name that it matched up to
+    let mut backtrack = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            },
+            Some(c) if *c == '?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            },
+            _ => match backtrack {
+                // Let the last `*` match one more character
+                Some((star, matched)) => {
+                    backtrack = Some((star, matched + 1));
+                    p = star + 1;
+                    n = matched + 1;
+                },
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[test]
+fn test_file_chooser_glob() {
+    assert!(matches_glob("*.rs", "main.rs"));
+    assert!(matches_glob("*.RS", "Main.rs"));
+    assert!(!matches_glob("*.rs", "main.rs.bak"));
+    assert!(matches_glob("*", ""));
+    assert!(matches_glob("img_??.*", "IMG_01.png"));
+    assert!(!matches_glob("img_??.*", "img_1.png"));
+    assert!(matches_glob("*a*b*c", "xxaxxbxxbxc"));
+    assert!(!matches_glob("*a*b*c", "xxaxxbxxbx"));
+    assert!(FileFilter::new("Images", &["*.png", "*.jpg"]).matches("photo.JPG"));
+
+    assert_eq!(format_file_size(512), "512 B");
+    assert_eq!(format_file_size(1536), "1.5 KB");
+    assert_eq!(format_file_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+}
+
+#[cfg(test)]
+fn test_entry(name: &str, is_directory: bool) -> FileEntry {
+    FileEntry { name: name.to_string(), path: PathBuf::from(name), is_directory, size: 0, modified: None }
+}
+
+#[test]
+fn test_file_chooser_entries() {
+    let mut state = FileChooserState::new(FileChooserMode::OpenFiles)
+        .with_filter(FileFilter::new("Rust files", &["*.rs"]))
+        .with_filter(FileFilter::new("All files", &["*"]));
+
+    state.set_entries(PathBuf::from("project"), vec![
+        test_entry("main.rs", false),
+        test_entry("src", true),
+        test_entry(".git", true),
+        test_entry("Cargo.toml", false),
+        test_entry("build.rs", false),
+    ]);
+
+    let names = |state: &FileChooserState| state.entries.iter().map(|entry| entry.name.clone()).collect::<Vec<_>>();
+    assert_eq!(names(&state), vec!["src", "build.rs", "main.rs"]);
+    assert_eq!(state.list.item_count, 3);
+
+    state.cycle_filter();
+    assert_eq!(names(&state), vec!["src", "build.rs", "Cargo.toml", "main.rs"]);
+    state.toggle_hidden();
+    assert_eq!(names(&state), vec![".git", "src", "build.rs", "Cargo.toml", "main.rs"]);
+
+    // Directories are opened, files are accepted
+    assert_eq!(state.accept(), FileChooserAction::None);
+    state.list.click_row(1, false, false);
+    assert_eq!(state.activate_cursor(), FileChooserAction::Navigate(PathBuf::from("src")));
+    state.list.click_row(2, false, false);
+    state.list.click_row(4, true, false);
+    assert_eq!(state.accept(), FileChooserAction::Finish(FileChooserResult::Accepted(vec![
+        PathBuf::from("build.rs"), PathBuf::from("Cargo.toml"), PathBuf::from("main.rs"),
+    ])));
+
+    state.list.move_cursor(0, false);
+    assert_eq!(state.jump_to_char('M'), Redraw);
+    assert_eq!(state.list.cursor, Some(4));
+}
+
+#[test]
+fn test_file_chooser_breadcrumbs() {
+    let root = if cfg!(windows) { "C:\\" } else { "/" };
+    let state = FileChooserState::new(FileChooserMode::OpenDirectory).with_directory(Path::new(root).join("home").join("user"));
+    let breadcrumbs = state.get_breadcrumbs().into_iter().map(|(name, _)| name).collect::<Vec<_>>();
+    assert_eq!(breadcrumbs, vec![root.to_string(), "home".to_string(), "user".to_string()]);
+
+    // Selecting a directory without selecting anything accepts the shown directory
+    assert_eq!(state.accept(), FileChooserAction::Finish(FileChooserResult::Accepted(vec![state.directory.clone()])));
+}
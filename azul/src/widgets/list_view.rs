@@ -0,0 +1,865 @@
+//! Virtualized list view, only builds the DOM nodes of the visible rows
+//!
+//! The rows are drawn in an IFrame, so the list knows the height of its viewport and only
+//! calls the row-render function for the visible rows (plus a few rows of overscan).
+//! This way, the cost of a frame doesn't depend on the number of rows - lists with
+//! millions of rows (i.e. log viewers) are just as fast as lists with ten rows.
+//!
+//! ```no_run,ignore
+//! struct LogViewer {
+//!     lines: Vec<String>,
+//!     list: ListViewState,
+//! }
+//!
+//! fn render_line(data: &LogViewer, row: usize) -> Dom<LogViewer> {
+//!     Dom::label(data.lines[row].clone())
+//! }
+//!
+//! impl Layout for LogViewer {
+//!     fn layout(&self, info: LayoutInfo<Self>) -> Dom<Self> {
+//!         ListView::new(render_line).dom(&self.list, self, info.window)
+//!     }
+//! }
+//! ```
+//!
+//! Since the rows are part of the IFrame, callbacks on the rows are not invoked - clicks
+//! and key presses are handled by the list, which updates the selection of the `ListViewState`.
+
+use std::{fmt, ops::Range};
+use azul_css::{CssProperty, LayoutHeight, LayoutMargin, PixelValue};
+use {
+    app::AppStateNoData,
+    callbacks::{IFrameClosureCallback, HidpiAdjustedBounds, UpdateScreen, Redraw, DontRedraw},
+    callbacks::{CallbackInfo, StackCheckedPointer, DefaultCallback},
+    dom::{Dom, On, EventFilter, FocusEventFilter, WindowEventFilter, TabIndex},
+    window::FakeWindow,
+    touch::Gesture,
+    prelude::VirtualKeyCode,
+};
+
+/// Height of rows that don't have a height in `RowHeight::Variable`
+pub const DEFAULT_ROW_HEIGHT: f32 = 20.0;
+/// Minimum height of the scrollbar thumb, so that it stays visible for very long lists
+const MIN_THUMB_HEIGHT: f32 = 20.0;
+/// Width of the scrollbar in the native styles - since the rows are drawn in an IFrame,
+/// clicks on the scrollbar are detected by their distance from the right edge of the list
+const SCROLLBAR_WIDTH: f32 = 10.0;
+
+/// Renders the row with the given index, only called for the visible rows
+pub type RenderRowFn<T> = fn(&T, usize) -> Dom<T>;
+
+pub struct ListView<T> {
+    render_row: RenderRowFn<T>,
+    /// How many rows are rendered above and below the visible rows
+    overscan: usize,
+}
+
+impl<T> Clone for ListView<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ListView<T> { }
+
+impl<T> fmt::Debug for ListView<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ListView {{ render_row: {:x}, overscan: {} }}", self.render_row as usize, self.overscan)
+    }
+}
+
+/// How high the rows of a `ListView` are
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowHeight {
+    /// All rows have the same height - finding the visible rows is O(1)
+    Fixed(f32),
+    /// Height of each row by index - finding the visible rows is O(n).
+    /// Rows without an entry are `DEFAULT_ROW_HEIGHT` pixels high.
+    Variable(Vec<f32>),
+}
+
+impl Default for RowHeight {
+    fn default() -> Self {
+        RowHeight::Fixed(DEFAULT_ROW_HEIGHT)
+    }
+}
+
+impl RowHeight {
+
+    pub fn get_row_height(&self, row: usize) -> f32 {
+        match self {
+            RowHeight::Fixed(height) => *height,
+            RowHeight::Variable(heights) => heights.get(row).cloned().unwrap_or(DEFAULT_ROW_HEIGHT),
+        }
+    }
+
+    /// Returns the distance from the top of the first row to the top of the `row`.
+    ///
+    /// Offsets are `f64`, since an `f32` can't represent the offsets of long lists
+    /// exactly (a million rows of 20 pixels are higher than 2^24 pixels).
+    pub fn get_row_offset(&self, row: usize) -> f64 {
+        match self {
+            RowHeight::Fixed(height) => row as f64 * f64::from(*height),
+            RowHeight::Variable(_) => (0..row).map(|row| f64::from(self.get_row_height(row))).sum(),
+        }
+    }
+
+    /// Returns the height of all rows together
+    pub fn get_total_height(&self, item_count: usize) -> f64 {
+        self.get_row_offset(item_count)
+    }
+
+    /// Returns the row at the vertical offset `y` (relative to the top of the first row)
+    pub fn get_row_at(&self, y: f64, item_count: usize) -> Option<usize> {
+        if y < 0.0 || item_count == 0 {
+            return None;
+        }
+        match self {
+            RowHeight::Fixed(height) => {
+                if *height <= 0.0 {
+                    return None;
+                }
+                let row = (y / f64::from(*height)) as usize;
+                if row < item_count { Some(row) } else { None }
+            },
+            RowHeight::Variable(_) => {
+                let mut row_top = 0.0;
+                for row in 0..item_count {
+                    row_top += f64::from(self.get_row_height(row));
+                    if y < row_top {
+                        return Some(row);
+                    }
+                }
+                None
+            },
+        }
+    }
+
+    /// Returns the rows that are (at least partially) visible in a viewport
+    /// of `viewport_height` pixels that is scrolled by `scroll_offset`
+    pub fn get_visible_rows(&self, scroll_offset: f64, viewport_height: f32, item_count: usize) -> Range<usize> {
+        if viewport_height <= 0.0 {
+            return 0..0;
+        }
+        let viewport_bottom = scroll_offset + f64::from(viewport_height);
+        match self {
+            RowHeight::Fixed(height) => {
+                if *height <= 0.0 {
+                    return 0..0;
+                }
+                let height = f64::from(*height);
+                let first = ((scroll_offset.max(0.0) / height) as usize).min(item_count);
+                let last = ((viewport_bottom / height).ceil() as usize).min(item_count);
+                first..last.max(first)
+            },
+            RowHeight::Variable(_) => {
+                let mut first = None;
+                let mut row_top = 0.0;
+                for row in 0..item_count {
+                    let row_bottom = row_top + f64::from(self.get_row_height(row));
+                    if first.is_none() && row_bottom > scroll_offset {
+                        first = Some(row);
+                    }
+                    if row_bottom >= viewport_bottom {
+                        return first.unwrap_or(row)..(row + 1);
+                    }
+                    row_top = row_bottom;
+                }
+                first.unwrap_or(item_count)..item_count
+            },
+        }
+    }
+}
+
+/// How the user can select rows with the mouse and keyboard
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SelectionMode {
+    /// Only one row can be selected
+    Single,
+    /// One contiguous range of rows can be selected by shift-clicking or with shift + arrow keys
+    Range,
+    /// Like `Range`, but ctrl-click toggles single rows and
+    /// ctrl + shift-click adds a range to the selection
+    Multiple,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListViewState {
+    /// Number of rows in the list
+    pub item_count: usize,
+    pub row_height: RowHeight,
+    pub selection_mode: SelectionMode,
+    /// How far the list is scrolled, in pixels from the top of the first row
+    /// (an `f64`, see `RowHeight::get_row_offset`)
+    pub scroll_offset: f64,
+    /// Row that is moved by the arrow keys, usually the last row that was clicked
+    pub cursor: Option<usize>,
+    /// Row that range selections start from
+    anchor: Option<usize>,
+    /// Sorted, non-overlapping ranges of selected rows
+    selection: Vec<Range<usize>>,
+    /// Row that should be scrolled into view on the next frame, see `scroll_to_row`
+    scroll_target: Option<usize>,
+    /// Height of the list in the last frame, updated whenever the list receives an event
+    viewport_height: f32,
+    /// Distance from the top of the scrollbar thumb to the cursor, while the thumb is dragged
+    thumb_drag: Option<f32>,
+}
+
+impl Default for ListViewState {
+    fn default() -> Self {
+        Self::new(0, RowHeight::default())
+    }
+}
+
+impl ListViewState {
+
+    pub fn new(item_count: usize, row_height: RowHeight) -> Self {
+        Self {
+            item_count,
+            row_height,
+            selection_mode: SelectionMode::Single,
+            scroll_offset: 0.0,
+            cursor: None,
+            anchor: None,
+            selection: Vec::new(),
+            scroll_target: None,
+            viewport_height: 0.0,
+            thumb_drag: None,
+        }
+    }
+
+    #[inline]
+    pub fn with_selection_mode(self, selection_mode: SelectionMode) -> Self {
+        Self { selection_mode, .. self }
+    }
+
+    /// Returns the selected rows as sorted, non-overlapping ranges
+    pub fn get_selection(&self) -> &[Range<usize>] {
+        &self.selection
+    }
+
+    pub fn get_selected_rows<'a>(&'a self) -> impl Iterator<Item = usize> + 'a {
+        self.selection.iter().flat_map(|range| range.clone())
+    }
+
+    pub fn get_selected_count(&self) -> usize {
+        self.selection.iter().map(|range| range.end - range.start).sum()
+    }
+
+    pub fn is_selected(&self, row: usize) -> bool {
+        use std::cmp::Ordering;
+        self.selection.binary_search_by(|range| {
+            if range.end <= row {
+                Ordering::Less
+            } else if range.start > row {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        }).is_ok()
+    }
+
+    /// Selects only the `row` and moves the cursor to it
+    pub fn select(&mut self, row: usize) {
+        if row >= self.item_count {
+            return;
+        }
+        self.selection.clear();
+        self.selection.push(row..(row + 1));
+        self.cursor = Some(row);
+        self.anchor = Some(row);
+    }
+
+    /// Replaces the selection with the `rows`, regardless of the `selection_mode`
+    pub fn select_range(&mut self, rows: Range<usize>) {
+        self.selection.clear();
+        insert_range(&mut self.selection, rows.start..rows.end.min(self.item_count));
+    }
+
+    pub fn select_all(&mut self) {
+        self.select_range(0..self.item_count);
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selection.clear();
+        self.anchor = None;
+    }
+
+    /// Adds the `row` to or removes it from the selection, moves the cursor to the `row`
+    pub fn toggle(&mut self, row: usize) {
+        if row >= self.item_count {
+            return;
+        }
+        if self.is_selected(row) {
+            remove_from_ranges(&mut self.selection, row);
+        } else {
+            insert_range(&mut self.selection, row..(row + 1));
+        }
+        self.cursor = Some(row);
+        self.anchor = Some(row);
+    }
+
+    /// Scrolls the list by the minimal amount so that the `row` is visible on the next frame
+    pub fn scroll_to_row(&mut self, row: usize) {
+        self.scroll_target = Some(row);
+    }
+
+    /// Scrolls to the last row, i.e. to follow the end of a log file
+    pub fn scroll_to_end(&mut self) {
+        if self.item_count > 0 {
+            self.scroll_to_row(self.item_count - 1);
+        }
+    }
+
+    /// Returns how far the list is scrolled in a viewport of the `viewport_height`,
+    /// clamped to the height of the rows (and with the `scroll_target` revealed)
+    pub fn get_scroll_offset(&self, viewport_height: f32) -> f64 {
+        let mut scroll_offset = self.scroll_offset;
+        let viewport_height = f64::from(viewport_height);
+
+        if let Some(row) = self.scroll_target.filter(|row| *row < self.item_count) {
+            let row_top = self.row_height.get_row_offset(row);
+            let row_bottom = row_top + f64::from(self.row_height.get_row_height(row));
+            if row_top < scroll_offset {
+                scroll_offset = row_top;
+            } else if row_bottom > scroll_offset + viewport_height {
+                scroll_offset = row_bottom - viewport_height;
+            }
+        }
+
+        let max_scroll_offset = (self.row_height.get_total_height(self.item_count) - viewport_height).max(0.0);
+        scroll_offset.min(max_scroll_offset).max(0.0)
+    }
+
+    /// Returns the offset from the top of the list and the height of the scrollbar thumb,
+    /// `None` if all rows fit into the viewport (no scrollbar is shown)
+    pub fn get_scrollbar_thumb(&self, viewport_height: f32) -> Option<(f32, f32)> {
+        let total_height = self.row_height.get_total_height(self.item_count);
+        let viewport = f64::from(viewport_height);
+        if total_height <= viewport {
+            return None;
+        }
+        let thumb_height = (viewport * viewport / total_height).max(f64::from(MIN_THUMB_HEIGHT)).min(viewport);
+        let thumb_offset = self.get_scroll_offset(viewport_height) / (total_height - viewport) * (viewport - thumb_height);
+        Some((thumb_offset as f32, thumb_height as f32))
+    }
+
+    /// Scrolls the list so that the top of the scrollbar thumb is at `thumb_offset`
+    /// pixels from the top of the list, i.e. while the thumb is dragged
+    pub fn scroll_to_thumb_offset(&mut self, thumb_offset: f32, viewport_height: f32) {
+        let thumb_height = match self.get_scrollbar_thumb(viewport_height) {
+            Some((_, height)) => f64::from(height),
+            None => return,
+        };
+        let viewport = f64::from(viewport_height);
+        let track_height = viewport - thumb_height;
+        let fraction = if track_height > 0.0 { f64::from(thumb_offset) / track_height } else { 0.0 };
+        self.scroll_target = None;
+        self.scroll_offset = fraction * (self.row_height.get_total_height(self.item_count) - viewport);
+        self.scroll_offset = self.get_scroll_offset(viewport_height);
+    }
+
+    /// Returns whether the scrollbar thumb is currently dragged
+    pub fn is_dragging_thumb(&self) -> bool {
+        self.thumb_drag.is_some()
+    }
+
+    /// Handles a click on the `row`, depending on the `selection_mode` and
+    /// which modifier keys are held down
+    pub fn click_row(&mut self, row: usize, shift_down: bool, ctrl_down: bool) {
+        match self.selection_mode {
+            SelectionMode::Single => self.select(row),
+            SelectionMode::Range => if shift_down {
+                self.extend_selection(row, false);
+            } else {
+                self.select(row);
+            },
+            SelectionMode::Multiple => if shift_down {
+                self.extend_selection(row, ctrl_down);
+            } else if ctrl_down {
+                self.toggle(row);
+            } else {
+                self.select(row);
+            },
+        }
+    }
+
+    /// Moves the cursor to the `row` (clamped to the last row) and scrolls it into view.
+    /// With `extend_selection`, the rows from the anchor to the `row` are selected,
+    /// otherwise only the `row` is selected.
+    pub fn move_cursor(&mut self, row: usize, extend_selection: bool) {
+        if self.item_count == 0 {
+            return;
+        }
+        let row = row.min(self.item_count - 1);
+        if extend_selection && self.selection_mode != SelectionMode::Single {
+            self.extend_selection(row, false);
+        } else {
+            self.select(row);
+        }
+        self.scroll_to_row(row);
+    }
+
+    /// Selects the rows from the anchor to the `row`, `keep_selection` adds them to the current selection
+    fn extend_selection(&mut self, row: usize, keep_selection: bool) {
+        if row >= self.item_count {
+            return;
+        }
+        let anchor = self.anchor.unwrap_or(row).min(self.item_count - 1);
+        if !keep_selection {
+            self.selection.clear();
+        }
+        insert_range(&mut self.selection, anchor.min(row)..(anchor.max(row) + 1));
+        self.anchor = Some(anchor);
+        self.cursor = Some(row);
+    }
+
+    /// Remembers the height of the list from the last frame, for paging with PageUp / PageDown
+    fn update_viewport_height<T>(&mut self, event: &CallbackInfo<T>) -> f32 {
+        if let Some(rect) = event.get_node_rect(event.hit_dom_node) {
+            self.viewport_height = rect.size.height;
+        }
+        self.viewport_height
+    }
+
+    /// Resolves the `scroll_target` and scrolls by `delta` pixels
+    fn scroll_by(&mut self, delta: f64) -> UpdateScreen {
+        let old_scroll_offset = self.get_scroll_offset(self.viewport_height);
+        self.scroll_target = None;
+        self.scroll_offset = old_scroll_offset + delta;
+        self.scroll_offset = self.get_scroll_offset(self.viewport_height);
+        if self.scroll_offset != old_scroll_offset { Redraw } else { DontRedraw }
+    }
+
+    /// Renders the visible rows, invoked by the IFrame of the `ListView`
+    pub fn render<T>(&self, data: &T, render_row: RenderRowFn<T>, overscan: usize, dimensions: HidpiAdjustedBounds) -> Dom<T> {
+        self.render_with(|row| render_row(data, row), overscan, dimensions)
+    }
+
+    /// Same as `render`, but the rows are rendered by a closure, for widgets that
+    /// embed a list and render the rows from their own state (i.e. the `FileChooser`)
+    pub fn render_with<T, F>(&self, render_row: F, overscan: usize, dimensions: HidpiAdjustedBounds) -> Dom<T> where F: Fn(usize) -> Dom<T> {
+
+        let viewport_height = dimensions.get_logical_size().height as f32;
+        let scroll_offset = self.get_scroll_offset(viewport_height);
+        let visible_rows = self.row_height.get_visible_rows(scroll_offset, viewport_height, self.item_count);
+        let first_row = visible_rows.start.saturating_sub(overscan);
+        let last_row = visible_rows.end.saturating_add(overscan).min(self.item_count);
+
+        // div.__azul-native-list-view-content
+        //     |-> div.__azul-native-list-view-rows (moved up by the part of the first row that is scrolled out)
+        //     |    '-> div.__azul-native-list-view-row (.__azul-native-list-view-row-selected)
+        //     '-> div.__azul-native-list-view-scrollbar
+        //          '-> div.__azul-native-list-view-scrollbar-thumb
+
+        // The difference is small, even if both offsets are too large for an f32
+        let rows_top = (self.row_height.get_row_offset(first_row) - scroll_offset) as f32;
+        let mut rows = Dom::div()
+            .with_class("__azul-native-list-view-rows")
+            .with_css_override("__azul_list_view_rows_offset", CssProperty::Margin(LayoutMargin {
+                top: Some(PixelValue::px(rows_top)),
+                .. Default::default()
+            }));
+
+        for row in first_row..last_row {
+            let mut row_dom = Dom::div()
+                .with_class("__azul-native-list-view-row")
+                .with_css_override("__azul_list_view_row_height", CssProperty::Height(LayoutHeight::px(self.row_height.get_row_height(row))))
+                .with_child(render_row(row));
+            if self.cursor == Some(row) {
+                row_dom.add_class("__azul-native-list-view-row-cursor");
+            }
+            if self.is_selected(row) {
+                row_dom.add_class("__azul-native-list-view-row-selected");
+            }
+            rows.add_child(row_dom);
+        }
+
+        let mut content = Dom::div()
+            .with_class("__azul-native-list-view-content")
+            .with_child(rows);
+
+        if let Some((thumb_offset, thumb_height)) = self.get_scrollbar_thumb(viewport_height) {
+            content.add_child(
+                Dom::div()
+                .with_class("__azul-native-list-view-scrollbar")
+                .with_child(
+                    Dom::div()
+                    .with_class("__azul-native-list-view-scrollbar-thumb")
+                    .with_css_override("__azul_list_view_thumb_height", CssProperty::Height(LayoutHeight::px(thumb_height)))
+                    .with_css_override("__azul_list_view_thumb_offset", CssProperty::Margin(LayoutMargin {
+                        top: Some(PixelValue::px(thumb_offset)),
+                        .. Default::default()
+                    }))
+                )
+            );
+        }
+
+        content
+    }
+
+    /// Scrolls the list with the mouse wheel
+    pub fn on_scroll<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        self.update_viewport_height(event);
+        let scroll_y = app_state_no_data.windows[event.window_id].get_mouse_state().scroll_y;
+        self.scroll_and_consume(scroll_y, event)
+    }
+
+    /// Scrolls the list when a finger is moved over it
+    pub fn on_pan<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        self.update_viewport_height(event);
+        let pan_y: f64 = app_state_no_data.windows[event.window_id].get_touch_state().gestures.iter().map(|gesture| match gesture {
+            Gesture::Pan { delta, .. } => delta.1,
+            _ => 0.0,
+        }).sum();
+        self.scroll_and_consume(-pan_y, event)
+    }
+
+    /// Scrolls the list itself instead of its scrollable parents - only if the list is
+    /// already scrolled to the top or bottom, the parents are scrolled by the window
+    fn scroll_and_consume<T>(&mut self, delta: f64, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        let update_screen = self.scroll_by(delta);
+        if update_screen == Redraw {
+            event.consume_scroll();
+        }
+        update_screen
+    }
+
+    /// Selects the clicked row or starts dragging the scrollbar thumb
+    pub fn on_mouse_down<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        self.update_viewport_height(event);
+
+        let rect = match event.get_node_rect(event.hit_dom_node) {
+            Some(s) => s,
+            None => return DontRedraw,
+        };
+
+        let (cursor_x, cursor_y) = match event.cursor_in_viewport {
+            Some(s) => s,
+            None => return DontRedraw,
+        };
+
+        let list_top = rect.origin.y;
+
+        if let Some((thumb_offset, thumb_height)) = self.get_scrollbar_thumb(self.viewport_height) {
+            if cursor_x >= rect.origin.x + rect.size.width - SCROLLBAR_WIDTH {
+                let cursor_offset = cursor_y - list_top;
+                let grab_offset = if cursor_offset >= thumb_offset && cursor_offset <= thumb_offset + thumb_height {
+                    cursor_offset - thumb_offset
+                } else {
+                    // Clicking the track moves the center of the thumb to the cursor
+                    let grab_offset = thumb_height / 2.0;
+                    let viewport_height = self.viewport_height;
+                    self.scroll_to_thumb_offset(cursor_offset - grab_offset, viewport_height);
+                    grab_offset
+                };
+                self.thumb_drag = Some(grab_offset);
+                return Redraw;
+            }
+        }
+
+        let scroll_offset = self.get_scroll_offset(self.viewport_height);
+        let row = match self.row_height.get_row_at(f64::from(cursor_y - list_top) + scroll_offset, self.item_count) {
+            Some(row) => row,
+            None => return DontRedraw,
+        };
+
+        let keyboard_state = app_state_no_data.windows[event.window_id].get_keyboard_state();
+        let (shift_down, ctrl_down) = (keyboard_state.shift_down, keyboard_state.ctrl_down);
+        self.click_row(row, shift_down, ctrl_down);
+        self.scroll_to_row(row);
+        Redraw
+    }
+
+    /// Scrolls the list while the scrollbar thumb is dragged
+    pub fn on_mouse_over<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        let grab_offset = self.thumb_drag?;
+        let mouse_state = app_state_no_data.windows[event.window_id].get_mouse_state();
+
+        // The mouse button was released outside of the window
+        if !mouse_state.left_down {
+            self.thumb_drag = None;
+            return DontRedraw;
+        }
+
+        let list_top = event.get_node_rect(event.hit_dom_node)?.origin.y;
+        let (_, cursor_y) = event.get_cursor_in_viewport(mouse_state)?;
+
+        let old_scroll_offset = self.get_scroll_offset(self.viewport_height);
+        let viewport_height = self.viewport_height;
+        self.scroll_to_thumb_offset(cursor_y - list_top - grab_offset, viewport_height);
+        if self.scroll_offset != old_scroll_offset { Redraw } else { DontRedraw }
+    }
+
+    /// Stops dragging the scrollbar thumb
+    pub fn on_mouse_up<T>(&mut self, _app_state_no_data: &mut AppStateNoData<T>, _event: &mut CallbackInfo<T>) -> UpdateScreen {
+        self.thumb_drag.take()?;
+        DontRedraw
+    }
+
+    /// Moves the cursor with the arrow keys, PageUp / PageDown and Home / End, Shift extends the
+    /// selection. Ctrl + A selects all rows and Ctrl + Space toggles the row under the cursor.
+    pub fn on_virtual_key_down<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        if self.item_count == 0 {
+            return DontRedraw;
+        }
+
+        let viewport_height = self.update_viewport_height(event);
+        let keyboard_state = app_state_no_data.windows[event.window_id].get_keyboard_state();
+        let shift_down = keyboard_state.shift_down;
+        let ctrl_down = keyboard_state.ctrl_down;
+        let last_row = self.item_count - 1;
+        let cursor = self.cursor.unwrap_or(0).min(last_row);
+        let cursor_top = self.row_height.get_row_offset(cursor);
+        let page_height = f64::from(viewport_height);
+
+        match keyboard_state.latest_virtual_keycode {
+            Some(VirtualKeyCode::Up) => self.move_cursor(cursor.saturating_sub(1), shift_down),
+            Some(VirtualKeyCode::Down) => self.move_cursor(cursor.saturating_add(1), shift_down),
+            Some(VirtualKeyCode::Home) => self.move_cursor(0, shift_down),
+            Some(VirtualKeyCode::End) => self.move_cursor(last_row, shift_down),
+            Some(VirtualKeyCode::PageUp) => {
+                let row = self.row_height.get_row_at(cursor_top - page_height, self.item_count).unwrap_or(0);
+                self.move_cursor(row, shift_down);
+            },
+            Some(VirtualKeyCode::PageDown) => {
+                let row = self.row_height.get_row_at(cursor_top + page_height, self.item_count).unwrap_or(last_row);
+                self.move_cursor(row, shift_down);
+            },
+            Some(VirtualKeyCode::A) if ctrl_down && self.selection_mode == SelectionMode::Multiple => self.select_all(),
+            Some(VirtualKeyCode::Space) if ctrl_down && self.selection_mode == SelectionMode::Multiple => self.toggle(cursor),
+            Some(VirtualKeyCode::Space) => self.select(cursor),
+            _ => return DontRedraw,
+        }
+
+        Redraw
+    }
+}
+
+impl<T> ListView<T> {
+
+    pub fn new(render_row: RenderRowFn<T>) -> Self {
+        Self { render_row, overscan: 5 }
+    }
+
+    /// Sets how many rows are rendered above and below the visible rows (default: 5)
+    #[inline]
+    pub fn with_overscan(self, overscan: usize) -> Self {
+        Self { overscan, .. self }
+    }
+
+    /// The `state` has to be stored inside of the application data `t`, so that the
+    /// list can update the selection and the scroll position. If a window has more than
+    /// one `ListView`, each list needs its own `ListViewState`.
+    pub fn dom(&self, state: &ListViewState, t: &T, window: &mut FakeWindow<T>) -> Dom<T> where T: 'static {
+
+        let ptr = match StackCheckedPointer::new(t, state) {
+            Some(ptr) => ptr,
+            None => return Dom::label(
+                "Cannot create list view from heap-allocated ListViewState, \
+                 please store the ListViewState in the application data"
+            ),
+        };
+
+        let render_row = self.render_row;
+        let overscan = self.overscan;
+
+        // The state is only read while the application data is locked for the IFrame callback
+        let mut dom = Dom::iframe_closure(IFrameClosureCallback::new(&("__azul_list_view", &ptr), move |data, _info, dimensions| {
+            let state = unsafe { ptr.get::<ListViewState>() };
+            state.render(data, render_row, overscan, dimensions)
+        }))
+        .with_class("__azul-native-list-view")
+        .with_tab_index(TabIndex::Auto);
+
+        let on_scroll = window.add_callback(ptr, DefaultCallback(Self::list_view_on_scroll));
+        let on_pan = window.add_callback(ptr, DefaultCallback(Self::list_view_on_pan));
+        let on_mouse_down = window.add_callback(ptr, DefaultCallback(Self::list_view_on_mouse_down));
+        let on_mouse_over = window.add_callback(ptr, DefaultCallback(Self::list_view_on_mouse_over));
+        let on_mouse_up = window.add_callback(ptr, DefaultCallback(Self::list_view_on_mouse_up));
+        let on_virtual_key_down = window.add_callback(ptr, DefaultCallback(Self::list_view_on_virtual_key_down));
+
+        dom.add_default_callback_id(On::Scroll, on_scroll);
+        dom.add_default_callback_id(On::Pan, on_pan);
+        dom.add_default_callback_id(On::MouseDown, on_mouse_down);
+        // The thumb can be dragged outside of the list
+        dom.add_default_callback_id(EventFilter::Window(WindowEventFilter::MouseOver), on_mouse_over);
+        dom.add_default_callback_id(EventFilter::Window(WindowEventFilter::MouseUp), on_mouse_up);
+        dom.add_default_callback_id(EventFilter::Focus(FocusEventFilter::VirtualKeyDown), on_virtual_key_down);
+        dom
+    }
+
+    fn list_view_on_scroll(ptr: &StackCheckedPointer<T>, data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { ptr.invoke_mut(ListViewState::on_scroll, data, event) }
+    }
+
+    fn list_view_on_pan(ptr: &StackCheckedPointer<T>, data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { ptr.invoke_mut(ListViewState::on_pan, data, event) }
+    }
+
+    fn list_view_on_mouse_down(ptr: &StackCheckedPointer<T>, data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { ptr.invoke_mut(ListViewState::on_mouse_down, data, event) }
+    }
+
+    fn list_view_on_mouse_over(ptr: &StackCheckedPointer<T>, data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { ptr.invoke_mut(ListViewState::on_mouse_over, data, event) }
+    }
+
+    fn list_view_on_mouse_up(ptr: &StackCheckedPointer<T>, data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { ptr.invoke_mut(ListViewState::on_mouse_up, data, event) }
+    }
+
+    fn list_view_on_virtual_key_down(ptr: &StackCheckedPointer<T>, data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { ptr.invoke_mut(ListViewState::on_virtual_key_down, data, event) }
+    }
+}
+
+/// Inserts the `range` into the sorted, non-overlapping `ranges`, merging overlapping and adjacent ranges
+fn insert_range(ranges: &mut Vec<Range<usize>>, range: Range<usize>) {
+    if range.start >= range.end {
+        return;
+    }
+
+    let mut merged = range;
+    let mut result = Vec::with_capacity(ranges.len() + 1);
+    let mut is_inserted = false;
+
+    for existing in ranges.drain(..) {
+        if existing.end < merged.start {
+            result.push(existing);
+        } else if existing.start > merged.end {
+            if !is_inserted {
+                result.push(merged.clone());
+                is_inserted = true;
+            }
+            result.push(existing);
+        } else {
+            merged = merged.start.min(existing.start)..merged.end.max(existing.end);
+        }
+    }
+
+    if !is_inserted {
+        result.push(merged);
+    }
+
+    *ranges = result;
+}
+
+/// Removes the `row` from the sorted, non-overlapping `ranges`, splitting the range that contains it
+fn remove_from_ranges(ranges: &mut Vec<Range<usize>>, row: usize) {
+    let position = match ranges.iter().position(|range| range.start <= row && row < range.end) {
+        Some(s) => s,
+        None => return,
+    };
+    let range = ranges.remove(position);
+    if row + 1 < range.end {
+        ranges.insert(position, (row + 1)..range.end);
+    }
+    if range.start < row {
+        ranges.insert(position, range.start..row);
+    }
+}
+
+#[test]
+fn test_list_view_visible_rows() {
+    let fixed = RowHeight::Fixed(20.0);
+    assert_eq!(fixed.get_visible_rows(0.0, 100.0, 1_000_000), 0..5);
+    assert_eq!(fixed.get_visible_rows(10.0, 100.0, 1_000_000), 0..6);
+    assert_eq!(fixed.get_visible_rows(19_999_900.0, 100.0, 1_000_000), 999_995..1_000_000);
+    assert_eq!(fixed.get_visible_rows(0.0, 100.0, 3), 0..3);
+    assert_eq!(fixed.get_row_at(39.0, 1_000_000), Some(1));
+    assert_eq!(fixed.get_row_at(20_000_000.0, 1_000_000), None);
+
+    let variable = RowHeight::Variable(vec![10.0, 50.0, 10.0]);
+    assert_eq!(variable.get_total_height(4), 90.0);
+    assert_eq!(variable.get_visible_rows(0.0, 30.0, 4), 0..2);
+    assert_eq!(variable.get_visible_rows(55.0, 30.0, 4), 1..4);
+    assert_eq!(variable.get_row_at(65.0, 4), Some(2));
+}
+
+#[test]
+fn test_list_view_scroll_offset() {
+    let mut state = ListViewState::new(100, RowHeight::Fixed(20.0));
+    state.scroll_offset = 5000.0;
+    assert_eq!(state.get_scroll_offset(100.0), 1900.0);
+
+    state.scroll_offset = 0.0;
+    state.scroll_to_row(10);
+    assert_eq!(state.get_scroll_offset(100.0), 120.0);
+    state.scroll_to_end();
+    assert_eq!(state.get_scroll_offset(100.0), 1900.0);
+}
+
+#[test]
+fn test_list_view_long_list_precision() {
+    // 10 million rows of 20 pixels are higher than an f32 can represent exactly
+    let mut state = ListViewState::new(10_000_000, RowHeight::Fixed(20.0));
+    state.scroll_to_end();
+    assert_eq!(state.get_scroll_offset(100.0), 199_999_900.0);
+    assert_eq!(state.row_height.get_visible_rows(state.get_scroll_offset(100.0), 100.0, 10_000_000), 9_999_995..10_000_000);
+
+    state.scroll_target = None;
+    state.scroll_offset = 199_999_910.0;
+    assert_eq!(state.row_height.get_visible_rows(state.scroll_offset, 100.0, 10_000_000), 9_999_995..10_000_000);
+    assert_eq!(state.row_height.get_row_at(199_999_999.0, 10_000_000), Some(9_999_999));
+}
+
+#[test]
+fn test_list_view_scrollbar_thumb() {
+    let mut state = ListViewState::new(100, RowHeight::Fixed(20.0));
+    assert_eq!(state.get_scrollbar_thumb(100.0), Some((0.0, 20.0)));
+
+    // The thumb moves along the track of 80 pixels, for a scroll range of 1900 pixels
+    state.scroll_to_thumb_offset(40.0, 100.0);
+    assert_eq!(state.get_scroll_offset(100.0), 950.0);
+    assert_eq!(state.get_scrollbar_thumb(100.0), Some((40.0, 20.0)));
+
+    state.scroll_to_thumb_offset(500.0, 100.0);
+    assert_eq!(state.get_scroll_offset(100.0), 1900.0);
+    state.scroll_to_thumb_offset(-10.0, 100.0);
+    assert_eq!(state.get_scroll_offset(100.0), 0.0);
+
+    // No scrollbar if all rows fit into the viewport
+    assert_eq!(ListViewState::new(3, RowHeight::Fixed(20.0)).get_scrollbar_thumb(100.0), None);
+}
+
+#[cfg(test)]
+fn get_selection_tuples(state: &ListViewState) -> Vec<(usize, usize)> {
+    state.get_selection().iter().map(|range| (range.start, range.end)).collect()
+}
+
+#[test]
+fn test_list_view_selection() {
+    let mut state = ListViewState::new(1_000_000, RowHeight::Fixed(20.0)).with_selection_mode(SelectionMode::Multiple);
+
+    state.click_row(10, false, false);
+    state.click_row(20, true, false);
+    assert_eq!(get_selection_tuples(&state), vec![(10, 21)]);
+
+    state.click_row(15, false, true);
+    assert_eq!(get_selection_tuples(&state), vec![(10, 15), (16, 21)]);
+    assert!(!state.is_selected(15));
+    assert!(state.is_selected(16));
+
+    // The anchor is the toggled row, ctrl + shift adds the range to the selection
+    state.click_row(30, true, true);
+    assert_eq!(get_selection_tuples(&state), vec![(10, 31)]);
+    assert_eq!(state.get_selected_count(), 21);
+
+    state.select_all();
+    assert_eq!(get_selection_tuples(&state), vec![(0, 1_000_000)]);
+
+    state.move_cursor(5, false);
+    state.move_cursor(2, true);
+    assert_eq!(get_selection_tuples(&state), vec![(2, 6)]);
+    assert_eq!(state.cursor, Some(2));
+
+    let mut single = ListViewState::new(10, RowHeight::Fixed(20.0));
+    single.click_row(3, false, false);
+    single.click_row(7, true, true);
+    assert_eq!(get_selection_tuples(&single), vec![(7, 8)]);
+}
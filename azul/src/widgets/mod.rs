@@ -1,11 +1,94 @@
+use std::time::{Duration, Instant};
+use azul_css::{CssProperty, LayoutFlexGrow, FloatValue};
+use dom::Dom;
+
 #[cfg(feature = "svg")]
 pub mod svg;
 pub mod button;
 pub mod label;
 pub mod text_input;
+pub mod checkbox;
+pub mod radio_group;
+pub mod switch;
+pub mod slider;
+pub mod spin_box;
 pub mod table_view;
+pub mod list_view;
+pub mod tree_view;
+pub mod tab_view;
+pub mod splitter;
+pub mod expander;
+pub mod tooltip;
+pub mod context_menu;
+pub mod dropdown;
+pub mod date_picker;
+pub mod time_picker;
+#[cfg(feature = "css_parser")]
+pub mod color_picker;
+pub mod progress;
+pub mod toast;
+pub mod file_chooser;
+
+/// Maximum time between two clicks that counts as a double click
+pub(crate) const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(500);
+
+/// Detects double clicks on the same item (i.e. the same row of a list)
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct DoubleClick<K> {
+    last_click: Option<(K, Instant)>,
+}
+
+impl<K> Default for DoubleClick<K> {
+    fn default() -> Self {
+        Self { last_click: None }
+    }
+}
+
+impl<K: PartialEq> DoubleClick<K> {
+
+    /// Registers a click on the `item`, returns whether the click completes a double click.
+    /// The click after a double click starts a new double click.
+    pub(crate) fn click(&mut self, item: K, now: Instant) -> bool {
+        let is_double_click = match &self.last_click {
+            Some((last_item, last_click)) => *last_item == item && now.duration_since(*last_click) < DOUBLE_CLICK_TIME,
+            None => false,
+        };
+        self.last_click = if is_double_click { None } else { Some((item, now)) };
+        is_double_click
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.last_click = None;
+    }
+}
+
+/// Div that takes the `flex_grow` share of the space of its parent, i.e. the filled part
+/// of a progress bar or slider. The flex-grow is set via the CSS override `override_id`.
+pub(crate) fn flex_grow_div<T>(class: &'static str, override_id: &'static str, flex_grow: f32) -> Dom<T> {
+    Dom::div()
+    .with_class(class)
+    .with_css_override(override_id, CssProperty::FlexGrow(LayoutFlexGrow(FloatValue::new(flex_grow))))
+}
 
 pub mod errors {
     #[cfg(all(feature = "svg", feature = "svg_parsing"))]
     pub use super::svg::SvgParseError;
+}
+
+#[test]
+fn test_double_click() {
+    let start = Instant::now();
+    let mut double_click = DoubleClick::default();
+
+    assert!(!double_click.click(1, start));
+    assert!(double_click.click(1, start + Duration::from_millis(200)));
+    // The third click starts a new double click
+    assert!(!double_click.click(1, start + Duration::from_millis(300)));
+
+    // Clicks on different items or with too much time in between are no double click
+    assert!(!double_click.click(2, start + Duration::from_millis(400)));
+    assert!(!double_click.click(2, start + Duration::from_millis(1000)));
+
+    double_click.reset();
+    assert!(!double_click.click(2, start + Duration::from_millis(1100)));
 }
\ No newline at end of file
@@ -0,0 +1,143 @@
+//! Progress indicators: a determinate / indeterminate `ProgressBar` and a `Spinner`
+//!
+//! The indicators are stateless, the animation of the indeterminate progress bar and the
+//! spinner is derived from the current time. In order to keep them moving, the window has
+//! to be redrawn regularly, which is what the `animation_timer` is for:
+//!
+//! ```no_run,ignore
+//! let task = Task::new_cancellable(&app_state.data, download);
+//! let handle = app_state.add_task(task);
+//! app_state.add_timer(TimerId::new(), animation_timer(|data: &MyData| data.download.is_some()));
+//! // in the layout() function:
+//! ProgressBar::for_task(&self.download_handle).dom()
+//! ```
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use {
+    dom::Dom,
+    callbacks::{TimerClosureCallback, Redraw, DontRedraw},
+    async::{Timer, TerminateTimer, TaskHandle},
+    widgets::flex_grow_div,
+};
+
+/// Interval of the `animation_timer`, ~60 FPS
+const ANIMATION_INTERVAL: Duration = Duration::from_millis(16);
+/// Time (in milliseconds) for the indeterminate progress bar to move from the left to the right
+const INDETERMINATE_PERIOD_MS: u64 = 1500;
+/// Width of the moving chunk of the indeterminate progress bar, relative to the bar
+const INDETERMINATE_CHUNK_SIZE: f32 = 0.3;
+/// Time (in milliseconds) for the spinner to complete one rotation
+const SPINNER_PERIOD_MS: u64 = 1000;
+/// Number of dots of the spinner, positioned in the CSS via `:nth-child`
+const SPINNER_DOTS: usize = 8;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct ProgressBar {
+    /// Progress between 0.0 and 1.0, `None` for an indeterminate progress bar
+    pub progress: Option<f32>,
+}
+
+impl ProgressBar {
+
+    /// Creates a determinate progress bar, the `progress` is clamped to 0.0 - 1.0
+    #[inline]
+    pub fn new(progress: f32) -> Self {
+        Self { progress: Some(progress.max(0.0).min(1.0)) }
+    }
+
+    /// Creates a progress bar that shows activity without a known progress
+    /// (needs an `animation_timer` in order to move)
+    #[inline]
+    pub fn indeterminate() -> Self {
+        Self { progress: None }
+    }
+
+    /// Shows the progress that the task reported via `TaskContext::set_progress::<f32>()`,
+    /// indeterminate if the task hasn't reported any progress yet
+    pub fn for_task(task: &TaskHandle) -> Self {
+        match task.get_progress::<f32>() {
+            Some(progress) => Self::new(progress),
+            None => Self::indeterminate(),
+        }
+    }
+
+    pub fn dom<T>(self) -> Dom<T> {
+
+        // The bar is split into (space before, filled chunk, space after) via flex-grow,
+        // since the width of a node can't be given in percent
+        let (before, chunk) = match self.progress {
+            Some(progress) => (0.0, progress),
+            None => {
+                // The chunk moves in from the left and out on the right
+                let phase = get_animation_phase(INDETERMINATE_PERIOD_MS);
+                let start = phase * (1.0 + INDETERMINATE_CHUNK_SIZE) - INDETERMINATE_CHUNK_SIZE;
+                let end = start + INDETERMINATE_CHUNK_SIZE;
+                (start.max(0.0), end.min(1.0) - start.max(0.0))
+            },
+        };
+        let after = 1.0 - before - chunk;
+
+        let mut bar = Dom::div()
+            .with_class("__azul-native-progress-bar")
+            .with_child(flex_grow_div("__azul-native-progress-bar-before", "__azul_progress_before", before))
+            .with_child(flex_grow_div("__azul-native-progress-bar-fill", "__azul_progress_fill", chunk))
+            .with_child(flex_grow_div("__azul-native-progress-bar-after", "__azul_progress_after", after));
+
+        if self.progress.is_none() {
+            bar.add_class("__azul-native-progress-bar-indeterminate");
+        }
+
+        bar
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Spinner { }
+
+impl Spinner {
+
+    #[inline]
+    pub fn new() -> Self {
+        Spinner { }
+    }
+
+    /// Renders a ring of dots, the highlighted dot moves around the ring
+    /// (needs an `animation_timer` in order to move)
+    pub fn dom<T>(self) -> Dom<T> {
+        let active_dot = (get_animation_phase(SPINNER_PERIOD_MS) * SPINNER_DOTS as f32) as usize % SPINNER_DOTS;
+        let trailing_dot = (active_dot + SPINNER_DOTS - 1) % SPINNER_DOTS;
+
+        (0..SPINNER_DOTS).map(|dot| {
+            let mut dot_dom = Dom::div().with_class("__azul-native-spinner-dot");
+            if dot == active_dot {
+                dot_dom.add_class("__azul-native-spinner-dot-active");
+            } else if dot == trailing_dot {
+                dot_dom.add_class("__azul-native-spinner-dot-trailing");
+            }
+            dot_dom
+        })
+        .collect::<Dom<T>>()
+        .with_class("__azul-native-spinner")
+    }
+}
+
+/// Returns a timer that redraws the window on every frame while `is_animating` returns true,
+/// in order to animate the indeterminate progress bars and spinners. The timer is
+/// removed once `is_animating` returns false.
+pub fn animation_timer<T: 'static>(is_animating: fn(&T) -> bool) -> Timer<T> {
+    Timer::from_closure(TimerClosureCallback::new(&("__azul_animation_timer", is_animating as usize), move |data, _| {
+        if is_animating(data) {
+            (Redraw, TerminateTimer::Continue)
+        } else {
+            (DontRedraw, TerminateTimer::Terminate)
+        }
+    }))
+    .with_interval(ANIMATION_INTERVAL)
+}
+
+/// Returns how far (0.0 - 1.0) the current animation cycle with the given period has progressed
+fn get_animation_phase(period_ms: u64) -> f32 {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    let millis = now.as_secs().wrapping_mul(1000).wrapping_add(u64::from(now.subsec_millis()));
+    (millis % period_ms) as f32 / period_ms as f32
+}
@@ -0,0 +1,168 @@
+//! Group of radio buttons, of which exactly one can be selected (two-way data binding)
+
+use {
+    callbacks::{UpdateScreen, Redraw, DontRedraw},
+    dom::{Dom, DomString, On, EventFilter, FocusEventFilter, TabIndex},
+    window::FakeWindow,
+    prelude::VirtualKeyCode,
+    callbacks::{CallbackInfo, StackCheckedPointer, DefaultCallback, DefaultCallbackId},
+    app::AppStateNoData,
+};
+
+#[derive(Debug, Default, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct RadioGroup {
+    on_select_callback: Option<(DefaultCallbackId, DefaultCallbackId)>,
+}
+
+#[derive(Debug, Default, Clone, Hash, PartialEq, Eq)]
+pub struct RadioGroupState {
+    pub options: Vec<DomString>,
+    /// Index of the selected option, `None` until the user selects an option
+    pub selected: Option<usize>,
+    /// Disabled radio groups can't be focused or changed
+    pub disabled: bool,
+}
+
+impl RadioGroupState {
+
+    pub fn new<S: Into<DomString>, I: IntoIterator<Item = S>>(options: I) -> Self {
+        Self {
+            options: options.into_iter().map(|option| option.into()).collect(),
+            .. Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn with_selected(self, selected: usize) -> Self {
+        Self { selected: Some(selected), .. self }
+    }
+
+    #[inline]
+    pub fn with_disabled(self, disabled: bool) -> Self {
+        Self { disabled, .. self }
+    }
+
+    /// Selects the option at the `index`, ignored if the `index` is out of range
+    pub fn select(&mut self, index: usize) {
+        if index < self.options.len() {
+            self.selected = Some(index);
+        }
+    }
+
+    /// Selects the next (or with a negative `offset`, the previous) option,
+    /// wrapping around at the first and last option like native radio groups
+    pub fn select_next(&mut self, offset: isize) {
+        let count = self.options.len() as isize;
+        if count == 0 {
+            return;
+        }
+        let next = match self.selected {
+            Some(selected) => ((selected as isize + offset) % count + count) % count,
+            None if offset < 0 => count - 1,
+            None => 0,
+        };
+        self.selected = Some(next as usize);
+    }
+}
+
+impl RadioGroup {
+
+    pub fn new() -> Self {
+        RadioGroup { on_select_callback: None }
+    }
+
+    pub fn bind<T>(self, window: &mut FakeWindow<T>, field: &RadioGroupState, data: &T) -> Self {
+        let ptr = StackCheckedPointer::new(data, field);
+        let on_select_callback = ptr.map(|ptr|{(
+            window.add_callback(ptr, DefaultCallback(RadioGroupState::on_click_private)),
+            window.add_callback(ptr, DefaultCallback(RadioGroupState::on_virtual_key_down_private))
+        )});
+
+        Self { on_select_callback }
+    }
+
+    pub fn dom<T>(&self, field: &RadioGroupState) -> Dom<T> {
+
+        let mut parent_div =
+            Dom::div()
+            .with_class("__azul-native-radio-group")
+            .with_disabled(field.disabled);
+
+        if !field.disabled {
+            parent_div.set_tab_index(TabIndex::Auto);
+            if let Some((click_callback, vk_callback)) = self.on_select_callback {
+                parent_div.add_default_callback_id(On::LeftMouseUp, click_callback);
+                parent_div.add_default_callback_id(EventFilter::Focus(FocusEventFilter::VirtualKeyDown), vk_callback);
+            }
+        }
+
+        for (index, option) in field.options.iter().enumerate() {
+            parent_div.add_child(
+                Dom::div()
+                .with_class("__azul-native-radio")
+                .with_checked(field.selected == Some(index))
+                .with_disabled(field.disabled)
+                .with_child(
+                    Dom::div()
+                    .with_class("__azul-native-radio-circle")
+                    .with_child(Dom::div().with_class("__azul-native-radio-dot"))
+                )
+                .with_child(Dom::label(option.clone()).with_class("__azul-native-radio-label"))
+            );
+        }
+
+        parent_div
+    }
+}
+
+impl RadioGroupState {
+
+    fn on_click_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_click, app_state_no_data, window_event) }
+    }
+
+    fn on_virtual_key_down_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_virtual_key_down, app_state_no_data, window_event) }
+    }
+
+    /// Selects the option that was clicked
+    pub fn on_click<T>(&mut self, _app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        match event.get_target_child_index() {
+            Some(index) if !self.disabled && self.selected != Some(index) => {
+                self.select(index);
+                Redraw
+            },
+            _ => DontRedraw,
+        }
+    }
+
+    /// Up / Left select the previous, Down / Right the next option
+    pub fn on_virtual_key_down<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        if self.disabled {
+            return DontRedraw;
+        }
+
+        let keyboard_state = app_state_no_data.windows[event.window_id].get_keyboard_state();
+
+        match keyboard_state.latest_virtual_keycode {
+            Some(VirtualKeyCode::Up) | Some(VirtualKeyCode::Left) => self.select_next(-1),
+            Some(VirtualKeyCode::Down) | Some(VirtualKeyCode::Right) => self.select_next(1),
+            Some(VirtualKeyCode::Space) if self.selected.is_none() => self.select_next(1),
+            _ => return DontRedraw,
+        }
+
+        Redraw
+    }
+}
+
+#[test]
+fn test_radio_group_select_next() {
+    let mut radio_group = RadioGroupState::new(vec!["Small", "Medium", "Large"]);
+    radio_group.select_next(-1);
+    assert_eq!(radio_group.selected, Some(2));
+    radio_group.select_next(1);
+    assert_eq!(radio_group.selected, Some(0));
+    radio_group.select(5);
+    assert_eq!(radio_group.selected, Some(0));
+}
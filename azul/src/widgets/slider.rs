@@ -0,0 +1,307 @@
+//! Horizontal or vertical slider for picking a number from a range (two-way data binding)
+
+use {
+    callbacks::{UpdateScreen, Redraw, DontRedraw},
+    dom::{Dom, On, EventFilter, FocusEventFilter, WindowEventFilter, TabIndex},
+    window::FakeWindow,
+    prelude::VirtualKeyCode,
+    callbacks::{CallbackInfo, StackCheckedPointer, DefaultCallback, DefaultCallbackId},
+    app::AppStateNoData,
+    widgets::flex_grow_div,
+};
+
+/// Size of the thumb in pixels along the slider, has to match the
+/// `__azul-native-slider-thumb` in the native stylesheets
+pub const THUMB_SIZE: f32 = 16.0;
+/// How many steps PageUp / PageDown move the slider
+const PAGE_STEPS: f32 = 10.0;
+/// Number of steps that the keyboard moves a slider without a `step`
+const DEFAULT_KEYBOARD_STEPS: f32 = 100.0;
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum Orientation {
+    Horizontal,
+    /// The minimum is at the bottom, the maximum at the top
+    Vertical,
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+struct SliderCallbacks {
+    mouse_down: DefaultCallbackId,
+    mouse_over: DefaultCallbackId,
+    mouse_up: DefaultCallbackId,
+    virtual_key_down: DefaultCallbackId,
+}
+
+#[derive(Debug, Default, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct Slider {
+    callbacks: Option<SliderCallbacks>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SliderState {
+    /// Current value, always between `min` and `max` if set via `set_value`
+    pub value: f32,
+    pub min: f32,
+    pub max: f32,
+    /// If set, the value snaps to multiples of the `step` (counted from the `min`),
+    /// otherwise the slider is continuous
+    pub step: Option<f32>,
+    pub orientation: Orientation,
+    /// Disabled sliders can't be focused or moved
+    pub disabled: bool,
+    /// Whether the thumb is currently dragged with the mouse
+    is_dragging: bool,
+}
+
+impl Default for SliderState {
+    fn default() -> Self {
+        SliderState {
+            value: 0.0,
+            min: 0.0,
+            max: 1.0,
+            step: None,
+            orientation: Orientation::Horizontal,
+            disabled: false,
+            is_dragging: false,
+        }
+    }
+}
+
+impl SliderState {
+
+    pub fn new(value: f32, min: f32, max: f32) -> Self {
+        let mut slider = Self { min, max, .. Default::default() };
+        slider.set_value(value);
+        slider
+    }
+
+    #[inline]
+    pub fn with_step(self, step: f32) -> Self {
+        let mut slider = Self { step: Some(step), .. self };
+        slider.set_value(self.value);
+        slider
+    }
+
+    #[inline]
+    pub fn with_orientation(self, orientation: Orientation) -> Self {
+        Self { orientation, .. self }
+    }
+
+    #[inline]
+    pub fn with_disabled(self, disabled: bool) -> Self {
+        Self { disabled, .. self }
+    }
+
+    /// Sets the value, clamped to the range of the slider and snapped to the `step`
+    pub fn set_value(&mut self, value: f32) {
+        let value = match self.step {
+            Some(step) if step > 0.0 => self.min + ((value - self.min) / step).round() * step,
+            _ => value,
+        };
+        self.value = value.min(self.max).max(self.min);
+    }
+
+    /// Returns how far the slider is moved towards the maximum, from 0.0 to 1.0
+    pub fn get_fraction(&self) -> f32 {
+        if self.max > self.min {
+            ((self.value - self.min) / (self.max - self.min)).max(0.0).min(1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Sets the value from a fraction between 0.0 (minimum) and 1.0 (maximum)
+    pub fn set_fraction(&mut self, fraction: f32) {
+        let value = self.min + fraction * (self.max - self.min);
+        self.set_value(value);
+    }
+
+    /// Moves the slider by a number of steps (or hundredths of the range, if the slider has no `step`)
+    pub fn step_by(&mut self, steps: f32) {
+        let step = self.step.unwrap_or((self.max - self.min) / DEFAULT_KEYBOARD_STEPS);
+        let value = self.value + steps * step;
+        self.set_value(value);
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.is_dragging
+    }
+}
+
+impl Slider {
+
+    pub fn new() -> Self {
+        Slider { callbacks: None }
+    }
+
+    pub fn bind<T>(self, window: &mut FakeWindow<T>, field: &SliderState, data: &T) -> Self {
+        let ptr = StackCheckedPointer::new(data, field);
+        let callbacks = ptr.map(|ptr| SliderCallbacks {
+            mouse_down: window.add_callback(ptr, DefaultCallback(SliderState::on_mouse_down_private)),
+            mouse_over: window.add_callback(ptr, DefaultCallback(SliderState::on_mouse_over_private)),
+            mouse_up: window.add_callback(ptr, DefaultCallback(SliderState::on_mouse_up_private)),
+            virtual_key_down: window.add_callback(ptr, DefaultCallback(SliderState::on_virtual_key_down_private)),
+        });
+
+        Self { callbacks }
+    }
+
+    pub fn dom<T>(&self, field: &SliderState) -> Dom<T> {
+
+        let orientation_class = match field.orientation {
+            Orientation::Horizontal => "__azul-native-slider-horizontal",
+            Orientation::Vertical => "__azul-native-slider-vertical",
+        };
+
+        let mut parent_div =
+            Dom::div()
+            .with_class("__azul-native-slider")
+            .with_class(orientation_class)
+            .with_disabled(field.disabled);
+
+        if field.is_dragging {
+            parent_div.add_class("__azul-native-slider-dragging");
+        }
+
+        if !field.disabled {
+            parent_div.set_tab_index(TabIndex::Auto);
+            if let Some(callbacks) = self.callbacks {
+                parent_div.add_default_callback_id(On::LeftMouseDown, callbacks.mouse_down);
+                // Dragging has to continue when the mouse leaves the slider
+                parent_div.add_default_callback_id(EventFilter::Window(WindowEventFilter::MouseOver), callbacks.mouse_over);
+                parent_div.add_default_callback_id(EventFilter::Window(WindowEventFilter::MouseUp), callbacks.mouse_up);
+                parent_div.add_default_callback_id(EventFilter::Focus(FocusEventFilter::VirtualKeyDown), callbacks.virtual_key_down);
+            }
+        }
+
+        // The filled and the remaining part of the track share the space next to the thumb
+        let fraction = field.get_fraction();
+
+        parent_div
+            .with_child(flex_grow_div("__azul-native-slider-track-filled", "__azul_slider_filled", fraction))
+            .with_child(Dom::div().with_class("__azul-native-slider-thumb"))
+            .with_child(flex_grow_div("__azul-native-slider-track", "__azul_slider_remaining", 1.0 - fraction))
+    }
+}
+
+impl SliderState {
+
+    fn on_mouse_down_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_mouse_down, app_state_no_data, window_event) }
+    }
+
+    fn on_mouse_over_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_mouse_over, app_state_no_data, window_event) }
+    }
+
+    fn on_mouse_up_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_mouse_up, app_state_no_data, window_event) }
+    }
+
+    fn on_virtual_key_down_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_virtual_key_down, app_state_no_data, window_event) }
+    }
+
+    /// Moves the thumb under the cursor and starts dragging it
+    pub fn on_mouse_down<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        if self.disabled {
+            return DontRedraw;
+        }
+        self.is_dragging = true;
+        self.set_value_from_cursor(app_state_no_data, event);
+        Redraw
+    }
+
+    pub fn on_mouse_over<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        if !self.is_dragging {
+            return DontRedraw;
+        }
+
+        // The mouse button was released outside of the window
+        if !app_state_no_data.windows[event.window_id].get_mouse_state().left_down {
+            self.is_dragging = false;
+            return Redraw;
+        }
+
+        self.set_value_from_cursor(app_state_no_data, event)
+    }
+
+    pub fn on_mouse_up<T>(&mut self, _app_state_no_data: &mut AppStateNoData<T>, _event: &mut CallbackInfo<T>) -> UpdateScreen {
+        if !self.is_dragging {
+            return DontRedraw;
+        }
+        self.is_dragging = false;
+        Redraw
+    }
+
+    /// The arrow keys move the slider by one step, PageUp / PageDown by ten steps
+    /// and Home / End move it to the minimum / maximum
+    pub fn on_virtual_key_down<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        if self.disabled {
+            return DontRedraw;
+        }
+
+        let keyboard_state = app_state_no_data.windows[event.window_id].get_keyboard_state();
+        let old_value = self.value;
+
+        match keyboard_state.latest_virtual_keycode {
+            Some(VirtualKeyCode::Right) | Some(VirtualKeyCode::Up) => self.step_by(1.0),
+            Some(VirtualKeyCode::Left) | Some(VirtualKeyCode::Down) => self.step_by(-1.0),
+            Some(VirtualKeyCode::PageUp) => self.step_by(PAGE_STEPS),
+            Some(VirtualKeyCode::PageDown) => self.step_by(-PAGE_STEPS),
+            Some(VirtualKeyCode::Home) => self.value = self.min,
+            Some(VirtualKeyCode::End) => self.value = self.max,
+            _ => return DontRedraw,
+        }
+
+        if self.value != old_value { Redraw } else { DontRedraw }
+    }
+
+    fn set_value_from_cursor<T>(&mut self, app_state_no_data: &AppStateNoData<T>, event: &CallbackInfo<T>) -> UpdateScreen {
+
+        let rect = match event.get_node_rect(event.hit_dom_node) {
+            Some(s) => s,
+            None => return DontRedraw,
+        };
+
+        let cursor = event.get_cursor_in_viewport(app_state_no_data.windows[event.window_id].get_mouse_state());
+
+        let (x, y) = match cursor {
+            Some(s) => s,
+            None => return DontRedraw,
+        };
+
+        // The center of the thumb can only move between the ends of the slider minus half a thumb
+        let fraction = match self.orientation {
+            Orientation::Horizontal => (x - rect.origin.x - THUMB_SIZE / 2.0) / (rect.size.width - THUMB_SIZE),
+            Orientation::Vertical => 1.0 - (y - rect.origin.y - THUMB_SIZE / 2.0) / (rect.size.height - THUMB_SIZE),
+        };
+
+        if !fraction.is_finite() {
+            return DontRedraw;
+        }
+
+        let old_value = self.value;
+        self.set_fraction(fraction);
+        if self.value != old_value { Redraw } else { DontRedraw }
+    }
+}
+
+#[test]
+fn test_slider_step() {
+    let mut slider = SliderState::new(12.0, 0.0, 100.0).with_step(5.0);
+    assert_eq!(slider.value, 10.0);
+    slider.step_by(1.0);
+    assert_eq!(slider.value, 15.0);
+    slider.step_by(PAGE_STEPS * 100.0);
+    assert_eq!(slider.value, 100.0);
+    slider.set_fraction(0.31);
+    assert_eq!(slider.value, 30.0);
+    assert_eq!(slider.get_fraction(), 0.3);
+
+    let mut continuous = SliderState::new(0.5, 0.0, 1.0);
+    continuous.step_by(-1.0);
+    assert!((continuous.value - 0.49).abs() < 0.0001);
+}
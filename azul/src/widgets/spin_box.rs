@@ -0,0 +1,310 @@
+//! Numeric input with increment / decrement buttons and validation (two-way data binding)
+
+use std::f64;
+use {
+    callbacks::{UpdateScreen, Redraw, DontRedraw},
+    dom::{Dom, On, EventFilter, FocusEventFilter, TabIndex},
+    window::FakeWindow,
+    prelude::VirtualKeyCode,
+    callbacks::{CallbackInfo, StackCheckedPointer, DefaultCallback, DefaultCallbackId},
+    app::AppStateNoData,
+    widgets::text_input::{TextInputState, Selection},
+};
+
+/// How many steps PageUp / PageDown change the value
+const PAGE_STEPS: f64 = 10.0;
+/// Index of the decrement button in the spin box
+const DECREMENT_BUTTON: usize = 1;
+/// Index of the increment button in the spin box
+const INCREMENT_BUTTON: usize = 2;
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+struct SpinBoxCallbacks {
+    click: DefaultCallbackId,
+    text_input: DefaultCallbackId,
+    virtual_key_down: DefaultCallbackId,
+    focus_lost: DefaultCallbackId,
+}
+
+#[derive(Debug, Default, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct SpinBox {
+    callbacks: Option<SpinBoxCallbacks>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpinBoxState {
+    /// Last valid value, always between `min` and `max` if set via `set_value`
+    pub value: f64,
+    pub min: f64,
+    pub max: f64,
+    /// How much the buttons and the arrow keys change the value
+    pub step: f64,
+    /// Number of digits after the decimal point
+    pub decimals: usize,
+    /// Disabled spin boxes can't be focused or edited
+    pub disabled: bool,
+    /// Text that is currently edited - it is validated while typing,
+    /// but only parsed into the `value` on Return or when the focus is lost
+    pub input: TextInputState,
+    /// Whether the `input` is a valid number between `min` and `max`
+    is_valid: bool,
+}
+
+impl Default for SpinBoxState {
+    fn default() -> Self {
+        Self::new(0.0, f64::MIN, f64::MAX)
+    }
+}
+
+impl SpinBoxState {
+
+    pub fn new(value: f64, min: f64, max: f64) -> Self {
+        let mut spin_box = SpinBoxState {
+            value: 0.0,
+            min,
+            max,
+            step: 1.0,
+            decimals: 0,
+            disabled: false,
+            input: TextInputState::default(),
+            is_valid: true,
+        };
+        spin_box.set_value(value);
+        spin_box
+    }
+
+    #[inline]
+    pub fn with_step(self, step: f64) -> Self {
+        Self { step, .. self }
+    }
+
+    /// Sets the number of digits after the decimal point (default: 0)
+    #[inline]
+    pub fn with_decimals(self, decimals: usize) -> Self {
+        let mut spin_box = Self { decimals, .. self };
+        let value = spin_box.value;
+        spin_box.set_value(value);
+        spin_box
+    }
+
+    #[inline]
+    pub fn with_disabled(self, disabled: bool) -> Self {
+        Self { disabled, .. self }
+    }
+
+    /// Sets the value (clamped to the range of the spin box) and replaces the edited text
+    pub fn set_value(&mut self, value: f64) {
+        self.value = value.min(self.max).max(self.min);
+        self.input = TextInputState::new(self.format_value());
+        self.is_valid = true;
+    }
+
+    /// Changes the value by a number of steps
+    pub fn step_by(&mut self, steps: f64) {
+        // Start from the edited value, so that typing "5" and pressing Up results in 6
+        let value = self.parse_input().unwrap_or(self.value);
+        self.set_value(value + steps * self.step);
+    }
+
+    /// Returns whether the edited text is a valid number between `min` and `max`
+    pub fn is_valid(&self) -> bool {
+        self.is_valid
+    }
+
+    /// Parses the edited text into the `value` - if the text is not valid,
+    /// the text is reset to the last valid value. Returns whether the text was valid.
+    pub fn commit(&mut self) -> bool {
+        match self.parse_input() {
+            Some(value) => {
+                self.set_value(value);
+                true
+            },
+            None => {
+                let value = self.value;
+                self.set_value(value);
+                false
+            },
+        }
+    }
+
+    /// Discards the edited text and shows the last valid value again
+    pub fn revert(&mut self) {
+        let value = self.value;
+        self.set_value(value);
+    }
+
+    fn format_value(&self) -> String {
+        format!("{:.*}", self.decimals, self.value)
+    }
+
+    /// Returns the edited text as a number, `None` if it isn't a number in range
+    fn parse_input(&self) -> Option<f64> {
+        let value = self.input.text.trim().parse::<f64>().ok()?;
+        if value.is_finite() && value >= self.min && value <= self.max {
+            Some(value)
+        } else {
+            None
+        }
+    }
+}
+
+impl SpinBox {
+
+    pub fn new() -> Self {
+        SpinBox { callbacks: None }
+    }
+
+    pub fn bind<T>(self, window: &mut FakeWindow<T>, field: &SpinBoxState, data: &T) -> Self {
+        let ptr = StackCheckedPointer::new(data, field);
+        let callbacks = ptr.map(|ptr| SpinBoxCallbacks {
+            click: window.add_callback(ptr, DefaultCallback(SpinBoxState::on_click_private)),
+            text_input: window.add_callback(ptr, DefaultCallback(SpinBoxState::on_text_input_private)),
+            virtual_key_down: window.add_callback(ptr, DefaultCallback(SpinBoxState::on_virtual_key_down_private)),
+            focus_lost: window.add_callback(ptr, DefaultCallback(SpinBoxState::on_focus_lost_private)),
+        });
+
+        Self { callbacks }
+    }
+
+    pub fn dom<T>(&self, field: &SpinBoxState) -> Dom<T> {
+
+        let mut parent_div =
+            Dom::div()
+            .with_class("__azul-native-spin-box")
+            .with_disabled(field.disabled);
+
+        if !field.is_valid {
+            parent_div.add_class("__azul-native-spin-box-invalid");
+        }
+
+        if !field.disabled {
+            parent_div.set_tab_index(TabIndex::Auto);
+            if let Some(callbacks) = self.callbacks {
+                parent_div.add_default_callback_id(On::LeftMouseUp, callbacks.click);
+                parent_div.add_default_callback_id(EventFilter::Focus(FocusEventFilter::TextInput), callbacks.text_input);
+                parent_div.add_default_callback_id(EventFilter::Focus(FocusEventFilter::VirtualKeyDown), callbacks.virtual_key_down);
+                parent_div.add_default_callback_id(On::FocusLost, callbacks.focus_lost);
+            }
+        }
+
+        // Order has to match DECREMENT_BUTTON and INCREMENT_BUTTON
+        parent_div
+            .with_child(Dom::label(field.input.text.clone()).with_class("__azul-native-spin-box-text"))
+            .with_child(
+                Dom::div()
+                .with_class("__azul-native-spin-box-button")
+                .with_disabled(field.disabled || field.value <= field.min)
+                .with_child(Dom::label("\u{2212}"))
+            )
+            .with_child(
+                Dom::div()
+                .with_class("__azul-native-spin-box-button")
+                .with_disabled(field.disabled || field.value >= field.max)
+                .with_child(Dom::label("+"))
+            )
+    }
+}
+
+impl SpinBoxState {
+
+    fn on_click_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_click, app_state_no_data, window_event) }
+    }
+
+    fn on_text_input_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_text_input, app_state_no_data, window_event) }
+    }
+
+    fn on_virtual_key_down_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_virtual_key_down, app_state_no_data, window_event) }
+    }
+
+    fn on_focus_lost_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_focus_lost, app_state_no_data, window_event) }
+    }
+
+    /// Handles clicks on the increment / decrement buttons
+    pub fn on_click<T>(&mut self, _app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        if self.disabled {
+            return DontRedraw;
+        }
+        match event.get_target_child_index() {
+            Some(DECREMENT_BUTTON) => self.step_by(-1.0),
+            Some(INCREMENT_BUTTON) => self.step_by(1.0),
+            _ => return DontRedraw,
+        }
+        Redraw
+    }
+
+    /// Edits the text, ignoring characters that can't be part of a number
+    pub fn on_text_input<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        let current_char = app_state_no_data.windows[event.window_id].get_keyboard_state().current_char;
+        match current_char {
+            Some(c) if !self.disabled && (c.is_ascii_digit() || ['-', '+', '.', 'e', 'E'].contains(&c)) => { },
+            _ => return DontRedraw,
+        }
+
+        let update = self.input.on_text_input(app_state_no_data, event);
+        self.is_valid = self.parse_input().is_some();
+        update
+    }
+
+    /// Up / Down change the value by one step, PageUp / PageDown by ten steps. Return commits
+    /// the edited text, Escape reverts it - all other keys are handled like in a `TextInput`.
+    pub fn on_virtual_key_down<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        if self.disabled {
+            return DontRedraw;
+        }
+
+        let keyboard_state = app_state_no_data.windows[event.window_id].get_keyboard_state();
+
+        match keyboard_state.latest_virtual_keycode {
+            Some(VirtualKeyCode::Up) => self.step_by(1.0),
+            Some(VirtualKeyCode::Down) => self.step_by(-1.0),
+            Some(VirtualKeyCode::PageUp) => self.step_by(PAGE_STEPS),
+            Some(VirtualKeyCode::PageDown) => self.step_by(-PAGE_STEPS),
+            Some(VirtualKeyCode::Return) => {
+                self.commit();
+                self.input.selection = Some(Selection::All);
+            },
+            Some(VirtualKeyCode::Escape) => self.revert(),
+            _ => {
+                let update = self.input.on_virtual_key_down(app_state_no_data, event);
+                self.is_valid = self.parse_input().is_some();
+                return update;
+            },
+        }
+
+        Redraw
+    }
+
+    /// Commits the edited text when the spin box loses the focus
+    pub fn on_focus_lost<T>(&mut self, _app_state_no_data: &mut AppStateNoData<T>, _event: &mut CallbackInfo<T>) -> UpdateScreen {
+        if self.input.text == self.format_value() && self.is_valid {
+            return DontRedraw;
+        }
+        self.commit();
+        Redraw
+    }
+}
+
+#[test]
+fn test_spin_box_validation() {
+    let mut spin_box = SpinBoxState::new(4.56789, 0.0, 10.0).with_decimals(2);
+    assert_eq!(spin_box.input.text, "4.57");
+
+    spin_box.input = TextInputState::new("12");
+    assert!(spin_box.parse_input().is_none());
+    assert!(!spin_box.commit());
+    assert_eq!(spin_box.input.text, "4.57");
+
+    spin_box.input = TextInputState::new("5");
+    spin_box.step_by(1.0);
+    assert_eq!(spin_box.value, 6.0);
+    assert_eq!(spin_box.input.text, "6.00");
+
+    spin_box.step_by(PAGE_STEPS);
+    assert_eq!(spin_box.value, 10.0);
+}
@@ -0,0 +1,308 @@
+//! Two panes with a draggable handle between them (two-way data binding)
+//!
+//! The size of the first pane is stored in the `SplitterState` and applied via a CSS override,
+//! the second pane takes the remaining space. Double-clicking the handle (or pressing Return
+//! while the handle is focused) collapses the first pane.
+
+use std::{f32, time::Instant};
+use azul_css::{CssProperty, LayoutWidth, LayoutHeight};
+use {
+    callbacks::{UpdateScreen, Redraw, DontRedraw},
+    dom::{Dom, On, EventFilter, FocusEventFilter, WindowEventFilter, TabIndex},
+    window::FakeWindow,
+    prelude::VirtualKeyCode,
+    callbacks::{CallbackInfo, StackCheckedPointer, DefaultCallback, DefaultCallbackId},
+    app::AppStateNoData,
+    widgets::{DoubleClick, slider::Orientation},
+    id_tree::NodeId,
+};
+
+/// How many pixels the arrow keys move the handle
+const KEYBOARD_STEP: f32 = 10.0;
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+struct SplitterCallbacks {
+    mouse_down: DefaultCallbackId,
+    mouse_over: DefaultCallbackId,
+    mouse_up: DefaultCallbackId,
+    virtual_key_down: DefaultCallbackId,
+}
+
+#[derive(Debug, Default, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct Splitter {
+    callbacks: Option<SplitterCallbacks>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct SplitterDrag {
+    /// Cursor position along the splitter when the handle was pressed
+    start_cursor: f32,
+    start_size: f32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplitterState {
+    /// `Horizontal`: the panes are next to each other, `Vertical`: the panes are stacked
+    pub orientation: Orientation,
+    /// Width (or height, for vertical splitters) of the first pane in pixels
+    pub size: f32,
+    pub min_size: f32,
+    pub max_size: f32,
+    /// Minimum size of the second pane, limits how far the handle can be dragged
+    pub min_remaining_size: f32,
+    /// If set, the first pane is hidden, but `size` is kept for restoring it
+    pub is_collapsed: bool,
+    drag: Option<SplitterDrag>,
+    /// Double-clicking the handle collapses or restores the first pane
+    double_click: DoubleClick<()>,
+}
+
+impl Default for SplitterState {
+    fn default() -> Self {
+        Self::new(Orientation::Horizontal, 200.0)
+    }
+}
+
+impl SplitterState {
+
+    pub fn new(orientation: Orientation, size: f32) -> Self {
+        Self {
+            orientation,
+            size,
+            min_size: 0.0,
+            max_size: f32::MAX,
+            min_remaining_size: 0.0,
+            is_collapsed: false,
+            drag: None,
+            double_click: DoubleClick::default(),
+        }
+    }
+
+    /// Limits the size of the first pane
+    #[inline]
+    pub fn with_min_max(self, min_size: f32, max_size: f32) -> Self {
+        let mut splitter = Self { min_size, max_size, .. self };
+        splitter.set_size(self.size, None);
+        splitter
+    }
+
+    #[inline]
+    pub fn with_min_remaining_size(self, min_remaining_size: f32) -> Self {
+        Self { min_remaining_size, .. self }
+    }
+
+    /// Sets the size of the first pane, clamped to the `min_size` / `max_size`
+    /// and, if the `total_size` of the splitter is known, to the `min_remaining_size`
+    pub fn set_size(&mut self, size: f32, total_size: Option<f32>) {
+        let max_size = match total_size {
+            Some(total_size) => self.max_size.min(total_size - self.min_remaining_size),
+            None => self.max_size,
+        };
+        self.size = size.min(max_size).max(self.min_size);
+        self.is_collapsed = false;
+    }
+
+    /// Returns the size that the first pane is currently rendered with
+    pub fn get_rendered_size(&self) -> f32 {
+        if self.is_collapsed { 0.0 } else { self.size }
+    }
+
+    pub fn toggle_collapsed(&mut self) {
+        self.is_collapsed = !self.is_collapsed;
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+}
+
+impl Splitter {
+
+    pub fn new() -> Self {
+        Splitter { callbacks: None }
+    }
+
+    pub fn bind<T>(self, window: &mut FakeWindow<T>, field: &SplitterState, data: &T) -> Self {
+        let ptr = StackCheckedPointer::new(data, field);
+        let callbacks = ptr.map(|ptr| SplitterCallbacks {
+            mouse_down: window.add_callback(ptr, DefaultCallback(SplitterState::on_mouse_down_private)),
+            mouse_over: window.add_callback(ptr, DefaultCallback(SplitterState::on_mouse_over_private)),
+            mouse_up: window.add_callback(ptr, DefaultCallback(SplitterState::on_mouse_up_private)),
+            virtual_key_down: window.add_callback(ptr, DefaultCallback(SplitterState::on_virtual_key_down_private)),
+        });
+
+        Self { callbacks }
+    }
+
+    pub fn dom<T>(&self, field: &SplitterState, first: Dom<T>, second: Dom<T>) -> Dom<T> {
+
+        let (orientation_class, first_pane) = match field.orientation {
+            Orientation::Horizontal => (
+                "__azul-native-splitter-horizontal",
+                Dom::div()
+                .with_class("__azul-native-splitter-pane-width")
+                .with_css_override("__azul_splitter_size", CssProperty::Width(LayoutWidth::px(field.get_rendered_size()))),
+            ),
+            Orientation::Vertical => (
+                "__azul-native-splitter-vertical",
+                Dom::div()
+                .with_class("__azul-native-splitter-pane-height")
+                .with_css_override("__azul_splitter_size", CssProperty::Height(LayoutHeight::px(field.get_rendered_size()))),
+            ),
+        };
+
+        let mut parent_div =
+            Dom::div()
+            .with_class("__azul-native-splitter")
+            .with_class(orientation_class);
+
+        if field.is_dragging() {
+            parent_div.add_class("__azul-native-splitter-dragging");
+        }
+
+        let mut handle = Dom::div()
+            .with_class("__azul-native-splitter-handle")
+            .with_tab_index(TabIndex::Auto);
+
+        if let Some(callbacks) = self.callbacks {
+            handle.add_default_callback_id(On::LeftMouseDown, callbacks.mouse_down);
+            handle.add_default_callback_id(EventFilter::Focus(FocusEventFilter::VirtualKeyDown), callbacks.virtual_key_down);
+            // Dragging has to continue when the mouse leaves the handle
+            parent_div.add_default_callback_id(EventFilter::Window(WindowEventFilter::MouseOver), callbacks.mouse_over);
+            parent_div.add_default_callback_id(EventFilter::Window(WindowEventFilter::MouseUp), callbacks.mouse_up);
+        }
+
+        let mut first_pane = first_pane.with_class("__azul-native-splitter-pane").with_child(first);
+        if field.is_collapsed {
+            first_pane.add_class("__azul-native-splitter-pane-collapsed");
+        }
+
+        parent_div
+            .with_child(first_pane)
+            .with_child(handle)
+            .with_child(Dom::div().with_class("__azul-native-splitter-pane").with_class("__azul-native-splitter-pane-remaining").with_child(second))
+    }
+}
+
+impl SplitterState {
+
+    fn on_mouse_down_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_mouse_down, app_state_no_data, window_event) }
+    }
+
+    fn on_mouse_over_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_mouse_over, app_state_no_data, window_event) }
+    }
+
+    fn on_mouse_up_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_mouse_up, app_state_no_data, window_event) }
+    }
+
+    fn on_virtual_key_down_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_virtual_key_down, app_state_no_data, window_event) }
+    }
+
+    /// Starts dragging the handle, a double click collapses or restores the first pane
+    pub fn on_mouse_down<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        if self.double_click.click((), Instant::now()) {
+            self.drag = None;
+            self.toggle_collapsed();
+            return Redraw;
+        }
+
+        let start_cursor = self.get_cursor_position(app_state_no_data, event)?;
+        self.drag = Some(SplitterDrag { start_cursor, start_size: self.get_rendered_size() });
+        Redraw
+    }
+
+    /// Resizes the first pane while the handle is dragged
+    pub fn on_mouse_over<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        let drag = self.drag?;
+
+        // The mouse button was released outside of the window
+        if !app_state_no_data.windows[event.window_id].get_mouse_state().left_down {
+            self.drag = None;
+            return Redraw;
+        }
+
+        let cursor = self.get_cursor_position(app_state_no_data, event)?;
+        let old_size = self.get_rendered_size();
+        let total_size = self.get_total_size(event);
+        self.set_size(drag.start_size + cursor - drag.start_cursor, total_size);
+        if self.get_rendered_size() != old_size { Redraw } else { DontRedraw }
+    }
+
+    pub fn on_mouse_up<T>(&mut self, _app_state_no_data: &mut AppStateNoData<T>, _event: &mut CallbackInfo<T>) -> UpdateScreen {
+        self.drag.take()?;
+        Redraw
+    }
+
+    /// The arrow keys move the handle, Return collapses or restores the first pane
+    pub fn on_virtual_key_down<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        let keyboard_state = app_state_no_data.windows[event.window_id].get_keyboard_state();
+
+        let offset = match (keyboard_state.latest_virtual_keycode, self.orientation) {
+            (Some(VirtualKeyCode::Return), _) => {
+                self.toggle_collapsed();
+                return Redraw;
+            },
+            (Some(VirtualKeyCode::Left), Orientation::Horizontal) | (Some(VirtualKeyCode::Up), Orientation::Vertical) => -KEYBOARD_STEP,
+            (Some(VirtualKeyCode::Right), Orientation::Horizontal) | (Some(VirtualKeyCode::Down), Orientation::Vertical) => KEYBOARD_STEP,
+            _ => return DontRedraw,
+        };
+
+        // The handle is the child of the splitter node
+        let total_size = event.parent(event.hit_dom_node).and_then(|splitter| self.get_node_size(event, splitter));
+        let size = self.get_rendered_size() + offset;
+        self.set_size(size, total_size);
+        Redraw
+    }
+
+    /// Returns the cursor position along the splitter, relative to the window
+    fn get_cursor_position<T>(&self, app_state_no_data: &AppStateNoData<T>, event: &CallbackInfo<T>) -> Option<f32> {
+        let (x, y) = event.get_cursor_in_viewport(app_state_no_data.windows[event.window_id].get_mouse_state())?;
+        Some(match self.orientation {
+            Orientation::Horizontal => x,
+            Orientation::Vertical => y,
+        })
+    }
+
+    /// Returns the size of the whole splitter (minus the handle), if the callback is invoked on the splitter
+    fn get_total_size<T>(&self, event: &CallbackInfo<T>) -> Option<f32> {
+        let handle = event.get_node(event.hit_dom_node)?.first_child.and_then(|first| event.get_node(first)?.next_sibling)?;
+        let handle_size = self.get_node_size(event, handle)?;
+        Some(self.get_node_size(event, event.hit_dom_node)? - handle_size)
+    }
+
+    fn get_node_size<T>(&self, event: &CallbackInfo<T>, node_id: NodeId) -> Option<f32> {
+        let rect = event.get_node_rect(node_id)?;
+        Some(match self.orientation {
+            Orientation::Horizontal => rect.size.width,
+            Orientation::Vertical => rect.size.height,
+        })
+    }
+}
+
+#[test]
+fn test_splitter_size_limits() {
+    let mut splitter = SplitterState::new(Orientation::Horizontal, 500.0).with_min_max(100.0, 400.0);
+    assert_eq!(splitter.size, 400.0);
+
+    splitter.set_size(50.0, None);
+    assert_eq!(splitter.size, 100.0);
+
+    // The second pane needs at least 300px of the 600px
+    splitter = splitter.with_min_remaining_size(300.0);
+    splitter.set_size(350.0, Some(600.0));
+    assert_eq!(splitter.size, 300.0);
+
+    // Collapsing keeps the size, resizing restores the pane
+    splitter.toggle_collapsed();
+    assert_eq!(splitter.get_rendered_size(), 0.0);
+    assert_eq!(splitter.size, 300.0);
+    splitter.set_size(200.0, None);
+    assert!(!splitter.is_collapsed);
+}
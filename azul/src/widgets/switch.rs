@@ -0,0 +1,132 @@
+//! Toggle switch, an on / off control for settings that take effect immediately (two-way data binding)
+
+use {
+    callbacks::{UpdateScreen, Redraw, DontRedraw},
+    dom::{Dom, DomString, On, EventFilter, FocusEventFilter, TabIndex},
+    window::FakeWindow,
+    prelude::VirtualKeyCode,
+    callbacks::{CallbackInfo, StackCheckedPointer, DefaultCallback, DefaultCallbackId},
+    app::AppStateNoData,
+};
+
+#[derive(Debug, Default, Clone, Hash, PartialEq, Eq)]
+pub struct Switch {
+    label: Option<DomString>,
+    on_toggle_callback: Option<(DefaultCallbackId, DefaultCallbackId)>,
+}
+
+#[derive(Debug, Default, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct SwitchState {
+    pub is_on: bool,
+    /// Disabled switches can't be focused or toggled
+    pub disabled: bool,
+}
+
+impl SwitchState {
+
+    pub fn new(is_on: bool) -> Self {
+        Self { is_on, disabled: false }
+    }
+
+    #[inline]
+    pub fn with_disabled(self, disabled: bool) -> Self {
+        Self { disabled, .. self }
+    }
+}
+
+impl Switch {
+
+    pub fn new() -> Self {
+        Switch { label: None, on_toggle_callback: None }
+    }
+
+    pub fn with_label<S: Into<DomString>>(label: S) -> Self {
+        Switch { label: Some(label.into()), on_toggle_callback: None }
+    }
+
+    pub fn bind<T>(self, window: &mut FakeWindow<T>, field: &SwitchState, data: &T) -> Self {
+        let ptr = StackCheckedPointer::new(data, field);
+        let on_toggle_callback = ptr.map(|ptr|{(
+            window.add_callback(ptr, DefaultCallback(SwitchState::on_click_private)),
+            window.add_callback(ptr, DefaultCallback(SwitchState::on_virtual_key_down_private))
+        )});
+
+        Self {
+            on_toggle_callback,
+            .. self
+        }
+    }
+
+    pub fn dom<T>(&self, field: &SwitchState) -> Dom<T> {
+
+        let mut parent_div =
+            Dom::div()
+            .with_class("__azul-native-switch")
+            .with_checked(field.is_on)
+            .with_disabled(field.disabled);
+
+        if !field.disabled {
+            parent_div.set_tab_index(TabIndex::Auto);
+            if let Some((click_callback, vk_callback)) = self.on_toggle_callback {
+                parent_div.add_default_callback_id(On::LeftMouseUp, click_callback);
+                parent_div.add_default_callback_id(EventFilter::Focus(FocusEventFilter::VirtualKeyDown), vk_callback);
+            }
+        }
+
+        // The thumb is moved to the right end of the track via `:checked` in the CSS
+        parent_div.add_child(
+            Dom::div()
+            .with_class("__azul-native-switch-track")
+            .with_child(Dom::div().with_class("__azul-native-switch-thumb"))
+        );
+
+        if let Some(label) = &self.label {
+            parent_div.add_child(Dom::label(label.clone()).with_class("__azul-native-switch-label"));
+        }
+
+        parent_div
+    }
+}
+
+impl SwitchState {
+
+    fn on_click_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_click, app_state_no_data, window_event) }
+    }
+
+    fn on_virtual_key_down_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_virtual_key_down, app_state_no_data, window_event) }
+    }
+
+    pub fn on_click<T>(&mut self, _app_state_no_data: &mut AppStateNoData<T>, _event: &mut CallbackInfo<T>) -> UpdateScreen {
+        if self.disabled {
+            return DontRedraw;
+        }
+        self.is_on = !self.is_on;
+        Redraw
+    }
+
+    /// Space toggles the switch, Left / Right turn it off / on
+    pub fn on_virtual_key_down<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        if self.disabled {
+            return DontRedraw;
+        }
+
+        let keyboard_state = app_state_no_data.windows[event.window_id].get_keyboard_state();
+
+        let is_on = match keyboard_state.latest_virtual_keycode {
+            Some(VirtualKeyCode::Space) => !self.is_on,
+            Some(VirtualKeyCode::Left) => false,
+            Some(VirtualKeyCode::Right) => true,
+            _ => return DontRedraw,
+        };
+
+        if is_on == self.is_on {
+            return DontRedraw;
+        }
+
+        self.is_on = is_on;
+        Redraw
+    }
+}
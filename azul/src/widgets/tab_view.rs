@@ -0,0 +1,376 @@
+//! Tab bar with closable and reorderable tabs above the content of the active tab (two-way data binding)
+//!
+//! Only the content of the active tab is created, so inactive tabs don't cost anything.
+//! Tabs are identified by a `TabId`, which stays the same when the tabs are reordered.
+
+use {
+    callbacks::{UpdateScreen, Redraw, DontRedraw},
+    dom::{Dom, DomString, On, EventFilter, FocusEventFilter, WindowEventFilter, TabIndex},
+    window::FakeWindow,
+    prelude::VirtualKeyCode,
+    callbacks::{CallbackInfo, StackCheckedPointer, DefaultCallback, DefaultCallbackId},
+    app::AppStateNoData,
+};
+
+/// How far (in pixels) a tab has to be dragged before it is moved instead of clicked
+const DRAG_THRESHOLD: f32 = 4.0;
+/// Index of the close button in a tab
+const CLOSE_BUTTON_INDEX: usize = 1;
+
+/// Identifies a tab independent of its position in the tab bar
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TabId(pub usize);
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct Tab {
+    pub id: TabId,
+    pub title: DomString,
+    /// Whether the tab has a close button (and can be closed with a middle click or Delete)
+    pub closable: bool,
+}
+
+impl Tab {
+
+    pub fn new<S: Into<DomString>>(id: TabId, title: S) -> Self {
+        Self { id, title: title.into(), closable: false }
+    }
+
+    #[inline]
+    pub fn with_closable(self, closable: bool) -> Self {
+        Self { closable, .. self }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct TabDrag {
+    /// Current index of the dragged tab
+    tab: usize,
+    start_x: f32,
+    is_moved: bool,
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+struct TabViewCallbacks {
+    mouse_down: DefaultCallbackId,
+    middle_mouse_down: DefaultCallbackId,
+    mouse_over: DefaultCallbackId,
+    mouse_up: DefaultCallbackId,
+    virtual_key_down: DefaultCallbackId,
+}
+
+#[derive(Debug, Default, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct TabView {
+    callbacks: Option<TabViewCallbacks>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TabViewState {
+    pub tabs: Vec<Tab>,
+    /// Index of the active tab in `tabs`
+    pub active: usize,
+    /// Whether the tabs can be reordered by dragging them (or with Ctrl + Left / Right)
+    pub reorderable: bool,
+    drag: Option<TabDrag>,
+    /// Tabs that were closed by the user, but not yet returned by `take_closed_tabs`
+    closed: Vec<TabId>,
+}
+
+impl TabViewState {
+
+    pub fn new(tabs: Vec<Tab>) -> Self {
+        Self { tabs, .. Default::default() }
+    }
+
+    #[inline]
+    pub fn with_active(self, active: usize) -> Self {
+        Self { active, .. self }
+    }
+
+    #[inline]
+    pub fn with_reorderable(self, reorderable: bool) -> Self {
+        Self { reorderable, .. self }
+    }
+
+    /// Returns the ID of the active tab, `None` if there are no tabs
+    pub fn get_active_tab(&self) -> Option<TabId> {
+        self.tabs.get(self.active).map(|tab| tab.id)
+    }
+
+    /// Appends a tab and activates it
+    pub fn add_tab(&mut self, tab: Tab) {
+        self.tabs.push(tab);
+        self.active = self.tabs.len() - 1;
+    }
+
+    /// Activates the tab at the `index`, ignored if the `index` is out of range
+    pub fn select(&mut self, index: usize) {
+        if index < self.tabs.len() {
+            self.active = index;
+        }
+    }
+
+    /// Removes the tab at the `index` if it is closable - the tab next to it becomes active
+    /// if the active tab is closed. Returns whether the tab was closed.
+    pub fn close(&mut self, index: usize) -> bool {
+        if !self.tabs.get(index).map(|tab| tab.closable).unwrap_or(false) {
+            return false;
+        }
+        let tab = self.tabs.remove(index);
+        self.closed.push(tab.id);
+        if index < self.active || self.active >= self.tabs.len() {
+            self.active = self.active.saturating_sub(1);
+        }
+        true
+    }
+
+    /// Returns the tabs that were closed since the last call, so that the application
+    /// can drop the data of the closed tabs
+    pub fn take_closed_tabs(&mut self) -> Vec<TabId> {
+        ::std::mem::replace(&mut self.closed, Vec::new())
+    }
+
+    /// Moves the tab at `from` to the index `to`, the active tab stays active
+    pub fn move_tab(&mut self, from: usize, to: usize) {
+        if from >= self.tabs.len() || to >= self.tabs.len() || from == to {
+            return;
+        }
+        let active = self.get_active_tab();
+        let tab = self.tabs.remove(from);
+        self.tabs.insert(to, tab);
+        if let Some(index) = self.tabs.iter().position(|tab| Some(tab.id) == active) {
+            self.active = index;
+        }
+    }
+
+    /// Returns whether a tab is currently dragged to a new position
+    pub fn is_dragging(&self) -> bool {
+        self.drag.map(|drag| drag.is_moved) == Some(true)
+    }
+}
+
+impl TabView {
+
+    pub fn new() -> Self {
+        TabView { callbacks: None }
+    }
+
+    pub fn bind<T>(self, window: &mut FakeWindow<T>, field: &TabViewState, data: &T) -> Self {
+        let ptr = StackCheckedPointer::new(data, field);
+        let callbacks = ptr.map(|ptr| TabViewCallbacks {
+            mouse_down: window.add_callback(ptr, DefaultCallback(TabViewState::on_mouse_down_private)),
+            middle_mouse_down: window.add_callback(ptr, DefaultCallback(TabViewState::on_middle_mouse_down_private)),
+            mouse_over: window.add_callback(ptr, DefaultCallback(TabViewState::on_mouse_over_private)),
+            mouse_up: window.add_callback(ptr, DefaultCallback(TabViewState::on_mouse_up_private)),
+            virtual_key_down: window.add_callback(ptr, DefaultCallback(TabViewState::on_virtual_key_down_private)),
+        });
+
+        Self { callbacks }
+    }
+
+    /// `content` is only invoked for the active tab
+    pub fn dom<T, F: FnOnce(TabId) -> Dom<T>>(&self, field: &TabViewState, content: F) -> Dom<T> {
+
+        let mut tab_bar = Dom::div()
+            .with_class("__azul-native-tab-bar")
+            .with_tab_index(TabIndex::Auto);
+
+        if let Some(callbacks) = self.callbacks {
+            tab_bar.add_default_callback_id(On::LeftMouseDown, callbacks.mouse_down);
+            tab_bar.add_default_callback_id(On::MiddleMouseDown, callbacks.middle_mouse_down);
+            // Dragging has to continue when the mouse leaves the tab bar
+            tab_bar.add_default_callback_id(EventFilter::Window(WindowEventFilter::MouseOver), callbacks.mouse_over);
+            tab_bar.add_default_callback_id(EventFilter::Window(WindowEventFilter::MouseUp), callbacks.mouse_up);
+            tab_bar.add_default_callback_id(EventFilter::Focus(FocusEventFilter::VirtualKeyDown), callbacks.virtual_key_down);
+        }
+
+        let dragged_tab = field.drag.filter(|drag| drag.is_moved).map(|drag| drag.tab);
+
+        for (index, tab) in field.tabs.iter().enumerate() {
+
+            // Order has to match CLOSE_BUTTON_INDEX
+            let mut tab_dom = Dom::div()
+                .with_class("__azul-native-tab")
+                .with_checked(index == field.active)
+                .with_child(Dom::label(tab.title.clone()).with_class("__azul-native-tab-title"));
+
+            if tab.closable {
+                tab_dom.add_child(Dom::label("\u{00D7}").with_class("__azul-native-tab-close"));
+            }
+
+            if dragged_tab == Some(index) {
+                tab_dom.add_class("__azul-native-tab-dragging");
+            }
+
+            tab_bar.add_child(tab_dom);
+        }
+
+        let mut tab_view = Dom::div()
+            .with_class("__azul-native-tab-view")
+            .with_child(tab_bar);
+
+        if let Some(active) = field.get_active_tab() {
+            tab_view.add_child(Dom::div().with_class("__azul-native-tab-content").with_child(content(active)));
+        }
+
+        tab_view
+    }
+}
+
+impl TabViewState {
+
+    fn on_mouse_down_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_mouse_down, app_state_no_data, window_event) }
+    }
+
+    fn on_middle_mouse_down_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_middle_mouse_down, app_state_no_data, window_event) }
+    }
+
+    fn on_mouse_over_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_mouse_over, app_state_no_data, window_event) }
+    }
+
+    fn on_mouse_up_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_mouse_up, app_state_no_data, window_event) }
+    }
+
+    fn on_virtual_key_down_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_virtual_key_down, app_state_no_data, window_event) }
+    }
+
+    /// Activates the clicked tab and starts dragging it, or closes the tab if the close button was clicked
+    pub fn on_mouse_down<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        let index = event.get_target_child_index()?;
+
+        // target = close button -> tab -> tab bar
+        let is_close_button = event.get_index_in_parent(event.get_target())
+            .map(|(child_index, tab)| child_index == CLOSE_BUTTON_INDEX && event.parent(tab) == Some(event.hit_dom_node))
+            .unwrap_or(false);
+
+        if is_close_button {
+            return if self.close(index) { Redraw } else { DontRedraw };
+        }
+
+        self.select(index);
+
+        if self.reorderable {
+            if let Some((x, _)) = event.get_cursor_in_viewport(app_state_no_data.windows[event.window_id].get_mouse_state()) {
+                self.drag = Some(TabDrag { tab: index, start_x: x, is_moved: false });
+            }
+        }
+
+        Redraw
+    }
+
+    /// Closes the tab under the cursor
+    pub fn on_middle_mouse_down<T>(&mut self, _app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        let index = event.get_target_child_index()?;
+        if self.close(index) { Redraw } else { DontRedraw }
+    }
+
+    /// Moves the dragged tab to the position of the tab under the cursor
+    pub fn on_mouse_over<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        let drag = self.drag?;
+
+        // The mouse button was released outside of the window
+        if !app_state_no_data.windows[event.window_id].get_mouse_state().left_down {
+            self.drag = None;
+            return if drag.is_moved { Redraw } else { DontRedraw };
+        }
+
+        let (x, _) = event.get_cursor_in_viewport(app_state_no_data.windows[event.window_id].get_mouse_state())?;
+
+        if !drag.is_moved {
+            if (x - drag.start_x).abs() <= DRAG_THRESHOLD {
+                return DontRedraw;
+            }
+            self.drag = Some(TabDrag { is_moved: true, .. drag });
+        }
+
+        // The callback is invoked on the tab bar, its children are the tabs
+        let tab_bar = event.hit_dom_node;
+        let tab_rects = tab_bar.children(event.get_node_hierarchy())
+            .filter_map(|tab| event.get_node_rect(tab))
+            .collect::<Vec<_>>();
+
+        let target = tab_rects.iter()
+            .position(|rect| x < rect.origin.x + rect.size.width)
+            .unwrap_or_else(|| tab_rects.len().saturating_sub(1));
+
+        if target != drag.tab {
+            self.move_tab(drag.tab, target);
+            self.drag = Some(TabDrag { tab: target, is_moved: true, .. drag });
+        }
+
+        Redraw
+    }
+
+    pub fn on_mouse_up<T>(&mut self, _app_state_no_data: &mut AppStateNoData<T>, _event: &mut CallbackInfo<T>) -> UpdateScreen {
+        let drag = self.drag.take()?;
+        if drag.is_moved { Redraw } else { DontRedraw }
+    }
+
+    /// Left / Right (and Home / End) activate the previous / next tab, Ctrl + Left / Right move
+    /// the active tab if the tabs are reorderable, Delete closes the active tab
+    pub fn on_virtual_key_down<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        if self.tabs.is_empty() {
+            return DontRedraw;
+        }
+
+        let keyboard_state = app_state_no_data.windows[event.window_id].get_keyboard_state();
+        let last = self.tabs.len() - 1;
+        let active = self.active;
+
+        match keyboard_state.latest_virtual_keycode {
+            Some(VirtualKeyCode::Left) if keyboard_state.ctrl_down && self.reorderable => self.move_tab(active, active.saturating_sub(1)),
+            Some(VirtualKeyCode::Right) if keyboard_state.ctrl_down && self.reorderable => self.move_tab(active, (active + 1).min(last)),
+            Some(VirtualKeyCode::Left) => self.active = if active == 0 { last } else { active - 1 },
+            Some(VirtualKeyCode::Right) => self.active = if active >= last { 0 } else { active + 1 },
+            Some(VirtualKeyCode::Home) => self.active = 0,
+            Some(VirtualKeyCode::End) => self.active = last,
+            Some(VirtualKeyCode::Delete) => return if self.close(active) { Redraw } else { DontRedraw },
+            _ => return DontRedraw,
+        }
+
+        Redraw
+    }
+}
+
+#[cfg(test)]
+fn get_test_tabs() -> TabViewState {
+    TabViewState::new((0..4).map(|id| Tab::new(TabId(id), format!("Tab {}", id)).with_closable(id != 3)).collect())
+}
+
+#[test]
+fn test_tab_view_close() {
+    let mut tabs = get_test_tabs().with_active(2);
+
+    // Closing a tab before the active tab keeps the same tab active
+    assert!(tabs.close(0));
+    assert_eq!(tabs.get_active_tab(), Some(TabId(2)));
+
+    // Closing the active tab activates the tab that takes its place
+    assert!(tabs.close(1));
+    assert_eq!(tabs.get_active_tab(), Some(TabId(3)));
+
+    // Tab 3 isn't closable
+    assert!(!tabs.close(1));
+    assert_eq!(tabs.take_closed_tabs(), vec![TabId(0), TabId(2)]);
+    assert!(tabs.take_closed_tabs().is_empty());
+
+    assert!(tabs.close(0));
+    assert_eq!(tabs.get_active_tab(), Some(TabId(3)));
+}
+
+#[test]
+fn test_tab_view_move() {
+    let mut tabs = get_test_tabs().with_active(1);
+    tabs.move_tab(0, 3);
+    let ids = tabs.tabs.iter().map(|tab| tab.id.0).collect::<Vec<_>>();
+    assert_eq!(ids, vec![1, 2, 3, 0]);
+    assert_eq!(tabs.active, 0);
+    assert_eq!(tabs.get_active_tab(), Some(TabId(1)));
+}
@@ -1,26 +1,179 @@
-//! Table view
+//! Table view, a spreadsheet-like grid over a sparse `Worksheet` of unbounded size
+//!
+//! The cells are drawn in an IFrame, so only the visible cells are part of the DOM.
+//! Since the cells are part of the IFrame, callbacks on the cells are not invoked - clicks,
+//! drags and key presses are handled by the table, which updates the `TableViewState`:
+//!
+//! - Click a cell to select it, shift-click or drag to select a range of cells
+//! - Type to replace the content of the selected cell, press Enter or F2 (or click the selected
+//!   cell again) to edit it - Enter / Tab commit the edit, Escape cancels it
+//! - Drag the right border of a column header to resize the column, drag the header to move
+//!   the column, click the header to sort the rows by the column
+//! - Ctrl + C / Ctrl + X / Ctrl + V copy, cut and paste the selected cells as tab-separated values
 
-use std::collections::BTreeMap;
+use std::{
+    fmt,
+    cmp::Ordering,
+    ops::Range,
+    collections::{BTreeMap, BTreeSet},
+};
+use azul_css::{CssProperty, LayoutWidth, LayoutHeight, LayoutMargin, PixelValue};
 use {
     app::AppStateNoData,
-    callbacks::{IFrameCallback, HidpiAdjustedBounds, UpdateScreen, DontRedraw},
-    dom::{Dom, On, NodeData, DomString, NodeType},
+    callbacks::{IFrameCallback, HidpiAdjustedBounds, UpdateScreen, Redraw, DontRedraw},
+    dom::{Dom, On, EventFilter, FocusEventFilter, WindowEventFilter, TabIndex},
     callbacks::{LayoutInfo, CallbackInfo},
     callbacks::{StackCheckedPointer, DefaultCallback},
     window::FakeWindow,
+    touch::Gesture,
+    prelude::VirtualKeyCode,
+    widgets::text_input::{TextInput, TextInputState, Selection},
 };
 
+/// Width of the column with the row numbers on the left of the table
+pub const ROW_NUMBER_WIDTH: f32 = 40.0;
+/// Columns can't be resized to be smaller than this
+pub const MIN_COLUMN_WIDTH: f32 = 20.0;
+/// How close (in pixels) the cursor has to be to the border of a column header to resize the column
+const RESIZE_HANDLE_WIDTH: f32 = 4.0;
+/// How far (in pixels) a column header has to be dragged before it is moved instead of clicked
+const DRAG_THRESHOLD: f32 = 4.0;
+
 #[derive(Debug, Default, Copy, Clone)]
 pub struct TableView {
 
 }
 
+/// Sorts the rows of the `Worksheet` when a column header is clicked
+pub struct SortCallback(pub fn(&mut Worksheet, &ColumnSort));
+
+impl Clone for SortCallback {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl Copy for SortCallback { }
+
+impl fmt::Debug for SortCallback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SortCallback @ 0x{:x}", self.0 as usize)
+    }
+}
+
+impl Default for SortCallback {
+    /// Sorts the rows with `Worksheet::sort_rows`
+    fn default() -> Self {
+        SortCallback(sort_rows_callback)
+    }
+}
+
+fn sort_rows_callback(work_sheet: &mut Worksheet, sort: &ColumnSort) {
+    work_sheet.sort_rows(sort.column, sort.order, sort.first_row);
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+/// Which column the rows of a table are sorted by
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ColumnSort {
+    /// Column in the `Worksheet` (not the position of the column on the screen)
+    pub column: usize,
+    pub order: SortOrder,
+    /// Rows above this row (the frozen header rows) are not sorted
+    pub first_row: usize,
+}
+
+/// Rectangular range of cells, as columns and rows on the screen
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CellRange {
+    pub columns: Range<usize>,
+    pub rows: Range<usize>,
+}
+
+impl CellRange {
+    pub fn contains(&self, column: usize, row: usize) -> bool {
+        self.columns.start <= column && column < self.columns.end &&
+        self.rows.start <= row && row < self.rows.end
+    }
+
+    pub fn is_single_cell(&self) -> bool {
+        self.columns.end - self.columns.start == 1 && self.rows.end - self.rows.start == 1
+    }
+}
+
+/// Cell that is currently edited, the edit is written to the `Worksheet` when it is committed
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CellEditor {
+    /// Column in the `Worksheet` (not the position of the column on the screen)
+    pub column: usize,
+    pub row: usize,
+    pub input: TextInputState,
+}
+
+/// What the user is currently doing with the left mouse button held down
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum TableDrag {
+    /// Selecting a range of cells
+    Select,
+    /// Resizing the `column` (in the `Worksheet`) by dragging the right border of its header
+    ResizeColumn { column: usize, start_x: f32, start_width: f32 },
+    /// Moving the `column` (on the screen) by dragging its header, a header
+    /// that is released without moving it sorts the rows by the column
+    MoveColumn { column: usize, start_x: f32, is_moved: bool },
+}
+
+/// Column that is visible on the screen, `left` is relative to the left edge of the table
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct VisibleColumn {
+    column: usize,
+    left: f32,
+    width: f32,
+}
+
+/// Part of the table that is under the mouse cursor
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum TableHit {
+    TopLeftRect,
+    ColumnName { column: usize, is_border: bool },
+    RowNumber(usize),
+    Cell(usize, usize),
+}
+
 #[derive(Debug, Clone)]
 pub struct TableViewState {
     pub work_sheet: Worksheet,
+    /// Width of the columns that were not resized
     pub column_width: f32,
     pub row_height: f32,
+    /// Active cell as (column, row) on the screen, moved by the arrow keys
     pub selected_cell: Option<(usize, usize)>,
+    /// Number of rows at the top of the table that don't scroll, i.e. header rows
+    pub frozen_rows: usize,
+    /// Number of columns on the left of the table that don't scroll
+    pub frozen_columns: usize,
+    /// How many (columns, rows) right of / below the frozen columns and rows are scrolled out
+    pub scroll_position: (usize, usize),
+    /// Column that the rows were last sorted by
+    pub sort: Option<ColumnSort>,
+    sort_callback: SortCallback,
+    /// Cell that range selections start from
+    anchor: Option<(usize, usize)>,
+    /// Width of the resized columns, by column in the `Worksheet`
+    column_widths: BTreeMap<usize, f32>,
+    /// Column in the `Worksheet` for each column on the screen,
+    /// columns after the end of the `Vec` are not moved
+    column_order: Vec<usize>,
+    editor: Option<CellEditor>,
+    drag: Option<TableDrag>,
+    /// Scroll distance (in pixels) that is not large enough to scroll by a whole column / row yet
+    scroll_remainder: (f32, f32),
+    /// Size of the table in the last frame, updated whenever the table is rendered or receives an event
+    viewport_size: (f32, f32),
 }
 
 impl Default for TableViewState {
@@ -30,6 +183,18 @@ impl Default for TableViewState {
             column_width: 100.0,
             row_height: 20.0,
             selected_cell: None,
+            frozen_rows: 0,
+            frozen_columns: 0,
+            scroll_position: (0, 0),
+            sort: None,
+            sort_callback: SortCallback::default(),
+            anchor: None,
+            column_widths: BTreeMap::new(),
+            column_order: Vec::new(),
+            editor: None,
+            drag: None,
+            scroll_remainder: (0.0, 0.0),
+            viewport_size: (0.0, 0.0),
         }
     }
 }
@@ -46,6 +211,68 @@ impl Worksheet {
             .or_insert_with(|| BTreeMap::new())
             .insert(y, value.into());
     }
+
+    pub fn get_cell(&self, x: usize, y: usize) -> Option<&String> {
+        self.data.get(&x).and_then(|column| column.get(&y))
+    }
+
+    pub fn clear_cell(&mut self, x: usize, y: usize) {
+        let is_column_empty = match self.data.get_mut(&x) {
+            Some(column) => { column.remove(&y); column.is_empty() },
+            None => return,
+        };
+        if is_column_empty {
+            self.data.remove(&x);
+        }
+    }
+
+    /// Clears the `rows` of the `columns`, only visits the cells that have content
+    pub fn clear_range(&mut self, columns: &[usize], rows: Range<usize>) {
+        for x in columns {
+            let filled_rows = match self.data.get(x) {
+                Some(column) => column.range(rows.clone()).map(|(y, _)| *y).collect::<Vec<_>>(),
+                None => continue,
+            };
+            for y in filled_rows {
+                self.clear_cell(*x, y);
+            }
+        }
+    }
+
+    /// Returns the `rows` of the `columns` (in the given order) as tab-separated values,
+    /// as spreadsheet applications put them into the clipboard
+    pub fn get_tsv(&self, columns: &[usize], rows: Range<usize>) -> String {
+        rows.map(|y| {
+            columns.iter()
+            .map(|x| escape_tsv_field(self.get_cell(*x, y).map(|s| s.as_str()).unwrap_or("")))
+            .collect::<Vec<_>>()
+            .join("\t")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+    }
+
+    /// Sorts all rows from the `first_row` downwards by the content of the `column`. Numbers
+    /// are compared by their value and sorted before text, empty cells are always sorted last.
+    /// Rows that are completely empty are removed, so the sorted rows start at the `first_row`.
+    pub fn sort_rows(&mut self, column: usize, order: SortOrder, first_row: usize) {
+
+        let filled_rows = self.data.values()
+            .flat_map(|cells| cells.range(first_row..).map(|(y, _)| *y))
+            .collect::<BTreeSet<usize>>();
+
+        let mut sorted_rows = filled_rows.into_iter().collect::<Vec<_>>();
+        sorted_rows.sort_by(|a, b| compare_cells(self.get_cell(column, *a), self.get_cell(column, *b), order));
+
+        let new_rows = sorted_rows.iter().enumerate()
+            .map(|(index, y)| (*y, first_row + index))
+            .collect::<BTreeMap<usize, usize>>();
+
+        for cells in self.data.values_mut() {
+            let unsorted = cells.split_off(&first_row);
+            cells.extend(unsorted.into_iter().map(|(y, value)| (new_rows[&y], value)));
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -60,11 +287,29 @@ impl TableView {
         }
     }
 
+    /// The `data` has to be stored inside of the application data `t`, so that the table
+    /// can update the selection, the scroll position and the edited cells.
     pub fn dom<T>(&self, data: &TableViewState, t: &T, window: &mut FakeWindow<T>) -> Dom<T> {
         if let Some(ptr) =  StackCheckedPointer::new(t, data) {
-            let mut dom = Dom::iframe(IFrameCallback(render_table_callback), ptr);
-            let callback_id = window.add_callback(ptr, DefaultCallback(Self::table_view_on_click));
-            dom.add_default_callback_id(On::MouseUp, callback_id);
+            let mut dom = Dom::iframe(IFrameCallback(render_table_callback), ptr)
+                .with_tab_index(TabIndex::Auto);
+
+            let on_mouse_down = window.add_callback(ptr, DefaultCallback(Self::table_view_on_mouse_down));
+            let on_mouse_move = window.add_callback(ptr, DefaultCallback(Self::table_view_on_mouse_move));
+            let on_mouse_up = window.add_callback(ptr, DefaultCallback(Self::table_view_on_mouse_up));
+            let on_scroll = window.add_callback(ptr, DefaultCallback(Self::table_view_on_scroll));
+            let on_pan = window.add_callback(ptr, DefaultCallback(Self::table_view_on_pan));
+            let on_virtual_key_down = window.add_callback(ptr, DefaultCallback(Self::table_view_on_virtual_key_down));
+            let on_text_input = window.add_callback(ptr, DefaultCallback(Self::table_view_on_text_input));
+
+            dom.add_default_callback_id(On::MouseDown, on_mouse_down);
+            // Dragging has to continue when the mouse leaves the table
+            dom.add_default_callback_id(EventFilter::Window(WindowEventFilter::MouseOver), on_mouse_move);
+            dom.add_default_callback_id(EventFilter::Window(WindowEventFilter::MouseUp), on_mouse_up);
+            dom.add_default_callback_id(On::Scroll, on_scroll);
+            dom.add_default_callback_id(On::Pan, on_pan);
+            dom.add_default_callback_id(EventFilter::Focus(FocusEventFilter::VirtualKeyDown), on_virtual_key_down);
+            dom.add_default_callback_id(EventFilter::Focus(FocusEventFilter::TextInput), on_text_input);
             dom
         } else {
             Dom::label(
@@ -74,10 +319,46 @@ impl TableView {
         }
     }
 
-    fn table_view_on_click<T>(ptr: &StackCheckedPointer<T>, data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>)
+    fn table_view_on_mouse_down<T>(ptr: &StackCheckedPointer<T>, data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>)
+    -> UpdateScreen
+    {
+        unsafe { ptr.invoke_mut(TableViewState::on_mouse_down, data, event) }
+    }
+
+    fn table_view_on_mouse_move<T>(ptr: &StackCheckedPointer<T>, data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>)
+    -> UpdateScreen
+    {
+        unsafe { ptr.invoke_mut(TableViewState::on_mouse_move, data, event) }
+    }
+
+    fn table_view_on_mouse_up<T>(ptr: &StackCheckedPointer<T>, data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>)
+    -> UpdateScreen
+    {
+        unsafe { ptr.invoke_mut(TableViewState::on_mouse_up, data, event) }
+    }
+
+    fn table_view_on_scroll<T>(ptr: &StackCheckedPointer<T>, data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>)
     -> UpdateScreen
     {
-        unsafe { ptr.invoke_mut(TableViewState::on_click, data, event) }
+        unsafe { ptr.invoke_mut(TableViewState::on_scroll, data, event) }
+    }
+
+    fn table_view_on_pan<T>(ptr: &StackCheckedPointer<T>, data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>)
+    -> UpdateScreen
+    {
+        unsafe { ptr.invoke_mut(TableViewState::on_pan, data, event) }
+    }
+
+    fn table_view_on_virtual_key_down<T>(ptr: &StackCheckedPointer<T>, data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>)
+    -> UpdateScreen
+    {
+        unsafe { ptr.invoke_mut(TableViewState::on_virtual_key_down, data, event) }
+    }
+
+    fn table_view_on_text_input<T>(ptr: &StackCheckedPointer<T>, data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>)
+    -> UpdateScreen
+    {
+        unsafe { ptr.invoke_mut(TableViewState::on_text_input, data, event) }
     }
 }
 
@@ -87,95 +368,802 @@ fn render_table_callback<T>(ptr: &StackCheckedPointer<T>, info: LayoutInfo<T>, d
     unsafe { ptr.invoke_mut_iframe(TableViewState::render, info, dimensions) }
 }
 
-
 impl TableViewState {
+
+    /// Freezes the first `frozen_rows` rows, so that they don't scroll and are not sorted
+    #[inline]
+    pub fn with_frozen_rows(self, frozen_rows: usize) -> Self {
+        Self { frozen_rows, .. self }
+    }
+
+    /// Freezes the first `frozen_columns` columns, so that they don't scroll
+    #[inline]
+    pub fn with_frozen_columns(self, frozen_columns: usize) -> Self {
+        Self { frozen_columns, .. self }
+    }
+
+    /// Sets the function that sorts the rows when a column header is clicked
+    /// (default: `Worksheet::sort_rows`)
+    #[inline]
+    pub fn with_sort_callback(self, sort_callback: SortCallback) -> Self {
+        Self { sort_callback, .. self }
+    }
+
+    /// Returns the column in the `Worksheet` that is shown at the `column` on the screen
+    pub fn get_data_column(&self, column: usize) -> usize {
+        self.column_order.get(column).cloned().unwrap_or(column)
+    }
+
+    /// Returns the width of the `column` in the `Worksheet`
+    pub fn get_column_width(&self, column: usize) -> f32 {
+        self.column_widths.get(&column).cloned().unwrap_or(self.column_width).max(MIN_COLUMN_WIDTH)
+    }
+
+    /// Sets the width of the `column` in the `Worksheet`
+    pub fn set_column_width(&mut self, column: usize, width: f32) {
+        self.column_widths.insert(column, width.max(MIN_COLUMN_WIDTH));
+    }
+
+    /// Moves the column at the position `from` on the screen to the position `to`
+    /// (the data in the `Worksheet` is not moved)
+    pub fn move_column(&mut self, from: usize, to: usize) {
+        if from == to {
+            return;
+        }
+        while self.column_order.len() <= from.max(to) {
+            let next_column = self.column_order.len();
+            self.column_order.push(next_column);
+        }
+        let column = self.column_order.remove(from);
+        self.column_order.insert(to, column);
+    }
+
+    /// Returns the selected range of cells, from the anchor to the `selected_cell`
+    pub fn get_selection(&self) -> Option<CellRange> {
+        let (column, row) = self.selected_cell?;
+        let (anchor_column, anchor_row) = self.anchor.unwrap_or((column, row));
+        Some(CellRange {
+            columns: column.min(anchor_column)..(column.max(anchor_column) + 1),
+            rows: row.min(anchor_row)..(row.max(anchor_row) + 1),
+        })
+    }
+
+    /// Selects only the cell at the `column` and `row`
+    pub fn select_cell(&mut self, column: usize, row: usize) {
+        self.selected_cell = Some((column, row));
+        self.anchor = Some((column, row));
+    }
+
+    /// Selects the cells from the anchor to the cell at the `column` and `row`
+    pub fn extend_selection(&mut self, column: usize, row: usize) {
+        self.anchor = self.anchor.or(self.selected_cell).or(Some((column, row)));
+        self.selected_cell = Some((column, row));
+    }
+
+    /// Selects the `range`, the top left cell of the range becomes the `selected_cell`
+    pub fn select_range(&mut self, range: CellRange) {
+        if range.columns.start >= range.columns.end || range.rows.start >= range.rows.end {
+            return;
+        }
+        self.selected_cell = Some((range.columns.start, range.rows.start));
+        self.anchor = Some((range.columns.end - 1, range.rows.end - 1));
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selected_cell = None;
+        self.anchor = None;
+    }
+
+    /// Moves the `selected_cell` and scrolls it into view. With `extend_selection`, the cells
+    /// from the anchor to the new `selected_cell` are selected, otherwise only the new cell.
+    pub fn move_cursor(&mut self, column: usize, row: usize, extend_selection: bool) {
+        if extend_selection {
+            self.extend_selection(column, row);
+        } else {
+            self.select_cell(column, row);
+        }
+        self.scroll_to_cell(column, row);
+    }
+
+    /// Scrolls by the minimal amount so that the cell at the `column` and `row` is visible
+    pub fn scroll_to_cell(&mut self, column: usize, row: usize) {
+
+        let (viewport_width, viewport_height) = self.viewport_size;
+
+        if row >= self.frozen_rows && viewport_height > 0.0 {
+            let scrolled_row = row - self.frozen_rows;
+            let row_count = self.get_scrollable_row_count(viewport_height);
+            if scrolled_row < self.scroll_position.1 {
+                self.scroll_position.1 = scrolled_row;
+            } else if scrolled_row >= self.scroll_position.1 + row_count {
+                self.scroll_position.1 = scrolled_row + 1 - row_count;
+            }
+        }
+
+        if column >= self.frozen_columns && viewport_width > 0.0 {
+            let scrolled_column = column - self.frozen_columns;
+            if scrolled_column < self.scroll_position.0 {
+                self.scroll_position.0 = scrolled_column;
+            } else {
+                let frozen_width: f32 = (0..self.frozen_columns).map(|c| self.get_column_width(self.get_data_column(c))).sum();
+                let available_width = viewport_width - ROW_NUMBER_WIDTH - frozen_width;
+                let first_scrolled_column = self.frozen_columns + self.scroll_position.0;
+                let visible_width: f32 = (first_scrolled_column..=column).map(|c| self.get_column_width(self.get_data_column(c))).sum();
+                if visible_width > available_width {
+                    // Scroll right until the column fits, but never past the column itself
+                    let mut first_column = column;
+                    let mut width = self.get_column_width(self.get_data_column(column));
+                    while first_column > first_scrolled_column {
+                        let previous_width = self.get_column_width(self.get_data_column(first_column - 1));
+                        if width + previous_width > available_width {
+                            break;
+                        }
+                        width += previous_width;
+                        first_column -= 1;
+                    }
+                    self.scroll_position.0 = first_column - self.frozen_columns;
+                }
+            }
+        }
+    }
+
+    /// Returns the cell that is currently edited
+    pub fn get_editor(&self) -> Option<&CellEditor> {
+        self.editor.as_ref()
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.editor.is_some()
+    }
+
+    /// Starts editing the `selected_cell`, the editor contains the current content of the cell
+    pub fn start_editing(&mut self) {
+        if let Some((column, row)) = self.selected_cell {
+            let column = self.get_data_column(column);
+            let text = self.work_sheet.get_cell(column, row).cloned().unwrap_or_default();
+            self.editor = Some(CellEditor { column, row, input: TextInputState::new(text) });
+        }
+    }
+
+    /// Writes the edited text into the `Worksheet` and stops editing
+    pub fn commit_edit(&mut self) {
+        if let Some(CellEditor { column, row, input }) = self.editor.take() {
+            if input.text.is_empty() {
+                self.work_sheet.clear_cell(column, row);
+            } else {
+                self.work_sheet.set_cell(column, row, input.text);
+            }
+        }
+    }
+
+    /// Stops editing without changing the `Worksheet`
+    pub fn cancel_edit(&mut self) {
+        self.editor = None;
+    }
+
+    /// Sorts the rows below the frozen rows by the `column` (in the `Worksheet`) with the sort callback.
+    /// If the rows are already sorted ascending by the `column`, they are sorted descending instead.
+    pub fn sort_by_column(&mut self, column: usize) {
+        let order = match self.sort {
+            Some(ColumnSort { column: sorted_column, order: SortOrder::Ascending, .. }) if sorted_column == column => SortOrder::Descending,
+            _ => SortOrder::Ascending,
+        };
+        let sort = ColumnSort { column, order, first_row: self.frozen_rows };
+        self.commit_edit();
+        (self.sort_callback.0)(&mut self.work_sheet, &sort);
+        self.sort = Some(sort);
+    }
+
+    /// Returns the selected cells as tab-separated values
+    pub fn get_selection_as_tsv(&self) -> Option<String> {
+        let selection = self.get_selection()?;
+        let columns = selection.columns.map(|c| self.get_data_column(c)).collect::<Vec<_>>();
+        Some(self.work_sheet.get_tsv(&columns, selection.rows))
+    }
+
+    /// Clears the content of all selected cells
+    pub fn clear_selected_cells(&mut self) {
+        if let Some(selection) = self.get_selection() {
+            let columns = selection.columns.map(|c| self.get_data_column(c)).collect::<Vec<_>>();
+            self.work_sheet.clear_range(&columns, selection.rows);
+        }
+    }
+
+    /// Pastes the tab-separated values starting at the top left selected cell and selects the pasted cells
+    pub fn paste_tsv(&mut self, tsv: &str) {
+
+        let (first_column, first_row) = match self.get_selection() {
+            Some(selection) => (selection.columns.start, selection.rows.start),
+            None => return,
+        };
+
+        let rows = parse_tsv(tsv);
+        let column_count = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        if column_count == 0 {
+            return;
+        }
+
+        for (y, row) in rows.iter().enumerate() {
+            for (x, value) in row.iter().enumerate() {
+                let column = self.get_data_column(first_column + x);
+                if value.is_empty() {
+                    self.work_sheet.clear_cell(column, first_row + y);
+                } else {
+                    self.work_sheet.set_cell(column, first_row + y, value.clone());
+                }
+            }
+        }
+
+        self.select_range(CellRange {
+            columns: first_column..(first_column + column_count),
+            rows: first_row..(first_row + rows.len()),
+        });
+    }
+
+    /// Returns how many rows below the frozen rows fit into the `viewport_height` (at least one)
+    fn get_scrollable_row_count(&self, viewport_height: f32) -> usize {
+        if self.row_height <= 0.0 {
+            return 1;
+        }
+        let height = viewport_height - self.row_height * (1 + self.frozen_rows) as f32;
+        ((height / self.row_height).floor() as usize).max(1)
+    }
+
+    /// Returns the frozen columns and the scrolled columns that fit into the `viewport_width`
+    fn get_visible_columns(&self, viewport_width: f32) -> Vec<VisibleColumn> {
+        let mut columns = Vec::new();
+        let mut left = ROW_NUMBER_WIDTH;
+        let first_scrolled_column = self.frozen_columns.saturating_add(self.scroll_position.0);
+        for column in (0..self.frozen_columns).chain(first_scrolled_column..) {
+            if left >= viewport_width {
+                break;
+            }
+            let width = self.get_column_width(self.get_data_column(column));
+            columns.push(VisibleColumn { column, left, width });
+            left += width;
+        }
+        columns
+    }
+
+    /// Returns the frozen rows and the scrolled rows that fit into the `viewport_height`,
+    /// together with the distance of the row from the top of the table
+    fn get_visible_rows(&self, viewport_height: f32) -> Vec<(usize, f32)> {
+        let mut rows = Vec::new();
+        if self.row_height <= 0.0 {
+            return rows;
+        }
+        let mut top = self.row_height;
+        let first_scrolled_row = self.frozen_rows.saturating_add(self.scroll_position.1);
+        for row in (0..self.frozen_rows).chain(first_scrolled_row..) {
+            if top >= viewport_height {
+                break;
+            }
+            rows.push((row, top));
+            top += self.row_height;
+        }
+        rows
+    }
+
+    /// Returns the part of the table at the position `x` / `y`, relative to the top left of the table
+    fn hit_test(&self, x: f32, y: f32) -> Option<TableHit> {
+
+        let (viewport_width, viewport_height) = self.viewport_size;
+        if x < 0.0 || y < 0.0 || x >= viewport_width || y >= viewport_height {
+            return None;
+        }
+
+        let columns = self.get_visible_columns(viewport_width);
+
+        if y < self.row_height {
+            if let Some(column) = columns.iter().find(|c| (c.left + c.width - x).abs() <= RESIZE_HANDLE_WIDTH) {
+                return Some(TableHit::ColumnName { column: column.column, is_border: true });
+            }
+            if x < ROW_NUMBER_WIDTH {
+                return Some(TableHit::TopLeftRect);
+            }
+        }
+
+        let column = columns.iter().find(|c| c.left <= x && x < c.left + c.width).map(|c| c.column);
+        let row = self.get_visible_rows(viewport_height).into_iter()
+            .find(|(_, top)| *top <= y && y < *top + self.row_height)
+            .map(|(row, _)| row);
+
+        if y < self.row_height {
+            column.map(|column| TableHit::ColumnName { column, is_border: false })
+        } else if x < ROW_NUMBER_WIDTH {
+            row.map(TableHit::RowNumber)
+        } else {
+            Some(TableHit::Cell(column?, row?))
+        }
+    }
+
+    /// Returns the position of the mouse relative to the top left of the table
+    fn get_cursor_position<T>(&mut self, app_state_no_data: &AppStateNoData<T>, event: &CallbackInfo<T>) -> Option<(f32, f32)> {
+        let rect = event.get_node_rect(event.hit_dom_node)?;
+        self.viewport_size = (rect.size.width, rect.size.height);
+        let (x, y) = event.get_cursor_in_viewport(app_state_no_data.windows[event.window_id].get_mouse_state())?;
+        Some((x - rect.origin.x, y - rect.origin.y))
+    }
+
+    /// Scrolls by whole columns and rows, the rest of the distance is kept for the next scroll event
+    fn scroll_by(&mut self, delta_x: f32, delta_y: f32) -> UpdateScreen {
+        let old_scroll_position = self.scroll_position;
+        let (column_delta, remainder_x) = get_scroll_steps(self.scroll_remainder.0 + delta_x, self.column_width);
+        let (row_delta, remainder_y) = get_scroll_steps(self.scroll_remainder.1 + delta_y, self.row_height);
+        self.scroll_position = (
+            add_scroll_steps(self.scroll_position.0, column_delta),
+            add_scroll_steps(self.scroll_position.1, row_delta),
+        );
+        self.scroll_remainder = (remainder_x, remainder_y);
+        if self.scroll_position != old_scroll_position { Redraw } else { DontRedraw }
+    }
+
     pub fn render<T>(state: &mut TableViewState, _info: LayoutInfo<T>, dimensions: HidpiAdjustedBounds)
     -> Dom<T>
     {
         let logical_size = dimensions.get_logical_size();
-        let necessary_columns = (logical_size.width as f32 / state.column_width).ceil() as usize;
-        let necessary_rows = (logical_size.height as f32 / state.row_height).ceil() as usize;
+        state.viewport_size = (logical_size.width as f32, logical_size.height as f32);
+
+        let visible_columns = state.get_visible_columns(state.viewport_size.0);
+        let visible_rows = state.get_visible_rows(state.viewport_size.1);
+        let selection = state.get_selection();
+        let dragged_column = match state.drag {
+            Some(TableDrag::MoveColumn { column, is_moved: true, .. }) => Some(column),
+            _ => None,
+        };
 
         // div.__azul-native-table-container
-        //     |-> div.__azul-native-table-column (Column 0)
-        //         |-> div.__azul-native-table-top-left-rect .__azul-native-table-column-name
-        //         '-> div.__azul-native-table-row-numbers .__azul-native-table-row
-        //
-        //     |-> div.__azul-native-table-column-container
-        //         |-> div.__azul-native-table-column (Column 1 ...)
-        //             |-> div.__azul-native-table-column-name
-        //             '-> div.__azul-native-table-row
-        //                 '-> div.__azul-native-table-cell
-
-        Dom::div()
-        .with_class("__azul-native-table-container")
-        .with_child(
-            Dom::div()
-            .with_class("__azul-native-table-row-number-wrapper")
+        //     |-> div.__azul-native-table-header
+        //     |    |-> div.__azul-native-table-top-left-rect
+        //     |    '-> p.__azul-native-table-column-name (Column A, B, C ...)
+        //     |-> div.__azul-native-table-row (frozen rows first, then the scrolled rows)
+        //     |    |-> p.__azul-native-table-row-number
+        //     |    '-> p.__azul-native-table-cell
+        //     |         '-> div.__azul-native-input-text .__azul-native-table-cell-editor (edited cell only)
+        //     '-> div.__azul-native-table-selection
+        //          '-> div.__azul-native-table-selection-handle
+
+        let row_height = CssProperty::Height(LayoutHeight::px(state.row_height));
+        let row_number_width = CssProperty::Width(LayoutWidth::px(ROW_NUMBER_WIDTH));
+
+        let mut header = Dom::div()
+            .with_class("__azul-native-table-header")
+            .with_css_override("__azul_table_row_height", row_height.clone())
             .with_child(
                 // Empty rectangle at the top left of the table
                 Dom::div()
                 .with_class("__azul-native-table-top-left-rect")
-            )
-            .with_child(
-                // Rows - "1", "2", "3"
-                (0..necessary_rows.saturating_sub(1))
-                .map(|row_idx|
-                    NodeData {
-                        node_type: NodeType::Label(DomString::Heap(format!("{}", row_idx + 1))),
-                        classes: vec![DomString::Static("__azul-native-table-row")],
-                        .. Default::default()
-                    }
-                )
-                .collect::<Dom<T>>()
-                .with_class("__azul-native-table-row-numbers")
-            )
-        )
-        .with_child(
-            (0..necessary_columns)
-            .map(|col_idx|
-                // Column name
-                Dom::new(NodeType::Div)
-                .with_class("__azul-native-table-column")
-                .with_child(Dom::label(column_name_from_number(col_idx)).with_class("__azul-native-table-column-name"))
-                .with_child(
-                    // Actual rows - if no content is given, they are simply empty
-                    (0..necessary_rows)
-                    .map(|row_idx|
-                        NodeData {
-                            node_type: if let Some(data) = state.work_sheet.data.get(&col_idx).and_then(|col| col.get(&row_idx)) {
-                                NodeType::Label(DomString::Heap(data.clone()))
-                            } else {
-                                NodeType::Div
-                            },
-                            classes: vec![DomString::Static("__azul-native-table-cell")],
-                            .. Default::default()
-                        }
-                    )
-                    .collect::<Dom<T>>()
-                    .with_class("__azul-native-table-rows")
-                )
-            )
-            .collect::<Dom<T>>()
-            .with_class("__azul-native-table-column-container")
-            // current active selection (s)
-            .with_child(
-                Dom::div()
+                .with_css_override("__azul_table_row_number_width", row_number_width.clone())
+            );
+
+        for visible_column in &visible_columns {
+            let data_column = state.get_data_column(visible_column.column);
+            let mut column_name = Dom::label(column_name_from_number(data_column))
+                .with_class("__azul-native-table-column-name")
+                .with_css_override("__azul_table_column_width", CssProperty::Width(LayoutWidth::px(visible_column.width)));
+            if selection.as_ref().map(|s| s.columns.start <= visible_column.column && visible_column.column < s.columns.end) == Some(true) {
+                column_name.add_class("__azul-native-table-column-name-selected");
+            }
+            match state.sort {
+                Some(ColumnSort { column, order: SortOrder::Ascending, .. }) if column == data_column => {
+                    column_name.add_class("__azul-native-table-column-name-sorted-ascending");
+                },
+                Some(ColumnSort { column, order: SortOrder::Descending, .. }) if column == data_column => {
+                    column_name.add_class("__azul-native-table-column-name-sorted-descending");
+                },
+                _ => { },
+            }
+            if dragged_column == Some(visible_column.column) {
+                column_name.add_class("__azul-native-table-column-name-dragged");
+            }
+            header.add_child(column_name);
+        }
+
+        let mut container = Dom::div()
+            .with_class("__azul-native-table-container")
+            .with_child(header);
+
+        for (row, _) in &visible_rows {
+            let row = *row;
+
+            let mut row_number = Dom::label(format!("{}", row + 1))
+                .with_class("__azul-native-table-row-number")
+                .with_css_override("__azul_table_row_number_width", row_number_width.clone());
+            if selection.as_ref().map(|s| s.rows.start <= row && row < s.rows.end) == Some(true) {
+                row_number.add_class("__azul-native-table-row-number-selected");
+            }
+
+            let mut row_dom = Dom::div()
+                .with_class("__azul-native-table-row")
+                .with_css_override("__azul_table_row_height", row_height.clone())
+                .with_child(row_number);
+
+            for visible_column in &visible_columns {
+                let column = visible_column.column;
+                let data_column = state.get_data_column(column);
+                let editor = state.editor.as_ref().filter(|editor| editor.column == data_column && editor.row == row);
+
+                let mut cell = match (editor, state.work_sheet.get_cell(data_column, row)) {
+                    (Some(editor), _) => {
+                        Dom::div()
+                        .with_child(TextInput::new().dom(&editor.input).with_class("__azul-native-table-cell-editor"))
+                    },
+                    (None, Some(content)) => Dom::label(content.clone()),
+                    (None, None) => Dom::div(),
+                };
+
+                cell.add_class("__azul-native-table-cell");
+                cell.add_css_override("__azul_table_column_width", CssProperty::Width(LayoutWidth::px(visible_column.width)));
+                if row < state.frozen_rows || column < state.frozen_columns {
+                    cell.add_class("__azul-native-table-cell-frozen");
+                }
+                if selection.as_ref().map(|s| s.contains(column, row)) == Some(true) {
+                    cell.add_class("__azul-native-table-cell-selected");
+                }
+                row_dom.add_child(cell);
+            }
+
+            container.add_child(row_dom);
+        }
+
+        // Border around the visible part of the current selection
+        if let Some(selection) = selection {
+            let selected_columns = visible_columns.iter()
+                .filter(|c| selection.columns.start <= c.column && c.column < selection.columns.end)
+                .collect::<Vec<_>>();
+            let selected_rows = visible_rows.iter()
+                .filter(|(row, _)| selection.rows.start <= *row && *row < selection.rows.end)
+                .collect::<Vec<_>>();
+
+            // The visible columns and rows are sorted from left to right and from top to bottom
+            if let (Some(first_column), Some(last_column), Some(first_row), Some(last_row)) =
+                (selected_columns.first(), selected_columns.last(), selected_rows.first(), selected_rows.last())
+            {
+                let (left, right) = (first_column.left, last_column.left + last_column.width);
+                let (top, bottom) = (first_row.1, last_row.1 + state.row_height);
+                container.add_child(
+                    Dom::div()
                     .with_class("__azul-native-table-selection")
+                    .with_css_override("__azul_table_selection_offset", CssProperty::Margin(LayoutMargin {
+                        top: Some(PixelValue::px(top)),
+                        left: Some(PixelValue::px(left)),
+                        .. Default::default()
+                    }))
+                    .with_css_override("__azul_table_selection_width", CssProperty::Width(LayoutWidth::px(right - left)))
+                    .with_css_override("__azul_table_selection_height", CssProperty::Height(LayoutHeight::px(bottom - top)))
                     .with_child(Dom::div().with_class("__azul-native-table-selection-handle"))
-            )
-        )
+                );
+            }
+        }
+
+        container
     }
 
-    pub fn on_click<T>(
-        &mut self,
-        _app_state: &mut AppStateNoData<T>,
-        _window_event: &mut CallbackInfo<T>)
-    -> UpdateScreen
-    {
-        println!("table was clicked");
-        DontRedraw
+    /// Selects the clicked cell (Shift extends the selection), starts resizing or moving a column
+    /// when a column header is clicked. Clicking the selected cell again starts editing it.
+    pub fn on_mouse_down<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        let (x, y) = match self.get_cursor_position(app_state_no_data, event) {
+            Some(s) => s,
+            None => return DontRedraw,
+        };
+
+        let shift_down = app_state_no_data.windows[event.window_id].get_keyboard_state().shift_down;
+
+        match self.hit_test(x, y) {
+            Some(TableHit::ColumnName { column, is_border: true }) => {
+                let column = self.get_data_column(column);
+                self.drag = Some(TableDrag::ResizeColumn { column, start_x: x, start_width: self.get_column_width(column) });
+                DontRedraw
+            },
+            Some(TableHit::ColumnName { column, is_border: false }) => {
+                self.drag = Some(TableDrag::MoveColumn { column, start_x: x, is_moved: false });
+                DontRedraw
+            },
+            Some(TableHit::Cell(column, row)) => {
+                let data_column = self.get_data_column(column);
+                if self.editor.as_ref().map(|editor| editor.column == data_column && editor.row == row) == Some(true) {
+                    return DontRedraw;
+                }
+                let is_selected_cell = self.selected_cell == Some((column, row)) &&
+                                       self.get_selection().map(|s| s.is_single_cell()) == Some(true);
+                self.commit_edit();
+                if shift_down {
+                    self.extend_selection(column, row);
+                } else if is_selected_cell {
+                    self.start_editing();
+                } else {
+                    self.select_cell(column, row);
+                }
+                self.drag = Some(TableDrag::Select);
+                Redraw
+            },
+            Some(TableHit::RowNumber(_)) | Some(TableHit::TopLeftRect) | None => DontRedraw,
+        }
+    }
+
+    /// Extends the selection or resizes the column while the mouse is dragged
+    pub fn on_mouse_move<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        let drag = match self.drag {
+            Some(s) => s,
+            None => return DontRedraw,
+        };
+
+        // The mouse button was released outside of the window
+        if !app_state_no_data.windows[event.window_id].get_mouse_state().left_down {
+            self.drag = None;
+            return DontRedraw;
+        }
+
+        let (x, y) = match self.get_cursor_position(app_state_no_data, event) {
+            Some(s) => s,
+            None => return DontRedraw,
+        };
+
+        match drag {
+            TableDrag::Select => match self.hit_test(x, y) {
+                Some(TableHit::Cell(column, row)) if self.selected_cell != Some((column, row)) => {
+                    self.extend_selection(column, row);
+                    Redraw
+                },
+                _ => DontRedraw,
+            },
+            TableDrag::ResizeColumn { column, start_x, start_width } => {
+                self.set_column_width(column, start_width + x - start_x);
+                Redraw
+            },
+            TableDrag::MoveColumn { column, start_x, is_moved: false } if (x - start_x).abs() > DRAG_THRESHOLD => {
+                self.drag = Some(TableDrag::MoveColumn { column, start_x, is_moved: true });
+                Redraw
+            },
+            TableDrag::MoveColumn { .. } => DontRedraw,
+        }
+    }
+
+    /// Finishes dragging: drops a moved column at the mouse position or sorts by a clicked column
+    pub fn on_mouse_up<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        match self.drag.take() {
+            Some(TableDrag::MoveColumn { column, is_moved: false, .. }) => {
+                let column = self.get_data_column(column);
+                self.sort_by_column(column);
+                Redraw
+            },
+            Some(TableDrag::MoveColumn { column, is_moved: true, .. }) => {
+                if let Some((x, _)) = self.get_cursor_position(app_state_no_data, event) {
+                    let visible_columns = self.get_visible_columns(self.viewport_size.0);
+                    let target = visible_columns.iter()
+                        .find(|c| x < c.left + c.width)
+                        .or_else(|| visible_columns.last())
+                        .map(|c| c.column);
+                    if let Some(target) = target {
+                        self.move_column(column, target);
+                        if let Some((_, row)) = self.selected_cell {
+                            self.select_cell(target, row);
+                        }
+                    }
+                }
+                Redraw
+            },
+            Some(TableDrag::Select) | Some(TableDrag::ResizeColumn { .. }) | None => DontRedraw,
+        }
+    }
+
+    /// Scrolls the table with the mouse wheel
+    pub fn on_scroll<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        let mouse_state = app_state_no_data.windows[event.window_id].get_mouse_state();
+        self.scroll_by(mouse_state.scroll_x as f32, mouse_state.scroll_y as f32)
+    }
+
+    /// Scrolls the table when a finger is moved over it
+    pub fn on_pan<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        let (pan_x, pan_y) = app_state_no_data.windows[event.window_id].get_touch_state().gestures.iter().fold((0.0, 0.0), |(x, y), gesture| match gesture {
+            Gesture::Pan { delta, .. } => (x + delta.0, y + delta.1),
+            _ => (x, y),
+        });
+        self.scroll_by(-pan_x as f32, -pan_y as f32)
+    }
+
+    /// Moves the selected cell with the arrow keys, Tab, Home and PageUp / PageDown (Shift extends
+    /// the selection), edits, clears, copies and pastes cells. While a cell is edited, the keys
+    /// are forwarded to its `TextInputState`, except for Enter / Tab (commit) and Escape (cancel).
+    pub fn on_virtual_key_down<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        let (keycode, shift_down, ctrl_down) = {
+            let keyboard_state = app_state_no_data.windows[event.window_id].get_keyboard_state();
+            (keyboard_state.latest_virtual_keycode, keyboard_state.shift_down, keyboard_state.ctrl_down)
+        };
+
+        if let Some(rect) = event.get_node_rect(event.hit_dom_node) {
+            self.viewport_size = (rect.size.width, rect.size.height);
+        }
+
+        let (column, row) = self.selected_cell.unwrap_or((0, 0));
+        let previous_column = column.saturating_sub(1);
+        let next_column = column.saturating_add(1);
+
+        if self.editor.is_some() {
+            match keycode {
+                Some(VirtualKeyCode::Return) => {
+                    self.commit_edit();
+                    let row = if shift_down { row.saturating_sub(1) } else { row.saturating_add(1) };
+                    self.move_cursor(column, row, false);
+                },
+                Some(VirtualKeyCode::Tab) => {
+                    self.commit_edit();
+                    self.move_cursor(if shift_down { previous_column } else { next_column }, row, false);
+                },
+                Some(VirtualKeyCode::Escape) => self.cancel_edit(),
+                _ => return match self.editor.as_mut() {
+                    Some(editor) => editor.input.on_virtual_key_down(app_state_no_data, event),
+                    None => DontRedraw,
+                },
+            }
+            return Redraw;
+        }
+
+        let page_size = self.get_scrollable_row_count(self.viewport_size.1);
+
+        match keycode {
+            Some(VirtualKeyCode::Up) => self.move_cursor(column, row.saturating_sub(1), shift_down),
+            Some(VirtualKeyCode::Down) => self.move_cursor(column, row.saturating_add(1), shift_down),
+            Some(VirtualKeyCode::Left) => self.move_cursor(previous_column, row, shift_down),
+            Some(VirtualKeyCode::Right) => self.move_cursor(next_column, row, shift_down),
+            Some(VirtualKeyCode::Tab) => self.move_cursor(if shift_down { previous_column } else { next_column }, row, false),
+            Some(VirtualKeyCode::Home) if ctrl_down => self.move_cursor(0, 0, shift_down),
+            Some(VirtualKeyCode::Home) => self.move_cursor(0, row, shift_down),
+            Some(VirtualKeyCode::PageUp) => {
+                self.scroll_position.1 = self.scroll_position.1.saturating_sub(page_size);
+                self.move_cursor(column, row.saturating_sub(page_size), shift_down);
+            },
+            Some(VirtualKeyCode::PageDown) => {
+                self.scroll_position.1 = self.scroll_position.1.saturating_add(page_size);
+                self.move_cursor(column, row.saturating_add(page_size), shift_down);
+            },
+            Some(VirtualKeyCode::Return) | Some(VirtualKeyCode::F2) => self.start_editing(),
+            Some(VirtualKeyCode::Delete) | Some(VirtualKeyCode::Back) => self.clear_selected_cells(),
+            Some(VirtualKeyCode::C) if ctrl_down => {
+                if let Some(tsv) = self.get_selection_as_tsv() {
+                    // Copying is best-effort, there is nothing to redraw if the clipboard is unavailable
+                    let _ = app_state_no_data.resources.set_clipboard_string(tsv);
+                }
+                return DontRedraw;
+            },
+            Some(VirtualKeyCode::X) if ctrl_down => {
+                if let Some(tsv) = self.get_selection_as_tsv() {
+                    if app_state_no_data.resources.set_clipboard_string(tsv).is_ok() {
+                        self.clear_selected_cells();
+                    }
+                }
+            },
+            Some(VirtualKeyCode::V) if ctrl_down => {
+                match app_state_no_data.resources.get_clipboard_string() {
+                    Ok(contents) => self.paste_tsv(&contents),
+                    Err(_) => return DontRedraw,
+                }
+            },
+            _ => return DontRedraw,
+        }
+
+        Redraw
+    }
+
+    /// Typing into a selected cell that isn't edited yet replaces its content
+    pub fn on_text_input<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        let (current_char, ctrl_down) = {
+            let keyboard_state = app_state_no_data.windows[event.window_id].get_keyboard_state();
+            (keyboard_state.current_char, keyboard_state.ctrl_down)
+        };
+
+        match current_char {
+            Some(c) if !c.is_control() && !ctrl_down => { },
+            _ => return DontRedraw,
+        }
+
+        if self.editor.is_none() {
+            self.start_editing();
+            if let Some(editor) = self.editor.as_mut() {
+                editor.input.selection = Some(Selection::All);
+            }
+        }
+
+        match self.editor.as_mut() {
+            Some(editor) => editor.input.on_text_input(app_state_no_data, event),
+            None => DontRedraw,
+        }
+    }
+}
+
+/// Splits a scroll distance into whole steps of `step_size` pixels and the remaining distance
+fn get_scroll_steps(distance: f32, step_size: f32) -> (isize, f32) {
+    if step_size <= 0.0 {
+        return (0, 0.0);
+    }
+    let steps = (distance / step_size).trunc();
+    (steps as isize, distance - steps * step_size)
+}
+
+fn add_scroll_steps(position: usize, steps: isize) -> usize {
+    if steps < 0 {
+        position.saturating_sub(steps.wrapping_neg() as usize)
+    } else {
+        position.saturating_add(steps as usize)
+    }
+}
+
+/// Compares the content of two cells for `Worksheet::sort_rows`
+fn compare_cells(a: Option<&String>, b: Option<&String>, order: SortOrder) -> Ordering {
+    let a = a.filter(|a| !a.is_empty());
+    let b = b.filter(|b| !b.is_empty());
+    let ordering = match (a, b) {
+        (None, None) => return Ordering::Equal,
+        (None, Some(_)) => return Ordering::Greater,
+        (Some(_), None) => return Ordering::Less,
+        (Some(a), Some(b)) => match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+            (Ok(a), Ok(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => a.to_lowercase().cmp(&b.to_lowercase()),
+        },
+    };
+    match order {
+        SortOrder::Ascending => ordering,
+        SortOrder::Descending => ordering.reverse(),
+    }
+}
+
+/// Quotes a field that contains tabs, line breaks or quotes
+fn escape_tsv_field(field: &str) -> String {
+    if field.contains(&['\t', '\n', '\r', '"'][..]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Parses tab-separated values into rows of fields. Fields can be quoted
+/// (with `""` as an escaped quote) to contain tabs and line breaks.
+pub fn parse_tsv(tsv: &str) -> Vec<Vec<String>> {
+
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut is_quoted = false;
+    let mut chars = tsv.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if is_quoted => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    is_quoted = false;
+                }
+            },
+            '"' if field.is_empty() => is_quoted = true,
+            '\t' if !is_quoted => {
+                row.push(field.clone());
+                field.clear();
+            },
+            '\r' if !is_quoted && chars.peek() == Some(&'\n') => { },
+            '\n' if !is_quoted => {
+                row.push(field.clone());
+                field.clear();
+                rows.push(row.clone());
+                row.clear();
+            },
+            c => field.push(c),
+        }
     }
+
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    rows
 }
 
 /// Maps an index number to a value, necessary for creating the column name:
@@ -226,4 +1214,85 @@ fn test_column_name_from_number() {
     assert_eq!(column_name_from_number(26), String::from("AA"));
     assert_eq!(column_name_from_number(27), String::from("AB"));
     assert_eq!(column_name_from_number(225), String::from("HR"));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_worksheet_tsv() {
+    let mut work_sheet = Worksheet::default();
+    work_sheet.set_cell(0, 0, "Name");
+    work_sheet.set_cell(1, 0, "Quote");
+    work_sheet.set_cell(0, 1, "Tab\tand \"quotes\"");
+    work_sheet.set_cell(1, 2, "Line\nbreak");
+
+    let tsv = work_sheet.get_tsv(&[0, 1], 0..3);
+    assert_eq!(tsv, "Name\tQuote\n\"Tab\tand \"\"quotes\"\"\"\t\n\t\"Line\nbreak\"");
+
+    let rows = parse_tsv(&tsv);
+    assert_eq!(rows, vec![
+        vec!["Name".to_string(), "Quote".to_string()],
+        vec!["Tab\tand \"quotes\"".to_string(), String::new()],
+        vec![String::new(), "Line\nbreak".to_string()],
+    ]);
+
+    // Spreadsheet applications end the copied rows with "\r\n"
+    assert_eq!(parse_tsv("1\t2\r\n3\t4\r\n"), vec![vec!["1".to_string(), "2".to_string()], vec!["3".to_string(), "4".to_string()]]);
+
+    work_sheet.clear_range(&[0, 1], 1..3);
+    assert_eq!(work_sheet.get_cell(0, 1), None);
+    assert_eq!(work_sheet.get_cell(1, 2), None);
+    assert_eq!(work_sheet.get_cell(1, 0), Some(&"Quote".to_string()));
+}
+
+#[test]
+fn test_worksheet_sort_rows() {
+    let mut work_sheet = Worksheet::default();
+    work_sheet.set_cell(0, 0, "Header");
+    for (row, value) in ["10", "b", "9", "A"].iter().enumerate() {
+        work_sheet.set_cell(0, row + 1, *value);
+        work_sheet.set_cell(1, row + 1, format!("row {}", row + 1));
+    }
+    // Row 6 has no value in the sorted column and row 5 is empty
+    work_sheet.set_cell(1, 6, "row 6");
+
+    work_sheet.sort_rows(0, SortOrder::Ascending, 1);
+    let column = |work_sheet: &Worksheet, x: usize| (0..7).map(|y| work_sheet.get_cell(x, y).cloned().unwrap_or_default()).collect::<Vec<_>>();
+    assert_eq!(column(&work_sheet, 0), vec!["Header", "9", "10", "A", "b", "", ""]);
+    assert_eq!(column(&work_sheet, 1), vec!["", "row 3", "row 1", "row 4", "row 2", "row 6", ""]);
+
+    work_sheet.sort_rows(0, SortOrder::Descending, 1);
+    assert_eq!(column(&work_sheet, 0), vec!["Header", "b", "A", "10", "9", "", ""]);
+}
+
+#[test]
+fn test_table_view_layout() {
+    let mut state = TableViewState::default().with_frozen_rows(1).with_frozen_columns(1);
+    state.viewport_size = (ROW_NUMBER_WIDTH + 250.0, 100.0);
+    state.set_column_width(2, 50.0);
+    state.scroll_position = (1, 10);
+
+    // Column 0 is frozen, column 1 is scrolled out
+    let columns = state.get_visible_columns(state.viewport_size.0).iter().map(|c| (c.column, c.left)).collect::<Vec<_>>();
+    assert_eq!(columns, vec![(0, 40.0), (2, 140.0), (3, 190.0)]);
+    let rows = state.get_visible_rows(state.viewport_size.1).iter().map(|(row, _)| *row).collect::<Vec<_>>();
+    assert_eq!(rows, vec![0, 11, 12, 13]);
+
+    assert_eq!(state.hit_test(10.0, 10.0), Some(TableHit::TopLeftRect));
+    assert_eq!(state.hit_test(189.0, 10.0), Some(TableHit::ColumnName { column: 2, is_border: true }));
+    assert_eq!(state.hit_test(160.0, 10.0), Some(TableHit::ColumnName { column: 2, is_border: false }));
+    assert_eq!(state.hit_test(10.0, 45.0), Some(TableHit::RowNumber(11)));
+    assert_eq!(state.hit_test(200.0, 30.0), Some(TableHit::Cell(3, 0)));
+
+    // Moving the cursor below the last visible row scrolls down by one row
+    state.move_cursor(3, 14, false);
+    assert_eq!(state.scroll_position, (1, 11));
+    state.move_cursor(4, 14, true);
+    assert_eq!(state.scroll_position, (3, 11));
+    assert_eq!(state.get_selection(), Some(CellRange { columns: 3..5, rows: 14..15 }));
+
+    state.move_column(3, 0);
+    assert_eq!((0..5).map(|c| state.get_data_column(c)).collect::<Vec<_>>(), vec![3, 0, 1, 2, 4]);
+
+    assert_eq!(get_scroll_steps(50.0, 20.0), (2, 10.0));
+    assert_eq!(get_scroll_steps(-5.0, 20.0), (0, -5.0));
+    assert_eq!(add_scroll_steps(1, -3), 0);
+}
@@ -1,4 +1,9 @@
 //! Text input (demonstrates two-way data binding)
+//!
+//! The text is edited by default callbacks, which are invoked before the `On::TextInput` and
+//! `On::VirtualKeyDown` callbacks of the application, so these already see the edited
+//! `TextInputState`. To reject a key press, add a capture callback (`Dom::with_capture_callback`)
+//! on the input or one of its parents and call `CallbackInfo::prevent_default`.
 
 use std::ops::Range;
 use {
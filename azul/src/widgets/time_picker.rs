@@ -0,0 +1,396 @@
+//! Time input with separately editable hour, minute and second fields (two-way data binding)
+
+use {
+    callbacks::{UpdateScreen, Redraw, DontRedraw},
+    dom::{Dom, On, EventFilter, FocusEventFilter, TabIndex},
+    window::FakeWindow,
+    prelude::VirtualKeyCode,
+    callbacks::{CallbackInfo, StackCheckedPointer, DefaultCallback, DefaultCallbackId},
+    app::AppStateNoData,
+};
+
+/// Time of the day, without a date or a time zone
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time {
+    /// Hour from 0 to 23
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+impl Time {
+
+    /// Returns `None` if the time doesn't exist
+    pub fn new(hour: u8, minute: u8, second: u8) -> Option<Self> {
+        if hour > 23 || minute > 59 || second > 59 {
+            return None;
+        }
+        Some(Self { hour, minute, second })
+    }
+}
+
+/// Part of the time that is edited via the keyboard
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TimeField {
+    Hour,
+    Minute,
+    Second,
+    /// AM / PM, only shown for 12-hour clocks
+    Period,
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+struct TimePickerCallbacks {
+    click: DefaultCallbackId,
+    text_input: DefaultCallbackId,
+    virtual_key_down: DefaultCallbackId,
+    focus_lost: DefaultCallbackId,
+}
+
+#[derive(Debug, Default, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct TimePicker {
+    callbacks: Option<TimePickerCallbacks>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TimePickerState {
+    pub time: Time,
+    /// Whether the seconds can be edited, otherwise they are always 0
+    pub show_seconds: bool,
+    /// Whether the hour is shown from 1 to 12 with an AM / PM field
+    pub twelve_hour_clock: bool,
+    /// How many minutes Up / Down change the time, i.e. 15 for quarter hours
+    pub minute_step: u8,
+    /// Disabled time pickers can't be focused or edited
+    pub disabled: bool,
+    /// Field that is edited via the keyboard, `None` if the time picker isn't focused
+    active_field: Option<TimeField>,
+    /// First digit that was typed into the `active_field`, waiting for the second one
+    pending_digit: Option<u8>,
+}
+
+impl Default for TimePickerState {
+    fn default() -> Self {
+        Self::new(Time::default())
+    }
+}
+
+impl TimePickerState {
+
+    pub fn new(time: Time) -> Self {
+        Self {
+            time,
+            show_seconds: false,
+            twelve_hour_clock: false,
+            minute_step: 1,
+            disabled: false,
+            active_field: None,
+            pending_digit: None,
+        }
+    }
+
+    #[inline]
+    pub fn with_seconds(self, show_seconds: bool) -> Self {
+        let time = if show_seconds { self.time } else { Time { second: 0, .. self.time } };
+        Self { show_seconds, time, .. self }
+    }
+
+    #[inline]
+    pub fn with_twelve_hour_clock(self, twelve_hour_clock: bool) -> Self {
+        Self { twelve_hour_clock, .. self }
+    }
+
+    #[inline]
+    pub fn with_minute_step(self, minute_step: u8) -> Self {
+        Self { minute_step: minute_step.max(1), .. self }
+    }
+
+    #[inline]
+    pub fn with_disabled(self, disabled: bool) -> Self {
+        Self { disabled, .. self }
+    }
+
+    /// Returns the fields in the order in which they are shown
+    pub fn get_fields(&self) -> Vec<TimeField> {
+        let mut fields = vec![TimeField::Hour, TimeField::Minute];
+        if self.show_seconds {
+            fields.push(TimeField::Second);
+        }
+        if self.twelve_hour_clock {
+            fields.push(TimeField::Period);
+        }
+        fields
+    }
+
+    pub fn get_active_field(&self) -> Option<TimeField> {
+        self.active_field
+    }
+
+    /// Returns the text of a field, i.e. "07" for the hour at 19:00 on a 12-hour clock
+    pub fn format_field(&self, field: TimeField) -> String {
+        match field {
+            TimeField::Hour if self.twelve_hour_clock => format!("{:02}", (self.time.hour + 11) % 12 + 1),
+            TimeField::Hour => format!("{:02}", self.time.hour),
+            TimeField::Minute => format!("{:02}", self.time.minute),
+            TimeField::Second => format!("{:02}", self.time.second),
+            TimeField::Period => if self.time.hour < 12 { "AM".into() } else { "PM".into() },
+        }
+    }
+
+    /// Changes a field by `steps` (minutes by `steps * minute_step`), wrapping around
+    /// without changing the other fields, i.e. one minute after 10:59 is 10:00
+    pub fn step_field(&mut self, field: TimeField, steps: i32) {
+        let wrap = |value: u8, steps: i32, modulo: i32| (((i32::from(value) + steps) % modulo + modulo) % modulo) as u8;
+        match field {
+            TimeField::Hour => self.time.hour = wrap(self.time.hour, steps, 24),
+            TimeField::Minute => {
+                // Snap to the step first, so that stepping from 10:07 with a step of 15
+                // results in 10:15 (up) or 10:00 (down)
+                let step = i32::from(self.minute_step);
+                let minute = i32::from(self.time.minute);
+                let snapped = minute / step * step;
+                let target = if steps < 0 && snapped != minute { snapped + (steps + 1) * step } else { snapped + steps * step };
+                self.time.minute = ((target % 60 + 60) % 60) as u8;
+            },
+            TimeField::Second => self.time.second = wrap(self.time.second, steps, 60),
+            TimeField::Period => self.time.hour = wrap(self.time.hour, 12, 24),
+        }
+    }
+
+    /// Types a digit into the active field: the first digit is kept if a second one can
+    /// follow it, after the second digit (or a first digit that can't be followed by
+    /// another one) the next field is activated
+    pub fn type_digit(&mut self, digit: u8) {
+
+        let field = match self.active_field {
+            Some(s) if s != TimeField::Period => s,
+            _ => return,
+        };
+
+        let (min, max) = match field {
+            TimeField::Hour if self.twelve_hour_clock => (1, 12),
+            TimeField::Hour => (0, 23),
+            _ => (0, 59),
+        };
+
+        let value = match self.pending_digit.take() {
+            Some(first) => (first * 10 + digit).min(max).max(min),
+            None if digit * 10 > max => digit.max(min),
+            None => {
+                self.pending_digit = Some(digit);
+                digit.max(min)
+            },
+        };
+
+        match field {
+            TimeField::Hour if self.twelve_hour_clock => {
+                let pm = if self.time.hour >= 12 { 12 } else { 0 };
+                self.time.hour = value % 12 + pm;
+            },
+            TimeField::Hour => self.time.hour = value,
+            TimeField::Minute => self.time.minute = value,
+            _ => self.time.second = value,
+        }
+
+        if self.pending_digit.is_none() {
+            self.move_active_field(1);
+        }
+    }
+
+    /// Activates the next / previous field, stays at the first / last field
+    fn move_active_field(&mut self, offset: isize) {
+        let fields = self.get_fields();
+        let current = self.active_field.and_then(|active| fields.iter().position(|f| *f == active)).unwrap_or(0);
+        let next = (current as isize + offset).max(0).min(fields.len() as isize - 1);
+        self.active_field = Some(fields[next as usize]);
+        self.pending_digit = None;
+    }
+}
+
+impl TimePicker {
+
+    pub fn new() -> Self {
+        TimePicker { callbacks: None }
+    }
+
+    pub fn bind<T>(self, window: &mut FakeWindow<T>, field: &TimePickerState, data: &T) -> Self {
+        let ptr = StackCheckedPointer::new(data, field);
+        let callbacks = ptr.map(|ptr| TimePickerCallbacks {
+            click: window.add_callback(ptr, DefaultCallback(TimePickerState::on_click_private)),
+            text_input: window.add_callback(ptr, DefaultCallback(TimePickerState::on_text_input_private)),
+            virtual_key_down: window.add_callback(ptr, DefaultCallback(TimePickerState::on_virtual_key_down_private)),
+            focus_lost: window.add_callback(ptr, DefaultCallback(TimePickerState::on_focus_lost_private)),
+        });
+
+        Self { callbacks }
+    }
+
+    pub fn dom<T>(&self, field: &TimePickerState) -> Dom<T> {
+
+        let mut parent_div =
+            Dom::div()
+            .with_class("__azul-native-time-picker")
+            .with_disabled(field.disabled);
+
+        if !field.disabled {
+            parent_div.set_tab_index(TabIndex::Auto);
+            if let Some(callbacks) = self.callbacks {
+                parent_div.add_default_callback_id(On::LeftMouseDown, callbacks.click);
+                parent_div.add_default_callback_id(EventFilter::Focus(FocusEventFilter::TextInput), callbacks.text_input);
+                parent_div.add_default_callback_id(EventFilter::Focus(FocusEventFilter::VirtualKeyDown), callbacks.virtual_key_down);
+                parent_div.add_default_callback_id(On::FocusLost, callbacks.focus_lost);
+            }
+        }
+
+        // Fields are every second child, separated by colons (or a space before the AM / PM field)
+        for (field_idx, time_field) in field.get_fields().into_iter().enumerate() {
+            if field_idx > 0 {
+                let separator = if time_field == TimeField::Period { " " } else { ":" };
+                parent_div.add_child(Dom::label(separator).with_class("__azul-native-time-picker-separator"));
+            }
+            let mut field_div = Dom::label(field.format_field(time_field)).with_class("__azul-native-time-picker-field");
+            if field.active_field == Some(time_field) {
+                field_div.add_class("__azul-native-time-picker-field-active");
+            }
+            parent_div.add_child(field_div);
+        }
+
+        parent_div
+    }
+}
+
+impl TimePickerState {
+
+    fn on_click_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_click, app_state_no_data, window_event) }
+    }
+
+    fn on_text_input_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_text_input, app_state_no_data, window_event) }
+    }
+
+    fn on_virtual_key_down_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_virtual_key_down, app_state_no_data, window_event) }
+    }
+
+    fn on_focus_lost_private<T>(data: &StackCheckedPointer<T>, app_state_no_data: &mut AppStateNoData<T>, window_event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { data.invoke_mut(Self::on_focus_lost, app_state_no_data, window_event) }
+    }
+
+    /// Activates the clicked field (or the first field, if the click was on a separator)
+    pub fn on_click<T>(&mut self, _app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        if self.disabled {
+            return DontRedraw;
+        }
+        let fields = self.get_fields();
+        let field_idx = event.get_target_child_index().filter(|child| child % 2 == 0).map(|child| child / 2).unwrap_or(0);
+        self.active_field = fields.get(field_idx).cloned();
+        self.pending_digit = None;
+        Redraw
+    }
+
+    /// Digits are typed into the active field, "a" / "p" switch between AM and PM
+    pub fn on_text_input<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        if self.disabled {
+            return DontRedraw;
+        }
+
+        if self.active_field.is_none() {
+            self.active_field = Some(TimeField::Hour);
+        }
+
+        let current_char = app_state_no_data.windows[event.window_id].get_keyboard_state().current_char?;
+
+        match current_char.to_ascii_lowercase() {
+            c if c.is_ascii_digit() => self.type_digit(c as u8 - b'0'),
+            'a' if self.twelve_hour_clock && self.time.hour >= 12 => self.step_field(TimeField::Period, 1),
+            'p' if self.twelve_hour_clock && self.time.hour < 12 => self.step_field(TimeField::Period, 1),
+            _ => return DontRedraw,
+        }
+
+        Redraw
+    }
+
+    /// Up / Down change the active field, Left / Right move between the fields
+    pub fn on_virtual_key_down<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        if self.disabled {
+            return DontRedraw;
+        }
+
+        let keyboard_state = app_state_no_data.windows[event.window_id].get_keyboard_state();
+        let active_field = self.active_field.unwrap_or(TimeField::Hour);
+
+        match keyboard_state.latest_virtual_keycode {
+            Some(VirtualKeyCode::Up) => self.step_field(active_field, 1),
+            Some(VirtualKeyCode::Down) => self.step_field(active_field, -1),
+            Some(VirtualKeyCode::Left) => self.move_active_field(-1),
+            Some(VirtualKeyCode::Right) => self.move_active_field(1),
+            Some(VirtualKeyCode::Home) => self.active_field = Some(TimeField::Hour),
+            Some(VirtualKeyCode::End) => self.active_field = self.get_fields().last().cloned(),
+            _ => return DontRedraw,
+        }
+
+        if self.active_field.is_none() {
+            self.active_field = Some(active_field);
+        }
+        self.pending_digit = None;
+
+        Redraw
+    }
+
+    /// Removes the highlight of the active field when the time picker loses the focus
+    pub fn on_focus_lost<T>(&mut self, _app_state_no_data: &mut AppStateNoData<T>, _event: &mut CallbackInfo<T>) -> UpdateScreen {
+        if self.active_field.is_none() {
+            return DontRedraw;
+        }
+        self.active_field = None;
+        self.pending_digit = None;
+        Redraw
+    }
+}
+
+#[test]
+fn test_time_picker_step() {
+    let mut picker = TimePickerState::new(Time::new(23, 59, 0).unwrap());
+    picker.step_field(TimeField::Minute, 1);
+    assert_eq!(picker.time, Time::new(23, 0, 0).unwrap());
+    picker.step_field(TimeField::Hour, 1);
+    assert_eq!(picker.time.hour, 0);
+
+    let mut quarters = TimePickerState::new(Time::new(10, 7, 0).unwrap()).with_minute_step(15);
+    quarters.step_field(TimeField::Minute, 1);
+    assert_eq!(quarters.time.minute, 15);
+    quarters.step_field(TimeField::Minute, -1);
+    assert_eq!(quarters.time.minute, 0);
+    quarters.step_field(TimeField::Minute, -1);
+    assert_eq!(quarters.time.minute, 45);
+
+    let twelve_hours = TimePickerState::new(Time::new(0, 30, 0).unwrap()).with_twelve_hour_clock(true);
+    assert_eq!(twelve_hours.format_field(TimeField::Hour), "12");
+    assert_eq!(twelve_hours.format_field(TimeField::Period), "AM");
+    assert_eq!(twelve_hours.get_fields(), vec![TimeField::Hour, TimeField::Minute, TimeField::Period]);
+}
+
+#[test]
+fn test_time_picker_typing() {
+    let mut picker = TimePickerState::new(Time::default()).with_seconds(true);
+    picker.active_field = Some(TimeField::Hour);
+
+    // "1" could be followed by another digit, "7" can't
+    picker.type_digit(1);
+    assert_eq!(picker.active_field, Some(TimeField::Hour));
+    picker.type_digit(9);
+    assert_eq!(picker.active_field, Some(TimeField::Minute));
+    picker.type_digit(7);
+    assert_eq!(picker.active_field, Some(TimeField::Second));
+    assert_eq!(picker.time, Time::new(19, 7, 0).unwrap());
+
+    let mut twelve_hours = TimePickerState::new(Time::new(15, 0, 0).unwrap()).with_twelve_hour_clock(true);
+    twelve_hours.active_field = Some(TimeField::Hour);
+    twelve_hours.type_digit(1);
+    twelve_hours.type_digit(2);
+    assert_eq!(twelve_hours.time.hour, 12);
+    assert_eq!(twelve_hours.format_field(TimeField::Period), "PM");
+}
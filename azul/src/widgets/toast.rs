@@ -0,0 +1,276 @@
+//! Toast notifications, stacked in a corner of the window and dismissed automatically
+//! after a timeout (or when they are clicked)
+//!
+//! Toasts are shown via timers, so that they can be raised from any callback and from the
+//! `Task::then` / `Task::or_else` timers of a task:
+//!
+//! ```no_run,ignore
+//! fn get_toasts(data: &mut MyApp) -> &mut ToastState { &mut data.toasts }
+//!
+//! let toasts = ToastArea::new(get_toasts);
+//! let task = Task::new(&app_state.data, save_file);
+//! let handle = task.get_handle();
+//! app_state.add_task(task
+//!     .then(toasts.show(Toast::new(ToastKind::Success, "File saved")))
+//!     .or_else(toasts.show_task_error(&handle, "Saving failed")));
+//!
+//! // in the layout() function:
+//! ToastArea::new(get_toasts).add_overlay(&self.toasts, &mut info);
+//! ```
+
+use std::{fmt, time::Duration};
+use {
+    dom::{Dom, On},
+    callbacks::{LayoutInfo, TimerClosureCallback, DefaultClosureCallback, Redraw, DontRedraw},
+    async::{Timer, TimerId, TerminateTimer, TaskHandle},
+    overlay::{OverlayAnchor, OverlayPlacement, OverlaySide, OverlayAlign},
+    app::AppStateNoData,
+};
+
+/// Timeout of toasts that don't set their own timeout
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Distance between the toasts and the edges of the window
+const WINDOW_MARGIN: f32 = 12.0;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ToastKind {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastKind {
+    fn get_class(&self) -> &'static str {
+        use self::ToastKind::*;
+        match self {
+            Info => "__azul-native-toast-info",
+            Success => "__azul-native-toast-success",
+            Warning => "__azul-native-toast-warning",
+            Error => "__azul-native-toast-error",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Toast {
+    pub kind: ToastKind,
+    pub message: String,
+    /// After how long the toast is dismissed, `None` if it stays until it is clicked
+    pub timeout: Option<Duration>,
+}
+
+impl Toast {
+
+    pub fn new<S: Into<String>>(kind: ToastKind, message: S) -> Self {
+        Self { kind, message: message.into(), timeout: Some(DEFAULT_TIMEOUT) }
+    }
+
+    #[inline]
+    pub fn with_timeout(self, timeout: Option<Duration>) -> Self {
+        Self { timeout, .. self }
+    }
+}
+
+/// Identifies a toast that is currently shown
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ToastId(usize);
+
+/// The toasts that are currently shown, oldest first
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToastState {
+    toasts: Vec<(ToastId, Toast)>,
+    next_id: usize,
+    /// If more toasts are shown at the same time, the oldest toasts are dismissed
+    pub max_toasts: usize,
+}
+
+impl Default for ToastState {
+    fn default() -> Self {
+        Self { toasts: Vec::new(), next_id: 0, max_toasts: 5 }
+    }
+}
+
+impl ToastState {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn with_max_toasts(self, max_toasts: usize) -> Self {
+        Self { max_toasts, .. self }
+    }
+
+    /// Shows the toast (without dismissing it after its timeout, see `ToastArea::show`)
+    pub fn push(&mut self, toast: Toast) -> ToastId {
+        let id = ToastId(self.next_id);
+        self.next_id += 1;
+        self.toasts.push((id, toast));
+        if self.toasts.len() > self.max_toasts {
+            let overflow = self.toasts.len() - self.max_toasts;
+            self.toasts.drain(..overflow);
+        }
+        id
+    }
+
+    /// Removes the toast, returns false if the toast was already dismissed
+    pub fn dismiss(&mut self, id: ToastId) -> bool {
+        let old_len = self.toasts.len();
+        self.toasts.retain(|(toast_id, _)| *toast_id != id);
+        self.toasts.len() != old_len
+    }
+
+    pub fn get_toasts(&self) -> &[(ToastId, Toast)] {
+        &self.toasts
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+}
+
+/// Corner of the window in which the toasts are stacked
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ToastCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// Shows the toasts of a `ToastState` as an overlay and creates the timers that show toasts.
+/// Since the toasts have to be modified from timers, the `ToastState` is accessed via a function.
+pub struct ToastArea<T> {
+    get_toasts: fn(&mut T) -> &mut ToastState,
+    corner: ToastCorner,
+}
+
+// #[derive(Debug, Copy, Clone)] for ToastArea<T>
+
+impl<T> fmt::Debug for ToastArea<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ToastArea {{ get_toasts: {:x}, corner: {:?} }}", self.get_toasts as usize, self.corner)
+    }
+}
+
+impl<T> Clone for ToastArea<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ToastArea<T> { }
+
+impl<T: 'static> ToastArea<T> {
+
+    pub fn new(get_toasts: fn(&mut T) -> &mut ToastState) -> Self {
+        Self { get_toasts, corner: ToastCorner::BottomRight }
+    }
+
+    #[inline]
+    pub fn with_corner(self, corner: ToastCorner) -> Self {
+        Self { corner, .. self }
+    }
+
+    /// Returns a timer that shows the toast once and dismisses it after its timeout. Add it via
+    /// `AppState::add_timer` or run it after a task is finished via `Task::then` / `Task::or_else`.
+    pub fn show(&self, toast: Toast) -> Timer<T> {
+        let get_toasts = self.get_toasts;
+        Timer::from_closure(TimerClosureCallback::new(&("__azul_show_toast", toast.clone()), move |data, app_state_no_data| {
+            show_toast(get_toasts, toast.clone(), data, app_state_no_data);
+            (Redraw, TerminateTimer::Terminate)
+        }))
+    }
+
+    /// Returns a timer for `Task::or_else` that shows an error toast with the
+    /// `title` and the error (the panic message) of the failed task
+    pub fn show_task_error(&self, task: &TaskHandle, title: &str) -> Timer<T> {
+        let get_toasts = self.get_toasts;
+        let task = task.clone();
+        let title = title.to_string();
+        Timer::from_closure(TimerClosureCallback::new(&("__azul_show_task_error", title.clone()), move |data, app_state_no_data| {
+            let message = match task.get_error() {
+                Some(error) => format!("{}: {}", title, error),
+                None => title.clone(),
+            };
+            show_toast(get_toasts, Toast::new(ToastKind::Error, message), data, app_state_no_data);
+            (Redraw, TerminateTimer::Terminate)
+        }))
+    }
+
+    /// Adds the toasts as an overlay in the corner of the window, clicking a toast dismisses it
+    pub fn add_overlay(&self, field: &ToastState, info: &mut LayoutInfo<T>) {
+
+        if field.is_empty() {
+            return;
+        }
+
+        let get_toasts = self.get_toasts;
+        let mut toast_area = Dom::div().with_class("__azul-native-toast-area");
+
+        // The newest toast is closest to the corner
+        let is_top = self.corner == ToastCorner::TopLeft || self.corner == ToastCorner::TopRight;
+        let mut toasts = field.toasts.iter().collect::<Vec<_>>();
+        if is_top {
+            toasts.reverse();
+        }
+
+        for (id, toast) in toasts {
+            let id = *id;
+            let callback_id = info.window.add_closure_callback(DefaultClosureCallback::new(&("__azul_toast", id), move |data, _, _| {
+                if get_toasts(data).dismiss(id) { Redraw } else { DontRedraw }
+            }));
+
+            let mut toast_dom = Dom::div()
+                .with_class("__azul-native-toast")
+                .with_class(toast.kind.get_class())
+                .with_child(Dom::label(toast.message.clone()).with_class("__azul-native-toast-message"))
+                .with_child(Dom::label("\u{00D7}").with_class("__azul-native-toast-close"));
+            toast_dom.add_default_callback_id(On::LeftMouseUp, callback_id);
+            toast_area.add_child(toast_dom);
+        }
+
+        let window_size = info.window.state.size.dimensions;
+        let (left, top) = (WINDOW_MARGIN, WINDOW_MARGIN);
+        let (right, bottom) = (window_size.width as f32 - WINDOW_MARGIN, window_size.height as f32 - WINDOW_MARGIN);
+
+        let (x, y, side, align) = match self.corner {
+            ToastCorner::TopLeft => (left, top, OverlaySide::Bottom, OverlayAlign::Start),
+            ToastCorner::TopRight => (right, top, OverlaySide::Bottom, OverlayAlign::End),
+            ToastCorner::BottomLeft => (left, bottom, OverlaySide::Top, OverlayAlign::Start),
+            ToastCorner::BottomRight => (right, bottom, OverlaySide::Top, OverlayAlign::End),
+        };
+
+        let placement = OverlayPlacement::new(side).with_align(align).with_flip(false);
+        info.add_overlay(OverlayAnchor::point(x, y), placement, toast_area);
+    }
+}
+
+/// Pushes the toast and starts a timer that dismisses it after its timeout
+fn show_toast<T: 'static>(get_toasts: fn(&mut T) -> &mut ToastState, toast: Toast, data: &mut T, app_state_no_data: &mut AppStateNoData<T>) {
+    let timeout = toast.timeout;
+    let id = get_toasts(data).push(toast);
+    if let Some(timeout) = timeout {
+        let dismiss_timer = Timer::from_closure(TimerClosureCallback::new(&("__azul_dismiss_toast", id), move |data, _| {
+            let redraw = if get_toasts(data).dismiss(id) { Redraw } else { DontRedraw };
+            (redraw, TerminateTimer::Terminate)
+        }));
+        app_state_no_data.add_timer(TimerId::new(), dismiss_timer.with_delay(timeout));
+    }
+}
+
+#[test]
+fn test_toast_state() {
+    let mut toasts = ToastState::new().with_max_toasts(2);
+    let first = toasts.push(Toast::new(ToastKind::Info, "first"));
+    let second = toasts.push(Toast::new(ToastKind::Info, "second"));
+    let third = toasts.push(Toast::new(ToastKind::Error, "third"));
+
+    // The oldest toast is dismissed if there are too many toasts
+    let ids = toasts.get_toasts().iter().map(|(id, _)| *id).collect::<Vec<_>>();
+    assert_eq!(ids, vec![second, third]);
+    assert!(!toasts.dismiss(first));
+    assert!(toasts.dismiss(second));
+    assert_eq!(toasts.get_toasts().len(), 1);
+}
@@ -0,0 +1,61 @@
+//! Small text popup, usually shown while the mouse is over a node
+
+use {
+    dom::{Dom, DomString},
+    callbacks::LayoutInfo,
+    overlay::{OverlayAnchor, OverlayPlacement, OverlaySide, OverlayAlign},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tooltip {
+    pub text: DomString,
+    pub placement: OverlayPlacement,
+}
+
+impl Tooltip {
+
+    /// Creates a tooltip that is centered below its anchor
+    #[inline]
+    pub fn new<S: Into<DomString>>(text: S) -> Self {
+        Self {
+            text: text.into(),
+            placement: OverlayPlacement::new(OverlaySide::Bottom)
+                .with_align(OverlayAlign::Center)
+                .with_offset(4.0),
+        }
+    }
+
+    #[inline]
+    pub fn with_placement(self, placement: OverlayPlacement) -> Self {
+        Self { placement, .. self }
+    }
+
+    #[inline]
+    pub fn dom<T>(self) -> Dom<T> {
+        Dom::div()
+            .with_class("__azul-native-tooltip")
+            .with_child(Dom::label(self.text))
+    }
+
+    /// Adds the tooltip as an overlay next to the `anchor`. Tooltips usually track
+    /// `On::MouseEnter` / `On::MouseLeave` of the anchor to decide whether to show up.
+    pub fn show<T>(self, anchor: OverlayAnchor, info: &mut LayoutInfo<T>) {
+        let placement = self.placement;
+        info.add_overlay(anchor, placement, self.dom());
+    }
+}
+
+#[test]
+fn test_tooltip_ui() {
+
+    struct Mock;
+
+    let expected = r#"
+        <div class="__azul-native-tooltip">
+            <p>Hello</p>
+        </div>
+    "#;
+    let tooltip: Dom<Mock> = Tooltip::new("Hello").dom();
+
+    tooltip.assert_eq(expected);
+}
@@ -0,0 +1,792 @@
+//! Tree view, shows a hierarchy of nodes that can be expanded and collapsed
+//!
+//! The tree doesn't store the nodes itself - it asks the application for the children of the
+//! root and of the expanded nodes, so the children of a node are only loaded once it is expanded.
+//! Like in the `ListView`, the rows are drawn in an IFrame and only the visible rows are rendered.
+//!
+//! ```no_run,ignore
+//! struct FileBrowser {
+//!     directories: Vec<Directory>,
+//!     tree: TreeViewState,
+//! }
+//!
+//! fn get_children(data: &FileBrowser, parent: Option<TreeNodeId>, is_expanded: bool) -> TreeChildren {
+//!     match parent {
+//!         None => TreeChildren::Loaded(vec![TreeNodeId(0)]),
+//!         Some(TreeNodeId(id)) if !is_expanded => TreeChildren::NotLoaded,
+//!         Some(TreeNodeId(id)) => data.directories[id].read_children(),
+//!     }
+//! }
+//!
+//! fn render_node(data: &FileBrowser, node: TreeNodeId) -> Dom<FileBrowser> {
+//!     Dom::label(data.directories[node.0].name.clone())
+//! }
+//!
+//! impl Layout for FileBrowser {
+//!     fn layout(&self, info: LayoutInfo<Self>) -> Dom<Self> {
+//!         TreeView::new(get_children, render_node).dom(&self.tree, self, info.window)
+//!     }
+//! }
+//! ```
+//!
+//! Since the rows are part of the IFrame, callbacks on the rows are not invoked - clicks and key
+//! presses are handled by the tree: clicking the arrow of a node (or pressing Left / Right)
+//! collapses and expands it, clicking a row or using the arrow keys updates the selection.
+
+use std::{fmt, cell::RefCell, collections::BTreeSet};
+use azul_css::{CssProperty, LayoutWidth, LayoutHeight, LayoutMargin, PixelValue};
+use {
+    app::AppStateNoData,
+    callbacks::{IFrameClosureCallback, HidpiAdjustedBounds, UpdateScreen, Redraw, DontRedraw},
+    callbacks::{CallbackInfo, StackCheckedPointer, DefaultCallback},
+    dom::{Dom, On, EventFilter, FocusEventFilter, TabIndex},
+    window::FakeWindow,
+    touch::Gesture,
+    prelude::VirtualKeyCode,
+    widgets::list_view::{RowHeight, SelectionMode, DEFAULT_ROW_HEIGHT},
+};
+
+/// Default width of one level of indentation
+pub const DEFAULT_INDENT_WIDTH: f32 = 16.0;
+/// Minimum height of the scrollbar thumb, so that it stays visible for very large trees
+const MIN_THUMB_HEIGHT: f32 = 20.0;
+
+/// ID of a node in the tree, chosen by the application (i.e. an index into the application data)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TreeNodeId(pub usize);
+
+/// Children of a node, returned by the `GetChildrenFn`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TreeChildren {
+    /// The node has no children, no expand arrow is drawn
+    None,
+    /// The node has children, but they are not loaded yet. While the node is
+    /// expanded, a "Loading..." row is drawn in place of the children.
+    NotLoaded,
+    Loaded(Vec<TreeNodeId>),
+}
+
+/// Returns the children of the `parent` node (`None` for the root nodes of the tree).
+/// The `bool` is `false` if the tree only needs to know whether to draw an expand arrow
+/// for the (collapsed) node, so expensive children don't have to be loaded yet.
+pub type GetChildrenFn<T> = fn(&T, Option<TreeNodeId>, bool) -> TreeChildren;
+
+/// Renders the content of the row of a node (without the indentation and the expand arrow)
+pub type RenderNodeFn<T> = fn(&T, TreeNodeId) -> Dom<T>;
+
+pub struct TreeView<T> {
+    get_children: GetChildrenFn<T>,
+    render_node: RenderNodeFn<T>,
+    /// How many rows are rendered above and below the visible rows
+    overscan: usize,
+}
+
+impl<T> Clone for TreeView<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TreeView<T> { }
+
+impl<T> fmt::Debug for TreeView<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TreeView {{ get_children: {:x}, render_node: {:x}, overscan: {} }}",
+            self.get_children as usize, self.render_node as usize, self.overscan)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum TreeRowKind {
+    /// Node without children
+    Leaf,
+    Collapsed,
+    Expanded,
+    /// Placeholder for the children of an expanded node that are not loaded yet
+    Loading,
+}
+
+/// Row of the flattened tree - the root nodes, plus the children of the expanded nodes
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+struct TreeRow {
+    /// Node of the row, for `TreeRowKind::Loading` the node whose children are loading
+    node: TreeNodeId,
+    parent: Option<TreeNodeId>,
+    depth: usize,
+    kind: TreeRowKind,
+}
+
+#[derive(Debug, Clone)]
+pub struct TreeViewState {
+    pub row_height: f32,
+    /// Width of one level of indentation (and of the expand arrows)
+    pub indent_width: f32,
+    pub selection_mode: SelectionMode,
+    /// How far the tree is scrolled, in pixels from the top of the first row
+    pub scroll_offset: f32,
+    /// Node that is moved by the arrow keys, usually the last node that was clicked
+    pub cursor: Option<TreeNodeId>,
+    expanded: BTreeSet<TreeNodeId>,
+    selection: BTreeSet<TreeNodeId>,
+    /// Node that range selections start from
+    anchor: Option<TreeNodeId>,
+    /// Node that should be scrolled into view on the next frame, see `scroll_to_node`
+    scroll_target: Option<TreeNodeId>,
+    /// Rows of the last frame, flattened in `TreeView::dom` while the application data is
+    /// available, so that the callbacks can navigate the tree without the application data
+    rows: RefCell<Vec<TreeRow>>,
+    /// Height of the tree in the last frame, updated whenever the tree receives an event
+    viewport_height: f32,
+}
+
+impl Default for TreeViewState {
+    fn default() -> Self {
+        Self {
+            row_height: DEFAULT_ROW_HEIGHT,
+            indent_width: DEFAULT_INDENT_WIDTH,
+            selection_mode: SelectionMode::Single,
+            scroll_offset: 0.0,
+            cursor: None,
+            expanded: BTreeSet::new(),
+            selection: BTreeSet::new(),
+            anchor: None,
+            scroll_target: None,
+            rows: RefCell::new(Vec::new()),
+            viewport_height: 0.0,
+        }
+    }
+}
+
+impl TreeViewState {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn with_selection_mode(self, selection_mode: SelectionMode) -> Self {
+        Self { selection_mode, .. self }
+    }
+
+    pub fn is_expanded(&self, node: TreeNodeId) -> bool {
+        self.expanded.contains(&node)
+    }
+
+    /// Returns the expanded nodes, i.e. to find out which children have to be loaded
+    pub fn get_expanded_nodes<'a>(&'a self) -> impl Iterator<Item = TreeNodeId> + 'a {
+        self.expanded.iter().cloned()
+    }
+
+    pub fn expand(&mut self, node: TreeNodeId) {
+        self.expanded.insert(node);
+    }
+
+    /// Collapses the `node` - if the cursor or selected nodes are hidden by collapsing
+    /// the `node`, they are deselected and the cursor is moved to the `node`
+    pub fn collapse(&mut self, node: TreeNodeId) {
+        if !self.expanded.remove(&node) {
+            return;
+        }
+
+        let hidden_nodes = {
+            let rows = self.rows.borrow();
+            match rows.iter().position(|row| row.node == node && row.kind != TreeRowKind::Loading) {
+                Some(index) => rows[(index + 1)..].iter()
+                    .take_while(|row| row.depth > rows[index].depth)
+                    .map(|row| row.node)
+                    .collect::<BTreeSet<_>>(),
+                None => BTreeSet::new(),
+            }
+        };
+
+        if self.cursor.map(|cursor| hidden_nodes.contains(&cursor)) == Some(true) {
+            self.cursor = Some(node);
+            self.anchor = Some(node);
+        }
+        self.selection.retain(|selected| !hidden_nodes.contains(selected));
+    }
+
+    pub fn toggle_expanded(&mut self, node: TreeNodeId) {
+        if self.is_expanded(node) {
+            self.collapse(node);
+        } else {
+            self.expand(node);
+        }
+    }
+
+    pub fn get_selection(&self) -> &BTreeSet<TreeNodeId> {
+        &self.selection
+    }
+
+    pub fn is_selected(&self, node: TreeNodeId) -> bool {
+        self.selection.contains(&node)
+    }
+
+    /// Selects only the `node` and moves the cursor to it
+    pub fn select(&mut self, node: TreeNodeId) {
+        self.selection.clear();
+        self.selection.insert(node);
+        self.cursor = Some(node);
+        self.anchor = Some(node);
+    }
+
+    /// Adds the `node` to or removes it from the selection, moves the cursor to the `node`
+    pub fn toggle(&mut self, node: TreeNodeId) {
+        if !self.selection.remove(&node) {
+            self.selection.insert(node);
+        }
+        self.cursor = Some(node);
+        self.anchor = Some(node);
+    }
+
+    /// Selects all nodes that were visible (not hidden in a collapsed node) in the last frame
+    pub fn select_all(&mut self) {
+        let rows = self.rows.borrow();
+        self.selection = rows.iter().filter(|row| row.kind != TreeRowKind::Loading).map(|row| row.node).collect();
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selection.clear();
+        self.anchor = None;
+    }
+
+    /// Scrolls the tree by the minimal amount so that the `node` is visible on the next frame
+    pub fn scroll_to_node(&mut self, node: TreeNodeId) {
+        self.scroll_target = Some(node);
+    }
+
+    /// Handles a click on the row of the `node`, depending on the `selection_mode` and
+    /// which modifier keys are held down
+    pub fn click_node(&mut self, node: TreeNodeId, shift_down: bool, ctrl_down: bool) {
+        match self.selection_mode {
+            SelectionMode::Single => self.select(node),
+            SelectionMode::Range => if shift_down {
+                self.extend_selection(node, false);
+            } else {
+                self.select(node);
+            },
+            SelectionMode::Multiple => if shift_down {
+                self.extend_selection(node, ctrl_down);
+            } else if ctrl_down {
+                self.toggle(node);
+            } else {
+                self.select(node);
+            },
+        }
+    }
+
+    /// Returns how far the tree is scrolled in a viewport of the `viewport_height`,
+    /// clamped to the height of the rows (and with the `scroll_target` revealed)
+    pub fn get_scroll_offset(&self, viewport_height: f32) -> f32 {
+        let mut scroll_offset = self.scroll_offset;
+
+        if let Some(index) = self.scroll_target.and_then(|node| self.get_row_index(node)) {
+            let row_top = index as f32 * self.row_height;
+            let row_bottom = row_top + self.row_height;
+            if row_top < scroll_offset {
+                scroll_offset = row_top;
+            } else if row_bottom > scroll_offset + viewport_height {
+                scroll_offset = row_bottom - viewport_height;
+            }
+        }
+
+        let max_scroll_offset = (self.rows.borrow().len() as f32 * self.row_height - viewport_height).max(0.0);
+        scroll_offset.min(max_scroll_offset).max(0.0)
+    }
+
+    /// Selects the rows from the anchor to the `node`, `keep_selection` adds them to the current selection
+    fn extend_selection(&mut self, node: TreeNodeId, keep_selection: bool) {
+        let anchor = self.anchor.unwrap_or(node);
+        let (anchor_index, node_index) = match (self.get_row_index(anchor), self.get_row_index(node)) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return self.select(node),
+        };
+
+        if !keep_selection {
+            self.selection.clear();
+        }
+
+        let rows = self.rows.borrow();
+        let range = &rows[anchor_index.min(node_index)..=anchor_index.max(node_index)];
+        self.selection.extend(range.iter().filter(|row| row.kind != TreeRowKind::Loading).map(|row| row.node));
+        self.anchor = Some(anchor);
+        self.cursor = Some(node);
+    }
+
+    /// Returns the index of the row of the `node` in the last frame,
+    /// `None` if the node was hidden in a collapsed node
+    fn get_row_index(&self, node: TreeNodeId) -> Option<usize> {
+        self.rows.borrow().iter().position(|row| row.node == node && row.kind != TreeRowKind::Loading)
+    }
+
+    fn get_row(&self, index: usize) -> Option<TreeRow> {
+        self.rows.borrow().get(index).cloned()
+    }
+
+    /// Returns the row closest to the `index` (searching downwards or upwards) that isn't a "Loading..." row
+    fn find_node_row(&self, index: usize, search_down: bool) -> Option<usize> {
+        let rows = self.rows.borrow();
+        if rows.is_empty() {
+            return None;
+        }
+        let index = index.min(rows.len() - 1);
+        if search_down {
+            (index..rows.len()).chain((0..index).rev()).find(|i| rows[*i].kind != TreeRowKind::Loading)
+        } else {
+            (0..=index).rev().chain((index + 1)..rows.len()).find(|i| rows[*i].kind != TreeRowKind::Loading)
+        }
+    }
+
+    /// Moves the cursor to the row at the `index` and scrolls it into view
+    fn move_cursor(&mut self, index: usize, search_down: bool, extend_selection: bool) {
+        let node = match self.find_node_row(index, search_down).and_then(|index| self.get_row(index)) {
+            Some(row) => row.node,
+            None => return,
+        };
+        if extend_selection && self.selection_mode != SelectionMode::Single {
+            self.extend_selection(node, false);
+        } else {
+            self.select(node);
+        }
+        self.scroll_to_node(node);
+    }
+
+    /// Right arrow key: expands the node under the cursor or moves the cursor to its first child
+    fn expand_or_select_child(&mut self) -> UpdateScreen {
+        let index = match self.cursor.and_then(|cursor| self.get_row_index(cursor)) {
+            Some(s) => s,
+            None => return DontRedraw,
+        };
+        let row = match self.get_row(index) {
+            Some(s) => s,
+            None => return DontRedraw,
+        };
+        match row.kind {
+            TreeRowKind::Collapsed => self.expand(row.node),
+            TreeRowKind::Expanded => match self.get_row(index + 1) {
+                Some(child) if child.parent == Some(row.node) && child.kind != TreeRowKind::Loading => {
+                    self.select(child.node);
+                    self.scroll_to_node(child.node);
+                },
+                _ => return DontRedraw,
+            },
+            TreeRowKind::Leaf | TreeRowKind::Loading => return DontRedraw,
+        }
+        Redraw
+    }
+
+    /// Left arrow key: collapses the node under the cursor or moves the cursor to its parent
+    fn collapse_or_select_parent(&mut self) -> UpdateScreen {
+        let row = match self.cursor.and_then(|cursor| self.get_row_index(cursor)).and_then(|index| self.get_row(index)) {
+            Some(s) => s,
+            None => return DontRedraw,
+        };
+        match (row.kind, row.parent) {
+            (TreeRowKind::Expanded, _) => self.collapse(row.node),
+            (_, Some(parent)) => {
+                self.select(parent);
+                self.scroll_to_node(parent);
+            },
+            (_, None) => return DontRedraw,
+        }
+        Redraw
+    }
+
+    /// Remembers the height of the tree from the last frame, for paging with PageUp / PageDown
+    fn update_viewport_height<T>(&mut self, event: &CallbackInfo<T>) -> f32 {
+        if let Some(rect) = event.get_node_rect(event.hit_dom_node) {
+            self.viewport_height = rect.size.height;
+        }
+        self.viewport_height
+    }
+
+    /// Resolves the `scroll_target` and scrolls by `delta` pixels
+    fn scroll_by(&mut self, delta: f32) -> UpdateScreen {
+        let old_scroll_offset = self.get_scroll_offset(self.viewport_height);
+        self.scroll_target = None;
+        self.scroll_offset = old_scroll_offset + delta;
+        self.scroll_offset = self.get_scroll_offset(self.viewport_height);
+        if self.scroll_offset != old_scroll_offset { Redraw } else { DontRedraw }
+    }
+
+    /// Renders the visible rows, invoked by the IFrame of the `TreeView`
+    pub fn render<T>(&self, data: &T, render_node: RenderNodeFn<T>, overscan: usize, dimensions: HidpiAdjustedBounds) -> Dom<T> {
+
+        let rows = self.rows.borrow();
+        let viewport_height = dimensions.get_logical_size().height as f32;
+        let scroll_offset = self.get_scroll_offset(viewport_height);
+        let visible_rows = RowHeight::Fixed(self.row_height).get_visible_rows(f64::from(scroll_offset), viewport_height, rows.len());
+        let first_row = visible_rows.start.saturating_sub(overscan);
+        let last_row = visible_rows.end.saturating_add(overscan).min(rows.len());
+
+        // div.__azul-native-tree-content
+        //     |-> div.__azul-native-tree-rows (moved up by the part of the first row that is scrolled out)
+        //     |    '-> div.__azul-native-tree-row (.__azul-native-tree-row-selected)
+        //     |         |-> div.__azul-native-tree-guide (one per level of indentation)
+        //     |         |-> p.__azul-native-tree-arrow (.__azul-native-tree-arrow-expanded)
+        //     |         '-> div.__azul-native-tree-node
+        //     '-> div.__azul-native-tree-scrollbar
+        //          '-> div.__azul-native-tree-scrollbar-thumb
+
+        let indent_width = CssProperty::Width(LayoutWidth::px(self.indent_width));
+        let row_height = CssProperty::Height(LayoutHeight::px(self.row_height));
+
+        let mut rows_dom = Dom::div()
+            .with_class("__azul-native-tree-rows")
+            .with_css_override("__azul_tree_rows_offset", CssProperty::Margin(LayoutMargin {
+                top: Some(PixelValue::px(first_row as f32 * self.row_height - scroll_offset)),
+                .. Default::default()
+            }));
+
+        for row in &rows[first_row..last_row] {
+            let mut row_dom = Dom::div()
+                .with_class("__azul-native-tree-row")
+                .with_css_override("__azul_tree_row_height", row_height.clone());
+
+            for _ in 0..row.depth {
+                row_dom.add_child(
+                    Dom::div()
+                    .with_class("__azul-native-tree-guide")
+                    .with_css_override("__azul_tree_indent_width", indent_width.clone())
+                );
+            }
+
+            let arrow = match row.kind {
+                TreeRowKind::Loading => {
+                    row_dom.add_child(Dom::label("Loading...").with_class("__azul-native-tree-loading"));
+                    rows_dom.add_child(row_dom);
+                    continue;
+                },
+                TreeRowKind::Leaf => Dom::div(),
+                TreeRowKind::Collapsed => Dom::label("\u{25b8}"),
+                TreeRowKind::Expanded => Dom::label("\u{25be}").with_class("__azul-native-tree-arrow-expanded"),
+            };
+
+            row_dom.add_child(
+                arrow
+                .with_class("__azul-native-tree-arrow")
+                .with_css_override("__azul_tree_indent_width", indent_width.clone())
+            );
+            row_dom.add_child(Dom::div().with_class("__azul-native-tree-node").with_child(render_node(data, row.node)));
+
+            if self.cursor == Some(row.node) {
+                row_dom.add_class("__azul-native-tree-row-cursor");
+            }
+            if self.is_selected(row.node) {
+                row_dom.add_class("__azul-native-tree-row-selected");
+            }
+            rows_dom.add_child(row_dom);
+        }
+
+        let mut content = Dom::div()
+            .with_class("__azul-native-tree-content")
+            .with_child(rows_dom);
+
+        let total_height = rows.len() as f32 * self.row_height;
+        if total_height > viewport_height {
+            let thumb_height = (viewport_height * viewport_height / total_height).max(MIN_THUMB_HEIGHT).min(viewport_height);
+            let thumb_offset = scroll_offset / (total_height - viewport_height) * (viewport_height - thumb_height);
+            content.add_child(
+                Dom::div()
+                .with_class("__azul-native-tree-scrollbar")
+                .with_child(
+                    Dom::div()
+                    .with_class("__azul-native-tree-scrollbar-thumb")
+                    .with_css_override("__azul_tree_thumb_height", CssProperty::Height(LayoutHeight::px(thumb_height)))
+                    .with_css_override("__azul_tree_thumb_offset", CssProperty::Margin(LayoutMargin {
+                        top: Some(PixelValue::px(thumb_offset)),
+                        .. Default::default()
+                    }))
+                )
+            );
+        }
+
+        content
+    }
+
+    /// Scrolls the tree with the mouse wheel
+    pub fn on_scroll<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        self.update_viewport_height(event);
+        let scroll_y = app_state_no_data.windows[event.window_id].get_mouse_state().scroll_y as f32;
+        self.scroll_by(scroll_y)
+    }
+
+    /// Scrolls the tree when a finger is moved over it
+    pub fn on_pan<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        self.update_viewport_height(event);
+        let pan_y: f64 = app_state_no_data.windows[event.window_id].get_touch_state().gestures.iter().map(|gesture| match gesture {
+            Gesture::Pan { delta, .. } => delta.1,
+            _ => 0.0,
+        }).sum();
+        self.scroll_by(-pan_y as f32)
+    }
+
+    /// Expands / collapses the node if its arrow was clicked, otherwise selects the clicked node
+    pub fn on_mouse_down<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        self.update_viewport_height(event);
+
+        let tree_origin = match event.get_node_rect(event.hit_dom_node) {
+            Some(rect) => rect.origin,
+            None => return DontRedraw,
+        };
+
+        let (cursor_x, cursor_y) = match event.cursor_in_viewport {
+            Some(s) => s,
+            None => return DontRedraw,
+        };
+
+        let scroll_offset = self.get_scroll_offset(self.viewport_height);
+        let row = match self.get_row_at(cursor_y - tree_origin.y + scroll_offset).and_then(|index| self.get_row(index)) {
+            Some(s) => s,
+            None => return DontRedraw,
+        };
+
+        let arrow_left = row.depth as f32 * self.indent_width;
+        let x = cursor_x - tree_origin.x;
+
+        match row.kind {
+            TreeRowKind::Loading => DontRedraw,
+            TreeRowKind::Collapsed | TreeRowKind::Expanded if x >= arrow_left && x < arrow_left + self.indent_width => {
+                self.toggle_expanded(row.node);
+                Redraw
+            },
+            _ => {
+                let keyboard_state = app_state_no_data.windows[event.window_id].get_keyboard_state();
+                let (shift_down, ctrl_down) = (keyboard_state.shift_down, keyboard_state.ctrl_down);
+                self.click_node(row.node, shift_down, ctrl_down);
+                self.scroll_to_node(row.node);
+                Redraw
+            },
+        }
+    }
+
+    /// Moves the cursor with the arrow keys, PageUp / PageDown and Home / End, Shift extends the
+    /// selection. Left / Right collapse and expand the node under the cursor (or move the cursor to
+    /// its parent / first child). Ctrl + A selects all nodes and Ctrl + Space toggles the node under the cursor.
+    pub fn on_virtual_key_down<T>(&mut self, app_state_no_data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+
+        let row_count = self.rows.borrow().len();
+        if row_count == 0 {
+            return DontRedraw;
+        }
+
+        let viewport_height = self.update_viewport_height(event);
+        let keyboard_state = app_state_no_data.windows[event.window_id].get_keyboard_state();
+        let shift_down = keyboard_state.shift_down;
+        let ctrl_down = keyboard_state.ctrl_down;
+        let is_multiple = self.selection_mode == SelectionMode::Multiple;
+        let cursor_index = self.cursor.and_then(|cursor| self.get_row_index(cursor)).unwrap_or(0);
+        let page_size = if self.row_height > 0.0 { ((viewport_height / self.row_height) as usize).max(1) } else { 1 };
+
+        match keyboard_state.latest_virtual_keycode {
+            Some(VirtualKeyCode::Up) => self.move_cursor(cursor_index.saturating_sub(1), false, shift_down),
+            Some(VirtualKeyCode::Down) => self.move_cursor(cursor_index.saturating_add(1), true, shift_down),
+            Some(VirtualKeyCode::Home) => self.move_cursor(0, true, shift_down),
+            Some(VirtualKeyCode::End) => self.move_cursor(row_count - 1, false, shift_down),
+            Some(VirtualKeyCode::PageUp) => self.move_cursor(cursor_index.saturating_sub(page_size), false, shift_down),
+            Some(VirtualKeyCode::PageDown) => self.move_cursor(cursor_index.saturating_add(page_size), true, shift_down),
+            Some(VirtualKeyCode::Right) => return self.expand_or_select_child(),
+            Some(VirtualKeyCode::Left) => return self.collapse_or_select_parent(),
+            Some(VirtualKeyCode::A) if ctrl_down && is_multiple => self.select_all(),
+            Some(VirtualKeyCode::Space) => match self.cursor {
+                Some(cursor) if ctrl_down && is_multiple => self.toggle(cursor),
+                Some(cursor) => self.select(cursor),
+                None => return DontRedraw,
+            },
+            _ => return DontRedraw,
+        }
+
+        Redraw
+    }
+
+    /// Returns the index of the row at the vertical offset `y` (relative to the top of the first row)
+    fn get_row_at(&self, y: f32) -> Option<usize> {
+        RowHeight::Fixed(self.row_height).get_row_at(f64::from(y), self.rows.borrow().len())
+    }
+}
+
+/// Flattens the tree into rows: the root nodes, plus the children of the expanded nodes
+fn flatten_tree<T>(data: &T, get_children: GetChildrenFn<T>, expanded: &BTreeSet<TreeNodeId>) -> Vec<TreeRow> {
+
+    let mut rows = Vec::new();
+
+    let roots = match get_children(data, None, true) {
+        TreeChildren::Loaded(roots) => roots,
+        TreeChildren::None | TreeChildren::NotLoaded => return rows,
+    };
+
+    // Depth-first, with an explicit stack so that very deep trees can't overflow the stack
+    let mut stack = roots.into_iter().rev().map(|node| (node, None, 0)).collect::<Vec<_>>();
+
+    while let Some((node, parent, depth)) = stack.pop() {
+
+        let is_expanded = expanded.contains(&node);
+        let children = get_children(data, Some(node), is_expanded);
+
+        let kind = match children {
+            TreeChildren::None => TreeRowKind::Leaf,
+            TreeChildren::Loaded(ref children) if children.is_empty() => TreeRowKind::Leaf,
+            _ if is_expanded => TreeRowKind::Expanded,
+            _ => TreeRowKind::Collapsed,
+        };
+
+        rows.push(TreeRow { node, parent, depth, kind });
+
+        if kind == TreeRowKind::Expanded {
+            match children {
+                TreeChildren::Loaded(children) => {
+                    stack.extend(children.into_iter().rev().map(|child| (child, Some(node), depth + 1)));
+                },
+                TreeChildren::None | TreeChildren::NotLoaded => {
+                    rows.push(TreeRow { node, parent: Some(node), depth: depth + 1, kind: TreeRowKind::Loading });
+                },
+            }
+        }
+    }
+
+    rows
+}
+
+impl<T> TreeView<T> {
+
+    pub fn new(get_children: GetChildrenFn<T>, render_node: RenderNodeFn<T>) -> Self {
+        Self { get_children, render_node, overscan: 5 }
+    }
+
+    /// Sets how many rows are rendered above and below the visible rows (default: 5)
+    #[inline]
+    pub fn with_overscan(self, overscan: usize) -> Self {
+        Self { overscan, .. self }
+    }
+
+    /// The `state` has to be stored inside of the application data `t`, so that the tree can
+    /// update the expanded nodes, the selection and the scroll position. If a window has more
+    /// than one `TreeView`, each tree needs its own `TreeViewState`.
+    pub fn dom(&self, state: &TreeViewState, t: &T, window: &mut FakeWindow<T>) -> Dom<T> where T: 'static {
+
+        let ptr = match StackCheckedPointer::new(t, state) {
+            Some(ptr) => ptr,
+            None => return Dom::label(
+                "Cannot create tree view from heap-allocated TreeViewState, \
+                 please store the TreeViewState in the application data"
+            ),
+        };
+
+        *state.rows.borrow_mut() = flatten_tree(t, self.get_children, &state.expanded);
+
+        let render_node = self.render_node;
+        let overscan = self.overscan;
+
+        // The state is only read while the application data is locked for the IFrame callback
+        let mut dom = Dom::iframe_closure(IFrameClosureCallback::new(&("__azul_tree_view", &ptr), move |data, _info, dimensions| {
+            let state = unsafe { ptr.get::<TreeViewState>() };
+            state.render(data, render_node, overscan, dimensions)
+        }))
+        .with_class("__azul-native-tree")
+        .with_tab_index(TabIndex::Auto);
+
+        let on_scroll = window.add_callback(ptr, DefaultCallback(Self::tree_view_on_scroll));
+        let on_pan = window.add_callback(ptr, DefaultCallback(Self::tree_view_on_pan));
+        let on_mouse_down = window.add_callback(ptr, DefaultCallback(Self::tree_view_on_mouse_down));
+        let on_virtual_key_down = window.add_callback(ptr, DefaultCallback(Self::tree_view_on_virtual_key_down));
+
+        dom.add_default_callback_id(On::Scroll, on_scroll);
+        dom.add_default_callback_id(On::Pan, on_pan);
+        dom.add_default_callback_id(On::MouseDown, on_mouse_down);
+        dom.add_default_callback_id(EventFilter::Focus(FocusEventFilter::VirtualKeyDown), on_virtual_key_down);
+        dom
+    }
+
+    fn tree_view_on_scroll(ptr: &StackCheckedPointer<T>, data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { ptr.invoke_mut(TreeViewState::on_scroll, data, event) }
+    }
+
+    fn tree_view_on_pan(ptr: &StackCheckedPointer<T>, data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { ptr.invoke_mut(TreeViewState::on_pan, data, event) }
+    }
+
+    fn tree_view_on_mouse_down(ptr: &StackCheckedPointer<T>, data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { ptr.invoke_mut(TreeViewState::on_mouse_down, data, event) }
+    }
+
+    fn tree_view_on_virtual_key_down(ptr: &StackCheckedPointer<T>, data: &mut AppStateNoData<T>, event: &mut CallbackInfo<T>) -> UpdateScreen {
+        unsafe { ptr.invoke_mut(TreeViewState::on_virtual_key_down, data, event) }
+    }
+}
+
+/// Tree for the tests: 0 has the children 1 and 2, 1 has the child 3 and the children of 4 are not loaded
+#[cfg(test)]
+fn get_test_children(_: &(), parent: Option<TreeNodeId>, _: bool) -> TreeChildren {
+    match parent.map(|node| node.0) {
+        None => TreeChildren::Loaded(vec![TreeNodeId(0), TreeNodeId(4)]),
+        Some(0) => TreeChildren::Loaded(vec![TreeNodeId(1), TreeNodeId(2)]),
+        Some(1) => TreeChildren::Loaded(vec![TreeNodeId(3)]),
+        Some(4) => TreeChildren::NotLoaded,
+        _ => TreeChildren::None,
+    }
+}
+
+#[cfg(test)]
+fn flatten_test_tree(state: &TreeViewState) -> Vec<(usize, usize, TreeRowKind)> {
+    *state.rows.borrow_mut() = flatten_tree(&(), get_test_children, &state.expanded);
+    state.rows.borrow().iter().map(|row| (row.node.0, row.depth, row.kind)).collect()
+}
+
+#[test]
+fn test_tree_view_flatten() {
+    let mut state = TreeViewState::new();
+    assert_eq!(flatten_test_tree(&state), vec![(0, 0, TreeRowKind::Collapsed), (4, 0, TreeRowKind::Collapsed)]);
+
+    state.expand(TreeNodeId(0));
+    state.expand(TreeNodeId(1));
+    state.expand(TreeNodeId(4));
+    assert_eq!(flatten_test_tree(&state), vec![
+        (0, 0, TreeRowKind::Expanded),
+        (1, 1, TreeRowKind::Expanded),
+        (3, 2, TreeRowKind::Leaf),
+        (2, 1, TreeRowKind::Leaf),
+        (4, 0, TreeRowKind::Expanded),
+        (4, 1, TreeRowKind::Loading),
+    ]);
+
+    // Collapsing a node moves the cursor out of the hidden children
+    state.select(TreeNodeId(3));
+    state.collapse(TreeNodeId(0));
+    assert_eq!(state.cursor, Some(TreeNodeId(0)));
+    assert!(state.get_selection().is_empty());
+    assert!(state.is_expanded(TreeNodeId(1)));
+}
+
+#[test]
+fn test_tree_view_navigation() {
+    let mut state = TreeViewState::new().with_selection_mode(SelectionMode::Multiple);
+    state.expand(TreeNodeId(0));
+    flatten_test_tree(&state);
+
+    // Right expands a collapsed node, then moves to its first child
+    state.select(TreeNodeId(0));
+    state.expand_or_select_child();
+    assert_eq!(state.cursor, Some(TreeNodeId(1)));
+    state.expand_or_select_child();
+    assert!(state.is_expanded(TreeNodeId(1)));
+    flatten_test_tree(&state);
+    state.expand_or_select_child();
+    assert_eq!(state.cursor, Some(TreeNodeId(3)));
+
+    // Left moves to the parent, then collapses it
+    state.collapse_or_select_parent();
+    assert_eq!(state.cursor, Some(TreeNodeId(1)));
+    state.collapse_or_select_parent();
+    assert!(!state.is_expanded(TreeNodeId(1)));
+    flatten_test_tree(&state);
+
+    // Shift + click selects all rows in between, the cursor skips the "Loading..." row
+    state.expand(TreeNodeId(4));
+    flatten_test_tree(&state);
+    state.click_node(TreeNodeId(1), false, false);
+    state.click_node(TreeNodeId(4), true, false);
+    assert_eq!(state.get_selection().iter().map(|node| node.0).collect::<Vec<_>>(), vec![1, 2, 4]);
+    state.move_cursor(4, true, false);
+    assert_eq!(state.cursor, Some(TreeNodeId(4)));
+}
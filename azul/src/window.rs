@@ -7,7 +7,7 @@ use std::{
 };
 use webrender::{
     api::{
-        PipelineId, Epoch, DocumentId,
+        PipelineId, Epoch, DocumentId, LayoutRect, LayoutSize,
         RenderApi, ExternalScrollId, RenderNotifier, DeviceIntSize,
     },
     Renderer, RendererOptions, RendererKind, ShaderPrecacheFlags, WrShaders,
@@ -28,15 +28,23 @@ use gleam::gl::{self, Gl};
 use azul_css::{Css, ColorU};
 #[cfg(debug_assertions)]
 use azul_css::HotReloadHandler;
+#[cfg(feature = "native_style")]
+use azul_native_style::NativeTheme;
 use {
     FastHashMap,
     compositor::Compositor,
     app::FrameEventInfo,
     callbacks::{
-        Callback, DefaultCallbackSystem, StackCheckedPointer,
-        DefaultCallback, DefaultCallbackId, Texture,
+        DefaultCallbackSystem, StackCheckedPointer,
+        LayoutCallback, CloseRequestedCallback, DefaultClosureCallback,
+        DefaultCallback, DefaultCallbackId, Texture, Callback,
     },
     display_list::ScrolledNodes,
+    ui_solver::LayoutResult,
+    overlay::{Overlay, OverlayAnchor},
+    menu::{Menu, MenuBar, OpenMenu, OpenMenuSource},
+    shortcuts::ShortcutRegistry,
+    touch::TouchState,
 };
 pub use webrender::api::HitTestItem;
 pub use window_state::*;
@@ -60,6 +68,21 @@ pub struct FakeWindow<T> {
     /// but not change any window properties from underneath - this would
     /// lead to mismatch between the
     pub(crate) read_only_window: Rc<Display>,
+    /// Layout of the last frame that was rendered, used for querying the
+    /// geometry of nodes from callbacks and from the `layout()` function
+    pub(crate) last_layout_result: Option<Rc<LayoutResult>>,
+    /// Overlays added in the `layout()` function, inserted into the DOM after the layout
+    pub(crate) overlays: Vec<Overlay<T>>,
+    /// Menu bar of the window, initialized from `WindowCreateOptions::menu_bar`
+    pub(crate) menu_bar: Option<MenuBar<T>>,
+    /// Menu (of the menu bar or a context menu) that is currently open
+    pub(crate) open_menu: Option<OpenMenu<T>>,
+    /// Keyboard shortcuts of the window, initialized from `WindowCreateOptions::shortcuts`
+    pub(crate) shortcuts: ShortcutRegistry<T>,
+    /// Theme of the native style, `None` if the window wasn't created
+    /// with `App::create_native_window`
+    #[cfg(feature = "native_style")]
+    pub(crate) native_theme: Option<NativeThemeSetting>,
 }
 
 impl<T> FakeWindow<T> {
@@ -82,6 +105,62 @@ impl<T> FakeWindow<T> {
         self.state.size.hidpi_factor
     }
 
+    /// Switches the native style of the window to a different theme (or back to following
+    /// the OS theme). The style is rebuilt on the next frame, styles that were passed to
+    /// `App::create_native_window` stay applied on top of the native style.
+    ///
+    /// Has no effect if the window wasn't created with `App::create_native_window`.
+    #[cfg(feature = "native_style")]
+    pub fn set_native_theme(&mut self, theme: NativeThemeSetting) {
+        if let Some(current) = self.native_theme.as_mut() {
+            *current = theme;
+        }
+    }
+
+    /// Returns the theme setting of the native style, `None` if the window
+    /// wasn't created with `App::create_native_window`
+    #[cfg(feature = "native_style")]
+    pub fn get_native_theme(&self) -> Option<NativeThemeSetting> {
+        self.native_theme
+    }
+
+    /// Replaces the menu bar of the window, i.e. to update the checked / enabled state of
+    /// the menu items. If a menu of the old menu bar is open, it stays open.
+    pub fn set_menu_bar(&mut self, menu_bar: Option<MenuBar<T>>) {
+        self.menu_bar = menu_bar;
+    }
+
+    pub fn get_menu_bar(&self) -> Option<&MenuBar<T>> {
+        self.menu_bar.as_ref()
+    }
+
+    /// Opens the `menu` as a context menu at the given position (usually the cursor
+    /// position), replacing any other open menu. The menu is closed when an item is
+    /// clicked, on clicks outside of the menu or when Escape is pressed.
+    pub fn open_context_menu(&mut self, menu: Menu<T>, position: (f32, f32)) {
+        let anchor = OverlayAnchor::point(position.0, position.1);
+        self.open_menu = Some(OpenMenu::new(OpenMenuSource::ContextMenu(menu, anchor)));
+    }
+
+    /// Closes the open menu of the menu bar or the open context menu
+    pub fn close_menu(&mut self) {
+        self.open_menu = None;
+    }
+
+    pub fn is_menu_open(&self) -> bool {
+        self.open_menu.is_some()
+    }
+
+    /// Returns the keyboard shortcuts of the window, i.e. to list them in a help screen
+    pub fn get_shortcuts(&self) -> &ShortcutRegistry<T> {
+        &self.shortcuts
+    }
+
+    /// Returns the keyboard shortcuts of the window in order to add or rebind shortcuts
+    pub fn get_shortcuts_mut(&mut self) -> &mut ShortcutRegistry<T> {
+        &mut self.shortcuts
+    }
+
     pub(crate) fn set_keyboard_state(&mut self, kb: &KeyboardState) {
         self.state.internal.keyboard_state = kb.clone();
     }
@@ -90,6 +169,10 @@ impl<T> FakeWindow<T> {
         self.state.internal.mouse_state = *mouse;
     }
 
+    pub(crate) fn set_touch_state(&mut self, touch: &TouchState) {
+        self.state.internal.touch_state = touch.clone();
+    }
+
     /// Returns the current keyboard keyboard state. We don't want the library
     /// user to be able to modify this state, only to read it.
     pub fn get_keyboard_state<'a>(&'a self) -> &'a KeyboardState {
@@ -102,6 +185,12 @@ impl<T> FakeWindow<T> {
         self.state.get_mouse_state()
     }
 
+    /// Returns the fingers that currently touch the window and the
+    /// gestures that were recognized from the current touch event
+    pub fn get_touch_state(&self) -> &TouchState {
+        self.state.get_touch_state()
+    }
+
     /// Adds a default callback to the window. The default callbacks are
     /// cleared after every frame, so two-way data binding widgets have to call this
     /// on every frame they want to insert a default callback.
@@ -120,6 +209,21 @@ impl<T> FakeWindow<T> {
         self.default_callbacks.add_callback(default_callback_id, callback_ptr, callback_fn);
         default_callback_id
     }
+
+    /// Same as `add_callback`, but for a closure: the closure can capture the state it
+    /// needs (i.e. the index of the row it belongs to) and gets the whole application
+    /// data instead of a `StackCheckedPointer`, so no `unsafe` code is necessary.
+    ///
+    /// Returns an ID by which the callback can be uniquely identified (used for hit-testing)
+    #[must_use]
+    pub fn add_closure_callback(&mut self, callback: DefaultClosureCallback<T>) -> DefaultCallbackId {
+
+        use callbacks::get_new_unique_default_callback_id;
+
+        let default_callback_id = get_new_unique_default_callback_id();
+        self.default_callbacks.add_closure_callback(default_callback_id, callback);
+        default_callback_id
+    }
 }
 
 /// Read-only window which can be used to create / draw
@@ -186,6 +290,35 @@ impl<T> fmt::Debug for FakeWindow<T> {
     }
 }
 
+/// Which theme the native style of a window should use, see `App::create_native_window`
+#[cfg(feature = "native_style")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum NativeThemeSetting {
+    /// Follow the theme of the OS, the style of the window is
+    /// switched automatically when the OS theme changes
+    System,
+    /// Always use the given theme, regardless of the OS theme
+    Fixed(NativeTheme),
+}
+
+#[cfg(feature = "native_style")]
+impl Default for NativeThemeSetting {
+    fn default() -> Self {
+        NativeThemeSetting::System
+    }
+}
+
+/// State necessary to rebuild the style of a window when the native theme changes
+#[cfg(feature = "native_style")]
+pub(crate) struct NativeStyle {
+    /// The theme setting the window was created with
+    pub(crate) setting: NativeThemeSetting,
+    /// The theme that `Window::css` was last built with
+    pub(crate) current_theme: NativeTheme,
+    /// User styles, appended to the native style on every rebuild (see `css::override_native`)
+    pub(crate) user_overrides: Css,
+}
+
 /// Options on how to initially create the window
 #[derive(Debug, Clone)]
 pub struct WindowCreateOptions<T> {
@@ -196,22 +329,39 @@ pub struct WindowCreateOptions<T> {
     /// Renderer type: Hardware-with-software-fallback, pure software or pure hardware renderer?
     pub renderer_type: RendererType,
     /// Win32 menu callbacks
+    #[deprecated(note = "never invoked, use `menu_bar` instead")]
     pub menu_callbacks: FastHashMap<u16, Callback<T>>,
+    /// Menu bar of the window, rendered via `LayoutInfo::menu_bar_dom`. The accelerators
+    /// of the menu items are active even if the menu bar isn't part of the DOM.
+    pub menu_bar: Option<MenuBar<T>>,
+    /// Keyboard shortcuts of the window, see the `shortcuts` module
+    pub shortcuts: ShortcutRegistry<T>,
     /// Sets the window icon (Windows and Linux only). Usually 16x16 px or 32x32px
     pub window_icon: Option<Icon>,
     /// Windows only: Sets the 256x256 taskbar icon during startup
     pub taskbar_icon: Option<Icon>,
+    /// Layout function for this window - if set to `None` (the default), the
+    /// window uses the `Layout::layout()` function of the application data.
+    pub layout_callback: Option<LayoutCallback<T>>,
+    /// Invoked when the user tries to close this window, can veto closing the window.
+    /// If set to `None` (the default), the window closes immediately.
+    pub on_close_requested: Option<CloseRequestedCallback<T>>,
 }
 
 impl<T> Default for WindowCreateOptions<T> {
+    #[allow(deprecated)]
     fn default() -> Self {
         Self {
             state: WindowState::default(),
             monitor: WindowMonitorTarget::default(),
             renderer_type: RendererType::default(),
             menu_callbacks: FastHashMap::default(),
+            menu_bar: None,
+            shortcuts: ShortcutRegistry::new(),
             window_icon: None,
             taskbar_icon: None,
+            layout_callback: None,
+            on_close_requested: None,
         }
     }
 }
@@ -366,6 +516,10 @@ pub struct Window<T> {
     /// enabled
     #[cfg(debug_assertions)]
     pub(crate) css_loader: Option<Box<dyn HotReloadHandler>>,
+    /// If the window was created with `App::create_native_window`, stores the
+    /// user styles, so that `css` can be rebuilt when the native theme changes
+    #[cfg(feature = "native_style")]
+    pub(crate) native_style: Option<NativeStyle>,
     /// Purely a marker, so that `app.run()` can infer the type of `T: Layout`
     /// of the `WindowCreateOptions`, so that we can write:
     ///
@@ -411,6 +565,38 @@ impl ScrollStates {
     pub(crate) fn remove_unused_scroll_states(&mut self) {
         self.0.retain(|_, state| state.used_this_frame);
     }
+
+    /// Scrolls the node by the minimal amount necessary so that the `child` rectangle
+    /// (relative to the top left of the unscrolled node) is visible in the `viewport`.
+    ///
+    /// Returns whether the scroll amount of the node changed.
+    pub(crate) fn scroll_rect_into_view(&mut self, scroll_id: &ExternalScrollId, viewport: LayoutSize, child: LayoutRect) -> bool {
+        let entry = match self.0.get_mut(scroll_id) {
+            Some(s) => s,
+            None => return false,
+        };
+
+        let (old_x, old_y) = (entry.scroll_amount_x, entry.scroll_amount_y);
+        let new_x = get_scroll_offset_to_reveal(old_x, viewport.width, child.origin.x, child.size.width);
+        let new_y = get_scroll_offset_to_reveal(old_y, viewport.height, child.origin.y, child.size.height);
+        entry.add(new_x - old_x, new_y - old_y);
+
+        entry.scroll_amount_x != old_x || entry.scroll_amount_y != old_y
+    }
+}
+
+/// Returns the scroll offset at which the `[start, start + length]` range is visible in
+/// a viewport of the size `viewport_length`. If the range is larger than the viewport,
+/// the start of the range is aligned with the start of the viewport.
+fn get_scroll_offset_to_reveal(current_offset: f32, viewport_length: f32, start: f32, length: f32) -> f32 {
+    let end = start + length;
+    if start < current_offset || length > viewport_length {
+        start
+    } else if end > current_offset + viewport_length {
+        end - viewport_length
+    } else {
+        current_offset
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -597,6 +783,8 @@ impl<'a, T> Window<T> {
             css,
             #[cfg(debug_assertions)]
             css_loader: None,
+            #[cfg(feature = "native_style")]
+            native_style: None,
             scroll_states: ScrollStates::new(),
             internal: WindowInternal { epoch, pipeline_id, document_id, last_scrolled_nodes },
             marker: PhantomData,
@@ -1036,4 +1224,16 @@ fn linux_get_hidpi_factor(monitor: &MonitorId, events_loop: &EventsLoop) -> f64
 
     let options = [winit_hidpi_factor, qt_font_dpi, gsettings_dpi_factor, xft_dpi];
     options.into_iter().filter_map(|x| *x).next().unwrap_or(winit_dpi)
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_scroll_offset_to_reveal() {
+    // Already visible, don't scroll
+    assert_eq!(get_scroll_offset_to_reveal(0.0, 100.0, 20.0, 30.0), 0.0);
+    // Below the viewport, scroll down until the end is visible
+    assert_eq!(get_scroll_offset_to_reveal(0.0, 100.0, 150.0, 30.0), 80.0);
+    // Above the viewport, scroll up until the start is visible
+    assert_eq!(get_scroll_offset_to_reveal(200.0, 100.0, 150.0, 30.0), 150.0);
+    // Larger than the viewport, align the start
+    assert_eq!(get_scroll_offset_to_reveal(0.0, 100.0, 150.0, 300.0), 150.0);
+}
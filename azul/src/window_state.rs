@@ -1,27 +1,32 @@
 use std::{
-    collections::{HashSet, BTreeMap},
+    collections::{HashSet, BTreeMap, BTreeSet},
     path::PathBuf,
+    time::Instant,
     fmt,
 };
 use glium::glutin::{
     Window, WindowEvent, KeyboardInput, ScanCode, ElementState,
-    MouseCursor, VirtualKeyCode, MouseScrollDelta, AxisId,
+    MouseCursor, MouseButton, VirtualKeyCode, MouseScrollDelta, AxisId, Touch, TouchPhase,
     ModifiersState, dpi::{LogicalPosition, LogicalSize},
 };
 use webrender::api::HitTestItem;
 use {
     app::FrameEventInfo,
     dom::{EventFilter, NotEventFilter, HoverEventFilter, FocusEventFilter, WindowEventFilter},
-    callbacks:: {CallbackInfo, Callback, DefaultCallbackId, UpdateScreen},
-    id_tree::NodeId,
+    callbacks:: {CallbackInfo, CallbackVariant, DefaultCallbackId, UpdateScreen, EventPhase},
+    id_tree::{NodeId, NodeHierarchy},
     ui_state::UiState,
     callbacks::FocusTarget,
     app::AppState,
+    touch::{TouchState, TouchPoint, Gesture, GestureRecognizer},
 };
 
 const DEFAULT_TITLE: &str = "Azul App";
 const DEFAULT_WIDTH: f64 = 800.0;
 const DEFAULT_HEIGHT: f64 = 600.0;
+/// How far (in logical pixels) the mouse has to move while pressed on a
+/// draggable node before the drag starts - shorter movements are still clicks
+const DRAG_THRESHOLD: f64 = 4.0;
 
 /// Determines which keys are pressed currently (modifiers, etc.)
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
@@ -184,6 +189,47 @@ pub(crate) struct CrateInternalWindowState {
     pub(crate) pending_focus_target: Option<FocusTarget>,
     /// What the last motion was in case a controller was used.
     pub(crate) last_motion: Option<(AxisId, f64)>,
+    /// Drag-and-drop operation that is currently in progress, if any
+    pub(crate) drag_session: Option<DragSession>,
+    /// Node that the dragged node or file would currently be dropped on,
+    /// important for styling `:drag-over` elements
+    pub(crate) drop_target: Option<NodeId>,
+    /// Fingers that currently touch the window and the gestures of the current touch event
+    pub(crate) touch_state: TouchState,
+    /// Recognizes taps, pans, pinches, etc. from the touch events
+    pub(crate) gesture_recognizer: GestureRecognizer,
+    /// Last touch event, repeated to recognize the long press of a resting finger
+    pub(crate) last_touch: Option<Touch>,
+}
+
+/// State of a drag-and-drop operation, from pressing the mouse on a draggable node
+/// (or a file being dragged into the window) until the drop or cancellation
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DragSession {
+    /// Node that is dragged, `None` if files are dragged in from the OS
+    pub(crate) source: Option<NodeId>,
+    /// Cursor position at the time the mouse was pressed on the source node
+    pub(crate) start_position: LogicalPosition,
+    /// Whether the cursor moved further than the `DRAG_THRESHOLD` - until then,
+    /// pressing the mouse on a draggable node is a normal click
+    pub(crate) is_active: bool,
+}
+
+/// Drag events caused by a single `WindowEvent`
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct DragEvents {
+    /// Node that is dragged, target of `DragStart` and `DragEnd`
+    pub(crate) source: Option<NodeId>,
+    /// Events that are sent to the dragged node (`DragStart` or `DragEnd`)
+    pub(crate) source_events: Vec<HoverEventFilter>,
+    /// Events that are sent to the node under the cursor (`DragOver` or `Drop`)
+    pub(crate) target_events: Vec<HoverEventFilter>,
+    /// A new drag operation started, the payload of the previous one has to be cleared
+    pub(crate) started: bool,
+    /// The drag operation ended, the payload has to be cleared after the callbacks have run
+    pub(crate) ended: bool,
+    /// The drag operation ended without a drop, all hovered nodes receive a `DragLeave`
+    pub(crate) cancelled: bool,
 }
 
 impl Default for CrateInternalWindowState {
@@ -197,6 +243,11 @@ impl Default for CrateInternalWindowState {
             previous_window_state: None,
             pending_focus_target: None,
             last_motion: None,
+            drag_session: None,
+            drop_target: None,
+            touch_state: TouchState::default(),
+            gesture_recognizer: GestureRecognizer::default(),
+            last_touch: None,
         }
     }
 }
@@ -256,6 +307,14 @@ impl WindowSize {
             self.dimensions.height / self.hidpi_factor * self.winit_hidpi_factor,
         )
     }
+
+    /// Converts the position of a cursor or touch event into the coordinate space of the layout
+    pub(crate) fn get_layout_position(&self, position: LogicalPosition) -> LogicalPosition {
+        LogicalPosition::new(
+            position.x / self.hidpi_factor * self.winit_hidpi_factor,
+            position.y / self.hidpi_factor * self.winit_hidpi_factor,
+        )
+    }
 }
 
 impl Default for WindowSize {
@@ -290,7 +349,8 @@ impl Default for WindowState {
 pub(crate) struct DetermineCallbackResult<T> {
     pub(crate) hit_test_item: Option<HitTestItem>,
     pub(crate) default_callbacks: BTreeMap<EventFilter, DefaultCallbackId>,
-    pub(crate) normal_callbacks: BTreeMap<EventFilter, Callback<T>>,
+    pub(crate) normal_callbacks: BTreeMap<EventFilter, CallbackVariant<T>>,
+    pub(crate) capture_callbacks: BTreeMap<EventFilter, CallbackVariant<T>>,
 }
 
 impl<T> Default for DetermineCallbackResult<T> {
@@ -299,6 +359,7 @@ impl<T> Default for DetermineCallbackResult<T> {
             hit_test_item: None,
             default_callbacks: BTreeMap::new(),
             normal_callbacks: BTreeMap::new(),
+            capture_callbacks: BTreeMap::new(),
         }
     }
 }
@@ -309,6 +370,7 @@ impl<T> Clone for DetermineCallbackResult<T> {
             hit_test_item: self.hit_test_item.clone(),
             default_callbacks: self.default_callbacks.clone(),
             normal_callbacks: self.normal_callbacks.clone(),
+            capture_callbacks: self.capture_callbacks.clone(),
         }
     }
 }
@@ -326,6 +388,93 @@ pub(crate) struct CallbacksOfHitTest<T> {
     /// Each `:hover` and `:active` group stores whether it modifies the layout, as
     /// a performance optimization.
     pub needs_relayout_anyways: bool,
+    /// Front-most node under the cursor, target of all `Hover` events
+    pub hover_target: Option<NodeId>,
+    /// Currently focused node, target of all `Focus` events
+    pub focus_target: Option<NodeId>,
+    /// Node that is currently dragged, target of `DragStart` and `DragEnd`
+    pub drag_source: Option<NodeId>,
+    /// Whether a new drag operation started with this event
+    pub drag_started: bool,
+    /// Whether the current drag operation ended with this event
+    pub drag_ended: bool,
+}
+
+impl<T> CallbacksOfHitTest<T> {
+
+    /// Returns all event filters that at least one node has a callback for
+    pub(crate) fn get_event_filters(&self) -> BTreeSet<EventFilter> {
+        self.nodes_with_callbacks.values().flat_map(|result| {
+            result.normal_callbacks.keys()
+            .chain(result.default_callbacks.keys())
+            .chain(result.capture_callbacks.keys())
+            .cloned()
+        }).collect()
+    }
+
+    /// Returns the node that the event was originally dispatched to, `node_id` is
+    /// the node whose callback is currently invoked
+    pub(crate) fn get_event_target(&self, event_filter: &EventFilter, node_id: NodeId) -> NodeId {
+        if !event_filter_propagates(event_filter) {
+            return node_id;
+        }
+
+        match event_filter {
+            EventFilter::Hover(HoverEventFilter::DragStart) |
+            EventFilter::Hover(HoverEventFilter::DragEnd) => self.drag_source.unwrap_or(node_id),
+            EventFilter::Hover(_) => self.hover_target.unwrap_or(node_id),
+            EventFilter::Focus(_) => self.focus_target.unwrap_or(node_id),
+            _ => node_id,
+        }
+    }
+
+    /// Returns in which order the callbacks for the given event filter have to be invoked:
+    /// First the capture callbacks from the root to the target, then the normal callbacks from
+    /// the target back to the root. The default callbacks are invoked in the same order as
+    /// the normal callbacks.
+    ///
+    /// For `Hover` and `Focus` events, the nodes are all on the path from the target to the root,
+    /// `Window` events are sent to all nodes, so the nodes are ordered by their depth instead.
+    pub(crate) fn get_dispatch_order(&self, event_filter: &EventFilter, node_hierarchy: &NodeHierarchy) -> Vec<(NodeId, EventPhase)> {
+
+        let mut capture_nodes = Vec::new();
+        let mut bubble_nodes = Vec::new();
+
+        for (node_id, result) in &self.nodes_with_callbacks {
+            let depth = node_id.ancestors(node_hierarchy).count();
+            if result.capture_callbacks.contains_key(event_filter) {
+                capture_nodes.push((depth, *node_id));
+            }
+            if result.normal_callbacks.contains_key(event_filter) || result.default_callbacks.contains_key(event_filter) {
+                bubble_nodes.push((depth, *node_id));
+            }
+        }
+
+        capture_nodes.sort_by(|(a_depth, a_id), (b_depth, b_id)| a_depth.cmp(b_depth).then(a_id.cmp(b_id)));
+        bubble_nodes.sort_by(|(a_depth, a_id), (b_depth, b_id)| b_depth.cmp(a_depth).then(a_id.cmp(b_id)));
+
+        capture_nodes.into_iter().map(|(_, node_id)| (node_id, EventPhase::Capture))
+        .chain(bubble_nodes.into_iter().map(|(_, node_id)| (node_id, EventPhase::Bubble)))
+        .collect()
+    }
+}
+
+/// Returns whether the event propagates from the target to its parents - same as in HTML,
+/// `MouseEnter`, `MouseLeave`, `DragEnter`, `DragLeave`, `FocusReceived` and `FocusLost`
+/// are only sent to the node itself. `Not` events don't have a target, so they don't propagate either.
+pub(crate) fn event_filter_propagates(event_filter: &EventFilter) -> bool {
+    match event_filter {
+        EventFilter::Hover(HoverEventFilter::MouseEnter) |
+        EventFilter::Hover(HoverEventFilter::MouseLeave) |
+        EventFilter::Hover(HoverEventFilter::DragEnter) |
+        EventFilter::Hover(HoverEventFilter::DragLeave) |
+        EventFilter::Focus(FocusEventFilter::MouseEnter) |
+        EventFilter::Focus(FocusEventFilter::MouseLeave) |
+        EventFilter::Focus(FocusEventFilter::FocusReceived) |
+        EventFilter::Focus(FocusEventFilter::FocusLost) |
+        EventFilter::Not(_) => false,
+        _ => true,
+    }
 }
 
 impl<T> fmt::Debug for DetermineCallbackResult<T> {
@@ -340,6 +489,11 @@ impl<T> Default for CallbacksOfHitTest<T> {
             nodes_with_callbacks: BTreeMap::new(),
             needs_redraw_anyways: false,
             needs_relayout_anyways: false,
+            hover_target: None,
+            focus_target: None,
+            drag_source: None,
+            drag_started: false,
+            drag_ended: false,
         }
     }
 }
@@ -354,6 +508,21 @@ impl WindowState
         &self.internal.keyboard_state
     }
 
+    pub fn get_touch_state(&self) -> &TouchState {
+        &self.internal.touch_state
+    }
+
+    /// Returns a copy of the last touch event if a finger rested on the screen long enough
+    /// for a long press. Touch screens don't necessarily send events for a resting finger,
+    /// so the window repeats the last event to recognize the long press.
+    pub(crate) fn get_long_press_event(&self, time: Instant) -> Option<WindowEvent> {
+        let touch = self.internal.last_touch?;
+        if self.internal.gesture_recognizer.get_pending_long_press(time) != Some(touch.id) {
+            return None;
+        }
+        Some(WindowEvent::Touch(Touch { phase: TouchPhase::Moved, .. touch }))
+    }
+
     pub fn get_hovered_file(&self) -> Option<&PathBuf> {
         self.internal.hovered_file.as_ref()
     }
@@ -362,6 +531,20 @@ impl WindowState
         self.internal.last_motion
     }
 
+    /// Returns whether something (a node or a file from the OS) is currently dragged over the window
+    pub fn is_dragging(&self) -> bool {
+        self.internal.drag_session.as_ref().map(|session| session.is_active).unwrap_or(false)
+    }
+
+    /// Returns the node that is currently dragged and how far (x, y) the
+    /// cursor moved since the drag started, in order to draw the drag ghost
+    pub(crate) fn get_drag_ghost(&self) -> Option<(NodeId, (f64, f64))> {
+        let session = self.internal.drag_session.as_ref().filter(|session| session.is_active)?;
+        let source = session.source?;
+        let cursor_pos = self.internal.mouse_state.cursor_pos?;
+        Some((source, (cursor_pos.x - session.start_position.x, cursor_pos.y - session.start_position.y)))
+    }
+
     /// Returns the window state of the previous frame, useful for calculating
     /// metrics for dragging motions. Note that you can't call this function
     /// recursively - calling `get_previous_window_state()` on the returned
@@ -381,8 +564,6 @@ impl WindowState
         ui_state: &UiState<T>
     ) -> CallbacksOfHitTest<T>
     {
-        use std::collections::BTreeSet;
-
         // Store the current window state so we can set it in this.previous_window_state later on
         let mut previous_state = Box::new(self.clone());
         previous_state.internal.previous_window_state = None;
@@ -416,6 +597,36 @@ impl WindowState
             new_hit_node_ids = BTreeMap::new();
         }
 
+        // The target of all hover events is the front-most node under the cursor,
+        // the event then propagates from the target to its parents
+        let hover_target = if event_was_mouse_leave {
+            None
+        } else {
+            hit_test_items.iter().rev()
+            .find_map(|item| ui_state.tag_ids_to_node_ids.get(&item.tag.0))
+            .cloned()
+        };
+
+        // Start, continue or end the current drag operation
+        let DragEvents {
+            source: drag_source,
+            source_events: drag_source_events,
+            target_events: drag_target_events,
+            started: drag_started,
+            ended: drag_ended,
+            cancelled: drag_cancelled,
+        } = self.update_drag_session(event, hit_test_items, ui_state);
+        let is_dragging = self.is_dragging();
+
+        // Only the node that would receive the drop is styled as `:drag-over`
+        self.internal.drop_target = match hover_target {
+            Some(hover_target) if is_dragging => {
+                let drag_source = self.internal.drag_session.as_ref().and_then(|session| session.source);
+                get_drop_target(hover_target, drag_source, ui_state)
+            },
+            _ => None,
+        };
+
         // Figure out what the current focused NodeId is
         if event_was_mouse_down || event_was_mouse_release {
 
@@ -429,8 +640,8 @@ impl WindowState
         }
 
         macro_rules! insert_only_non_empty_callbacks {
-            ($node_id:expr, $hit_test_item:expr, $normal_hover_callbacks:expr, $default_hover_callbacks:expr) => ({
-                if !($normal_hover_callbacks.is_empty() && $default_hover_callbacks.is_empty()) {
+            ($node_id:expr, $hit_test_item:expr, $normal_hover_callbacks:expr, $default_hover_callbacks:expr, $capture_hover_callbacks:expr) => ({
+                if !($normal_hover_callbacks.is_empty() && $default_hover_callbacks.is_empty() && $capture_hover_callbacks.is_empty()) {
                     let mut callback_result = nodes_with_callbacks.entry(*$node_id)
                     .or_insert_with(|| DetermineCallbackResult::default());
 
@@ -440,6 +651,7 @@ impl WindowState
                     }
                     callback_result.normal_callbacks.extend($normal_hover_callbacks.into_iter());
                     callback_result.default_callbacks.extend($default_hover_callbacks.into_iter());
+                    callback_result.capture_callbacks.extend($capture_hover_callbacks.into_iter());
                 }
             })
         }
@@ -453,14 +665,14 @@ impl WindowState
             $current_hover_events:ident,
             $event_filter:ident
         ) => ({
-                // BTreeMap<EventFilter, Callback<T>>
+                // BTreeMap<EventFilter, CallbackVariant<T>>
                 let mut normal_hover_callbacks = BTreeMap::new();
 
                 // Insert all normal Hover events
                 if let Some(ui_state_hover_event_filters) = ui_state.$hover_callbacks.get($node_id) {
                     for current_hover_event in &$current_hover_events {
                         if let Some(callback) = ui_state_hover_event_filters.get(current_hover_event) {
-                            normal_hover_callbacks.insert(EventFilter::$event_filter(*current_hover_event), *callback);
+                            normal_hover_callbacks.insert(EventFilter::$event_filter(*current_hover_event), callback.clone());
                         }
                     }
                 }
@@ -477,7 +689,23 @@ impl WindowState
                     }
                 }
 
-                insert_only_non_empty_callbacks!($node_id, $hit_test_item, normal_hover_callbacks, default_hover_callbacks);
+                // BTreeMap<EventFilter, CallbackVariant<T>>
+                let mut capture_hover_callbacks = BTreeMap::new();
+
+                // Insert all capture Hover events (only for events that propagate)
+                if let Some(ui_state_capture_event_filters) = ui_state.capture_callbacks.get($node_id) {
+                    for current_hover_event in &$current_hover_events {
+                        let event_filter = EventFilter::$event_filter(*current_hover_event);
+                        if !event_filter_propagates(&event_filter) {
+                            continue;
+                        }
+                        if let Some(callback) = ui_state_capture_event_filters.get(&event_filter) {
+                            capture_hover_callbacks.insert(event_filter, callback.clone());
+                        }
+                    }
+                }
+
+                insert_only_non_empty_callbacks!($node_id, $hit_test_item, normal_hover_callbacks, default_hover_callbacks, capture_hover_callbacks);
             })
         }
 
@@ -485,30 +713,94 @@ impl WindowState
         for (window_node_id, window_callbacks) in &ui_state.window_callbacks {
             let normal_window_callbacks = window_callbacks.iter()
                 .filter(|(current_window_event, _)| current_window_events.contains(current_window_event))
-                .map(|(current_window_event, callback)| (EventFilter::Window(*current_window_event), *callback))
+                .map(|(current_window_event, callback)| (EventFilter::Window(*current_window_event), callback.clone()))
                 .collect::<BTreeMap<_, _>>();
             let default_window_callbacks = BTreeMap::<EventFilter, DefaultCallbackId>::new();
-            insert_only_non_empty_callbacks!(window_node_id, None, normal_window_callbacks, default_window_callbacks);
+            let capture_window_callbacks = BTreeMap::<EventFilter, CallbackVariant<T>>::new();
+            insert_only_non_empty_callbacks!(window_node_id, None, normal_window_callbacks, default_window_callbacks, capture_window_callbacks);
         }
 
         // Insert all default window events
         for (window_node_id, window_callbacks) in &ui_state.window_default_callbacks {
-            let normal_window_callbacks = BTreeMap::<EventFilter, Callback<T>>::new();
+            let normal_window_callbacks = BTreeMap::<EventFilter, CallbackVariant<T>>::new();
             let default_window_callbacks = window_callbacks.iter()
                 .filter(|(current_window_event, _)| current_window_events.contains(current_window_event))
                 .map(|(current_window_event, callback)| (EventFilter::Window(*current_window_event), *callback))
                 .collect::<BTreeMap<_, _>>();
-            insert_only_non_empty_callbacks!(window_node_id, None, normal_window_callbacks, default_window_callbacks);
+            let capture_window_callbacks = BTreeMap::<EventFilter, CallbackVariant<T>>::new();
+            insert_only_non_empty_callbacks!(window_node_id, None, normal_window_callbacks, default_window_callbacks, capture_window_callbacks);
+        }
+
+        // Insert all capture window events
+        for (window_node_id, capture_callbacks) in &ui_state.capture_callbacks {
+            let normal_window_callbacks = BTreeMap::<EventFilter, CallbackVariant<T>>::new();
+            let default_window_callbacks = BTreeMap::<EventFilter, DefaultCallbackId>::new();
+            let capture_window_callbacks = capture_callbacks.iter()
+                .filter(|(event_filter, _)| match event_filter {
+                    EventFilter::Window(current_window_event) => current_window_events.contains(current_window_event),
+                    _ => false,
+                })
+                .map(|(event_filter, callback)| (*event_filter, callback.clone()))
+                .collect::<BTreeMap<_, _>>();
+            insert_only_non_empty_callbacks!(window_node_id, None, normal_window_callbacks, default_window_callbacks, capture_window_callbacks);
+        }
+
+        let node_hierarchy = &ui_state.dom.arena.node_layout;
+
+        // Insert (normal + default + capture) hover events for the target and all of its parents
+        if let Some(hover_target) = hover_target {
+            for hover_node_id in hover_target.ancestors(node_hierarchy) {
+                let hit_test_item = new_hit_node_ids.get(&hover_node_id).cloned();
+                insert_callbacks!(&hover_node_id, hit_test_item, hover_callbacks, hover_default_callbacks, current_hover_events, Hover);
+            }
+        }
+
+        // Insert (normal + default + capture) focus events for the focused node and all of its parents
+        // NOTE: The focused node can be out of range if the DOM shrunk since the last frame
+        if let Some(current_focused_node) = self.internal.focused_node.filter(|node_id| node_hierarchy.get(*node_id).is_some()) {
+            for focus_node_id in current_focused_node.ancestors(node_hierarchy) {
+                insert_callbacks!(&focus_node_id, None, focus_callbacks, focus_default_callbacks, current_focus_events, Focus);
+            }
+        }
+
+        // Insert DragStart / DragEnd for the dragged node and all of its parents
+        // NOTE: The dragged node can be out of range if the DOM shrunk since the drag started
+        if let Some(drag_source) = drag_source.filter(|node_id| node_hierarchy.get(*node_id).is_some()) {
+            for drag_node_id in drag_source.ancestors(node_hierarchy) {
+                insert_callbacks!(&drag_node_id, None, hover_callbacks, hover_default_callbacks, drag_source_events, Hover);
+            }
         }
 
-        // Insert (normal + default) hover events
-        for (hover_node_id, hit_test_item) in &new_hit_node_ids {
-            insert_callbacks!(hover_node_id, Some(hit_test_item.clone()), hover_callbacks, hover_default_callbacks, current_hover_events, Hover);
+        // Insert DragOver / Drop for the node under the cursor and all of its parents
+        if let Some(hover_target) = hover_target {
+            for hover_node_id in hover_target.ancestors(node_hierarchy) {
+                let hit_test_item = new_hit_node_ids.get(&hover_node_id).cloned();
+                insert_callbacks!(&hover_node_id, hit_test_item, hover_callbacks, hover_default_callbacks, drag_target_events, Hover);
+            }
         }
 
-        // Insert (normal + default) focus events
-        if let Some(current_focused_node) = &self.internal.focused_node {
-            insert_callbacks!(current_focused_node, None, focus_callbacks, focus_default_callbacks, current_focus_events, Focus);
+        // Insert DragEnter / DragLeave for the nodes that the cursor moved into or out of while
+        // dragging. When the drag starts, all hovered nodes are entered, when it is cancelled, all are left.
+        let drag_enter_events = [HoverEventFilter::DragEnter];
+        let drag_leave_events = [HoverEventFilter::DragLeave];
+        if is_dragging {
+            let previous_hovered_nodes = &previous_state.internal.hovered_nodes;
+            for (node_id, hit_test_item) in new_hit_node_ids.iter().filter(|(node_id, _)| drag_started || !previous_hovered_nodes.contains_key(*node_id)) {
+                insert_callbacks!(node_id, Some(hit_test_item.clone()), hover_callbacks, hover_default_callbacks, drag_enter_events, Hover);
+            }
+            for (node_id, hit_test_item) in previous_hovered_nodes.iter().filter(|(node_id, _)| !new_hit_node_ids.contains_key(*node_id)) {
+                insert_callbacks!(node_id, Some(hit_test_item.clone()), hover_callbacks, hover_default_callbacks, drag_leave_events, Hover);
+            }
+        } else if drag_cancelled {
+            for (node_id, hit_test_item) in &new_hit_node_ids {
+                insert_callbacks!(node_id, Some(hit_test_item.clone()), hover_callbacks, hover_default_callbacks, drag_leave_events, Hover);
+            }
+        }
+
+        // The drag ghost follows the cursor and `:drag-over` nodes change while dragging
+        if is_dragging || previous_state.is_dragging() {
+            needs_hover_redraw = true;
+            needs_hover_relayout = true;
         }
 
         // If the last focused node and the current focused node aren't the same,
@@ -550,13 +842,13 @@ impl WindowState
 
                 let node_is_focused = self.internal.focused_node == Some($node_id);
 
-                // BTreeMap<EventFilter, Callback<T>>
+                // BTreeMap<EventFilter, CallbackVariant<T>>
                 let mut normal_callbacks = BTreeMap::new();
 
                 // Insert all normal Hover(MouseEnter) events
                 if let Some(ui_state_hover_event_filters) = ui_state.hover_callbacks.get(&$node_id) {
                     if let Some(callback) = ui_state_hover_event_filters.get(&HoverEventFilter::$event_filter) {
-                        normal_callbacks.insert(EventFilter::Hover(HoverEventFilter::$event_filter), *callback);
+                        normal_callbacks.insert(EventFilter::Hover(HoverEventFilter::$event_filter), callback.clone());
                     }
                 }
 
@@ -564,7 +856,7 @@ impl WindowState
                 if node_is_focused {
                     if let Some(ui_state_focus_event_filters) = ui_state.focus_callbacks.get(&$node_id) {
                         if let Some(callback) = ui_state_focus_event_filters.get(&FocusEventFilter::$event_filter) {
-                            normal_callbacks.insert(EventFilter::Focus(FocusEventFilter::$event_filter), *callback);
+                            normal_callbacks.insert(EventFilter::Focus(FocusEventFilter::$event_filter), callback.clone());
                         }
                     }
                 }
@@ -633,61 +925,42 @@ impl WindowState
             mouse_enter!(node_id, hit_test_item, MouseLeave);
         }
 
-        // Insert all Not-callbacks, we need to filter out all Hover and Focus callbacks
-        // and then look at what callbacks were currently
-
-        // In order to create the Not Events we have to record which events were fired and on what nodes
-        // Then we need to go through the events and fire them if the event was present, but the NodeID was not
-        let mut reverse_event_hover_normal_list = BTreeMap::<HoverEventFilter, BTreeSet<NodeId>>::new();
-        let mut reverse_event_focus_normal_list = BTreeMap::<FocusEventFilter, BTreeSet<NodeId>>::new();
-        let mut reverse_event_hover_default_list = BTreeMap::<HoverEventFilter, BTreeSet<NodeId>>::new();
-        let mut reverse_event_focus_default_list = BTreeMap::<FocusEventFilter, BTreeSet<NodeId>>::new();
-
-        for (node_id, DetermineCallbackResult { default_callbacks, normal_callbacks, .. }) in &nodes_with_callbacks {
-            for event_filter in normal_callbacks.keys() {
-                match event_filter {
-                    EventFilter::Hover(h) => {
-                        reverse_event_hover_normal_list.entry(*h).or_insert_with(|| BTreeSet::new()).insert(*node_id);
-                    },
-                    EventFilter::Focus(f) => {
-                        reverse_event_focus_normal_list.entry(*f).or_insert_with(|| BTreeSet::new()).insert(*node_id);
-                    },
-                    _ => { },
-                }
-            }
-            for event_filter in default_callbacks.keys() {
-                match event_filter {
-                    EventFilter::Hover(h) => {
-                        reverse_event_hover_default_list.entry(*h).or_insert_with(|| BTreeSet::new()).insert(*node_id);
-                    },
-                    EventFilter::Focus(f) => {
-                        reverse_event_focus_default_list.entry(*f).or_insert_with(|| BTreeSet::new()).insert(*node_id);
-                    },
-                    _ => { },
-                }
-            }
+        // Insert all Not-callbacks: A `Not(Hover(event))` callback is invoked if the event
+        // happened in this frame, but the node (and all of its children) wasn't hit, so it can
+        // be used to detect clicks outside of a node, i.e. to dismiss popups.
+        let hovered_node_path = hover_target
+            .map(|hover_target| hover_target.ancestors(node_hierarchy).collect::<BTreeSet<NodeId>>())
+            .unwrap_or_default();
+        let focused_node_path = self.internal.focused_node
+            .filter(|node_id| node_hierarchy.get(*node_id).is_some())
+            .map(|focused_node| focused_node.ancestors(node_hierarchy).collect::<BTreeSet<NodeId>>())
+            .unwrap_or_default();
+
+        let not_event_is_active = |not_event_filter: &NotEventFilter, node_id: &NodeId| match not_event_filter {
+            NotEventFilter::Hover(h) => current_hover_events.contains(h) && !hovered_node_path.contains(node_id),
+            NotEventFilter::Focus(f) => current_focus_events.contains(f) && !focused_node_path.contains(node_id),
+        };
+
+        // Insert all normal Not events
+        for (node_id, not_callbacks) in &ui_state.not_callbacks {
+            let normal_not_callbacks = not_callbacks.iter()
+                .filter(|(not_event_filter, _)| not_event_is_active(not_event_filter, node_id))
+                .map(|(not_event_filter, callback)| (EventFilter::Not(*not_event_filter), callback.clone()))
+                .collect::<BTreeMap<_, _>>();
+            let default_not_callbacks = BTreeMap::<EventFilter, DefaultCallbackId>::new();
+            let capture_not_callbacks = BTreeMap::<EventFilter, CallbackVariant<T>>::new();
+            insert_only_non_empty_callbacks!(node_id, None, normal_not_callbacks, default_not_callbacks, capture_not_callbacks);
         }
 
-        // Insert NotEventFilter callbacks
-        for (node_id, not_event_filter_callback_list) in &ui_state.not_callbacks {
-            for (event_filter, event_callback) in not_event_filter_callback_list {
-                // If we have the event filter, but we don't have the NodeID, then insert the callback
-                match event_filter {
-                    NotEventFilter::Hover(h) => {
-                        if let Some(on_node_ids) = reverse_event_hover_normal_list.get(&h) {
-                            if !on_node_ids.contains(node_id) {
-                                nodes_with_callbacks.entry(*node_id)
-                                .or_insert_with(|| DetermineCallbackResult::default())
-                                .normal_callbacks.insert(EventFilter::Not(*event_filter), *event_callback);
-                            }
-                        }
-                        // TODO: Same thing for default callbacks here
-                    },
-                    NotEventFilter::Focus(f) => {
-                        // TODO: Same thing for focus
-                    }
-                }
-            }
+        // Insert all default Not events
+        for (node_id, not_default_callbacks) in &ui_state.not_default_callbacks {
+            let normal_not_callbacks = BTreeMap::<EventFilter, CallbackVariant<T>>::new();
+            let default_not_callbacks = not_default_callbacks.iter()
+                .filter(|(not_event_filter, _)| not_event_is_active(not_event_filter, node_id))
+                .map(|(not_event_filter, callback_id)| (EventFilter::Not(*not_event_filter), *callback_id))
+                .collect::<BTreeMap<_, _>>();
+            let capture_not_callbacks = BTreeMap::<EventFilter, CallbackVariant<T>>::new();
+            insert_only_non_empty_callbacks!(node_id, None, normal_not_callbacks, default_not_callbacks, capture_not_callbacks);
         }
 
         self.internal.hovered_nodes = new_hit_node_ids;
@@ -697,7 +970,119 @@ impl WindowState
             needs_redraw_anyways: needs_hover_redraw,
             needs_relayout_anyways: needs_hover_relayout,
             nodes_with_callbacks,
+            hover_target,
+            focus_target: self.internal.focused_node,
+            drag_source,
+            drag_started,
+            drag_ended,
+        }
+    }
+
+    /// Starts, updates or ends the current drag operation and returns which drag events
+    /// the `event` causes. Must be called after the mouse state has been updated.
+    ///
+    /// Pressing the left mouse button on a draggable node only starts the drag once the
+    /// cursor moved further than the `DRAG_THRESHOLD`, so that clicks on draggable nodes
+    /// still work. Files dragged in from the OS start the drag immediately.
+    fn update_drag_session<T>(
+        &mut self,
+        event: &WindowEvent,
+        hit_test_items: &[HitTestItem],
+        ui_state: &UiState<T>
+    ) -> DragEvents
+    {
+        let mut drag_events = DragEvents::default();
+        let cursor_pos = self.internal.mouse_state.cursor_pos;
+
+        match event {
+            WindowEvent::MouseInput { state: ElementState::Pressed, button: MouseButton::Left, .. } => {
+                // The front-most draggable node under the cursor is the drag source
+                let drag_source = hit_test_items.iter().rev()
+                    .find_map(|item| ui_state.draggable_tags.get(&item.tag.0))
+                    .cloned();
+
+                self.internal.drag_session = match (drag_source, cursor_pos) {
+                    (Some(source), Some(start_position)) => Some(DragSession {
+                        source: Some(source),
+                        start_position,
+                        is_active: false,
+                    }),
+                    _ => None,
+                };
+            },
+            WindowEvent::CursorMoved { .. } => {
+                if let Some(session) = &mut self.internal.drag_session {
+                    if !session.is_active {
+                        let moved_far_enough = cursor_pos.map(|pos| {
+                            let (dx, dy) = (pos.x - session.start_position.x, pos.y - session.start_position.y);
+                            (dx * dx + dy * dy).sqrt() >= DRAG_THRESHOLD
+                        }).unwrap_or(false);
+
+                        if moved_far_enough {
+                            session.is_active = true;
+                            drag_events.started = true;
+                            drag_events.source_events.push(HoverEventFilter::DragStart);
+                        }
+                    }
+
+                    if session.is_active {
+                        drag_events.target_events.push(HoverEventFilter::DragOver);
+                    }
+
+                    drag_events.source = session.source;
+                }
+            },
+            WindowEvent::MouseInput { state: ElementState::Released, button: MouseButton::Left, .. } => {
+                if let Some(session) = self.internal.drag_session.take() {
+                    if session.is_active {
+                        drag_events.target_events.push(HoverEventFilter::Drop);
+                        drag_events.source_events.push(HoverEventFilter::DragEnd);
+                        drag_events.ended = true;
+                    }
+                    drag_events.source = session.source;
+                }
+            },
+            WindowEvent::KeyboardInput {
+                input: KeyboardInput { state: ElementState::Pressed, virtual_keycode: Some(VirtualKeyCode::Escape), .. }, ..
+            } => {
+                // Escape cancels the drag operation, without a drop
+                if let Some(session) = self.internal.drag_session.take() {
+                    if session.is_active {
+                        drag_events.source_events.push(HoverEventFilter::DragEnd);
+                        drag_events.ended = true;
+                        drag_events.cancelled = true;
+                    }
+                    drag_events.source = session.source;
+                }
+            },
+            WindowEvent::HoveredFile(_) => {
+                // One HoveredFile event is sent for each file, all belong to the same drag operation
+                let is_new_session = self.internal.drag_session.as_ref().map(|session| session.source.is_some()).unwrap_or(true);
+                if is_new_session {
+                    self.internal.drag_session = Some(DragSession {
+                        source: None,
+                        start_position: cursor_pos.unwrap_or(LogicalPosition::new(0.0, 0.0)),
+                        is_active: true,
+                    });
+                    drag_events.started = true;
+                }
+                drag_events.target_events.push(HoverEventFilter::DragOver);
+            },
+            WindowEvent::DroppedFile(_) => {
+                // The payload of dropped files stays available until the next drag operation starts,
+                // since one DroppedFile event (and therefore one Drop) is sent for each file
+                self.internal.drag_session = None;
+                drag_events.target_events.push(HoverEventFilter::Drop);
+            },
+            WindowEvent::HoveredFileCancelled => {
+                self.internal.drag_session = None;
+                drag_events.ended = true;
+                drag_events.cancelled = true;
+            },
+            _ => { },
         }
+
+        drag_events
     }
 
     // Returns the frame events + if the window should close
@@ -710,6 +1095,7 @@ impl WindowState
                 should_window_close = true;
             }
             self.update_mouse_cursor_position(event);
+            self.update_touch_points(event);
             self.update_scroll_state(event);
             self.update_keyboard_modifiers(event);
             self.update_keyboard_pressed_chars(event);
@@ -741,9 +1127,7 @@ impl WindowState
     fn update_mouse_cursor_position(&mut self, event: &WindowEvent) {
         match event {
             WindowEvent::CursorMoved { position, .. } => {
-                let world_pos_x = position.x / self.size.hidpi_factor * self.size.winit_hidpi_factor;
-                let world_pos_y = position.y / self.size.hidpi_factor * self.size.winit_hidpi_factor;
-                self.internal.mouse_state.cursor_pos = Some(LogicalPosition::new(world_pos_x, world_pos_y));
+                self.internal.mouse_state.cursor_pos = Some(self.size.get_layout_position(*position));
             },
             WindowEvent::CursorLeft { .. } => {
                 self.internal.mouse_state.cursor_pos = None;
@@ -755,6 +1139,41 @@ impl WindowState
         }
     }
 
+    /// Inserts new touches and updates the position of moved touches (lifted fingers are
+    /// removed per event in `get_window_events`). The first finger that touches the screen
+    /// moves the cursor, so that touch events are hit-tested like mouse events.
+    fn update_touch_points(&mut self, event: &WindowEvent) {
+        let touch = match event {
+            WindowEvent::Touch(touch) => touch,
+            _ => return,
+        };
+
+        self.internal.last_touch = Some(*touch);
+
+        let Touch { phase, location, id, .. } = touch;
+        let position = self.size.get_layout_position(*location);
+
+        let touch_state = &mut self.internal.touch_state;
+
+        match phase {
+            TouchPhase::Started => {
+                touch_state.touches.insert(*id, TouchPoint { id: *id, position, start_position: position, pressure: None });
+                if touch_state.primary_touch.is_none() {
+                    touch_state.primary_touch = Some(*id);
+                }
+            },
+            TouchPhase::Moved | TouchPhase::Ended | TouchPhase::Cancelled => {
+                if let Some(touch) = touch_state.touches.get_mut(id) {
+                    touch.position = position;
+                }
+            },
+        }
+
+        if touch_state.primary_touch == Some(*id) {
+            self.internal.mouse_state.cursor_pos = Some(position);
+        }
+    }
+
     fn update_scroll_state(&mut self, event: &WindowEvent) {
         match event {
             WindowEvent::MouseWheel { delta, .. } => {
@@ -830,6 +1249,9 @@ fn get_window_events(window_state: &mut WindowState, event: &WindowEvent) -> Has
 
     let mut events_vec = HashSet::<WindowEventFilter>::new();
 
+    window_state.internal.touch_state.changed_touch = None;
+    window_state.internal.touch_state.gestures.clear();
+
     match event {
         WindowEvent::MouseInput { state: ElementState::Pressed, button, .. } => {
             events_vec.insert(WindowEventFilter::MouseDown);
@@ -903,6 +1325,43 @@ fn get_window_events(window_state: &mut WindowState, event: &WindowEvent) -> Has
         WindowEvent::CursorLeft { .. } => {
             events_vec.insert(WindowEventFilter::MouseLeave);
         },
+        WindowEvent::Touch(Touch { phase, location, id, .. }) => {
+            events_vec.insert(match phase {
+                TouchPhase::Started => WindowEventFilter::TouchStart,
+                TouchPhase::Moved => WindowEventFilter::TouchMove,
+                TouchPhase::Ended => WindowEventFilter::TouchEnd,
+                TouchPhase::Cancelled => WindowEventFilter::TouchCancel,
+            });
+
+            let position = window_state.size.get_layout_position(*location);
+            let gestures = window_state.internal.gesture_recognizer.on_touch(*id, *phase, position, Instant::now());
+
+            for gesture in &gestures {
+                events_vec.insert(match gesture {
+                    Gesture::Tap { .. } => WindowEventFilter::Tap,
+                    Gesture::LongPress { .. } => WindowEventFilter::LongPress,
+                    Gesture::Pan { delta, .. } => {
+                        // Panning scrolls the content under the finger, in the opposite direction of a mouse wheel
+                        window_state.internal.mouse_state.scroll_x -= delta.0;
+                        window_state.internal.mouse_state.scroll_y -= delta.1;
+                        WindowEventFilter::Pan
+                    },
+                    Gesture::Pinch { .. } => WindowEventFilter::Pinch,
+                    Gesture::Swipe { .. } => WindowEventFilter::Swipe,
+                });
+            }
+
+            let touch_state = &mut window_state.internal.touch_state;
+            touch_state.gestures = gestures;
+            touch_state.changed_touch = touch_state.touches.get(id).cloned();
+
+            if *phase == TouchPhase::Ended || *phase == TouchPhase::Cancelled {
+                touch_state.touches.remove(id);
+                if touch_state.primary_touch == Some(*id) {
+                    touch_state.primary_touch = None;
+                }
+            }
+        },
         _ => { }
     }
     events_vec
@@ -941,14 +1400,16 @@ pub(crate) fn window_should_close(event: &WindowEvent, frame_event_info: &mut Fr
             frame_event_info.should_redraw_window = true;
         },
         WindowEvent::CloseRequested | WindowEvent::Destroyed => {
-            // TODO: Callback the windows onclose method
-            // (ex. for implementing a "do you really want to close" dialog)
+            // The window can still veto closing via `WindowCreateOptions::on_close_requested`
             return true;
         },
         WindowEvent::KeyboardInput { .. } |
         WindowEvent::ReceivedCharacter(_) |
         WindowEvent::MouseWheel { .. } |
         WindowEvent::MouseInput { .. } |
+        WindowEvent::HoveredFile(_) |
+        WindowEvent::DroppedFile(_) |
+        WindowEvent::HoveredFileCancelled |
         WindowEvent::Touch(_) => {
             frame_event_info.should_hittest = true;
         },
@@ -991,6 +1452,20 @@ impl AcceleratorKey {
     }
 }
 
+/// Returns the node that a dragged node or file would be dropped on: the node under the cursor
+/// or its closest parent that has a `Drop` callback - but never the dragged node itself
+fn get_drop_target<T>(hover_target: NodeId, drag_source: Option<NodeId>, ui_state: &UiState<T>) -> Option<NodeId> {
+
+    let drop = HoverEventFilter::Drop;
+    let accepts_drop = |node_id: &NodeId| {
+        ui_state.hover_callbacks.get(node_id).map(|callbacks| callbacks.contains_key(&drop)).unwrap_or(false) ||
+        ui_state.hover_default_callbacks.get(node_id).map(|callbacks| callbacks.contains_key(&drop)).unwrap_or(false)
+    };
+
+    hover_target.ancestors(&ui_state.dom.arena.node_layout)
+        .find(|node_id| drag_source != Some(*node_id) && accepts_drop(node_id))
+}
+
 /// Utility function that, given the current keyboard state and a list of
 /// keyboard accelerators + callbacks, checks what callback can be invoked
 /// and the first matching callback. This leads to very readable
@@ -1008,6 +1483,10 @@ impl AcceleratorKey {
 ///     ])
 /// }
 /// ```
+///
+/// Note that extra modifiers are ignored, i.e. `[Ctrl, S]` also matches if Ctrl + Shift + S
+/// is pressed. The shortcuts of `WindowCreateOptions::shortcuts` match the modifiers exactly
+/// and support chords and focus scopes, see the `shortcuts` module.
 pub fn keymap<T>(
     app_state: &mut AppState<T>,
     event: &mut CallbackInfo<T>,
@@ -1025,4 +1504,226 @@ pub fn keymap<T>(
         })
         .next()
         .and_then(|(_, callback)| (callback)(app_state, event))
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_dispatch_order_capture_then_bubble() {
+
+    use dom::{Dom, On};
+    use callbacks::{Callback, DontRedraw};
+
+    fn callback(_: &mut AppState<()>, _: &mut CallbackInfo<()>) -> UpdateScreen { DontRedraw }
+
+    // list row (0) -> button (1) -> label (2)
+    let dom: Dom<()> = Dom::div().with_child(Dom::div().with_child(Dom::label("Delete")));
+    let node_hierarchy = &dom.arena.node_layout;
+    let event_filter = EventFilter::Hover(HoverEventFilter::MouseUp);
+
+    let mut row = DetermineCallbackResult::default();
+    row.capture_callbacks.insert(event_filter, Callback(callback).into());
+    row.normal_callbacks.insert(event_filter, Callback(callback).into());
+    let mut button = DetermineCallbackResult::default();
+    button.normal_callbacks.insert(event_filter, Callback(callback).into());
+    let mut label = DetermineCallbackResult::default();
+    label.capture_callbacks.insert(event_filter, Callback(callback).into());
+
+    let mut callbacks_of_hit_test = CallbacksOfHitTest::default();
+    callbacks_of_hit_test.hover_target = Some(NodeId::new(2));
+    callbacks_of_hit_test.nodes_with_callbacks.insert(NodeId::new(0), row);
+    callbacks_of_hit_test.nodes_with_callbacks.insert(NodeId::new(1), button);
+    callbacks_of_hit_test.nodes_with_callbacks.insert(NodeId::new(2), label);
+
+    assert_eq!(callbacks_of_hit_test.get_dispatch_order(&event_filter, node_hierarchy), vec![
+        (NodeId::new(0), EventPhase::Capture),
+        (NodeId::new(2), EventPhase::Capture),
+        (NodeId::new(1), EventPhase::Bubble),
+        (NodeId::new(0), EventPhase::Bubble),
+    ]);
+    assert_eq!(callbacks_of_hit_test.get_event_target(&event_filter, NodeId::new(0)), NodeId::new(2));
+    assert!(!event_filter_propagates(&EventFilter::Hover(HoverEventFilter::MouseEnter)));
+}
+
+#[test]
+fn test_drag_event_targets() {
+
+    // kanban column (0) -> card (1)
+    let mut callbacks_of_hit_test = CallbacksOfHitTest::<()>::default();
+    callbacks_of_hit_test.hover_target = Some(NodeId::new(0));
+    callbacks_of_hit_test.drag_source = Some(NodeId::new(1));
+
+    let drag_start = EventFilter::Hover(HoverEventFilter::DragStart);
+    let drag_over = EventFilter::Hover(HoverEventFilter::DragOver);
+    let drag_enter = EventFilter::Hover(HoverEventFilter::DragEnter);
+
+    assert_eq!(callbacks_of_hit_test.get_event_target(&drag_start, NodeId::new(0)), NodeId::new(1));
+    assert_eq!(callbacks_of_hit_test.get_event_target(&drag_over, NodeId::new(1)), NodeId::new(0));
+    assert_eq!(callbacks_of_hit_test.get_event_target(&drag_enter, NodeId::new(1)), NodeId::new(1));
+    assert!(!event_filter_propagates(&drag_enter));
+}
+
+#[cfg(test)]
+fn drag_test_mouse_input(state: ElementState) -> WindowEvent {
+    WindowEvent::MouseInput {
+        device_id: unsafe { ::glium::glutin::DeviceId::dummy() },
+        state,
+        button: MouseButton::Left,
+        modifiers: ModifiersState::default(),
+    }
+}
+
+#[cfg(test)]
+fn drag_test_cursor_moved(window_state: &mut WindowState, x: f64, y: f64) -> WindowEvent {
+    let position = LogicalPosition::new(x, y);
+    window_state.internal.mouse_state.cursor_pos = Some(position);
+    WindowEvent::CursorMoved {
+        device_id: unsafe { ::glium::glutin::DeviceId::dummy() },
+        position,
+        modifiers: ModifiersState::default(),
+    }
+}
+
+#[cfg(test)]
+fn drag_test_hit_test_item(tag_id: u64) -> HitTestItem {
+    use webrender::api::{PipelineId, LayoutPoint};
+    HitTestItem {
+        pipeline: PipelineId(0, 0),
+        tag: (tag_id, 0),
+        point_in_viewport: LayoutPoint::new(0.0, 0.0),
+        point_relative_to_item: LayoutPoint::new(0.0, 0.0),
+    }
+}
+
+#[test]
+fn test_drag_session_threshold_and_drop() {
+
+    use dom::Dom;
+
+    let mut card = Dom::<()>::div();
+    card.set_draggable(true);
+    let ui_state = Dom::div().with_child(card).into_ui_state();
+    let card_tag = *ui_state.draggable_tags.keys().next().unwrap();
+    let hit_card = [drag_test_hit_test_item(card_tag)];
+
+    let mut window_state = WindowState::default();
+    drag_test_cursor_moved(&mut window_state, 10.0, 10.0);
+
+    // Pressing the mouse on a draggable node doesn't start the drag yet
+    let events = window_state.update_drag_session(&drag_test_mouse_input(ElementState::Pressed), &hit_card, &ui_state);
+    assert_eq!(events, DragEvents::default());
+    assert!(!window_state.is_dragging());
+
+    // Moving less than the threshold is still a click
+    let moved = drag_test_cursor_moved(&mut window_state, 12.0, 11.0);
+    let events = window_state.update_drag_session(&moved, &hit_card, &ui_state);
+    assert!(!events.started);
+    assert!(events.source_events.is_empty());
+    assert!(events.target_events.is_empty());
+    assert!(!window_state.is_dragging());
+
+    let moved = drag_test_cursor_moved(&mut window_state, 20.0, 10.0);
+    let events = window_state.update_drag_session(&moved, &hit_card, &ui_state);
+    assert!(events.started);
+    assert_eq!(events.source, Some(NodeId::new(1)));
+    assert_eq!(events.source_events, vec![HoverEventFilter::DragStart]);
+    assert_eq!(events.target_events, vec![HoverEventFilter::DragOver]);
+    assert!(window_state.is_dragging());
+
+    let events = window_state.update_drag_session(&drag_test_mouse_input(ElementState::Released), &[], &ui_state);
+    assert!(events.ended);
+    assert!(!events.cancelled);
+    assert_eq!(events.source_events, vec![HoverEventFilter::DragEnd]);
+    assert_eq!(events.target_events, vec![HoverEventFilter::Drop]);
+    assert!(!window_state.is_dragging());
+}
+
+#[test]
+fn test_drag_session_escape_cancels() {
+
+    use dom::Dom;
+
+    let mut card = Dom::<()>::div();
+    card.set_draggable(true);
+    let ui_state = card.into_ui_state();
+    let card_tag = *ui_state.draggable_tags.keys().next().unwrap();
+    let hit_card = [drag_test_hit_test_item(card_tag)];
+
+    let mut window_state = WindowState::default();
+    drag_test_cursor_moved(&mut window_state, 0.0, 0.0);
+    window_state.update_drag_session(&drag_test_mouse_input(ElementState::Pressed), &hit_card, &ui_state);
+    let moved = drag_test_cursor_moved(&mut window_state, 50.0, 0.0);
+    window_state.update_drag_session(&moved, &hit_card, &ui_state);
+    assert!(window_state.is_dragging());
+
+    let escape = WindowEvent::KeyboardInput {
+        device_id: unsafe { ::glium::glutin::DeviceId::dummy() },
+        input: KeyboardInput {
+            scancode: 0,
+            state: ElementState::Pressed,
+            virtual_keycode: Some(VirtualKeyCode::Escape),
+            modifiers: ModifiersState::default(),
+        },
+    };
+
+    let events = window_state.update_drag_session(&escape, &[], &ui_state);
+    assert!(events.ended);
+    assert!(events.cancelled);
+    assert_eq!(events.source_events, vec![HoverEventFilter::DragEnd]);
+    assert!(events.target_events.is_empty());
+    assert!(!window_state.is_dragging());
+
+    // Releasing the mouse after cancelling doesn't drop anything
+    let events = window_state.update_drag_session(&drag_test_mouse_input(ElementState::Released), &[], &ui_state);
+    assert_eq!(events, DragEvents::default());
+}
+
+#[test]
+fn test_drag_session_files() {
+
+    use dom::Dom;
+
+    let ui_state = Dom::<()>::div().into_ui_state();
+    let mut window_state = WindowState::default();
+
+    // One HoveredFile event is sent per file, but they belong to the same drag operation
+    let events = window_state.update_drag_session(&WindowEvent::HoveredFile("a.txt".into()), &[], &ui_state);
+    assert!(events.started);
+    assert_eq!(events.source, None);
+    assert_eq!(events.target_events, vec![HoverEventFilter::DragOver]);
+    assert!(window_state.is_dragging());
+
+    let events = window_state.update_drag_session(&WindowEvent::HoveredFile("b.txt".into()), &[], &ui_state);
+    assert!(!events.started);
+    assert_eq!(events.target_events, vec![HoverEventFilter::DragOver]);
+
+    let events = window_state.update_drag_session(&WindowEvent::DroppedFile("a.txt".into()), &[], &ui_state);
+    assert_eq!(events.target_events, vec![HoverEventFilter::Drop]);
+    assert!(!window_state.is_dragging());
+
+    window_state.update_drag_session(&WindowEvent::HoveredFile("a.txt".into()), &[], &ui_state);
+    let events = window_state.update_drag_session(&WindowEvent::HoveredFileCancelled, &[], &ui_state);
+    assert!(events.ended);
+    assert!(events.cancelled);
+    assert!(events.target_events.is_empty());
+    assert!(!window_state.is_dragging());
+}
+
+#[test]
+fn test_drop_target() {
+
+    use dom::{Dom, On};
+    use callbacks::{Callback, DontRedraw};
+
+    fn on_drop(_: &mut AppState<()>, _: &mut CallbackInfo<()>) -> UpdateScreen { DontRedraw }
+
+    // board (0) -> column (1, accepts drops) -> card (2, accepts drops) -> label (3)
+    let mut card = Dom::div().with_callback(On::Drop, Callback(on_drop)).with_child(Dom::label("Card"));
+    card.set_draggable(true);
+    let column = Dom::div().with_callback(On::Drop, Callback(on_drop)).with_child(card);
+    let ui_state = Dom::<()>::div().with_child(column).into_ui_state();
+
+    // Only the closest node that accepts drops is the drop target, not all hovered nodes
+    assert_eq!(get_drop_target(NodeId::new(3), None, &ui_state), Some(NodeId::new(2)));
+    // The dragged node can't be dropped on itself
+    assert_eq!(get_drop_target(NodeId::new(3), Some(NodeId::new(2)), &ui_state), Some(NodeId::new(1)));
+    assert_eq!(get_drop_target(NodeId::new(0), None, &ui_state), None);
+}
@@ -501,7 +501,7 @@ pub type FilteredComponentArguments = ComponentArguments;
 fn validate_and_filter_component_args(xml_attributes: &XmlAttributeMap, valid_args: &FilteredComponentArguments)
 -> Result<FilteredComponentArguments, RenderDomError> {
 
-    const DEFAULT_ARGS: [&str;5] = ["id", "class", "tabindex", "draggable", "focusable"];
+    const DEFAULT_ARGS: [&str;7] = ["id", "class", "tabindex", "draggable", "focusable", "checked", "disabled"];
 
     let mut map = FilteredComponentArguments::default();
 
@@ -717,6 +717,20 @@ fn set_attributes<T>(dom: &mut Dom<T>, xml_attributes: &XmlAttributeMap, filtere
         dom.set_draggable(drag);
     }
 
+    if let Some(checked) = xml_attributes.get("checked")
+        .map(|c| format_args_dynamic(c, &filtered_xml_attributes))
+        .and_then(|c| parse_bool(&c))
+    {
+        dom.set_checked(checked);
+    }
+
+    if let Some(disabled) = xml_attributes.get("disabled")
+        .map(|d| format_args_dynamic(d, &filtered_xml_attributes))
+        .and_then(|d| parse_bool(&d))
+    {
+        dom.set_disabled(disabled);
+    }
+
     if let Some(focusable) = xml_attributes.get("focusable")
         .map(|f| format_args_dynamic(f, &filtered_xml_attributes))
         .and_then(|f| parse_bool(&f))
@@ -869,6 +883,14 @@ fn render_single_dom_node_to_string<T>(dom: &Dom<T>, existing_str: &mut String)
     if head.is_draggable {
         *existing_str += ".is_draggable(true)";
     }
+
+    if head.is_checked {
+        *existing_str += ".with_checked(true)";
+    }
+
+    if head.is_disabled {
+        *existing_str += ".with_disabled(true)";
+    }
 }
 
 #[test]
@@ -4,7 +4,7 @@ extern crate azul;
 
 use azul::{
     prelude::*,
-    widgets::{button::Button, label::Label},
+    widgets::{button::Button, label::Label, progress::Spinner, toast::*},
 };
 use std::{
     thread,
@@ -21,11 +21,16 @@ enum ConnectionStatus {
 
 struct MyDataModel {
     connection_status: ConnectionStatus,
+    toasts: ToastState,
+}
+
+fn get_toasts(state: &mut MyDataModel) -> &mut ToastState {
+    &mut state.toasts
 }
 
 impl Layout for MyDataModel {
 
-    fn layout(&self, _info: LayoutInfo<Self>) -> Dom<Self> {
+    fn layout(&self, mut info: LayoutInfo<Self>) -> Dom<Self> {
 
         use self::ConnectionStatus::*;
 
@@ -53,9 +58,14 @@ impl Layout for MyDataModel {
                     .with_callback(On::MouseUp, Callback(reset_connection))
                 );
             }
-            InProgress(_, _) => { },
+            InProgress(_, _) => {
+                // The spinner is animated by the timer_timer, which redraws the window on every frame
+                dom.add_child(Spinner::new().dom());
+            },
         }
 
+        ToastArea::new(get_toasts).add_overlay(&self.toasts, &mut info);
+
         dom
     }
 }
@@ -69,7 +79,11 @@ fn start_connection(app_state: &mut AppState<MyDataModel>, _event: &mut Callback
     let status = ConnectionStatus::InProgress(Instant::now(), Duration::from_secs(0));
     app_state.data.modify(|state| state.connection_status = status)?;
     let task = Task::new(&app_state.data, connect_to_db_async);
-    app_state.add_task(task);
+    let handle = task.get_handle();
+    let toasts = ToastArea::new(get_toasts);
+    app_state.add_task(task
+        .then(toasts.show(Toast::new(ToastKind::Success, "Connected to the database")))
+        .or_else(toasts.show_task_error(&handle, "Could not connect to the database")));
     app_state.add_timer(TimerId::new(), Timer::new(timer_timer));
     Redraw
 }
@@ -89,7 +103,7 @@ fn connect_to_db_async(app_data: Arc<Mutex<MyDataModel>>, _: DropCheck) {
 }
 
 fn main() {
-    let model = MyDataModel { connection_status: ConnectionStatus::NotConnected };
+    let model = MyDataModel { connection_status: ConnectionStatus::NotConnected, toasts: ToastState::new() };
     let mut app = App::new(model, AppConfig::default()).unwrap();
     let window = app.create_window(WindowCreateOptions::default(), css::native()).unwrap();
     app.run(window).unwrap();
@@ -0,0 +1,78 @@
+#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
+
+extern crate azul;
+
+use std::{fs, cell::RefCell, path::PathBuf};
+use azul::prelude::*;
+use azul::widgets::{list_view::SelectionMode, tree_view::*};
+
+struct FileBrowser {
+    /// Files and directories that were loaded so far, the `TreeNodeId` is the index into this list
+    entries: RefCell<Vec<Entry>>,
+    tree: TreeViewState,
+}
+
+struct Entry {
+    path: PathBuf,
+    name: String,
+    /// `None` for files, `Some(None)` for directories that were not read yet
+    children: Option<Option<Vec<TreeNodeId>>>,
+}
+
+impl Entry {
+    fn new(path: PathBuf) -> Self {
+        let name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_else(|| path.display().to_string());
+        let children = if path.is_dir() { Some(None) } else { None };
+        Self { path, name, children }
+    }
+}
+
+// Directories are only read once they are expanded for the first time
+fn get_children(data: &FileBrowser, parent: Option<TreeNodeId>, is_expanded: bool) -> TreeChildren {
+    let parent = match parent {
+        Some(parent) => parent.0,
+        None => return TreeChildren::Loaded(vec![TreeNodeId(0)]),
+    };
+
+    let mut entries = data.entries.borrow_mut();
+    let path = match &entries[parent].children {
+        None => return TreeChildren::None,
+        Some(Some(children)) => return TreeChildren::Loaded(children.clone()),
+        Some(None) if !is_expanded => return TreeChildren::NotLoaded,
+        Some(None) => entries[parent].path.clone(),
+    };
+
+    let mut paths = fs::read_dir(&path)
+        .map(|dir| dir.filter_map(|entry| entry.ok()).map(|entry| entry.path()).collect::<Vec<_>>())
+        .unwrap_or_default();
+    paths.sort_by_key(|path| (!path.is_dir(), path.file_name().map(|name| name.to_string_lossy().to_lowercase())));
+
+    let first_child = entries.len();
+    entries.extend(paths.into_iter().map(Entry::new));
+    let children = (first_child..entries.len()).map(TreeNodeId).collect::<Vec<_>>();
+    entries[parent].children = Some(Some(children.clone()));
+    TreeChildren::Loaded(children)
+}
+
+fn render_node(data: &FileBrowser, node: TreeNodeId) -> Dom<FileBrowser> {
+    Dom::label(data.entries.borrow()[node.0].name.clone())
+}
+
+impl Layout for FileBrowser {
+    fn layout(&self, info: LayoutInfo<Self>) -> Dom<Self> {
+        TreeView::new(get_children, render_node).dom(&self.tree, self, info.window)
+    }
+}
+
+fn main() {
+    let root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let mut tree = TreeViewState::new().with_selection_mode(SelectionMode::Multiple);
+    tree.expand(TreeNodeId(0));
+    let data = FileBrowser {
+        entries: RefCell::new(vec![Entry::new(root)]),
+        tree,
+    };
+    let mut app = App::new(data, AppConfig::default()).unwrap();
+    let window = app.create_window(WindowCreateOptions::default(), css::native()).unwrap();
+    app.run(window).unwrap();
+}
@@ -0,0 +1,95 @@
+#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
+
+extern crate azul;
+
+use azul::prelude::*;
+use azul::dialogs;
+use azul::widgets::{button::Button, label::Label, file_chooser::*};
+
+struct FileChooserApp {
+    chooser: FileChooserState,
+    /// Use the built-in chooser even if native dialogs are available
+    force_built_in: bool,
+    message: String,
+}
+
+fn get_chooser(data: &mut FileChooserApp) -> &mut FileChooserState {
+    &mut data.chooser
+}
+
+fn on_close(data: &mut FileChooserApp, _: &mut AppStateNoData<FileChooserApp>, result: FileChooserResult) -> UpdateScreen {
+    data.message = match result {
+        FileChooserResult::Accepted(paths) => paths.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join("\n"),
+        FileChooserResult::Cancelled => "Cancelled".to_string(),
+    };
+    Redraw
+}
+
+fn chooser() -> FileChooser<FileChooserApp> {
+    FileChooser::new(get_chooser).with_on_close(on_close)
+}
+
+impl Layout for FileChooserApp {
+    fn layout(&self, mut info: LayoutInfo<Self>) -> Dom<Self> {
+
+        chooser().add_overlay(&self.chooser, self, &mut info);
+
+        let native = if self.force_built_in { "Built-in chooser (click to use native dialogs)" } else { "Native dialogs if available (click to toggle)" };
+
+        Dom::div()
+            .with_child(Button::with_label("Open File").dom().with_callback(On::MouseUp, Callback(open_file)))
+            .with_child(Button::with_label("Open Files").dom().with_callback(On::MouseUp, Callback(open_files)))
+            .with_child(Button::with_label("Save File").dom().with_callback(On::MouseUp, Callback(save_file)))
+            .with_child(Button::with_label("Select Folder").dom().with_callback(On::MouseUp, Callback(select_folder)))
+            .with_child(Button::with_label(native).dom().with_callback(On::MouseUp, Callback(toggle_built_in)))
+            .with_child(Label::new(self.message.clone()).dom())
+    }
+}
+
+fn show(app_state: &mut AppState<FileChooserApp>, mode: FileChooserMode) -> UpdateScreen {
+    let task = app_state.data.modify_clone(|data| {
+        data.chooser = FileChooserState::new(mode)
+            .with_filter(FileFilter::new("Rust files", &["*.rs"]))
+            .with_filter(FileFilter::new("Text files", &["*.txt", "*.md"]))
+            .with_filter(FileFilter::new("All files", &["*"]));
+        if data.force_built_in {
+            chooser().open(&mut data.chooser)
+        } else {
+            dialogs::show_file_chooser(&chooser(), &mut data.chooser)
+        }
+    })?;
+    app_state.add_task(task);
+    Redraw
+}
+
+fn open_file(app_state: &mut AppState<FileChooserApp>, _: &mut CallbackInfo<FileChooserApp>) -> UpdateScreen {
+    show(app_state, FileChooserMode::OpenFile)
+}
+
+fn open_files(app_state: &mut AppState<FileChooserApp>, _: &mut CallbackInfo<FileChooserApp>) -> UpdateScreen {
+    show(app_state, FileChooserMode::OpenFiles)
+}
+
+fn save_file(app_state: &mut AppState<FileChooserApp>, _: &mut CallbackInfo<FileChooserApp>) -> UpdateScreen {
+    show(app_state, FileChooserMode::SaveFile)
+}
+
+fn select_folder(app_state: &mut AppState<FileChooserApp>, _: &mut CallbackInfo<FileChooserApp>) -> UpdateScreen {
+    show(app_state, FileChooserMode::OpenDirectory)
+}
+
+fn toggle_built_in(app_state: &mut AppState<FileChooserApp>, _: &mut CallbackInfo<FileChooserApp>) -> UpdateScreen {
+    app_state.data.modify(|data| data.force_built_in = !data.force_built_in)?;
+    Redraw
+}
+
+fn main() {
+    let data = FileChooserApp {
+        chooser: FileChooserState::new(FileChooserMode::OpenFile),
+        force_built_in: !dialogs::has_native_file_dialogs(),
+        message: String::new(),
+    };
+    let mut app = App::new(data, AppConfig::default()).unwrap();
+    let window = app.create_window(WindowCreateOptions::default(), css::native()).unwrap();
+    app.run(window).unwrap();
+}
@@ -0,0 +1,91 @@
+#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
+
+extern crate azul;
+
+use azul::prelude::*;
+use azul::widgets::{checkbox::*, radio_group::*, switch::*, slider::*, spin_box::*, dropdown::*};
+
+const CSS: &str = "
+#form {
+    padding: 20px;
+    flex-direction: column;
+}
+
+#sliders {
+    height: 120px;
+    flex-direction: row;
+    align-items: center;
+}
+
+#volume {
+    width: 200px;
+    margin-right: 20px;
+}
+
+#balance {
+    height: 100px;
+}
+";
+
+struct FormControls {
+    notifications: CheckboxState,
+    select_all: CheckboxState,
+    disabled_checkbox: CheckboxState,
+    size: RadioGroupState,
+    dark_mode: SwitchState,
+    volume: SliderState,
+    balance: SliderState,
+    quantity: SpinBoxState,
+    country: DropdownState<&'static str>,
+    font: DropdownState<String>,
+}
+
+impl Default for FormControls {
+    fn default() -> Self {
+        Self {
+            notifications: CheckboxState::new(true),
+            select_all: CheckboxState::tri_state(CheckState::Indeterminate),
+            disabled_checkbox: CheckboxState::new(false).with_disabled(true),
+            size: RadioGroupState::new(vec!["Small", "Medium", "Large"]).with_selected(1),
+            dark_mode: SwitchState::new(false),
+            volume: SliderState::new(50.0, 0.0, 100.0),
+            balance: SliderState::new(0.0, -5.0, 5.0).with_step(1.0).with_orientation(Orientation::Vertical),
+            quantity: SpinBoxState::new(1.5, 0.0, 10.0).with_step(0.5).with_decimals(1),
+            country: DropdownState::new(vec!["Austria", "Belgium", "Denmark", "Germany", "Netherlands", "Switzerland"]).with_selected(3),
+            font: DropdownState::new(vec!["Monospace".to_string(), "Sans".to_string(), "Serif".to_string()]).editable(),
+        }
+    }
+}
+
+impl Layout for FormControls {
+    fn layout(&self, mut info: LayoutInfo<Self>) -> Dom<Self> {
+
+        // The dropdowns need the `LayoutInfo` in order to show their list as an overlay
+        let country = Dropdown::new("country").bind(info.window, &self.country, &self).dom(&self.country, &mut info);
+        let font = Dropdown::new("font").bind(info.window, &self.font, &self).dom(&self.font, &mut info);
+
+        let window = info.window;
+
+        let sliders = Dom::div().with_id("sliders")
+            .with_child(Slider::new().bind(window, &self.volume, &self).dom(&self.volume).with_id("volume"))
+            .with_child(Slider::new().bind(window, &self.balance, &self).dom(&self.balance).with_id("balance"));
+
+        Dom::div().with_id("form")
+            .with_child(Checkbox::with_label("Enable notifications").bind(window, &self.notifications, &self).dom(&self.notifications))
+            .with_child(Checkbox::with_label("Select all").bind(window, &self.select_all, &self).dom(&self.select_all))
+            .with_child(Checkbox::with_label("Disabled").bind(window, &self.disabled_checkbox, &self).dom(&self.disabled_checkbox))
+            .with_child(RadioGroup::new().bind(window, &self.size, &self).dom(&self.size))
+            .with_child(Switch::with_label("Dark mode").bind(window, &self.dark_mode, &self).dom(&self.dark_mode))
+            .with_child(sliders)
+            .with_child(SpinBox::new().bind(window, &self.quantity, &self).dom(&self.quantity))
+            .with_child(country)
+            .with_child(font)
+    }
+}
+
+fn main() {
+    let mut app = App::new(FormControls::default(), AppConfig::default()).unwrap();
+    let css = css::override_native(CSS).unwrap();
+    let window = app.create_window(WindowCreateOptions::default(), css).unwrap();
+    app.run(window).unwrap();
+}
@@ -0,0 +1,67 @@
+#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
+
+extern crate azul;
+
+use azul::prelude::*;
+
+struct InlineText {
+    clicked_link: Option<&'static str>,
+}
+
+const LINKS: [&str; 2] = ["the documentation", "the examples"];
+
+const CUSTOM_CSS: &str = "
+    #paragraph { font-size: 16px; padding: 20px; max-width: 400px; color: #222222; }
+    .large { font-size: 28px; color: #a52a2a; }
+    .code { font-family: monospace; color: #006400; }
+    .link { color: #0645ad; }
+    .link:hover { color: #0b0080; }
+    #status { font-size: 12px; padding: 20px; color: #555555; }
+";
+
+impl Layout for InlineText {
+    fn layout(&self, _: LayoutInfo<Self>) -> Dom<Self> {
+
+        // All spans are laid out in the same paragraph as their parent label,
+        // so the line wrapping isn't interrupted by the differently styled words
+        let paragraph = Dom::label("Inline spans can mix ")
+            .with_id("paragraph")
+            .with_child(Dom::span("large words").with_class("large"))
+            .with_child(Dom::span(", "))
+            .with_child(Dom::span("monospaced code").with_class("code"))
+            .with_child(Dom::span(" and links inside of a single paragraph of text. For more information, see "))
+            .with_child(Dom::span(LINKS[0]).with_class("link").with_callback(On::LeftMouseUp, Callback(click_link)))
+            .with_child(Dom::span(" or "))
+            .with_child(Dom::span(LINKS[1]).with_class("link").with_callback(On::LeftMouseUp, Callback(click_link)))
+            .with_child(Dom::span("."));
+
+        let status = match self.clicked_link {
+            Some(link) => format!("Clicked on \"{}\"", link),
+            None => String::from("Click on one of the links"),
+        };
+
+        Dom::div()
+            .with_child(paragraph)
+            .with_child(Dom::label(status).with_id("status"))
+    }
+}
+
+fn click_link(app_state: &mut AppState<InlineText>, event: &mut CallbackInfo<InlineText>) -> UpdateScreen {
+
+    // The links are the 5th and the 7th span of the paragraph
+    let link = match event.target_index_in_parent()? {
+        4 => LINKS[0],
+        6 => LINKS[1],
+        _ => return DontRedraw,
+    };
+
+    app_state.data.lock().ok()?.clicked_link = Some(link);
+    Redraw
+}
+
+fn main() {
+    let mut app = App::new(InlineText { clicked_link: None }, AppConfig::default()).unwrap();
+    let css = css::override_native(CUSTOM_CSS).unwrap();
+    let window = app.create_window(WindowCreateOptions::default(), css).unwrap();
+    app.run(window).unwrap();
+}
@@ -0,0 +1,37 @@
+#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
+
+extern crate azul;
+
+use azul::prelude::*;
+use azul::widgets::list_view::*;
+
+const LINE_COUNT: usize = 1_000_000;
+
+struct LogViewer {
+    list: ListViewState,
+}
+
+// The lines are generated on the fly, a real log viewer would index into the lines of the file
+fn render_line(_: &LogViewer, row: usize) -> Dom<LogViewer> {
+    let level = match row % 7 {
+        0 => "ERROR",
+        1 | 2 => "WARN ",
+        _ => "INFO ",
+    };
+    Dom::label(format!("{:>7} {} request {} handled in {} ms", row + 1, level, row * 31 % 9973, row % 250))
+}
+
+impl Layout for LogViewer {
+    fn layout(&self, info: LayoutInfo<Self>) -> Dom<Self> {
+        ListView::new(render_line).dom(&self.list, self, info.window)
+    }
+}
+
+fn main() {
+    let data = LogViewer {
+        list: ListViewState::new(LINE_COUNT, RowHeight::Fixed(20.0)).with_selection_mode(SelectionMode::Multiple),
+    };
+    let mut app = App::new(data, AppConfig::default()).unwrap();
+    let window = app.create_window(WindowCreateOptions::default(), css::native()).unwrap();
+    app.run(window).unwrap();
+}
@@ -0,0 +1,78 @@
+#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
+
+extern crate azul;
+
+use azul::prelude::*;
+use azul::widgets::{tab_view::*, splitter::*, expander::*, slider::Orientation, label::Label, button::Button};
+
+struct Panes {
+    sidebar: SplitterState,
+    console: SplitterState,
+    tabs: TabViewState,
+    settings: AccordionState,
+    details: ExpanderState,
+    next_tab_id: usize,
+}
+
+impl Default for Panes {
+    fn default() -> Self {
+        let tabs = ["main.rs", "lib.rs", "Cargo.toml"].iter().enumerate()
+            .map(|(id, title)| Tab::new(TabId(id), *title).with_closable(true))
+            .collect();
+        Self {
+            sidebar: SplitterState::new(Orientation::Horizontal, 220.0).with_min_max(100.0, 400.0).with_min_remaining_size(200.0),
+            console: SplitterState::new(Orientation::Vertical, 300.0).with_min_remaining_size(60.0),
+            tabs: TabViewState::new(tabs).with_reorderable(true),
+            settings: AccordionState::new(Some(0)),
+            details: ExpanderState::new(false),
+            next_tab_id: 3,
+        }
+    }
+}
+
+impl Layout for Panes {
+    fn layout(&self, info: LayoutInfo<Self>) -> Dom<Self> {
+
+        let window = info.window;
+
+        let sidebar = Accordion::new(vec!["General", "Editor", "Appearance"])
+            .bind(window, &self.settings, &self)
+            .dom(&self.settings, |index| Label::new(format!("Settings of panel {}", index)).dom());
+
+        let details = Expander::new("Details")
+            .bind(window, &self.details, &self)
+            .dom(&self.details, || Label::new("Only created while the expander is open").dom());
+
+        // Only the content of the active tab is created
+        let editor = TabView::new()
+            .bind(window, &self.tabs, &self)
+            .dom(&self.tabs, |id| Label::new(format!("Content of tab {}", id.0)).dom());
+
+        let add_tab = Button::with_label("New tab").dom().with_callback(On::MouseUp, Callback(add_tab));
+
+        let console = Dom::div()
+            .with_child(add_tab)
+            .with_child(details);
+
+        let right_side = Splitter::new().bind(window, &self.console, &self).dom(&self.console, editor, console);
+
+        Splitter::new().bind(window, &self.sidebar, &self).dom(&self.sidebar, sidebar, right_side)
+    }
+}
+
+fn add_tab(app_state: &mut AppState<Panes>, _event: &mut CallbackInfo<Panes>) -> UpdateScreen {
+    app_state.data.modify(|state| {
+        let id = state.next_tab_id;
+        state.next_tab_id += 1;
+        state.tabs.add_tab(Tab::new(TabId(id), format!("untitled-{}", id)).with_closable(true));
+        // The application would drop the data of the closed tabs here
+        state.tabs.take_closed_tabs();
+    })?;
+    Redraw
+}
+
+fn main() {
+    let mut app = App::new(Panes::default(), AppConfig::default()).unwrap();
+    let window = app.create_window(WindowCreateOptions::default(), css::native()).unwrap();
+    app.run(window).unwrap();
+}
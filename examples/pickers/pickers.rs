@@ -0,0 +1,68 @@
+#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
+
+extern crate azul;
+
+use azul::prelude::*;
+use azul::css::CssColor;
+use azul::widgets::{date_picker::*, time_picker::*, color_picker::*};
+
+const CSS: &str = "
+#pickers {
+    padding: 20px;
+    flex-direction: row;
+}
+
+#date_and_time {
+    margin-right: 20px;
+    flex-direction: column;
+}
+
+#meeting_time {
+    margin-top: 10px;
+}
+";
+
+struct Pickers {
+    meeting_date: DatePickerState,
+    meeting_time: TimePickerState,
+    accent_color: ColorPickerState,
+}
+
+impl Default for Pickers {
+    fn default() -> Self {
+        let today = Date::today();
+        Self {
+            // Meetings can only be planned in the next three months
+            meeting_date: DatePickerState::new(Some(today))
+                .with_min(today)
+                .with_max(today.add_months(3))
+                .with_locale("en-US"),
+            meeting_time: TimePickerState::new(Time::new(9, 30, 0).unwrap())
+                .with_minute_step(15)
+                .with_twelve_hour_clock(true),
+            accent_color: ColorPickerState::new(CssColor::from_str("#4a90d9").unwrap()),
+        }
+    }
+}
+
+impl Layout for Pickers {
+    fn layout(&self, info: LayoutInfo<Self>) -> Dom<Self> {
+
+        let window = info.window;
+
+        let date_and_time = Dom::div().with_id("date_and_time")
+            .with_child(DatePicker::new().bind(window, &self.meeting_date, &self).dom(&self.meeting_date))
+            .with_child(TimePicker::new().bind(window, &self.meeting_time, &self).dom(&self.meeting_time).with_id("meeting_time"));
+
+        Dom::div().with_id("pickers")
+            .with_child(date_and_time)
+            .with_child(ColorPicker::new().bind(window, &self.accent_color, &self).dom(&self.accent_color))
+    }
+}
+
+fn main() {
+    let mut app = App::new(Pickers::default(), AppConfig::default()).unwrap();
+    let css = css::override_native(CSS).unwrap();
+    let window = app.create_window(WindowCreateOptions::default(), css).unwrap();
+    app.run(window).unwrap();
+}
@@ -16,8 +16,19 @@ impl Layout for TableDemo {
 
 fn main() {
 
-    let mut table_state = TableViewState::default();
-    table_state.work_sheet.set_cell(3, 4, "Hello World");
+    // The first row contains the column headers, it doesn't scroll and isn't sorted
+    let mut table_state = TableViewState::default().with_frozen_rows(1);
+    table_state.work_sheet.set_cell(0, 0, "Name");
+    table_state.work_sheet.set_cell(1, 0, "Population");
+
+    let cities = [("Tokyo", 37_400_068), ("Delhi", 28_514_000), ("Shanghai", 25_582_000), ("São Paulo", 21_650_000)];
+    for (row, (name, population)) in cities.iter().enumerate() {
+        table_state.work_sheet.set_cell(0, row + 1, *name);
+        table_state.work_sheet.set_cell(1, row + 1, format!("{}", population));
+    }
+
+    // The worksheet is sparse, so cells can be set far outside of the visible area
+    table_state.work_sheet.set_cell(1000, 100_000, "Hello World");
 
     let mut app = App::new(TableDemo { table_state }, AppConfig::default()).unwrap();
     let window = app.create_window(WindowCreateOptions::default(), css::native()).unwrap();